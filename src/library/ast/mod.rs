@@ -0,0 +1,848 @@
+#![allow(dead_code)]
+
+use library::lexeme::definition::{NumberSuffix, Radix, TokenType};
+
+/**
+ * Span:
+ * range of token ids [lo, hi] covered by a node, along with the
+ * line and column of its first token and the byte range [start, end)
+ * of the source text it was read from
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub lo: u32,
+    pub hi: u32,
+    pub line: u32,
+    pub column: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(lo: u32, hi: u32, line: u32) -> Span {
+        Span {
+            lo,
+            hi,
+            line,
+            ..Span::default()
+        }
+    }
+
+    // smallest span covering both self and other
+    pub fn to(self, other: Span) -> Span {
+        let first = if self.lo <= other.lo { self } else { other };
+        Span {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+            line: first.line,
+            column: first.column,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Modifier {
+    Unsigned,
+    Signed,
+    Const,
+    Static,
+    Default, //none applied
+}
+
+//CType c/c++ type of a declaration, parameter or cast
+#[derive(Debug, Clone, PartialEq)]
+pub enum CType {
+    //builtin type token (int, char, ...) with its signedness
+    Primitive(TokenType, Modifier),
+    //user defined struct/class/union/enum or typedef name
+    Named(String),
+    Pointer(Box<CType>),
+    //T&, refers to an object and is never NULL
+    Reference(Box<CType>),
+    //element type and optional length expression
+    Array(Box<CType>, Option<Box<Expr>>),
+    //return and parameter types, function pointers point to one
+    Function(Box<CType>, Vec<CType>),
+    //class template with its arguments, Stack<int>
+    Generic(String, Vec<TemplateArg>),
+    //type behind a pointer to const, const char *s => Pointer(Const(char))
+    Const(Box<CType>),
+}
+
+//TemplateParam parameter of a function or class template
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateParam {
+    pub name: String,
+    //type of a non-type parameter, template<int N>, None for typename T
+    pub ty: Option<CType>,
+}
+
+//TemplateArg argument given to a template, a type or a constant
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateArg {
+    Type(CType),
+    Value(Expr),
+}
+
+impl CType {
+    pub fn is_pointer(&self) -> bool {
+        matches!(*self, CType::Pointer(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(*self, CType::Array(..))
+    }
+
+    pub fn is_function_pointer(&self) -> bool {
+        matches!(*self, CType::Pointer(ref inner) if matches!(**inner, CType::Function(..)))
+    }
+
+    // type a pointer points to, other types are returned as they are
+    pub fn pointee(&self) -> &CType {
+        match *self {
+            CType::Pointer(ref inner) => inner.unqualified(),
+            _ => self,
+        }
+    }
+
+    // const T *, the pointer may not be written through
+    pub fn is_const_pointer(&self) -> bool {
+        matches!(*self, CType::Pointer(ref inner) if matches!(**inner, CType::Const(_)))
+    }
+
+    // type without its const, other types are returned as they are
+    pub fn unqualified(&self) -> &CType {
+        match *self {
+            CType::Const(ref inner) => inner,
+            _ => self,
+        }
+    }
+
+    pub fn is_reference(&self) -> bool {
+        matches!(*self, CType::Reference(_))
+    }
+
+    // type a reference refers to, other types are returned as they are
+    pub fn referred(&self) -> &CType {
+        match *self {
+            CType::Reference(ref inner) => inner,
+            _ => self,
+        }
+    }
+
+    // innermost non pointer, non array type
+    pub fn base(&self) -> &CType {
+        match *self {
+            CType::Pointer(ref inner)
+            | CType::Array(ref inner, _)
+            | CType::Reference(ref inner)
+            | CType::Const(ref inner) => inner.base(),
+            _ => self,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationUnit {
+    pub items: Vec<Item>,
+}
+
+impl TranslationUnit {
+    // items of the unit along with the ones inside namespaces, in order
+    pub fn all_items(&self) -> Vec<&Item> {
+        let mut all = Vec::new();
+        collect_items(&self.items, &mut all);
+        all
+    }
+}
+
+fn collect_items<'a>(items: &'a [Item], all: &mut Vec<&'a Item>) {
+    for item in items {
+        match item.kind {
+            ItemKind::Namespace(ref namespace) => collect_items(&namespace.items, all),
+            _ => all.push(item),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    Function(Function),
+    Declaration(Declaration),
+    Record(Record),
+    Enum(EnumDef),
+    Typedef(Typedef),
+    Macro(MacroDef),
+    //preprocessor line, kept verbatim
+    Directive(String),
+    Comment(String),
+    //explicit instantiation of a template, kept verbatim
+    Instantiation(String),
+    Namespace(Namespace),
+    Using(Using),
+    Unsupported(Unsupported),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    //class of a member function defined outside of it, int A::f() { }
+    pub class: Option<String>,
+    pub return_type: CType,
+    pub params: Vec<Param>,
+    //takes variable arguments after the params, printf(const char *fmt, ...)
+    pub variadic: bool,
+    pub is_inline: bool,
+    //prototype declared extern or inside extern "C" { }, defined in another file
+    pub is_extern: bool,
+    //virtual member function, pure virtual ones end with `= 0`
+    pub is_virtual: bool,
+    pub is_pure: bool,
+    //static member function, called without an object
+    pub is_static: bool,
+    //const member function, it doesn't change the object
+    pub is_const: bool,
+    //member initializer list of a constructor
+    pub initializers: Vec<Initializer>,
+    //parameters of a function template, empty for other functions
+    pub template: Vec<TemplateParam>,
+    //None for prototypes
+    pub body: Option<Vec<Stmt>>,
+    pub span: Span,
+}
+
+impl Function {
+    // symbol of an overloaded operator, `+` for operator+
+    pub fn operator(&self) -> Option<&str> {
+        self.name
+            .strip_prefix("operator")
+            .filter(|op| op.starts_with(|c: char| !c.is_alphanumeric() && c != '_'))
+    }
+}
+
+//Initializer member or base class set by a constructor, : a(1), Base(2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Initializer {
+    pub member: String,
+    pub args: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    //unnamed parameters are allowed in prototypes
+    pub name: Option<String>,
+    pub ty: CType,
+}
+
+//Declaration one declaration statement, int a = 1, *b;
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub is_static: bool,
+    pub is_const: bool,
+    //declared here, defined in another translation unit
+    pub is_extern: bool,
+    pub is_volatile: bool,
+    pub declarators: Vec<Declarator>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declarator {
+    pub name: String,
+    //complete type of this declarator, including pointers and arrays
+    pub ty: CType,
+    pub init: Option<Expr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    Struct,
+    Union,
+    Class,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    Protected,
+    Private,
+}
+
+//Record struct, union or class definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub kind: RecordKind,
+    pub name: String,
+    //base classes in declaration order, class B : public A
+    pub bases: Vec<String>,
+    //parameters of a class template, empty for other classes
+    pub template: Vec<TemplateParam>,
+    pub members: Vec<Member>,
+    pub span: Span,
+}
+
+impl Record {
+    // data members of every object in declaration order
+    pub fn fields(&self) -> Vec<&Declarator> {
+        let mut fields = Vec::new();
+        for member in &self.members {
+            match member.kind {
+                MemberKind::Field(ref decl) if !decl.is_static => {
+                    fields.extend(decl.declarators.iter())
+                }
+                _ => {}
+            }
+        }
+        fields
+    }
+
+    // static data members, shared by all objects of the class
+    pub fn statics(&self) -> Vec<(&Declaration, &Declarator)> {
+        let mut statics = Vec::new();
+        for member in &self.members {
+            match member.kind {
+                MemberKind::Field(ref decl) if decl.is_static => {
+                    statics.extend(decl.declarators.iter().map(|field| (decl, field)))
+                }
+                _ => {}
+            }
+        }
+        statics
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub access: Access,
+    pub kind: MemberKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemberKind {
+    Field(Declaration),
+    Method(Function),
+    Constructor(Function),
+    Destructor(Function),
+    //function declared a friend of the class and defined inside it
+    Friend(Function),
+    Comment(String),
+    Unsupported(Unsupported),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDef {
+    pub name: Option<String>,
+    //enum class, its enumerators are only named as E::A
+    pub scoped: bool,
+    //underlying type, enum E : unsigned char
+    pub underlying: Option<CType>,
+    pub variants: Vec<Enumerator>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enumerator {
+    pub name: String,
+    pub value: Option<Expr>,
+}
+
+//Typedef typedef ty name;
+#[derive(Debug, Clone, PartialEq)]
+pub struct Typedef {
+    pub name: String,
+    pub ty: CType,
+}
+
+//Namespace namespace name { items }, anonymous namespaces have no name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Namespace {
+    pub name: Option<String>,
+    pub items: Vec<Item>,
+}
+
+//Using using namespace a::b; or using a::b::f; of a single name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Using {
+    pub path: String,
+    pub namespace: bool,
+}
+
+//MacroDef #define line and the rust item its body can become
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroDef {
+    pub name: String,
+    //parameter names, None for object-like macros
+    pub params: Option<Vec<String>>,
+    pub body: MacroBody,
+    //every use of a parameter in the body is parenthesized, ((x) * 2)
+    pub grouped: bool,
+    //the #define line as written
+    pub text: String,
+}
+
+impl MacroDef {
+    // whether uses of the macro are left for the rust item to handle
+    pub fn is_translated(&self) -> bool {
+        matches!(
+            self.body,
+            MacroBody::Constant(_) | MacroBody::Expression(_) | MacroBody::Statement(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MacroBody {
+    //object-like macro with a constant expression body
+    Constant(Expr),
+    //function-like macro expanding to an expression
+    Expression(Expr),
+    //function-like macro expanding to a statement, do { } while (0)
+    Statement(Stmt),
+    //flag macro such as an include guard
+    Empty,
+    //reason the macro is expanded in place instead
+    Unsupported(String),
+}
+
+//Unsupported construct crust can not translate, such as a template or
+//code that failed to parse, kept as source text for the generated diagnostic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsupported {
+    pub reason: String,
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+impl Stmt {
+    // statements nested directly inside, in order of appearance
+    pub fn children(&self) -> Vec<&Stmt> {
+        match self.kind {
+            StmtKind::Block(ref stmts) => stmts.iter().collect(),
+            StmtKind::If {
+                ref then,
+                ref otherwise,
+                ..
+            } => {
+                let mut children: Vec<&Stmt> = vec![then];
+                children.extend(otherwise.iter().map(|otherwise| &**otherwise));
+                children
+            }
+            StmtKind::While { ref body, .. } | StmtKind::DoWhile { ref body, .. } => vec![body],
+            StmtKind::For {
+                ref init, ref body, ..
+            } => {
+                let mut children: Vec<&Stmt> = init.iter().map(|init| &**init).collect();
+                children.push(body);
+                children
+            }
+            StmtKind::Switch { ref cases, .. } => {
+                cases.iter().flat_map(|case| case.body.iter()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    // expressions of the statement itself, without the nested statements
+    pub fn exprs(&self) -> Vec<&Expr> {
+        match self.kind {
+            StmtKind::Declaration(ref decl) => decl
+                .declarators
+                .iter()
+                .filter_map(|declarator| declarator.init.as_ref())
+                .collect(),
+            StmtKind::Expr(ref expr)
+            | StmtKind::If { cond: ref expr, .. }
+            | StmtKind::While { cond: ref expr, .. }
+            | StmtKind::DoWhile { cond: ref expr, .. }
+            | StmtKind::Switch { cond: ref expr, .. }
+            | StmtKind::Return(Some(ref expr)) => vec![expr],
+            StmtKind::For {
+                ref cond, ref step, ..
+            } => cond.iter().chain(step.iter()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    Declaration(Declaration),
+    Expr(Expr),
+    Block(Vec<Stmt>),
+    If {
+        cond: Expr,
+        then: Box<Stmt>,
+        otherwise: Option<Box<Stmt>>,
+    },
+    While {
+        cond: Expr,
+        body: Box<Stmt>,
+    },
+    DoWhile {
+        body: Box<Stmt>,
+        cond: Expr,
+    },
+    For {
+        init: Option<Box<Stmt>>,
+        cond: Option<Expr>,
+        step: Option<Expr>,
+        body: Box<Stmt>,
+    },
+    Switch {
+        cond: Expr,
+        cases: Vec<SwitchCase>,
+    },
+    Break,
+    Continue,
+    //goto name;
+    Goto(String),
+    //name: in front of the statement it labels
+    Label(String),
+    Return(Option<Expr>),
+    Comment(String),
+    Unsupported(Unsupported),
+    Empty,
+}
+
+//SwitchCase one case label and the statements following it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchCase {
+    pub label: CaseLabel,
+    pub body: Vec<Stmt>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseLabel {
+    Value(Expr),
+    //case 1 ... 5:, a gnu extension
+    Range(Expr, Expr),
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Expr {
+        Expr { kind, span }
+    }
+
+    // names used as values in the expression, in order of appearance
+    pub fn idents(&self) -> Vec<String> {
+        let mut idents: Vec<String> = Vec::new();
+        self.collect_idents(&mut idents);
+        idents
+    }
+
+    pub fn collect_idents(&self, idents: &mut Vec<String>) {
+        if let ExprKind::Ident(ref name) = self.kind {
+            idents.push(name.clone());
+        }
+        for child in self.children() {
+            child.collect_idents(idents);
+        }
+    }
+
+    // direct sub expressions, in order of appearance
+    pub fn children(&self) -> Vec<&Expr> {
+        match self.kind {
+            ExprKind::Literal(_)
+            | ExprKind::SizeOfType(_)
+            | ExprKind::Ident(_)
+            | ExprKind::Generic(..)
+            | ExprKind::This => Vec::new(),
+            ExprKind::Unary(_, ref operand)
+            | ExprKind::Postfix(_, ref operand)
+            | ExprKind::Member(ref operand, _, _)
+            | ExprKind::Cast(_, ref operand)
+            | ExprKind::SizeOfExpr(ref operand)
+            | ExprKind::Delete(ref operand) => vec![operand],
+            ExprKind::Binary(_, ref lhs, ref rhs)
+            | ExprKind::Assign(_, ref lhs, ref rhs)
+            | ExprKind::Index(ref lhs, ref rhs)
+            | ExprKind::MemberPointer(ref lhs, ref rhs, _) => vec![lhs, rhs],
+            ExprKind::Call(ref callee, ref args) => {
+                let mut children: Vec<&Expr> = vec![callee];
+                children.extend(args.iter());
+                children
+            }
+            ExprKind::Conditional(ref cond, ref then, ref otherwise) => vec![cond, then, otherwise],
+            ExprKind::Comma(ref exprs)
+            | ExprKind::InitList(ref exprs)
+            | ExprKind::New(_, ref exprs) => exprs.iter().collect(),
+        }
+    }
+
+    // direct sub expressions to rewrite them
+    pub fn children_mut(&mut self) -> Vec<&mut Expr> {
+        match self.kind {
+            ExprKind::Literal(_)
+            | ExprKind::SizeOfType(_)
+            | ExprKind::Ident(_)
+            | ExprKind::Generic(..)
+            | ExprKind::This => Vec::new(),
+            ExprKind::Unary(_, ref mut operand)
+            | ExprKind::Postfix(_, ref mut operand)
+            | ExprKind::Member(ref mut operand, _, _)
+            | ExprKind::Cast(_, ref mut operand)
+            | ExprKind::SizeOfExpr(ref mut operand)
+            | ExprKind::Delete(ref mut operand) => vec![operand],
+            ExprKind::Binary(_, ref mut lhs, ref mut rhs)
+            | ExprKind::Assign(_, ref mut lhs, ref mut rhs)
+            | ExprKind::Index(ref mut lhs, ref mut rhs)
+            | ExprKind::MemberPointer(ref mut lhs, ref mut rhs, _) => vec![lhs, rhs],
+            ExprKind::Call(ref mut callee, ref mut args) => {
+                let mut children: Vec<&mut Expr> = vec![callee];
+                children.extend(args.iter_mut());
+                children
+            }
+            ExprKind::Conditional(ref mut cond, ref mut then, ref mut otherwise) => {
+                vec![cond, then, otherwise]
+            }
+            ExprKind::Comma(ref mut exprs)
+            | ExprKind::InitList(ref mut exprs)
+            | ExprKind::New(_, ref mut exprs) => exprs.iter_mut().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Literal(Literal),
+    Ident(String),
+    This,
+    Unary(UnaryOp, Box<Expr>),
+    Postfix(PostfixOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    //None for plain `=`, Some(op) for compound `op=`
+    Assign(Option<BinaryOp>, Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Vec<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    //base, member name and whether accessed through `->`
+    Member(Box<Expr>, String, bool),
+    //object, pointer to member and whether accessed through `->*`
+    MemberPointer(Box<Expr>, Box<Expr>, bool),
+    Cast(CType, Box<Expr>),
+    //cond ? then : otherwise
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+    SizeOfType(CType),
+    SizeOfExpr(Box<Expr>),
+    Comma(Vec<Expr>),
+    //brace enclosed initializer {1, 2, 3}
+    InitList(Vec<Expr>),
+    //new T, new T(args) and new T[n], the length of new T[n] is part of the type
+    New(CType, Vec<Expr>),
+    //delete p and delete[] p
+    Delete(Box<Expr>),
+    //template name with explicit arguments, max<int>
+    Generic(String, Vec<TemplateArg>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Integer(Number),
+    Float(Number),
+    Char(String),
+    Str(String),
+    Bool(bool),
+    Null,
+}
+
+//Number numeric literal as written, 0x1F'00u has digits 1F_00, hexadecimal radix and u suffix
+#[derive(Debug, Clone, PartialEq)]
+pub struct Number {
+    //digits without radix prefix and suffix, separators are written as `_`
+    pub digits: String,
+    pub radix: Radix,
+    pub suffix: NumberSuffix,
+}
+
+impl Number {
+    // value of an integer literal, None if it does not fit in 64 bits
+    pub fn value(&self) -> Option<u64> {
+        let base = match self.radix {
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+            Radix::Octal => 8,
+            Radix::Binary => 2,
+        };
+        u64::from_str_radix(&self.digits.replace('_', ""), base).ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Plus,
+    Minus,
+    Not,
+    BitNot,
+    Deref,
+    AddressOf,
+    PreIncrement,
+    PreDecrement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostfixOp {
+    Increment,
+    Decrement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Eq,
+    Ne,
+    BitAnd,
+    BitXor,
+    BitOr,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    /**
+     * precedence:
+     * binding power of the operator in c/c++, higher binds tighter
+     */
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 10,
+            BinaryOp::Add | BinaryOp::Sub => 9,
+            BinaryOp::Shl | BinaryOp::Shr => 8,
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => 7,
+            BinaryOp::Eq | BinaryOp::Ne => 6,
+            BinaryOp::BitAnd => 5,
+            BinaryOp::BitXor => 4,
+            BinaryOp::BitOr => 3,
+            BinaryOp::And => 2,
+            BinaryOp::Or => 1,
+        }
+    }
+
+    /**
+     * rust_precedence:
+     * binding power of the operator in rust, bitwise operators bind
+     * tighter than comparisons unlike c/c++
+     */
+    pub fn rust_precedence(self) -> u8 {
+        match self {
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 10,
+            BinaryOp::Add | BinaryOp::Sub => 9,
+            BinaryOp::Shl | BinaryOp::Shr => 8,
+            BinaryOp::BitAnd => 7,
+            BinaryOp::BitXor => 6,
+            BinaryOp::BitOr => 5,
+            BinaryOp::Lt
+            | BinaryOp::Gt
+            | BinaryOp::Le
+            | BinaryOp::Ge
+            | BinaryOp::Eq
+            | BinaryOp::Ne => 4,
+            BinaryOp::And => 3,
+            BinaryOp::Or => 2,
+        }
+    }
+
+    // comparisons can not be chained in rust without parentheses
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge | BinaryOp::Eq | BinaryOp::Ne
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::Lt => "<",
+            BinaryOp::Gt => ">",
+            BinaryOp::Le => "<=",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitXor => "^",
+            BinaryOp::BitOr => "|",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    // maps binary operator token to its operator
+    pub fn from_token(token_type: TokenType) -> Option<BinaryOp> {
+        match token_type {
+            TokenType::Multiplication => Some(BinaryOp::Mul),
+            TokenType::Divide => Some(BinaryOp::Div),
+            TokenType::Module => Some(BinaryOp::Rem),
+            TokenType::Plus => Some(BinaryOp::Add),
+            TokenType::Minus => Some(BinaryOp::Sub),
+            TokenType::BitwiseLeftShift => Some(BinaryOp::Shl),
+            TokenType::BitwiseRightShift => Some(BinaryOp::Shr),
+            TokenType::LessThan => Some(BinaryOp::Lt),
+            TokenType::GreaterThan => Some(BinaryOp::Gt),
+            TokenType::LessThanOrEqual => Some(BinaryOp::Le),
+            TokenType::GreaterThanOrEqual => Some(BinaryOp::Ge),
+            TokenType::Equal => Some(BinaryOp::Eq),
+            TokenType::NotEqual => Some(BinaryOp::Ne),
+            TokenType::BitwiseAnd => Some(BinaryOp::BitAnd),
+            TokenType::BitwiseXor => Some(BinaryOp::BitXor),
+            TokenType::BitwiseOr => Some(BinaryOp::BitOr),
+            TokenType::LogicalAnd => Some(BinaryOp::And),
+            TokenType::LogicalOr => Some(BinaryOp::Or),
+            _ => None,
+        }
+    }
+
+    // maps compound assignment token to the operator it applies
+    pub fn from_assign_token(token_type: TokenType) -> Option<BinaryOp> {
+        match token_type {
+            TokenType::PlusEqual => Some(BinaryOp::Add),
+            TokenType::MinusEqual => Some(BinaryOp::Sub),
+            TokenType::MultiplyEqual => Some(BinaryOp::Mul),
+            TokenType::DivideEqual => Some(BinaryOp::Div),
+            TokenType::ModuleEqual => Some(BinaryOp::Rem),
+            TokenType::BitwiseAndEqual => Some(BinaryOp::BitAnd),
+            TokenType::BitwiseOrEqual => Some(BinaryOp::BitOr),
+            TokenType::BitwiseXorEqual => Some(BinaryOp::BitXor),
+            TokenType::LeftShiftEqual => Some(BinaryOp::Shl),
+            TokenType::RightShiftEqual => Some(BinaryOp::Shr),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use library::ast::*;
+
+/**
+ * discriminants:
+ * value of every enumerator, an enumerator without one is the previous
+ * value plus one. Values that aren't integer constant expressions of
+ * literals and enumerators known so far are None
+ */
+pub fn discriminants(def: &EnumDef, known: &[(String, i64)]) -> Vec<Option<i64>> {
+    let mut known = known.to_vec();
+    let mut values: Vec<Option<i64>> = Vec::new();
+    let mut previous: Option<i64> = Some(-1);
+    for variant in &def.variants {
+        let value = match variant.value {
+            Some(ref value) => evaluate(value, &known),
+            None => previous.and_then(|previous| previous.checked_add(1)),
+        };
+        if let Some(value) = value {
+            known.push((variant.name.clone(), value));
+            if let Some(ref name) = def.name {
+                known.push((format!("{}::{}", name, variant.name), value));
+            }
+        }
+        values.push(value);
+        previous = value;
+    }
+    values
+}
+
+// value of an integer constant expression, 1 << 2 or RED + 1
+fn evaluate(expr: &Expr, known: &[(String, i64)]) -> Option<i64> {
+    match expr.kind {
+        ExprKind::Literal(Literal::Integer(ref number)) => number.value().map(|value| value as i64),
+        ExprKind::Literal(Literal::Char(ref text)) => {
+            let mut chars = text.trim_matches('\'').chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c as i64),
+                _ => None,
+            }
+        }
+        ExprKind::Ident(ref name) => known
+            .iter()
+            .rev()
+            .find(|entry| entry.0 == *name)
+            .map(|entry| entry.1),
+        ExprKind::Cast(_, ref operand) => evaluate(operand, known),
+        ExprKind::Unary(op, ref operand) => {
+            let value = evaluate(operand, known)?;
+            match op {
+                UnaryOp::Plus => Some(value),
+                UnaryOp::Minus => value.checked_neg(),
+                UnaryOp::BitNot => Some(!value),
+                UnaryOp::Not => Some((value == 0) as i64),
+                _ => None,
+            }
+        }
+        ExprKind::Binary(op, ref lhs, ref rhs) => {
+            let lhs = evaluate(lhs, known)?;
+            let rhs = evaluate(rhs, known)?;
+            match op {
+                BinaryOp::Add => lhs.checked_add(rhs),
+                BinaryOp::Sub => lhs.checked_sub(rhs),
+                BinaryOp::Mul => lhs.checked_mul(rhs),
+                BinaryOp::Div => lhs.checked_div(rhs),
+                BinaryOp::Rem => lhs.checked_rem(rhs),
+                BinaryOp::Shl => lhs.checked_shl(rhs as u32),
+                BinaryOp::Shr => lhs.checked_shr(rhs as u32),
+                BinaryOp::BitAnd => Some(lhs & rhs),
+                BinaryOp::BitOr => Some(lhs | rhs),
+                BinaryOp::BitXor => Some(lhs ^ rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
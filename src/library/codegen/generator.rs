@@ -0,0 +1,5383 @@
+#![allow(dead_code)]
+
+use library::ast::*;
+use library::codegen::enumeration::*;
+use library::codegen::goto::*;
+use library::codegen::hierarchy::*;
+use library::codegen::loops::*;
+use library::codegen::namespace::*;
+use library::codegen::operator::*;
+use library::codegen::pointer::*;
+use library::codegen::rust_type::*;
+use library::codegen::standard::*;
+use library::codegen::switch::*;
+use library::codegen::template::*;
+use library::doc::DocType::*;
+use library::lexeme::definition::{NumberSuffix, Radix, TokenType};
+
+//rust precedence of expressions that are not binary operators
+const CAST: u8 = 11;
+const UNARY: u8 = 12;
+//literals, names, calls, member access and blocks
+const ATOM: u8 = 13;
+
+//Labels break, continue and the block of a for loop body name, restored
+//when a loop ends
+type Labels = (Option<String>, Option<String>, Option<String>);
+
+pub struct Generator {
+    //defalt false
+    strict: bool,
+    //default false
+    in_main: bool,
+    //label `break` names inside a labelled switch block, None in loops
+    break_label: Option<String>,
+    //label of the loop `continue` names when it leaves a labelled switch block
+    continue_label: Option<String>,
+    //block of a for loop body `continue` breaks out of to run the step
+    continue_block: Option<String>,
+    //switches the statement being generated is nested in
+    switches: usize,
+    //label the next loop gets, a goto out of nested loops breaks it
+    loop_label: Option<String>,
+    //labels gotos can reach and whether they jump back to them
+    gotos: Vec<(String, bool)>,
+    //labels of the function and where they are
+    labels: Vec<(String, Span)>,
+    //gotos of the function and the labels in their block or the blocks around it
+    visible: Vec<(Span, Vec<String>)>,
+    //struct/class definitions, used to initialize declared variables
+    records: Vec<Record>,
+    //typedef name to aliased type
+    aliases: Vec<(String, CType)>,
+    //typedefs of function pointers replaced by the pointer type, generated by their name
+    function_aliases: Vec<String>,
+    //macro constant name to its rust type
+    constants: Vec<(String, String)>,
+    //function-like macros generated as macro_rules!, called with `name!`
+    macro_rules: Vec<String>,
+    //parameters of the macro_rules! body being generated, written as $name
+    macro_params: Vec<String>,
+    //pointer translations of every function, decided before generation
+    tables: Vec<PointerTable>,
+    //pointer translations of the function being generated
+    pointers: Option<PointerTable>,
+    //pointers of the function to a base class used for its non-virtual members
+    non_virtual: Vec<(String, String)>,
+    //arrays of function pointers with empty slots, elements are Option<fn>
+    function_tables: Vec<String>,
+    //data members of the class being generated, bare names refer to them
+    members: Vec<String>,
+    //value the members belong to, `this` while a constructor builds it
+    receiver: String,
+    //methods callable without an object inside the class being generated
+    methods: Vec<(String, Receiver)>,
+    //class whose member function is being generated
+    class: Option<String>,
+    //self parameter of every method
+    receivers: Receivers,
+    //operators defined outside of classes, generated with the class they take
+    free_operators: Vec<Function>,
+    //& or &mut before returned values, Index returns a borrow of the element
+    returned_borrow: String,
+    //ostream parameter of the operator<< generated as Display::fmt
+    stream: Option<String>,
+    //parameters of the function or class template being generated
+    template: Vec<TemplateParam>,
+    //bounds and copies of its values found for that template
+    bounds: Bounds,
+    //namespace being generated, as the path of its module
+    module: Vec<String>,
+    //paths of every namespace of the unit
+    modules: Vec<Vec<String>>,
+    //standard library names brought in scope by using, * for all of them
+    std_imports: Vec<String>,
+    //named enums with the value of every enumerator
+    enums: Vec<(EnumDef, Vec<Option<i64>>)>,
+    //functions of the unit without their bodies, for the types of calls
+    prototypes: Vec<Function>,
+    //global variables and their types
+    globals: Vec<(String, CType)>,
+    //global variables defined in the unit and functions already linked,
+    //extern declarations of them are dropped
+    defined: Vec<String>,
+    //static data members defined outside of their class and their values, A::count = 0
+    static_values: Vec<(String, Expr)>,
+    //parameters and locals of the function being generated and their types
+    variables: Vec<(String, CType)>,
+    //return type of the function being generated
+    return_type: Option<CType>,
+}
+
+/**
+ * init_generator:
+ * walks the translation unit and returns the stream of rust code
+ */
+pub fn init_generator(unit: &TranslationUnit, strict: bool) -> Vec<String> {
+    let mut stream: Vec<String> = Vec::new();
+    stream.push(CRUST.get_doc().to_string());
+
+    let mut generator = Generator::new(strict);
+    stream.append(&mut generator.gen_program(unit));
+    stream
+}
+
+impl Generator {
+    pub fn new(strict: bool) -> Generator {
+        Generator {
+            strict,
+            in_main: false,
+            break_label: None,
+            continue_label: None,
+            continue_block: None,
+            switches: 0,
+            loop_label: None,
+            gotos: Vec::new(),
+            labels: Vec::new(),
+            visible: Vec::new(),
+            records: Vec::new(),
+            aliases: Vec::new(),
+            function_aliases: Vec::new(),
+            constants: Vec::new(),
+            macro_rules: Vec::new(),
+            macro_params: Vec::new(),
+            tables: Vec::new(),
+            pointers: None,
+            non_virtual: Vec::new(),
+            function_tables: Vec::new(),
+            members: Vec::new(),
+            receiver: "self".to_string(),
+            methods: Vec::new(),
+            class: None,
+            receivers: Vec::new(),
+            free_operators: Vec::new(),
+            returned_borrow: String::new(),
+            stream: None,
+            template: Vec::new(),
+            bounds: Bounds::default(),
+            module: Vec::new(),
+            modules: Vec::new(),
+            std_imports: Vec::new(),
+            enums: Vec::new(),
+            prototypes: Vec::new(),
+            globals: Vec::new(),
+            defined: Vec::new(),
+            static_values: Vec::new(),
+            variables: Vec::new(),
+            return_type: None,
+        }
+    }
+
+    pub fn gen_program(&mut self, unit: &TranslationUnit) -> Vec<String> {
+        //typedefs of function pointers get the translation of the pointer type
+        let (unit, function_aliases) = resolve_function_aliases(unit);
+        let unit = &unit;
+        self.function_aliases = function_aliases;
+        for item in unit.all_items() {
+            match item.kind {
+                ItemKind::Record(ref record) => self.records.push(record.clone()),
+                ItemKind::Typedef(ref typedef) => self
+                    .aliases
+                    .push((typedef.name.clone(), typedef.ty.clone())),
+                ItemKind::Macro(ref def) => self.register_macro(def),
+                ItemKind::Enum(ref def) if def.name.is_some() => {
+                    let known: Vec<(String, i64)> = self
+                        .enums
+                        .iter()
+                        .flat_map(|(def, values)| {
+                            def.variants
+                                .iter()
+                                .zip(values)
+                                .filter_map(|(variant, value)| {
+                                    Some((variant.name.clone(), (*value)?))
+                                })
+                        })
+                        .collect();
+                    let values = discriminants(def, &known);
+                    self.enums.push((def.clone(), values));
+                }
+                ItemKind::Declaration(ref decl) => {
+                    self.defined.extend(
+                        decl.declarators
+                            .iter()
+                            .filter(|declarator| !decl.is_extern || declarator.init.is_some())
+                            .map(|declarator| declarator.name.clone()),
+                    );
+                    self.static_values
+                        .extend(decl.declarators.iter().filter_map(|declarator| {
+                            match declarator.init {
+                                Some(ref init) if declarator.name.contains("::") => {
+                                    Some((declarator.name.clone(), init.clone()))
+                                }
+                                _ => None,
+                            }
+                        }));
+                }
+                ItemKind::Function(ref function) if function.class.is_none() => {
+                    self.prototypes.push(Function {
+                        body: None,
+                        ..function.clone()
+                    })
+                }
+                _ => {}
+            }
+        }
+        let definitions: Vec<&Function> = unit
+            .all_items()
+            .into_iter()
+            .filter_map(|item| match item.kind {
+                ItemKind::Function(ref function) if function.class.is_some() => Some(function),
+                _ => None,
+            })
+            .collect();
+        merge_definitions(&mut self.records, &definitions);
+        self.free_operators = unit
+            .all_items()
+            .into_iter()
+            .filter_map(|item| match item.kind {
+                ItemKind::Function(ref function)
+                    if function.class.is_none()
+                        && function.body.is_some()
+                        && function.operator().is_some() =>
+                {
+                    Some(function.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        self.receivers = infer_receivers(&self.records);
+        self.tables = analyze_pointers(unit, &self.receivers);
+        module_paths(&unit.items, &[], &mut self.modules);
+        let mut stream: Vec<String> = Vec::new();
+        //integers are converted to enums by TryFrom, not in the prelude of rust 2015
+        if !self.enums.is_empty() {
+            stream.push("use std::convert::TryFrom;\n".to_string());
+        }
+        stream.append(&mut self.gen_items(&unit.items));
+        stream
+    }
+
+    // items of the unit or of a namespace, a reopened namespace is generated once
+    fn gen_items(&mut self, items: &[Item]) -> Vec<String> {
+        let items = merge_namespaces(items);
+        let mut stream: Vec<String> = Vec::new();
+        let mut index = 0;
+        while index < items.len() {
+            if let ItemKind::Directive(_) = items[index].kind {
+                //consecutive preprocessor lines are commented out as one block
+                stream.push(INCLUDE_STMT.get_doc().to_string());
+                while let Some(&Item {
+                    kind: ItemKind::Directive(ref line),
+                    ..
+                }) = items.get(index)
+                {
+                    stream.push(line.clone() + "\n");
+                    index += 1;
+                }
+                stream.push("**/\n".to_string());
+                continue;
+            }
+            stream.append(&mut self.gen_item(&items[index]));
+            index += 1;
+        }
+        stream
+    }
+
+    fn gen_item(&mut self, item: &Item) -> Vec<String> {
+        match item.kind {
+            ItemKind::Function(ref function) if function.class.is_some() => {
+                self.gen_definition(function)
+            }
+            //operators taking a class are generated with the class
+            ItemKind::Function(ref function) if function.operator().is_some() => {
+                match self.operator_record(function) {
+                    Some(_) => Vec::new(),
+                    None => self.gen_operators(None, &[(function.clone(), false)]),
+                }
+            }
+            ItemKind::Function(ref function) => self.gen_function(function),
+            ItemKind::Declaration(ref decl) => self.gen_declaration(decl, true),
+            ItemKind::Record(ref record) => {
+                //with the member functions defined outside of it
+                let merged = record_named(&self.records, &record.name)
+                    .filter(|_| !record.name.is_empty())
+                    .cloned();
+                self.gen_record(merged.as_ref().unwrap_or(record))
+            }
+            ItemKind::Enum(ref def) => self.gen_enum(def),
+            ItemKind::Typedef(ref typedef) => self.gen_typedef(typedef),
+            ItemKind::Macro(ref def) => self.gen_macro(def, item.span),
+            ItemKind::Comment(ref comment) => vec![comment.clone() + "\n"],
+            ItemKind::Directive(ref line) => vec![line.clone() + "\n"],
+            //generics are instantiated where they are used
+            ItemKind::Instantiation(ref text) => vec![format!(
+                "// {} rust instantiates generics where they are used\n",
+                text.replace('\n', " ")
+            )],
+            ItemKind::Namespace(ref namespace) => self.gen_namespace(namespace),
+            ItemKind::Using(ref using) => self.gen_using(using),
+            ItemKind::Unsupported(ref unsupported) => gen_unsupported(unsupported),
+        }
+    }
+
+    /**
+     * gen_namespace:
+     * namespace a { } => mod a { }, the module sees the names of the
+     * enclosing ones like the namespace does. Items of an anonymous
+     * namespace stay where it is
+     */
+    fn gen_namespace(&mut self, namespace: &Namespace) -> Vec<String> {
+        let name = match namespace.name {
+            Some(ref name) => name,
+            None => return self.gen_items(&namespace.items),
+        };
+        let mut stream: Vec<String> = Vec::new();
+        stream.extend(self.exported());
+        stream.push("mod".to_string());
+        stream.push(name.clone());
+        stream.push("{".to_string());
+        stream.push("use super::*;".to_string());
+        self.module.push(name.clone());
+        //using declarations end with the namespace
+        let imports = self.std_imports.len();
+        stream.append(&mut self.gen_items(&namespace.items));
+        self.std_imports.truncate(imports);
+        self.module.pop();
+        stream.push("}\n".to_string());
+        stream
+    }
+
+    /**
+     * gen_using:
+     * using namespace a; => use crate::a::*; and using a::f; => use crate::a::f;
+     * names of the standard library are mapped where they are used instead
+     */
+    fn gen_using(&mut self, using: &Using) -> Vec<String> {
+        if using.path == "std" || using.path.starts_with("std::") {
+            let name = match using.path.rfind("::") {
+                Some(index) if !using.namespace => using.path[index + 2..].to_string(),
+                _ => "*".to_string(),
+            };
+            self.std_imports.push(name);
+            let keyword = if using.namespace { "namespace " } else { "" };
+            return vec![format!(
+                "// using {}{}; rust names of the standard library are used instead\n",
+                keyword, using.path
+            )];
+        }
+        let path = resolve_path(&self.modules, &self.module, &using.path);
+        let glob = if using.namespace { "::*" } else { "" };
+        vec![format!("use {}{};", path, glob)]
+    }
+
+    // pub before items inside a namespace, they are used from outside the module
+    fn exported(&self) -> Option<String> {
+        if self.module.is_empty() {
+            None
+        } else {
+            Some("pub".to_string())
+        }
+    }
+
+    // rust type or function of a standard library name, std::vector or vector after using
+    fn std_name(&self, name: &str, lookup: fn(&str) -> Option<&'static str>) -> Option<String> {
+        let unqualified = match name.strip_prefix("std::") {
+            Some(unqualified) => unqualified,
+            None if self
+                .std_imports
+                .iter()
+                .any(|import| import == "*" || import == name) =>
+            {
+                name
+            }
+            None => return None,
+        };
+        lookup(unqualified).map(|path| path.to_string())
+    }
+
+    /**
+     * gen_function:
+     * rust function for c/c++ function definition,
+     * prototypes are not needed in rust and are dropped
+     */
+    fn gen_function(&mut self, function: &Function) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let body = match function.body {
+            Some(ref body) => body,
+            None if function.is_extern && function.class.is_none() => {
+                return self.gen_extern_function(function)
+            }
+            None => return stream,
+        };
+
+        self.pointers = Some(analyze_function(function, &self.tables, &self.receivers));
+        self.enter_scope(function);
+        if function.name != "main" {
+            stream.append(&mut self.gen_signature_docs());
+        }
+        if function.variadic {
+            stream.push(VARIADIC.get_doc().to_string());
+        }
+        if function.is_inline {
+            stream.push("#[inline]".to_string());
+        }
+        let outer = self.enter_template(&function.template, &[function], &[]);
+        stream.extend(self.exported());
+        stream.push("fn".to_string());
+        stream.push(function.name.clone() + &self.gen_generics(&function.template, true));
+        stream.push("(".to_string());
+        // rust does not have arguments or return type for main
+        if function.name == "main" {
+            self.in_main = true;
+            stream.push(")".to_string());
+            stream.push("{".to_string());
+            // declare argc and argv inside main, if required
+            if !function.params.is_empty() {
+                if !self.strict {
+                    stream.push(NO_STRICT.get_doc().to_string());
+                    stream.push("let mut argv: Vec<_> = std::env::args().collect();".to_string());
+                    stream.push("let mut argc = argv.len();".to_string());
+                } else {
+                    stream.push(STRICT.get_doc().to_string());
+                    stream.push("let argv: Vec<_> = std::env::args().collect();".to_string());
+                    stream.push("let argc = argv.len();".to_string());
+                }
+            }
+        } else {
+            stream.append(&mut self.gen_params(&function.params));
+            stream.push(")".to_string());
+            stream.append(&mut self.gen_return_type(&function.return_type));
+            stream.push("{".to_string());
+        }
+
+        stream.append(&mut self.gen_body(body));
+        stream.push("}".to_string());
+        self.in_main = false;
+        self.pointers = None;
+        self.leave_template(outer);
+        stream
+    }
+
+    /**
+     * enter_template:
+     * makes the parameters of a function or class template known while
+     * it is generated, the bounds of its type parameters are inferred
+     * from the functions. Returns the template it is nested in
+     */
+    fn enter_template(
+        &mut self,
+        params: &[TemplateParam],
+        functions: &[&Function],
+        fields: &[&Declarator],
+    ) -> (Vec<TemplateParam>, Bounds) {
+        let outer = (self.template.clone(), self.bounds.clone());
+        if !params.is_empty() {
+            let bounds = infer_bounds(params, functions, fields, &self.records);
+            self.template.extend(params.iter().cloned());
+            self.bounds.traits.extend(bounds.traits);
+            self.bounds.clones.extend(bounds.clones);
+        }
+        outer
+    }
+
+    fn leave_template(&mut self, outer: (Vec<TemplateParam>, Bounds)) {
+        self.template = outer.0;
+        self.bounds = outer.1;
+    }
+
+    // <T: PartialOrd, const N: usize> of a template, without bounds for structs
+    fn gen_generics(&self, params: &[TemplateParam], bounded: bool) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let params: Vec<String> = params
+            .iter()
+            .map(|param| match param.ty {
+                Some(ref ty) => format!("const {}: {}", param.name, self.const_param_type(ty)),
+                None => {
+                    let traits = self.bounds.of(&param.name);
+                    if bounded && !traits.is_empty() {
+                        format!("{}: {}", param.name, traits.join(" + "))
+                    } else {
+                        param.name.clone()
+                    }
+                }
+            })
+            .collect();
+        format!("<{}>", params.join(", "))
+    }
+
+    /**
+     * gen_where:
+     * bounds a method of a class template needs, like c++ only checks
+     * the members that are used, a method only requires them for itself
+     */
+    fn gen_where(&self, record: &Record, function: &Function) -> String {
+        if record.template.is_empty() {
+            return String::new();
+        }
+        let bounds = infer_bounds(
+            &record.template,
+            &[function],
+            &record.fields(),
+            &self.records,
+        );
+        let clauses: Vec<String> = record
+            .template
+            .iter()
+            .map(|param| (param, bounds.of(&param.name)))
+            .filter(|&(param, ref traits)| param.ty.is_none() && !traits.is_empty())
+            .map(|(param, traits)| format!("{}: {}", param.name, traits.join(" + ")))
+            .collect();
+        if clauses.is_empty() {
+            return String::new();
+        }
+        format!("where {}", clauses.join(", "))
+    }
+
+    // integer parameters are usize, they are mostly array lengths
+    fn const_param_type(&self, ty: &CType) -> String {
+        match *ty {
+            CType::Primitive(TokenType::Boolean, _) | CType::Primitive(TokenType::Character, _) => {
+                self.gen_type(ty)
+            }
+            CType::Primitive(..) => "usize".to_string(),
+            _ => self.gen_type(ty),
+        }
+    }
+
+    // type parameter of the template being generated
+    fn is_type_param(&self, ty: &CType) -> bool {
+        matches!(*ty, CType::Named(ref name) if self
+            .template
+            .iter()
+            .any(|param| param.name == *name && param.ty.is_none()))
+    }
+
+    // non-type parameter of the template being generated
+    fn const_param(&self, name: &str) -> Option<&CType> {
+        self.template
+            .iter()
+            .find(|param| param.name == name)
+            .and_then(|param| param.ty.as_ref())
+    }
+
+    // <T, N> naming the parameters of a class template, empty for other classes
+    fn template_args(&self, class: &str) -> String {
+        match self.find_record(class) {
+            Some(record) if !record.template.is_empty() => {
+                let names: Vec<String> = record
+                    .template
+                    .iter()
+                    .map(|param| param.name.clone())
+                    .collect();
+                format!("<{}>", names.join(", "))
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn gen_template_args(&self, args: &[TemplateArg]) -> String {
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| match *arg {
+                TemplateArg::Type(ref ty) => self.gen_type(ty),
+                TemplateArg::Value(Expr {
+                    kind: ExprKind::Ident(ref name),
+                    ..
+                }) => name.clone(),
+                TemplateArg::Value(
+                    ref value @ Expr {
+                        kind: ExprKind::Literal(_),
+                        ..
+                    },
+                ) => self.gen_expr(value),
+                TemplateArg::Value(ref value) => format!("{{ {} }}", self.gen_expr(value)),
+            })
+            .collect();
+        format!("<{}>", args.join(", "))
+    }
+
+    // reasons for the translation of pointer parameters and return type
+    fn gen_signature_docs(&self) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let table = match self.pointers {
+            Some(ref table) => table,
+            None => return stream,
+        };
+        for info in table.params.iter().flatten() {
+            stream.push(self.gen_pointer_doc(info));
+        }
+        if let Some(ref ret) = table.ret {
+            let info = PointerInfo {
+                name: "return".to_string(),
+                ..ret.clone()
+            };
+            stream.push(self.gen_pointer_doc(&info));
+        }
+        stream
+    }
+
+    fn gen_params(&self, params: &[Param]) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        for param in params {
+            match param.name {
+                Some(ref name) => {
+                    //c/c++ arguments are mutable locals
+                    if !self.strict {
+                        stream.push("mut".to_string());
+                    }
+                    stream.push(name.clone());
+                }
+                None => stream.push("_".to_string()),
+            }
+            stream.push(":".to_string());
+            let info = param.name.as_ref().and_then(|name| self.pointer_info(name));
+            match info {
+                Some(info) => stream.push(self.gen_pointer_type(info)),
+                None => stream.push(self.gen_type(&param.ty)),
+            }
+            stream.push(",".to_string());
+        }
+        stream
+    }
+
+    fn gen_return_type(&self, ty: &CType) -> Vec<String> {
+        let ret = self.pointers.as_ref().and_then(|table| table.ret.as_ref());
+        match (ty, ret) {
+            (&CType::Primitive(TokenType::Void, _), _) => Vec::new(),
+            //string literals live for the whole program
+            (_, Some(ret)) if ret.kind == PointerKind::Str && ret.name.is_empty() => {
+                vec!["->".to_string(), "&'static str".to_string()]
+            }
+            (_, Some(ret)) => vec!["->".to_string(), self.gen_pointer_type(ret)],
+            _ => vec!["->".to_string(), self.gen_type(ty)],
+        }
+    }
+
+    /**
+     * gen_body:
+     * function body, a trailing return statement is converted
+     * to rust shorthand notation
+     */
+    fn gen_body(&mut self, body: &[Stmt]) -> Vec<String> {
+        if body.iter().any(|stmt| label_of(stmt).is_some()) {
+            return self.gen_stmts(body);
+        }
+        let mut stream: Vec<String> = Vec::new();
+        let last = body
+            .iter()
+            .rposition(|stmt| !matches!(stmt.kind, StmtKind::Comment(_)));
+        for (index, stmt) in body.iter().enumerate() {
+            match stmt.kind {
+                StmtKind::Return(Some(ref value)) if Some(index) == last && !self.in_main => {
+                    stream.push(NO_RETURN.get_doc().to_string());
+                    stream.push(self.gen_return_value(value));
+                }
+                _ => stream.append(&mut self.gen_stmt(stmt)),
+            }
+        }
+        stream
+    }
+
+    /**
+     * gen_extern:
+     * extern int x; is dropped when x is defined in the unit, where its
+     * definition is translated, otherwise x is linked from another file
+     * extern "C" { static mut x: i32; }
+     */
+    fn gen_extern(&mut self, decl: &Declaration, global: bool) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        for declarator in &decl.declarators {
+            if global {
+                self.globals
+                    .push((declarator.name.clone(), declarator.ty.clone()));
+            } else {
+                self.variables
+                    .push((declarator.name.clone(), declarator.ty.clone()));
+            }
+            if self.defined.contains(&declarator.name) {
+                continue;
+            }
+            stream.push(EXTERN.get_doc().to_string());
+            stream.push(format!(
+                "extern \"C\" {{ {} {}: {}; }}\n",
+                if decl.is_const {
+                    "static"
+                } else {
+                    "static mut"
+                },
+                declarator.name,
+                self.gen_type(&declarator.ty)
+            ));
+        }
+        stream
+    }
+
+    /**
+     * gen_extern_function:
+     * extern int f(int); and prototypes inside extern "C" { } of functions
+     * the unit does not define are linked from another file
+     * extern "C" { fn f(_: i32) -> i32; }
+     */
+    fn gen_extern_function(&mut self, function: &Function) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        if self.is_function_name(&function.name) || self.defined.contains(&function.name) {
+            return stream;
+        }
+        self.defined.push(function.name.clone());
+        let mut params: Vec<String> = function
+            .params
+            .iter()
+            .map(|param| {
+                format!(
+                    "{}: {}",
+                    param.name.as_deref().unwrap_or("_"),
+                    self.gen_type(&param.ty)
+                )
+            })
+            .collect();
+        if function.variadic {
+            params.push("...".to_string());
+        }
+        let ret = match function.return_type {
+            CType::Primitive(TokenType::Void, _) => String::new(),
+            ref ty => format!(" -> {}", self.gen_type(ty)),
+        };
+        stream.push(EXTERN_FUNCTION.get_doc().to_string());
+        stream.push(format!(
+            "extern \"C\" {{ fn {}({}){}; }}\n",
+            function.name,
+            params.join(", "),
+            ret
+        ));
+        stream
+    }
+
+    /**
+     * gen_declaration:
+     * let/static binding for every declarator in the declaration
+     */
+    fn gen_declaration(&mut self, decl: &Declaration, global: bool) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        //int A::count = 0; is generated with the class of the static member
+        if decl
+            .declarators
+            .iter()
+            .all(|declarator| self.static_member(&declarator.name).is_some())
+        {
+            return stream;
+        }
+        //extern int x = 5; defines x like int x = 5;
+        if decl.is_extern
+            && decl
+                .declarators
+                .iter()
+                .all(|declarator| declarator.init.is_none())
+        {
+            return self.gen_extern(decl, global);
+        }
+        if decl.is_volatile {
+            stream.push(VOLATILE.get_doc().to_string());
+        }
+        let record_init = decl
+            .declarators
+            .iter()
+            .all(|declarator| self.is_record_init(declarator));
+        if record_init {
+            stream.push(STRUCT_INIT.get_doc().to_string());
+        } else if !self.strict {
+            stream.push(NO_STRICT.get_doc().to_string());
+        } else {
+            stream.push(STRICT.get_doc().to_string());
+        }
+
+        for declarator in &decl.declarators {
+            let pointer = match declarator.ty {
+                CType::Pointer(_) if global => Some(global_pointer(declarator)),
+                CType::Pointer(_) => self.pointer_info(&declarator.name).cloned(),
+                _ => None,
+            };
+            if let Some(ref info) = pointer {
+                stream.push(self.gen_pointer_doc(info));
+            }
+            if global {
+                stream.extend(self.exported());
+                self.globals
+                    .push((declarator.name.clone(), declarator.ty.clone()));
+            } else {
+                self.variables
+                    .push((declarator.name.clone(), declarator.ty.clone()));
+            }
+            stream.push(self.gen_binding(decl.is_const, global));
+            stream.push(declarator.name.clone());
+
+            if self.is_record_init(declarator) {
+                //struct A a; => let a = A {..};
+                let name = match declarator.ty {
+                    CType::Generic(ref name, _) => name.clone(),
+                    _ => self.gen_type(&declarator.ty),
+                };
+                stream.push(":".to_string());
+                stream.push(self.gen_type(&declarator.ty));
+                stream.push("=".to_string());
+                stream.push(self.gen_record_literal(&name));
+                stream.push(";".to_string());
+                continue;
+            }
+
+            if self.is_function_table(declarator) {
+                self.function_tables.push(declarator.name.clone());
+            }
+            let ty = match pointer {
+                Some(ref info) => self.gen_pointer_type(info),
+                None => self.gen_declarator_type(declarator),
+            };
+            if ty != "_" {
+                stream.push(":".to_string());
+                stream.push(ty);
+            }
+            match (&declarator.init, &pointer) {
+                (Some(init), Some(info)) => {
+                    stream.push("=".to_string());
+                    stream.push(self.gen_pointer_value(info, init));
+                }
+                (Some(init), None) => {
+                    stream.push("=".to_string());
+                    stream.push(self.gen_initializer(&declarator.ty, init));
+                }
+                (None, _) => {
+                    // rust statics must be initialized
+                    if self.is_function_table(declarator) {
+                        stream.push("=".to_string());
+                        stream.push(format!("[None ; {}]", self.gen_table_length(declarator)));
+                    } else if global
+                        || declarator.ty.is_array()
+                        || self.is_constructed(&declarator.ty)
+                        || self.is_type_param(&declarator.ty)
+                        || self.is_std_type(&declarator.ty)
+                    {
+                        stream.push("=".to_string());
+                        stream.push(self.default_value(&declarator.ty));
+                    }
+                }
+            }
+            stream.push(";".to_string());
+        }
+        stream
+    }
+
+    fn gen_binding(&self, is_const: bool, global: bool) -> String {
+        let binding = match (global, is_const, self.strict) {
+            (true, true, _) => "const",
+            (true, false, false) => "static mut",
+            (true, false, true) => "static",
+            (false, true, _) | (false, false, true) => "let",
+            (false, false, false) => "let mut",
+        };
+        binding.to_string()
+    }
+
+    // whether the declarator is a struct/class variable without explicit initial values
+    fn is_record_init(&self, declarator: &Declarator) -> bool {
+        let name = match declarator.ty {
+            CType::Named(ref name) | CType::Generic(ref name, _) => name,
+            _ => return false,
+        };
+        //classes with constructors are built by them
+        if self.find_record(name).is_none() || self.has_constructors(name) {
+            return false;
+        }
+        match declarator.init {
+            None => true,
+            //A a = A();
+            Some(Expr {
+                kind: ExprKind::Call(ref callee, ref args),
+                ..
+            }) => args.is_empty() && callee.kind == ExprKind::Ident(name.clone()),
+            _ => false,
+        }
+    }
+
+    /**
+     * is_function_table:
+     * array of function pointers with slots left empty or set to NULL,
+     * tables filled with functions keep plain fn elements
+     */
+    fn is_function_table(&self, declarator: &Declarator) -> bool {
+        let length = match declarator.ty {
+            CType::Array(ref inner, ref length) if self.is_function_pointer(inner) => length,
+            _ => return false,
+        };
+        match declarator.init {
+            Some(Expr {
+                kind: ExprKind::InitList(ref values),
+                ..
+            }) => self.has_empty_slots(length, values),
+            _ => true,
+        }
+    }
+
+    // function pointer type, typedef int (*binop)(int, int) names one as well
+    fn is_function_pointer(&self, ty: &CType) -> bool {
+        let mut ty = ty;
+        while let CType::Named(ref name) = *ty {
+            match self.aliases.iter().find(|alias| alias.0 == *name) {
+                Some(alias) if alias.1 != *ty => ty = &alias.1,
+                _ => break,
+            }
+        }
+        ty.is_function_pointer()
+    }
+
+    // initializer setting a slot to NULL or leaving the last ones out
+    fn has_empty_slots(&self, length: &Option<Box<Expr>>, values: &[Expr]) -> bool {
+        values.iter().any(is_null)
+            || length
+                .as_ref()
+                .is_some_and(|length| self.gen_length(length) != values.len().to_string())
+    }
+
+    fn gen_table_length(&self, declarator: &Declarator) -> String {
+        match (&declarator.ty, &declarator.init) {
+            (&CType::Array(_, Some(ref length)), _) => self.gen_length(length),
+            (
+                _,
+                &Some(Expr {
+                    kind: ExprKind::InitList(ref values),
+                    ..
+                }),
+            ) => values.len().to_string(),
+            _ => "0".to_string(),
+        }
+    }
+
+    fn find_record(&self, name: &str) -> Option<&Record> {
+        //geo::Point of a namespace
+        let mut name = name.rsplit("::").next().unwrap_or_default().to_string();
+        //resolve typedef chains to the record name
+        while let Some(&(_, CType::Named(ref aliased))) =
+            self.aliases.iter().find(|alias| alias.0 == name)
+        {
+            if *aliased == name {
+                break;
+            }
+            name = aliased.clone();
+        }
+        self.records.iter().find(|record| record.name == name)
+    }
+
+    // struct literal with every field set to its default value
+    fn gen_record_literal(&self, name: &str) -> String {
+        let mut literal = format!("{} {{", name);
+        if let Some(record) = self.find_record(name) {
+            if let Some(base) = composed(&self.records, record) {
+                literal.push_str(&format!(
+                    " base : {} ,",
+                    self.default_value(&CType::Named(base.name.clone()))
+                ));
+            }
+            for field in record.fields() {
+                literal.push_str(&format!(
+                    " {} : {} ,",
+                    field.name,
+                    self.default_value(&field.ty)
+                ));
+            }
+        }
+        literal.push_str(" }");
+        literal
+    }
+
+    fn default_value(&self, ty: &CType) -> String {
+        match *ty {
+            CType::Primitive(token_type, Modifier::Unsigned) => match token_type {
+                TokenType::Character => "0u8".to_string(),
+                _ => "0".to_string(),
+            },
+            CType::Primitive(token_type, _) => get_default_value_for(token_type),
+            //std::vector<int> v; starts out empty
+            _ if self.is_std_type(ty) => "Default::default()".to_string(),
+            //an enum starts out as its zero enumerator, or its first one
+            _ if self.enum_named(ty).is_some() => self.enum_default(ty),
+            //A a; calls the default constructor
+            CType::Named(ref name) if self.has_constructors(name) => {
+                self.gen_construction(name, &[])
+            }
+            CType::Named(ref name) if self.find_record(name).is_some() => {
+                self.gen_record_literal(name)
+            }
+            CType::Generic(ref name, _) if self.has_constructors(name) => {
+                self.gen_construction(name, &[])
+            }
+            CType::Generic(ref name, _) => self.gen_record_literal(name),
+            //values of a type parameter are not Copy
+            CType::Array(ref inner, Some(_)) if self.is_type_param(inner.base()) => {
+                "std::array::from_fn(|_| Default::default())".to_string()
+            }
+            CType::Array(ref inner, Some(ref length)) => {
+                format!(
+                    "[{} ; {}]",
+                    self.default_value(inner),
+                    self.gen_length(length)
+                )
+            }
+            CType::Pointer(_) if ty.is_function_pointer() => "None".to_string(),
+            CType::Pointer(_) if ty.is_const_pointer() => "std::ptr::null()".to_string(),
+            CType::Pointer(_) => "std::ptr::null_mut()".to_string(),
+            _ => "Default::default()".to_string(),
+        }
+    }
+
+    fn gen_declarator_type(&self, declarator: &Declarator) -> String {
+        match (&declarator.ty, &declarator.init) {
+            (CType::Array(inner, _), _) if self.is_function_table(declarator) => format!(
+                "[Option<{}> ; {}]",
+                self.gen_type(inner),
+                self.gen_table_length(declarator)
+            ),
+            //int a[] = {1, 2, 3}; takes the length from initializer
+            (
+                &CType::Array(ref inner, None),
+                &Some(Expr {
+                    kind: ExprKind::InitList(ref values),
+                    ..
+                }),
+            ) => format!("[{} ; {}]", self.gen_type(inner), values.len()),
+            //char s[] = "text";
+            (
+                &CType::Array(_, _),
+                &Some(Expr {
+                    kind: ExprKind::Literal(Literal::Str(_)),
+                    ..
+                }),
+            ) => "&str".to_string(),
+            (ty, _) => self.gen_type(ty),
+        }
+    }
+
+    fn gen_initializer(&self, ty: &CType, init: &Expr) -> String {
+        match (ty, &init.kind) {
+            //slots of a table with empty ones
+            (CType::Array(inner, length), ExprKind::InitList(values))
+                if self.is_function_pointer(inner) && self.has_empty_slots(length, values) =>
+            {
+                let mut slots: Vec<String> = values
+                    .iter()
+                    .map(|value| self.gen_function_value(PointerKind::NullableFunction, value))
+                    .collect();
+                //slots past the initializer start out empty
+                if let Some(ExprKind::Literal(Literal::Integer(ref number))) =
+                    length.as_ref().map(|length| &length.kind)
+                {
+                    let length = number.value().unwrap_or(0) as usize;
+                    slots.resize(length.max(slots.len()), "None".to_string());
+                }
+                format!("[{}]", slots.join(", "))
+            }
+            (CType::Array(inner, _), ExprKind::InitList(values)) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|value| self.gen_initializer(inner, value))
+                    .collect();
+                format!("[{}]", values.join(", "))
+            }
+            //struct P p = {1, 2}; sets the fields in the order they are declared
+            (_, ExprKind::InitList(values))
+                if type_name(ty).is_some_and(|name| {
+                    self.find_record(&name).is_some() && !self.has_constructors(&name)
+                }) =>
+            {
+                let name = type_name(ty).unwrap_or_default();
+                let record = self.find_record(&name).expect("record checked above");
+                let mut literal = format!("{} {{", self.gen_type(ty));
+                if let Some(base) = composed(&self.records, record) {
+                    literal.push_str(&format!(
+                        " base : {} ,",
+                        self.default_value(&CType::Named(base.name.clone()))
+                    ));
+                }
+                //fields left out of the initializer start out as zero
+                for (index, field) in record.fields().into_iter().enumerate() {
+                    let value = match values.get(index) {
+                        Some(value) if field.ty.is_pointer() => {
+                            self.gen_pointer_value(&field_pointer(field), value)
+                        }
+                        Some(value) => self.gen_initializer(&field.ty, value),
+                        None => self.default_value(&field.ty),
+                    };
+                    literal.push_str(&format!(" {} : {} ,", field.name, value));
+                }
+                literal.push_str(" }");
+                literal
+            }
+            _ => self.gen_converted(ty, init),
+        }
+    }
+
+    /**
+     * gen_type:
+     * rust equivalent of the c/c++ type
+     */
+    pub fn gen_type(&self, ty: &CType) -> String {
+        match *ty {
+            CType::Primitive(TokenType::Void, _) => "()".to_string(),
+            CType::Primitive(token_type, modifier) => {
+                parse_type(token_type, modifier).unwrap_or_else(|| "_".to_string())
+            }
+            CType::Named(ref name) if self.std_name(name, std_type).is_some() => {
+                self.std_name(name, std_type).unwrap_or_default()
+            }
+            //a class template names itself without arguments, Stack => Stack<T>
+            CType::Named(ref name) => name.clone() + &self.template_args(name),
+            CType::Generic(ref name, ref args) => {
+                let name = self
+                    .std_name(name, std_type)
+                    .unwrap_or_else(|| name.clone());
+                name + &self.gen_template_args(args)
+            }
+            //typedefs of function pointers are resolved before generation, the name is kept
+            CType::Function(ref ret, ref params) => self
+                .aliases
+                .iter()
+                .find(|alias| {
+                    *alias.1.pointee() == *ty
+                        && alias.1.is_function_pointer()
+                        && self.function_aliases.contains(&alias.0)
+                })
+                .map(|alias| alias.0.clone())
+                .unwrap_or_else(|| self.gen_signature("fn", ret, params)),
+            CType::Pointer(ref inner) if ty.is_function_pointer() => self.gen_type(inner),
+            //pointers of variables are translated by gen_pointer_type
+            CType::Pointer(ref inner) => {
+                if self.strict || ty.is_const_pointer() {
+                    format!("*const {}", self.gen_pointee(inner))
+                } else {
+                    format!("*mut {}", self.gen_pointee(inner))
+                }
+            }
+            CType::Array(ref inner, Some(ref length)) => {
+                format!("[{} ; {}]", self.gen_type(inner), self.gen_length(length))
+            }
+            CType::Array(ref inner, None) => format!("Vec<{}>", self.gen_type(inner)),
+            //references of variables are translated by gen_pointer_type
+            CType::Reference(ref inner) => format!("&{}", self.gen_type(inner)),
+            //*const T is chosen by the pointer around it
+            CType::Const(ref inner) => self.gen_type(inner),
+        }
+    }
+
+    // fn(A, B) -> R, without the return type for void functions
+    fn gen_signature(&self, prefix: &str, ret: &CType, params: &[CType]) -> String {
+        let params: Vec<String> = params.iter().map(|param| self.gen_type(param)).collect();
+        match *ret {
+            CType::Primitive(TokenType::Void, _) => format!("{}({})", prefix, params.join(", ")),
+            _ => format!(
+                "{}({}) -> {}",
+                prefix,
+                params.join(", "),
+                self.gen_type(ret)
+            ),
+        }
+    }
+
+    fn gen_pointee(&self, ty: &CType) -> String {
+        match *ty.unqualified() {
+            CType::Primitive(TokenType::Void, _) => "std::ffi::c_void".to_string(),
+            _ => self.gen_type(ty),
+        }
+    }
+
+    /**
+     * gen_pointer_type:
+     * rust type chosen for a pointer variable by the pointer analysis
+     */
+    fn gen_pointer_type(&self, info: &PointerInfo) -> String {
+        let inner = match info.ty {
+            CType::Pointer(ref inner) => self.gen_pointee(inner),
+            CType::Reference(ref inner) => self.gen_type(inner),
+            ref ty => self.gen_type(ty),
+        };
+        //pointers to a base class reach the derived classes through its trait
+        let inner = match *info.ty.pointee().referred() {
+            CType::Named(ref name)
+                if !matches!(info.kind, PointerKind::Slice | PointerKind::Vec)
+                    && is_base(&self.records, name)
+                    && self.non_virtual_use(info).is_none() =>
+            {
+                object_trait(&self.records, name)
+                    .map(|name| format!("dyn {}", name))
+                    .unwrap_or(inner)
+            }
+            _ => inner,
+        };
+        let reference = if info.mutable {
+            format!("&mut {}", inner)
+        } else {
+            format!("&{}", inner)
+        };
+        match info.kind {
+            PointerKind::Borrowed => reference,
+            PointerKind::Nullable => format!("Option<{}>", reference),
+            PointerKind::Owned => format!("Box<{}>", inner),
+            PointerKind::NullableOwned => format!("Option<Box<{}>>", inner),
+            PointerKind::Str => "&str".to_string(),
+            PointerKind::Slice if info.mutable => format!("&mut [{}]", inner),
+            PointerKind::Slice => format!("&[{}]", inner),
+            PointerKind::Vec => format!("Vec<{}>", inner),
+            PointerKind::Function => inner,
+            PointerKind::NullableFunction => format!("Option<{}>", inner),
+            PointerKind::Callback => match info.ty.pointee() {
+                CType::Function(ref ret, ref params) => {
+                    format!("&dyn {}", self.gen_signature("Fn", ret, params))
+                }
+                _ => inner,
+            },
+            PointerKind::Raw if info.mutable && !info.ty.is_const_pointer() => {
+                format!("*mut {}", inner)
+            }
+            PointerKind::Raw => format!("*const {}", inner),
+        }
+    }
+
+    // comment recording why the pointer got its rust type
+    fn gen_pointer_doc(&self, info: &PointerInfo) -> String {
+        match info.kind {
+            PointerKind::Raw => format!(
+                "{} {}: {}\n */\n",
+                NO_POINTER.get_doc(),
+                info.name,
+                info.reason
+            ),
+            _ => match self.non_virtual_use(info) {
+                Some(reached) => format!(
+                    "\n//{}: {}, {}, {}\n",
+                    info.name,
+                    self.gen_pointer_type(info),
+                    info.reason,
+                    reached
+                ),
+                None => format!(
+                    "\n//{}: {}, {}\n",
+                    info.name,
+                    self.gen_pointer_type(info),
+                    info.reason
+                ),
+            },
+        }
+    }
+
+    // use of a data member or non-virtual method the trait object could not make
+    fn non_virtual_use(&self, info: &PointerInfo) -> Option<&String> {
+        //fields of the class are generated outside of any function
+        self.pointers.as_ref()?;
+        self.non_virtual
+            .iter()
+            .find(|found| found.0 == info.name)
+            .map(|found| &found.1)
+    }
+
+    /**
+     * gen_pointer_value:
+     * value stored in a translated pointer, NULL => None and &a => Some(&mut a)
+     * for nullable pointers, malloc(sizeof(T)) => Box::new(..) for owned ones
+     */
+    fn gen_pointer_value(&self, info: &PointerInfo, value: &Expr) -> String {
+        let wrap = |value: String| {
+            if info.kind.is_nullable() {
+                format!("Some({})", value)
+            } else {
+                value
+            }
+        };
+        match value.kind {
+            _ if info.kind.is_function() => self.gen_function_value(info.kind, value),
+            //int &r = a => &mut a, references are passed on as they are
+            ExprKind::Ident(ref name) if info.ty.is_reference() && self.is_reference(name) => {
+                name.clone()
+            }
+            _ if info.ty.is_reference() && info.mutable => {
+                format!("&mut {}", self.gen_operand(value, UNARY))
+            }
+            _ if info.ty.is_reference() => format!("&{}", self.gen_operand(value, UNARY)),
+            _ if is_null(value) => match info.kind {
+                PointerKind::Nullable | PointerKind::NullableOwned => "None".to_string(),
+                PointerKind::Vec => "Vec::new()".to_string(),
+                PointerKind::Raw if !info.mutable || info.ty.is_const_pointer() => {
+                    "std::ptr::null()".to_string()
+                }
+                _ => "std::ptr::null_mut()".to_string(),
+            },
+            //&a[i] => &mut a[i..] for slices
+            ExprKind::Unary(UnaryOp::AddressOf, ref operand) if info.kind == PointerKind::Slice => {
+                match operand.kind {
+                    ExprKind::Index(ref base, ref index) => {
+                        self.gen_rest(&self.gen_operand(base, ATOM), Some(index), info.mutable)
+                    }
+                    _ if info.mutable => format!(
+                        "std::slice::from_mut(&mut {})",
+                        self.gen_operand(operand, UNARY)
+                    ),
+                    _ => format!(
+                        "std::slice::from_ref(&{})",
+                        self.gen_operand(operand, UNARY)
+                    ),
+                }
+            }
+            ExprKind::Unary(UnaryOp::AddressOf, ref operand) => {
+                let operand = self.gen_operand(operand, UNARY);
+                if info.mutable {
+                    wrap(format!("&mut {}", operand))
+                } else {
+                    wrap(format!("&{}", operand))
+                }
+            }
+            _ if (info.kind.is_owned() || info.kind == PointerKind::Vec)
+                && allocation(value).is_some() =>
+            {
+                wrap(self.gen_allocation(info, value))
+            }
+            //a raw pointer takes the value out of its box, free(p) puts it back
+            _ if info.kind == PointerKind::Raw
+                && allocation(value).is_some()
+                && is_single_allocation(value, info.ty.pointee()) =>
+            {
+                format!("Box::into_raw({})", self.gen_allocation(info, value))
+            }
+            ExprKind::Conditional(ref cond, ref then, ref otherwise) => format!(
+                "if {} {{ {} }} else {{ {} }}",
+                self.gen_cond(cond),
+                self.gen_pointer_value(info, then),
+                self.gen_pointer_value(info, otherwise)
+            ),
+            //void * only takes other pointers through a cast
+            ExprKind::Ident(ref name)
+                if info.kind == PointerKind::Raw
+                    && matches!(*info.ty.base(), CType::Primitive(TokenType::Void, _))
+                    && self.pointer_info(name).is_some() =>
+            {
+                let pointer = if info.mutable && !info.ty.is_const_pointer() {
+                    "*mut"
+                } else {
+                    "*const"
+                };
+                format!(
+                    "{} as {} _ as {}",
+                    name,
+                    pointer,
+                    self.gen_pointer_type(info)
+                )
+            }
+            //p = q + i
+            ExprKind::Binary(BinaryOp::Add, ..) | ExprKind::Binary(BinaryOp::Sub, ..)
+                if self.gen_pointer_offset(value, info.mutable).is_some() =>
+            {
+                self.gen_pointer_offset(value, info.mutable)
+                    .expect("pointer checked above")
+            }
+            //p = arr + i
+            ExprKind::Binary(BinaryOp::Add, ref base, ref offset)
+                if info.kind == PointerKind::Slice && matches!(base.kind, ExprKind::Ident(_)) =>
+            {
+                self.gen_rest(&self.gen_expr(base), Some(offset), info.mutable)
+            }
+            //p = arr + i, the address of the element for other pointers
+            ExprKind::Binary(BinaryOp::Add, ..) if self.array_address(value).is_some() => {
+                let (array, index) = self.array_address(value).expect("array checked above");
+                let index = index.expect("arr + i has an index");
+                match info.kind {
+                    PointerKind::Raw => format!(
+                        "unsafe {{ {} }}",
+                        self.gen_array_pointer(array, Some(index), info.mutable)
+                    ),
+                    _ if info.mutable => wrap(format!(
+                        "&mut {}[{}]",
+                        self.gen_operand(array, ATOM),
+                        self.gen_index(index)
+                    )),
+                    _ => wrap(format!(
+                        "&{}[{}]",
+                        self.gen_operand(array, ATOM),
+                        self.gen_index(index)
+                    )),
+                }
+            }
+            //arrays decay to the slice of all their elements
+            ExprKind::Ident(ref name) if self.pointer_info(name).is_none() => match info.kind {
+                PointerKind::Slice => self.gen_rest(name, None, info.mutable),
+                PointerKind::Raw if info.mutable => format!("{}.as_mut_ptr()", name),
+                PointerKind::Raw => format!("{}.as_ptr()", name),
+                _ => name.clone(),
+            },
+            ExprKind::Ident(ref name) => match self.pointer_info(name) {
+                //a raw pointer is only borrowed from inside unsafe
+                Some(source)
+                    if source.kind == PointerKind::Raw
+                        && matches!(
+                            info.kind,
+                            PointerKind::Borrowed | PointerKind::Nullable | PointerKind::Slice
+                        ) =>
+                {
+                    let reference = if info.mutable { "&mut" } else { "&" };
+                    match info.kind {
+                        PointerKind::Nullable if info.mutable => {
+                            format!("unsafe {{ {}.as_mut() }}", name)
+                        }
+                        PointerKind::Nullable => format!("unsafe {{ {}.as_ref() }}", name),
+                        PointerKind::Slice if info.mutable => {
+                            format!("std::slice::from_mut(unsafe {{ &mut *{} }})", name)
+                        }
+                        PointerKind::Slice => {
+                            format!("std::slice::from_ref(unsafe {{ &*{} }})", name)
+                        }
+                        _ => format!("unsafe {{ {} *{} }}", reference, name),
+                    }
+                }
+                Some(source) if info.kind == PointerKind::Slice => match source.kind {
+                    PointerKind::Slice | PointerKind::Vec => {
+                        self.gen_rest(name, None, info.mutable)
+                    }
+                    _ if info.mutable => format!("std::slice::from_mut({})", name),
+                    _ => format!("std::slice::from_ref({})", name),
+                },
+                Some(source)
+                    if (source.kind == PointerKind::Slice || source.kind == PointerKind::Vec)
+                        && info.kind != PointerKind::Vec =>
+                {
+                    match info.kind {
+                        PointerKind::Raw if info.mutable => format!("{}.as_mut_ptr()", name),
+                        PointerKind::Raw => format!("{}.as_ptr()", name),
+                        _ if info.mutable => wrap(format!("&mut {}[0]", name)),
+                        _ => wrap(format!("&{}[0]", name)),
+                    }
+                }
+                Some(source) if source.kind.is_owned() && !info.kind.is_owned() => {
+                    let reference = if info.mutable { "&mut" } else { "&" };
+                    match source.kind {
+                        PointerKind::NullableOwned if info.mutable => {
+                            format!("{}.as_deref_mut()", name)
+                        }
+                        PointerKind::NullableOwned => format!("{}.as_deref()", name),
+                        _ => wrap(format!("{} *{}", reference, name)),
+                    }
+                }
+                //references are cast to the raw pointer, None becomes NULL
+                Some(source)
+                    if info.kind == PointerKind::Raw
+                        && matches!(source.kind, PointerKind::Borrowed | PointerKind::Nullable) =>
+                {
+                    let target = self.gen_pointer_type(info);
+                    let cast = match target.strip_prefix("*mut ") {
+                        Some(pointee) if !source.mutable => {
+                            format!("{} as *const {} as {}", name, pointee, target)
+                        }
+                        _ => format!("{} as {}", name, target),
+                    };
+                    match source.kind {
+                        PointerKind::Nullable if target.starts_with("*mut") => {
+                            format!("{}.map_or(std::ptr::null_mut(), |{}| {})", name, name, cast)
+                        }
+                        PointerKind::Nullable => {
+                            format!("{}.map_or(std::ptr::null(), |{}| {})", name, name, cast)
+                        }
+                        _ => cast,
+                    }
+                }
+                Some(source) if !source.kind.is_nullable() => wrap(name.clone()),
+                _ => name.clone(),
+            },
+            //a pointer returned by a function that never returns NULL
+            ExprKind::Call(ref callee, _)
+                if info.kind.is_nullable()
+                    && self
+                        .returned(callee)
+                        .is_some_and(|ret| !ret.kind.is_nullable()) =>
+            {
+                wrap(self.gen_expr(value))
+            }
+            _ => self.gen_expr(value),
+        }
+    }
+
+    // translation of the pointer the called function returns
+    fn returned(&self, callee: &Expr) -> Option<&PointerInfo> {
+        match callee.kind {
+            ExprKind::Ident(ref name) => self
+                .tables
+                .iter()
+                .find(|table| table.function == *name)
+                .and_then(|table| table.ret.as_ref()),
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_function_value:
+     * function stored in a function pointer, &f and f both name the
+     * function, Some(f) for nullable ones and &f for callbacks
+     */
+    fn gen_function_value(&self, kind: PointerKind, value: &Expr) -> String {
+        if is_null(value) {
+            return "None".to_string();
+        }
+        let value = match value.kind {
+            ExprKind::Unary(UnaryOp::AddressOf, ref operand) => operand,
+            _ => value,
+        };
+        let source = match self.pointer(value) {
+            Some(source) => Some(source.kind),
+            None if self.is_nullable_function(value) => Some(PointerKind::NullableFunction),
+            None => None,
+        };
+        let function = self.gen_expr(value);
+        match (kind, source) {
+            (PointerKind::Callback, Some(PointerKind::Callback))
+            | (PointerKind::NullableFunction, Some(PointerKind::NullableFunction)) => function,
+            (PointerKind::Callback, Some(PointerKind::NullableFunction)) => {
+                format!("&{}.unwrap()", function)
+            }
+            (PointerKind::Callback, _) => format!("&{}", function),
+            (PointerKind::NullableFunction, _) => format!("Some({})", function),
+            (_, Some(PointerKind::NullableFunction)) => format!("{}.unwrap()", function),
+            _ => function,
+        }
+    }
+
+    // function pointer that may be NULL: variables, struct fields and slots of tables
+    fn is_nullable_function(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Ident(_) => self
+                .pointer(expr)
+                .is_some_and(|info| info.kind == PointerKind::NullableFunction),
+            ExprKind::Member(_, ref member, _) => self.records.iter().any(|record| {
+                record
+                    .fields()
+                    .iter()
+                    .any(|field| field.name == *member && field.ty.is_function_pointer())
+            }),
+            ExprKind::Index(ref base, _) => {
+                matches!(base.kind, ExprKind::Ident(ref name) if self.function_tables.contains(name))
+            }
+            _ => false,
+        }
+    }
+
+    // called expression, (*f)(x) calls the function f points to
+    fn gen_callee(&self, callee: &Expr) -> String {
+        let callee = match callee.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => operand,
+            _ => callee,
+        };
+        //std::max(a, b) => std::cmp::max(a, b)
+        if let ExprKind::Ident(ref name) = callee.kind {
+            if let Some(path) = self
+                .std_name(name, std_function)
+                .filter(|_| !self.is_function_name(name))
+            {
+                return path;
+            }
+        }
+        if self.is_nullable_function(callee) {
+            format!("{}.unwrap()", self.gen_operand(callee, ATOM))
+        } else {
+            self.gen_operand(callee, ATOM)
+        }
+    }
+
+    // whether the name is one of the functions of the translation unit
+    fn is_function_name(&self, name: &str) -> bool {
+        self.tables.iter().any(|table| table.function == name)
+    }
+
+    /**
+     * gen_allocation:
+     * memory owned by the pointer, malloc(sizeof(T)) => Box::new(..),
+     * malloc(n * sizeof(T)) => vec![..; n] and realloc(p, n * sizeof(T))
+     * resizes the vector of p
+     */
+    fn gen_allocation(&self, info: &PointerInfo, value: &Expr) -> String {
+        let mut value = value;
+        while let ExprKind::Cast(_, ref operand) = value.kind {
+            value = operand;
+        }
+        let pointee = info.ty.pointee();
+        let count = allocated_count(value, pointee);
+        match (&value.kind, count) {
+            (ExprKind::New(ref ty, ref args), _) => self.gen_new(ty, args),
+            (ExprKind::Call(_, ref args), Some(count))
+                if info.kind == PointerKind::Vec && allocation(value) == Some("realloc") =>
+            {
+                match args[0].kind {
+                    ExprKind::Ident(ref source) => format!(
+                        "{{ {}; {} }}",
+                        self.gen_resize(source, pointee, count),
+                        source
+                    ),
+                    _ => format!(
+                        "vec![{} ; {}]",
+                        self.default_value(pointee),
+                        self.gen_length(count)
+                    ),
+                }
+            }
+            (_, Some(count)) if info.kind == PointerKind::Vec => format!(
+                "vec![{} ; {}]",
+                self.default_value(pointee),
+                self.gen_length(count)
+            ),
+            _ => format!("Box::new({})", self.default_value(pointee)),
+        }
+    }
+
+    // p = realloc(p, n * sizeof(T)); => p.resize(n, ..);
+    fn gen_resize(&self, name: &str, pointee: &CType, count: &Expr) -> String {
+        format!(
+            "{}.resize({}, {})",
+            name,
+            self.gen_length(count),
+            self.default_value(pointee)
+        )
+    }
+
+    // realloc of the pointer's own vector, resized in place
+    fn gen_realloc(&self, info: &PointerInfo, value: &Expr) -> Option<String> {
+        let pointee = match (info.kind, &info.ty) {
+            (PointerKind::Vec, CType::Pointer(_)) => info.ty.pointee(),
+            _ => return None,
+        };
+        let mut value = value;
+        while let ExprKind::Cast(_, ref operand) = value.kind {
+            value = operand;
+        }
+        match value.kind {
+            ExprKind::Call(_, ref args)
+                if allocation(value) == Some("realloc")
+                    && args[0].kind == ExprKind::Ident(info.name.clone()) =>
+            {
+                let count = allocated_count(value, pointee)?;
+                Some(self.gen_resize(&info.name, pointee, count))
+            }
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_new:
+     * new T(args) => Box::new(T::new(args)), new int(1) => Box::new(1)
+     * and new T[n] => vec![..; n]
+     */
+    fn gen_new(&self, ty: &CType, args: &[Expr]) -> String {
+        match *ty {
+            CType::Array(ref inner, Some(ref length)) => format!(
+                "vec![{} ; {}]",
+                self.default_value(inner),
+                self.gen_length(length)
+            ),
+            CType::Named(ref name) if !args.is_empty() || self.has_constructors(name) => {
+                format!("Box::new({})", self.gen_construction(name, args))
+            }
+            _ if args.len() == 1 => format!("Box::new({})", self.gen_expr(&args[0])),
+            _ => format!("Box::new({})", self.default_value(ty)),
+        }
+    }
+
+    fn pointer_info(&self, name: &str) -> Option<&PointerInfo> {
+        self.pointers.as_ref().and_then(|table| table.find(name))
+    }
+
+    // translation of the pointer variable the expression names
+    fn is_reference(&self, name: &str) -> bool {
+        self.pointer_info(name)
+            .is_some_and(|info| info.ty.is_reference())
+    }
+
+    fn pointer(&self, expr: &Expr) -> Option<&PointerInfo> {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self.pointer_info(name),
+            _ => None,
+        }
+    }
+
+    // array and index of the decayed arrays arr, arr + i and &arr[i]
+    fn array_address<'b>(&self, expr: &'b Expr) -> Option<(&'b Expr, Option<&'b Expr>)> {
+        let is_array = |expr: &Expr| match expr.kind {
+            ExprKind::Ident(ref name) if self.pointer_info(name).is_none() => {
+                matches!(self.expr_type(expr), Some(CType::Array(..)))
+            }
+            _ => false,
+        };
+        match expr.kind {
+            ExprKind::Ident(_) if is_array(expr) => Some((expr, None)),
+            ExprKind::Binary(BinaryOp::Add, ref array, ref index)
+            | ExprKind::Binary(BinaryOp::Add, ref index, ref array)
+                if is_array(array) =>
+            {
+                Some((array, Some(index)))
+            }
+            ExprKind::Unary(UnaryOp::AddressOf, ref operand) => match operand.kind {
+                ExprKind::Index(ref array, ref index) if is_array(array) => {
+                    Some((array, Some(index)))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // arr + i as a raw pointer, arr.as_ptr().add(i), offsets need unsafe
+    fn gen_array_pointer(&self, array: &Expr, index: Option<&Expr>, mutable: bool) -> String {
+        let pointer = if mutable {
+            format!("{}.as_mut_ptr()", self.gen_operand(array, ATOM))
+        } else {
+            format!("{}.as_ptr()", self.gen_operand(array, ATOM))
+        };
+        match index {
+            Some(index) => format!("{}.add({})", pointer, self.gen_index(index)),
+            None => pointer,
+        }
+    }
+
+    /**
+     * gen_difference:
+     * p - q between raw pointers counts the elements in between, as isize
+     * unless it is stored into another integer type
+     */
+    fn gen_difference(&self, expr: &Expr, ty: Option<&CType>) -> Option<String> {
+        let (lhs, rhs) = match expr.kind {
+            ExprKind::Binary(BinaryOp::Sub, ref lhs, ref rhs)
+                if self.pointer(lhs).is_some() || self.pointer(rhs).is_some() =>
+            {
+                (lhs, rhs)
+            }
+            _ => return None,
+        };
+        let difference = format!(
+            "{}.offset_from({})",
+            self.gen_raw_address(lhs)?,
+            self.gen_raw_address(rhs)?
+        );
+        match ty.map(|ty| self.gen_type(ty)) {
+            Some(ty) if ty != "isize" => Some(format!("unsafe {{ {} as {} }}", difference, ty)),
+            _ => Some(format!("unsafe {{ {} }}", difference)),
+        }
+    }
+
+    // raw pointer operand of p - q, a pointer variable or a decayed array
+    fn gen_raw_address(&self, expr: &Expr) -> Option<String> {
+        match self.pointer(expr) {
+            Some(_) => Some(self.gen_expr(expr)),
+            None => {
+                let (array, index) = self.array_address(expr)?;
+                Some(self.gen_array_pointer(array, index, false))
+            }
+        }
+    }
+
+    // pointer, index and direction of *p, *(p + i), *(p - i) and p[i]
+    fn element<'b>(&self, expr: &'b Expr) -> Option<(&PointerInfo, Option<&'b Expr>, bool)> {
+        let (info, index, backwards) = match expr.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => match operand.kind {
+                ExprKind::Ident(_) => (self.pointer(operand)?, None, false),
+                ExprKind::Binary(BinaryOp::Add, ref base, ref index)
+                    if self.pointer(base).is_some() =>
+                {
+                    (self.pointer(base)?, Some(&**index), false)
+                }
+                ExprKind::Binary(BinaryOp::Add, ref index, ref base) => {
+                    (self.pointer(base)?, Some(&**index), false)
+                }
+                ExprKind::Binary(BinaryOp::Sub, ref base, ref index)
+                    if self.pointer(index).is_none() =>
+                {
+                    (self.pointer(base)?, Some(&**index), true)
+                }
+                _ => return None,
+            },
+            ExprKind::Index(ref base, ref index) => (self.pointer(base)?, Some(&**index), false),
+            _ => return None,
+        };
+        match info.kind {
+            PointerKind::Slice | PointerKind::Vec | PointerKind::Str | PointerKind::Raw => {
+                Some((info, index, backwards))
+            }
+            _ if index.is_none() => Some((info, index, backwards)),
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_element:
+     * element read or written through a translated pointer, *p => p[0]
+     * for slices, the next char for strings and an offset for raw pointers
+     */
+    fn gen_element(
+        &self,
+        info: &PointerInfo,
+        index: Option<&Expr>,
+        backwards: bool,
+        place: bool,
+    ) -> String {
+        let name = &info.name;
+        match (info.kind, index) {
+            (PointerKind::Slice, None) | (PointerKind::Vec, None) => format!("{}[0]", name),
+            (PointerKind::Slice, Some(index)) | (PointerKind::Vec, Some(index)) => {
+                format!("{}[{}]", name, self.gen_index(index))
+            }
+            (PointerKind::Str, None) => format!("{}.chars().next().unwrap_or('\\0')", name),
+            (PointerKind::Str, Some(index)) => format!(
+                "{}.chars().nth({}).unwrap_or('\\0')",
+                name,
+                self.gen_index(index)
+            ),
+            (PointerKind::Raw, _) => {
+                let target = match index {
+                    //p[0] is *p
+                    Some(index) if is_zero(index) => format!("*{}", name),
+                    Some(index) => {
+                        format!("*{}.offset({})", name, self.gen_offset(index, backwards))
+                    }
+                    None => format!("*{}", name),
+                };
+                if place {
+                    target
+                } else {
+                    format!("unsafe {{ {} }}", target)
+                }
+            }
+            (kind, _) if kind.is_nullable() && place => {
+                format!("*{}.as_deref_mut().unwrap()", name)
+            }
+            (kind, _) if kind.is_nullable() => format!("*{}.as_deref().unwrap()", name),
+            _ => format!("*{}", name),
+        }
+    }
+
+    // raw pointers are offset by isize, p[-1] the same as *(p - 1)
+    fn gen_offset(&self, offset: &Expr, backwards: bool) -> String {
+        if let ExprKind::Unary(UnaryOp::Minus, ref operand) = offset.kind {
+            if let ExprKind::Literal(Literal::Integer(_)) = operand.kind {
+                return self.gen_offset(operand, !backwards);
+            }
+        }
+        match (&offset.kind, backwards) {
+            (ExprKind::Literal(Literal::Integer(_)), false) => self.gen_expr(offset),
+            (ExprKind::Literal(Literal::Integer(_)), true) => format!("-{}", self.gen_expr(offset)),
+            (_, false) => format!("{} as isize", self.gen_operand(offset, CAST)),
+            (_, true) => format!("-({} as isize)", self.gen_operand(offset, CAST)),
+        }
+    }
+
+    // &a[i..], the elements from i on
+    fn gen_rest(&self, base: &str, start: Option<&Expr>, mutable: bool) -> String {
+        let start = match start {
+            Some(start) => self.gen_index(start),
+            None => String::new(),
+        };
+        if mutable {
+            format!("&mut {}[{}..]", base, start)
+        } else {
+            format!("&{}[{}..]", base, start)
+        }
+    }
+
+    // p + i and p - i as a value, the rest of a slice or an offset raw pointer
+    fn gen_pointer_offset(&self, expr: &Expr, mutable: bool) -> Option<String> {
+        let (base, offset, backwards) = match expr.kind {
+            ExprKind::Binary(BinaryOp::Add, ref base, ref offset)
+                if self.pointer(base).is_some() =>
+            {
+                (base, offset, false)
+            }
+            ExprKind::Binary(BinaryOp::Add, ref offset, ref base) => (base, offset, false),
+            ExprKind::Binary(BinaryOp::Sub, ref base, ref offset) => (base, offset, true),
+            _ => return None,
+        };
+        let info = self.pointer(base)?;
+        match info.kind {
+            PointerKind::Raw if self.pointer(offset).is_some() => None,
+            PointerKind::Raw => Some(format!(
+                "unsafe {{ {}.offset({}) }}",
+                info.name,
+                self.gen_offset(offset, backwards)
+            )),
+            PointerKind::Slice | PointerKind::Vec | PointerKind::Str if !backwards => {
+                Some(self.gen_rest(
+                    &info.name,
+                    Some(offset),
+                    mutable && info.kind != PointerKind::Str,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_step:
+     * p++ and p += n, slices move on to their rest and raw pointers
+     * are offset, p = &p[1..] and p = unsafe { p.add(1) }
+     */
+    fn gen_step(
+        &self,
+        info: &PointerInfo,
+        count: Option<&Expr>,
+        backwards: bool,
+    ) -> Option<String> {
+        let name = &info.name;
+        let start = match count {
+            Some(count) => self.gen_index(count),
+            None => "1".to_string(),
+        };
+        let step = match info.kind {
+            //a mutable slice can't be reborrowed from itself
+            PointerKind::Slice if info.mutable && !backwards => {
+                format!("{0} = &mut std::mem::take(&mut {0})[{1}..]", name, start)
+            }
+            PointerKind::Slice | PointerKind::Str if !backwards => {
+                format!("{0} = &{0}[{1}..]", name, start)
+            }
+            PointerKind::Raw => {
+                let moved = match (count, backwards) {
+                    (None, false) => format!("{}.add(1)", name),
+                    (None, true) => format!("{}.sub(1)", name),
+                    (Some(count), _) => {
+                        format!("{}.offset({})", name, self.gen_offset(count, backwards))
+                    }
+                };
+                format!("{} = unsafe {{ {} }}", name, moved)
+            }
+            _ => return None,
+        };
+        Some(step)
+    }
+
+    // p++, ++p, p-- and --p moving a slice or raw pointer
+    fn step_of(&self, expr: &Expr) -> Option<String> {
+        let (operand, backwards) = match expr.kind {
+            ExprKind::Postfix(PostfixOp::Increment, ref operand)
+            | ExprKind::Unary(UnaryOp::PreIncrement, ref operand) => (operand, false),
+            ExprKind::Postfix(PostfixOp::Decrement, ref operand)
+            | ExprKind::Unary(UnaryOp::PreDecrement, ref operand) => (operand, true),
+            _ => return None,
+        };
+        self.gen_step(self.pointer(operand)?, None, backwards)
+    }
+
+    // p used as a condition, p.is_some() and !p.is_null()
+    fn gen_null_test(&self, expr: &Expr, is_null: bool) -> Option<String> {
+        //*s tests for the end of the string
+        if let ExprKind::Unary(UnaryOp::Deref, ref operand) = expr.kind {
+            let info = self.pointer(operand)?;
+            return match (info.kind, is_null) {
+                (PointerKind::Str, true) => Some(format!("{}.is_empty()", info.name)),
+                (PointerKind::Str, false) => Some(format!("!{}.is_empty()", info.name)),
+                _ => None,
+            };
+        }
+        //fields and table slots holding functions
+        if self.pointer(expr).is_none() && self.is_nullable_function(expr) {
+            let slot = self.gen_operand(expr, ATOM);
+            return match is_null {
+                true => Some(format!("{}.is_none()", slot)),
+                false => Some(format!("{}.is_some()", slot)),
+            };
+        }
+        let info = self.pointer(expr)?;
+        let test = match (info.kind, is_null) {
+            (kind, true) if kind.is_nullable() => format!("{}.is_none()", info.name),
+            (kind, false) if kind.is_nullable() => format!("{}.is_some()", info.name),
+            (PointerKind::Raw, true) => format!("{}.is_null()", info.name),
+            (PointerKind::Raw, false) => format!("!{}.is_null()", info.name),
+            _ => return None,
+        };
+        Some(test)
+    }
+
+    // condition of if, while and for statements
+    fn gen_cond(&self, cond: &Expr) -> String {
+        match cond.kind {
+            //a negated number tests it against 0 without the int conversion
+            ExprKind::Unary(UnaryOp::Not, ref operand) if self.is_number(operand) => {
+                format!(
+                    "{} == 0",
+                    self.gen_int_operand(operand, BinaryOp::Eq.rust_precedence() + 1)
+                )
+            }
+            _ => self
+                .gen_null_test(cond, false)
+                .unwrap_or_else(|| self.gen_expr(cond)),
+        }
+    }
+
+    /**
+     * is_number:
+     * whether the value is known to be an integer or floating number, c's
+     * logical ! on it is not rust's bitwise !
+     */
+    fn is_number(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Literal(Literal::Integer(_)) | ExprKind::Literal(Literal::Float(_)) => true,
+            //& | ^ also combine bools
+            ExprKind::Binary(BinaryOp::BitAnd, ref lhs, ref rhs)
+            | ExprKind::Binary(BinaryOp::BitOr, ref lhs, ref rhs)
+            | ExprKind::Binary(BinaryOp::BitXor, ref lhs, ref rhs) => {
+                self.is_number(lhs) || self.is_number(rhs)
+            }
+            ExprKind::Binary(op, _, _) => {
+                !op.is_comparison() && op != BinaryOp::And && op != BinaryOp::Or
+            }
+            ExprKind::Unary(UnaryOp::Plus, _)
+            | ExprKind::Unary(UnaryOp::Minus, _)
+            | ExprKind::Unary(UnaryOp::BitNot, _) => true,
+            ExprKind::Unary(UnaryOp::Not, ref operand) => self.is_number(operand),
+            _ => match self.expr_type(expr) {
+                Some(CType::Primitive(TokenType::Boolean, _))
+                | Some(CType::Primitive(TokenType::Character, _)) => false,
+                Some(ref ty) => is_arithmetic(ty) || self.enum_named(ty).is_some(),
+                None => false,
+            },
+        }
+    }
+
+    // !x on a number, generated as a cast of the comparison with 0
+    fn is_number_not(&self, expr: &Expr) -> bool {
+        matches!(expr.kind, ExprKind::Unary(UnaryOp::Not, ref operand) if self.is_number(operand))
+    }
+
+    fn gen_return_value(&self, value: &Expr) -> String {
+        let ret = self.pointers.as_ref().and_then(|table| table.ret.as_ref());
+        match ret {
+            //Display::fmt returns the stream to chain further output
+            _ if self.stream.is_some() => match value.kind {
+                ExprKind::Ident(ref name) if Some(name) == self.stream.as_ref() => {
+                    "Ok(())".to_string()
+                }
+                _ => format!("{{ {}; Ok(()) }}", self.gen_expr(value)),
+            },
+            Some(ret) => self.gen_pointer_value(ret, value),
+            None if self.returned_borrow.is_empty() => match self.return_type {
+                Some(ref ty) => self.gen_converted(ty, value),
+                None => self.gen_expr(value),
+            },
+            None => self.gen_returned_borrow(value),
+        }
+    }
+
+    // element Index returns, the branches of a conditional are borrowed one by one
+    fn gen_returned_borrow(&self, value: &Expr) -> String {
+        match value.kind {
+            ExprKind::Conditional(ref cond, ref then, ref otherwise) => format!(
+                "if {} {{ {} }} else {{ {} }}",
+                self.gen_cond(cond),
+                self.gen_returned_borrow(then),
+                self.gen_returned_borrow(otherwise)
+            ),
+            _ => format!("{}{}", self.returned_borrow, self.gen_operand(value, UNARY)),
+        }
+    }
+
+    // array length, named constants are typed and need a cast to usize
+    fn gen_length(&self, length: &Expr) -> String {
+        match length.kind {
+            ExprKind::Literal(Literal::Integer(_)) => self.gen_expr(length),
+            ExprKind::Ident(ref name) if self.const_param(name).is_some() => name.clone(),
+            _ => format!("{} as usize", self.gen_operand(length, CAST)),
+        }
+    }
+
+    /* gen_typedef:
+     * typedef typename newtype; => type newtype = typename;
+     */
+    fn gen_typedef(&self, typedef: &Typedef) -> Vec<String> {
+        if let CType::Named(ref name) = typedef.ty {
+            //rust doesnt allow to have struct with same name as type alias
+            if *name == typedef.name {
+                return Vec::new();
+            }
+        }
+        let mut stream: Vec<String> = self.exported().into_iter().collect();
+        stream.push("type".to_string());
+        stream.push(typedef.name.clone());
+        stream.push("=".to_string());
+        match *typedef.ty.pointee() {
+            CType::Function(ref ret, ref params) if typedef.ty.is_function_pointer() => {
+                stream.push(self.gen_signature("fn", ret, params))
+            }
+            _ => stream.push(self.gen_type(&typedef.ty)),
+        }
+        stream.push(";".to_string());
+        stream
+    }
+
+    /**
+     * gen_enum:
+     * enum => #[repr(i32)] enum with the value of every enumerator and a
+     * TryFrom impl converting integers back, an enumerator repeating the
+     * value of another one becomes an associated constant
+     */
+    fn gen_enum(&self, def: &EnumDef) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        match def.name {
+            Some(ref name) => {
+                let values = self
+                    .enums
+                    .iter()
+                    .find(|entry| entry.0.name == def.name)
+                    .map(|entry| entry.1.clone())
+                    .unwrap_or_default();
+                let repr = self.enum_repr(def);
+                stream.push(format!("#[repr({})]", repr));
+                stream.push("#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]".to_string());
+                stream.extend(self.exported());
+                stream.push("enum".to_string());
+                stream.push(name.clone());
+                stream.push("{".to_string());
+                let mut aliases: Vec<(&str, &str)> = Vec::new();
+                for (index, variant) in def.variants.iter().enumerate() {
+                    let value = values.get(index).cloned().flatten();
+                    //rust enums can't repeat a discriminant
+                    let earlier = def.variants[..index]
+                        .iter()
+                        .zip(&values)
+                        .find(|entry| value.is_some() && *entry.1 == value);
+                    if let Some((original, _)) = earlier {
+                        aliases.push((&variant.name, &original.name));
+                        continue;
+                    }
+                    stream.push(variant.name.clone());
+                    stream.push("=".to_string());
+                    stream.push(match (value, &variant.value) {
+                        (Some(value), _) => value.to_string(),
+                        (None, Some(expr)) => self.gen_expr(expr),
+                        (None, None) => {
+                            format!("{}::{} as {} + 1", name, def.variants[index - 1].name, repr)
+                        }
+                    });
+                    stream.push(",".to_string());
+                }
+                stream.push("}\n".to_string());
+                if !aliases.is_empty() {
+                    stream.push(format!("impl {} {{", name));
+                    for (alias, original) in aliases {
+                        stream.push(format!(
+                            "pub const {} : {} = {}::{} ;",
+                            alias, name, name, original
+                        ));
+                    }
+                    stream.push("}\n".to_string());
+                }
+                stream.append(&mut self.gen_try_from(def, &values, &repr));
+            }
+            None => {
+                //anonymous enum only introduces integer constants
+                let mut previous: Option<String> = None;
+                for variant in &def.variants {
+                    let value = match (&variant.value, &previous) {
+                        (Some(value), _) => self.gen_expr(value),
+                        (None, Some(previous)) => format!("{} + 1", previous),
+                        (None, None) => "0".to_string(),
+                    };
+                    stream.extend(self.exported());
+                    stream.push(format!("const {} : i32 = {} ;", variant.name, value));
+                    previous = Some(variant.name.clone());
+                }
+            }
+        }
+        stream
+    }
+
+    // whether the case labels name every variant of the enum, aliases name their original
+    fn is_exhaustive(&self, def: &EnumDef, labels: &[&Expr]) -> bool {
+        let values = self
+            .enums
+            .iter()
+            .find(|entry| entry.0.name == def.name)
+            .map(|entry| entry.1.clone())
+            .unwrap_or_default();
+        let value_of = |name: &str| {
+            def.variants
+                .iter()
+                .position(|variant| variant.name == name)
+                .map(|index| (index, values.get(index).cloned().flatten()))
+        };
+        let matched: Vec<(usize, Option<i64>)> = labels
+            .iter()
+            .filter_map(|label| match label.kind {
+                ExprKind::Ident(ref name) => value_of(name.rsplit("::").next().unwrap_or_default()),
+                _ => None,
+            })
+            .collect();
+        def.variants.iter().enumerate().all(|(index, _)| {
+            let value = values.get(index).cloned().flatten();
+            matched
+                .iter()
+                .any(|entry| entry.0 == index || (value.is_some() && entry.1 == value))
+        })
+    }
+
+    // impl TryFrom<i32>, integers that are not the value of an enumerator are errors
+    fn gen_try_from(&self, def: &EnumDef, values: &[Option<i64>], repr: &str) -> Vec<String> {
+        let name = def.name.clone().unwrap_or_default();
+        let mut stream: Vec<String> = Vec::new();
+        stream.push(format!(
+            "impl TryFrom<{}> for {} {{ type Error = {};",
+            repr, name, repr
+        ));
+        stream.push(format!(
+            "fn try_from(value: {}) -> Result<Self, Self::Error> {{ match value {{",
+            repr
+        ));
+        let mut matched: Vec<i64> = Vec::new();
+        for (variant, value) in def.variants.iter().zip(values) {
+            match *value {
+                Some(value) if matched.contains(&value) => {}
+                Some(value) => {
+                    matched.push(value);
+                    stream.push(format!("{} => Ok({}::{}),", value, name, variant.name));
+                }
+                None => stream.push(format!(
+                    "value if value == {}::{} as {} => Ok({}::{}),",
+                    name, variant.name, repr, name, variant.name
+                )),
+            }
+        }
+        stream.push("_ => Err(value), } } }\n".to_string());
+        stream
+    }
+
+    // integer type of the enum, i32 unless an underlying type is given
+    fn enum_repr(&self, def: &EnumDef) -> String {
+        match def.underlying {
+            Some(ref ty) => self.gen_type(ty),
+            None => "i32".to_string(),
+        }
+    }
+
+    // value of an enum left uninitialized, Color::RED
+    fn enum_default(&self, ty: &CType) -> String {
+        let (def, values) = match self
+            .enum_named(ty)
+            .and_then(|def| self.enums.iter().find(|entry| entry.0.name == def.name))
+        {
+            Some(entry) => (&entry.0, &entry.1),
+            None => return "Default::default()".to_string(),
+        };
+        let index = values
+            .iter()
+            .position(|value| *value == Some(0))
+            .unwrap_or(0);
+        match def.variants.get(index) {
+            Some(variant) => format!("{}::{}", def.name.clone().unwrap_or_default(), variant.name),
+            None => "Default::default()".to_string(),
+        }
+    }
+
+    // named enum a type refers to, through typedefs as well
+    fn enum_named(&self, ty: &CType) -> Option<&EnumDef> {
+        let mut name = match *ty {
+            CType::Named(ref name) => name.rsplit("::").next().unwrap_or_default().to_string(),
+            _ => return None,
+        };
+        while let Some(&(_, CType::Named(ref aliased))) =
+            self.aliases.iter().find(|alias| alias.0 == name)
+        {
+            if *aliased == name {
+                break;
+            }
+            name = aliased.clone();
+        }
+        self.enums
+            .iter()
+            .map(|entry| &entry.0)
+            .find(|def| def.name.as_ref() == Some(&name))
+    }
+
+    // enum of an enumerator, RED of an enum or Color::RED of any
+    fn enumerator(&self, name: &str) -> Option<&EnumDef> {
+        let (scope, variant) = match name.rfind("::") {
+            Some(index) => (Some(&name[..index]), &name[index + 2..]),
+            None => (None, name),
+        };
+        self.enums.iter().map(|entry| &entry.0).find(|def| {
+            let in_scope = match scope {
+                Some(scope) => def.name.as_deref() == scope.rsplit("::").next(),
+                None => !def.scoped,
+            };
+            in_scope && def.variants.iter().any(|known| known.name == variant)
+        })
+    }
+
+    // enum of the value of an expression
+    fn enum_of(&self, expr: &Expr) -> Option<&EnumDef> {
+        self.enum_named(&self.expr_type(expr)?)
+    }
+
+    /**
+     * expr_type:
+     * declared type of a variable, field, enumerator or call result as
+     * far as the generator knows them, None otherwise
+     */
+    fn expr_type(&self, expr: &Expr) -> Option<CType> {
+        let field = |name: &str| {
+            self.records
+                .iter()
+                .flat_map(|record| record.fields())
+                .find(|field| field.name == name)
+                .map(|field| field.ty.clone())
+        };
+        let method = |name: &str| {
+            self.records
+                .iter()
+                .flat_map(|record| methods(record))
+                .find(|method| method.name == name)
+                .map(|method| method.return_type.clone())
+        };
+        match expr.kind {
+            ExprKind::Ident(ref name) => self
+                .variables
+                .iter()
+                .rev()
+                .chain(self.globals.iter().rev())
+                .find(|variable| variable.0 == *name)
+                .map(|variable| variable.1.clone())
+                .or_else(|| {
+                    self.enumerator(name)
+                        .and_then(|def| def.name.clone())
+                        .map(CType::Named)
+                })
+                .or_else(|| field(name)),
+            ExprKind::Member(ref base, ref name, _) => {
+                let class = self.expr_type(base).and_then(|ty| match *ty.referred() {
+                    CType::Pointer(ref inner) => self.find_record(&type_name(inner)?),
+                    ref ty => self.find_record(&type_name(ty)?),
+                });
+                class
+                    .and_then(|record| {
+                        record
+                            .fields()
+                            .into_iter()
+                            .find(|known| known.name == *name)
+                            .map(|known| known.ty.clone())
+                    })
+                    .or_else(|| field(name))
+            }
+            ExprKind::Call(ref callee, _) => match callee.kind {
+                ExprKind::Ident(ref name) => self
+                    .prototypes
+                    .iter()
+                    .find(|function| function.name == *name)
+                    .map(|function| function.return_type.clone())
+                    .or_else(|| method(name)),
+                ExprKind::Member(_, ref name, _) => method(name),
+                _ => None,
+            },
+            ExprKind::Index(ref base, _) => match self.expr_type(base)? {
+                CType::Array(inner, _) => Some(*inner),
+                ty @ CType::Pointer(_) => Some(ty.pointee().clone()),
+                _ => None,
+            },
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => match self.expr_type(operand)? {
+                ty @ CType::Pointer(_) => Some(ty.pointee().clone()),
+                _ => None,
+            },
+            ExprKind::Cast(ref ty, _) => Some(ty.clone()),
+            ExprKind::Conditional(_, ref then, _) => self.expr_type(then),
+            ExprKind::Assign(_, ref lhs, _) => self.expr_type(lhs),
+            _ => None,
+        }
+    }
+
+    // rust name of the overloaded method of the class or of a base it is composed of
+    fn overload(&self, class: &str, method: &str, args: &[Expr]) -> Option<(String, &Function)> {
+        let types: Vec<Option<CType>> = args.iter().map(|arg| self.arg_type(arg)).collect();
+        let mut record = self.find_record(class);
+        while let Some(current) = record {
+            if methods(current)
+                .iter()
+                .any(|declared| declared.name == method)
+            {
+                let name = method_for(current, method, &types)?;
+                return method_names(current)
+                    .into_iter()
+                    .find(|named| named.0 == name);
+            }
+            record = composed(&self.records, current);
+        }
+        None
+    }
+
+    // overload of a method called on an object, obj.f(args) and p->f(args)
+    fn method_overload(
+        &self,
+        object: &Expr,
+        method: &str,
+        args: &[Expr],
+    ) -> Option<(String, &Function)> {
+        let class = match object.kind {
+            ExprKind::This => self.class.clone(),
+            _ => match self.expr_type(object)? {
+                CType::Pointer(inner) | CType::Reference(inner) => type_name(&inner),
+                ty => type_name(&ty),
+            },
+        };
+        self.overload(&class?, method, args)
+    }
+
+    // type of an argument, choosing between overloads
+    fn arg_type(&self, arg: &Expr) -> Option<CType> {
+        let primitive = |token_type: TokenType, modifier: Modifier| {
+            Some(CType::Primitive(token_type, modifier))
+        };
+        match arg.kind {
+            ExprKind::Literal(Literal::Integer(ref number)) => match number.suffix {
+                NumberSuffix::Unsigned => primitive(TokenType::Integer, Modifier::Unsigned),
+                NumberSuffix::Long | NumberSuffix::LongLong => {
+                    primitive(TokenType::Long, Modifier::Default)
+                }
+                NumberSuffix::UnsignedLong | NumberSuffix::UnsignedLongLong => {
+                    primitive(TokenType::Long, Modifier::Unsigned)
+                }
+                _ => primitive(TokenType::Integer, Modifier::Default),
+            },
+            ExprKind::Literal(Literal::Float(ref number))
+                if number.suffix == NumberSuffix::Float =>
+            {
+                primitive(TokenType::Float, Modifier::Default)
+            }
+            ExprKind::Literal(Literal::Float(_)) => primitive(TokenType::Double, Modifier::Default),
+            ExprKind::Literal(Literal::Char(_)) => {
+                primitive(TokenType::Character, Modifier::Default)
+            }
+            ExprKind::Literal(Literal::Bool(_)) => primitive(TokenType::Boolean, Modifier::Default),
+            ExprKind::Unary(UnaryOp::Minus, ref operand) => self.arg_type(operand),
+            _ => self.expr_type(arg),
+        }
+    }
+
+    fn is_variable(&self, name: &str) -> bool {
+        self.variables
+            .iter()
+            .chain(self.globals.iter())
+            .any(|variable| variable.0 == name)
+    }
+
+    // parameters of the function being generated, locals are added as they are declared
+    fn enter_scope(&mut self, function: &Function) {
+        self.variables = function
+            .params
+            .iter()
+            .filter_map(|param| Some((param.name.clone()?, param.ty.clone())))
+            .collect();
+        self.return_type = Some(function.return_type.clone());
+        self.labels.clear();
+        self.visible.clear();
+        self.non_virtual.clear();
+        if let Some(ref body) = function.body {
+            self.non_virtual = non_virtual_uses(&self.records, &self.variables, body);
+            labels(body, &mut self.labels);
+            visible_labels(body, &[], &mut self.visible);
+        }
+    }
+
+    /**
+     * gen_converted:
+     * value stored into a place of the type, enum values become integers
+     * with `as` and integers become enum values with TryFrom
+     */
+    fn gen_converted(&self, ty: &CType, value: &Expr) -> String {
+        let target = self.enum_named(ty);
+        let source = self.enum_of(value);
+        //size_t and ptrdiff_t are named types
+        if is_arithmetic(ty) || matches!(*ty, CType::Named(ref name) if name.ends_with("_t")) {
+            if let Some(difference) = self.gen_difference(value, Some(ty)) {
+                return difference;
+            }
+        }
+        match (target, source) {
+            (Some(target), Some(source)) if target.name == source.name => self.gen_expr(value),
+            (Some(target), _) => format!(
+                "{}::try_from({}).unwrap()",
+                target.name.clone().unwrap_or_default(),
+                self.gen_int_operand(value, 0)
+            ),
+            (None, Some(_)) if is_arithmetic(ty) => {
+                format!("{} as {}", self.gen_operand(value, CAST), self.gen_type(ty))
+            }
+            _ => self.gen_expr(value),
+        }
+    }
+
+    // operand of an operator taking integers, enum values are cast to their integer type
+    fn gen_int_operand(&self, expr: &Expr, precedence: u8) -> String {
+        match self.enum_of(expr) {
+            Some(def) => {
+                let cast = format!(
+                    "{} as {}",
+                    self.gen_operand(expr, CAST),
+                    self.enum_repr(def)
+                );
+                if CAST < precedence {
+                    format!("({})", cast)
+                } else {
+                    cast
+                }
+            }
+            None => self.gen_operand(expr, precedence),
+        }
+    }
+
+    // records the type of macro constants and which macros become macro_rules!
+    fn register_macro(&mut self, def: &MacroDef) {
+        match (&def.body, &def.params) {
+            (MacroBody::Constant(expr), _) => {
+                let ty = self.value_type(expr, &[], "");
+                self.constants.push((def.name.clone(), ty));
+            }
+            (MacroBody::Expression(expr), Some(params))
+                if def.grouped && self.fn_signature(params, expr).is_some() => {}
+            (MacroBody::Expression(_), _) | (MacroBody::Statement(_), _) => {
+                self.macro_rules.push(def.name.clone())
+            }
+            _ => {}
+        }
+    }
+
+    /**
+     * gen_macro:
+     * #define NAME 256 => const NAME: i32 = 256;
+     * function-like macros whose types follow from their literals and whose
+     * parameters are parenthesized become #[inline] functions, the other
+     * ones macro_rules! definitions, which keep F(1 + 2) grouped.
+     * Macros that were expanded in place are listed with the reason
+     */
+    fn gen_macro(&mut self, def: &MacroDef, span: Span) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let params: Vec<String> = def.params.clone().unwrap_or_default();
+        match def.body {
+            MacroBody::Constant(ref expr) => {
+                let ty = self
+                    .constant_type(&def.name)
+                    .unwrap_or_else(|| "i32".to_string());
+                stream.push(format!(
+                    "const {} : {} = {} ;",
+                    def.name,
+                    ty,
+                    self.gen_expr(expr)
+                ));
+            }
+            MacroBody::Expression(ref expr) if !self.macro_rules.contains(&def.name) => {
+                if let Some((param_type, return_type)) = self.fn_signature(&params, expr) {
+                    stream.push("#[inline]\n".to_string());
+                    stream.push("fn".to_string());
+                    stream.push(def.name.clone());
+                    stream.push("(".to_string());
+                    for param in &params {
+                        stream.push(format!("{} : {} ,", param, param_type));
+                    }
+                    stream.push(")".to_string());
+                    stream.push("->".to_string());
+                    stream.push(return_type);
+                    stream.push("{".to_string());
+                    stream.push(self.gen_expr(expr));
+                    stream.push("}".to_string());
+                }
+            }
+            MacroBody::Expression(_) | MacroBody::Statement(_) => {
+                self.macro_params = params.clone();
+                let matcher: Vec<String> = params
+                    .iter()
+                    .map(|param| format!("${}:expr", param))
+                    .collect();
+                stream.push("macro_rules!".to_string());
+                stream.push(def.name.clone());
+                stream.push("{".to_string());
+                stream.push(format!("({})", matcher.join(", ")));
+                stream.push("=>".to_string());
+                stream.push("{".to_string());
+                match def.body {
+                    MacroBody::Expression(ref expr) => stream.push(self.gen_expr(expr)),
+                    MacroBody::Statement(ref stmt) => {
+                        //do { ... } while (0) only groups the statements
+                        stream.push("{".to_string());
+                        match stmt.kind {
+                            StmtKind::DoWhile { ref body, ref cond } if is_always_false(cond) => {
+                                stream.append(&mut self.gen_unbraced(body))
+                            }
+                            _ => stream.append(&mut self.gen_unbraced(stmt)),
+                        }
+                        stream.push("}".to_string());
+                    }
+                    _ => {}
+                }
+                stream.push("}".to_string());
+                stream.push(";".to_string());
+                stream.push("}\n".to_string());
+                self.macro_params.clear();
+            }
+            MacroBody::Unsupported(ref reason) => {
+                stream.push(MACRO.get_doc().to_string());
+                stream.push(format!(
+                    "{}\n * {} ({})\n **/\n",
+                    reason,
+                    def.text.replace("*/", "* /"),
+                    location(span)
+                ));
+            }
+            MacroBody::Empty => {}
+        }
+        stream
+    }
+
+    fn constant_type(&self, name: &str) -> Option<String> {
+        self.constants
+            .iter()
+            .find(|constant| constant.0 == name)
+            .map(|constant| constant.1.clone())
+    }
+
+    /**
+     * value_type:
+     * rust type of a macro body, the types of literals and constants
+     * decide it. `params` have the type `param_type`
+     */
+    fn value_type(&self, expr: &Expr, params: &[String], param_type: &str) -> String {
+        match expr.kind {
+            ExprKind::Literal(ref literal) => {
+                literal_type(literal).unwrap_or_else(|| "i32".to_string())
+            }
+            ExprKind::Ident(ref name) if params.contains(name) => param_type.to_string(),
+            ExprKind::Ident(ref name) => self
+                .constant_type(name)
+                .unwrap_or_else(|| "i32".to_string()),
+            ExprKind::Unary(_, ref operand) => self.value_type(operand, params, param_type),
+            ExprKind::Binary(op, _, _)
+                if op.is_comparison() || op == BinaryOp::And || op == BinaryOp::Or =>
+            {
+                "bool".to_string()
+            }
+            ExprKind::Binary(op, ref lhs, ref rhs) => {
+                let lhs_type = self.value_type(lhs, params, param_type);
+                let rhs_type = self.value_type(rhs, params, param_type);
+                //integer operands are promoted when mixed with floating ones
+                if op != BinaryOp::Shl && op != BinaryOp::Shr && rhs_type.starts_with('f') {
+                    rhs_type
+                } else {
+                    lhs_type
+                }
+            }
+            ExprKind::Conditional(_, ref then, _) => self.value_type(then, params, param_type),
+            ExprKind::Cast(ref ty, _) => self.gen_type(ty),
+            ExprKind::SizeOfType(_) => "usize".to_string(),
+            _ => "i32".to_string(),
+        }
+    }
+
+    /**
+     * fn_signature:
+     * parameter and return type of a function-like macro that can be
+     * a function. The body must use each parameter once, to evaluate
+     * arguments as often as the macro would, and its literals and
+     * constants must all have one type, which the parameters take
+     */
+    fn fn_signature(&self, params: &[String], expr: &Expr) -> Option<(String, String)> {
+        let idents = expr.idents();
+        if params
+            .iter()
+            .any(|param| idents.iter().filter(|ident| *ident == param).count() > 1)
+        {
+            return None;
+        }
+        let mut types: Vec<String> = Vec::new();
+        if !self.operand_types(expr, params, &mut types) {
+            return None;
+        }
+        let param_type = types.pop()?;
+        if types.iter().any(|ty| *ty != param_type) {
+            return None;
+        }
+        let return_type = self.value_type(expr, params, &param_type);
+        Some((param_type, return_type))
+    }
+
+    // collects the types of literals and constants, false for expressions a function can not hold
+    fn operand_types(&self, expr: &Expr, params: &[String], types: &mut Vec<String>) -> bool {
+        match expr.kind {
+            ExprKind::Literal(ref literal @ Literal::Integer(_))
+            | ExprKind::Literal(ref literal @ Literal::Float(_)) => {
+                types.extend(literal_type(literal));
+                true
+            }
+            ExprKind::Ident(ref name) if params.contains(name) => true,
+            ExprKind::Ident(ref name) => match self.constant_type(name) {
+                Some(ref ty) if ty == "bool" => true,
+                Some(ty) => {
+                    types.push(ty);
+                    true
+                }
+                None => false,
+            },
+            ExprKind::Unary(UnaryOp::Plus, ref operand)
+            | ExprKind::Unary(UnaryOp::Minus, ref operand)
+            | ExprKind::Unary(UnaryOp::Not, ref operand)
+            | ExprKind::Unary(UnaryOp::BitNot, ref operand) => {
+                self.operand_types(operand, params, types)
+            }
+            ExprKind::Binary(_, ref lhs, ref rhs) => {
+                self.operand_types(lhs, params, types) && self.operand_types(rhs, params, types)
+            }
+            ExprKind::Conditional(ref cond, ref then, ref otherwise) => {
+                self.operand_types(cond, params, types)
+                    && self.operand_types(then, params, types)
+                    && self.operand_types(otherwise, params, types)
+            }
+            _ => false,
+        }
+    }
+
+    /**
+     * gen_record:
+     * struct and class become rust struct with methods wrapped in an impl block,
+     * union becomes an enum with a variant for every member
+     */
+    fn gen_record(&mut self, record: &Record) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        if record.kind == RecordKind::Union {
+            stream.push(UNION.get_doc().to_string());
+            stream.extend(self.exported());
+            stream.push("enum".to_string());
+            stream.push(record.name.clone());
+            stream.push("{".to_string());
+            for field in record.fields() {
+                stream.push(field.name.clone());
+                stream.push("(".to_string());
+                stream.push(self.gen_type(&field.ty));
+                stream.push("),".to_string());
+            }
+            stream.push("}\n".to_string());
+            return stream;
+        }
+
+        let records = self.records.clone();
+        let interface = is_interface(record);
+        let base = composed(&records, record);
+        //parameters of the class and of its member templates
+        let functions: Vec<&Function> = record
+            .members
+            .iter()
+            .filter_map(|member| match member.kind {
+                MemberKind::Method(ref function)
+                | MemberKind::Constructor(ref function)
+                | MemberKind::Destructor(ref function)
+                | MemberKind::Friend(ref function) => Some(function),
+                _ => None,
+            })
+            .collect();
+        let mut params = record.template.clone();
+        for function in &functions {
+            params.extend(function.template.iter().cloned());
+        }
+        let outer = self.enter_template(&params, &functions, &record.fields());
+        //bases that are only interfaces become traits, there is no base field
+        if base.is_some() {
+            stream.push(INHERITANCE.get_doc().to_string());
+        }
+        let mut fields: Vec<String> = Vec::new();
+        let mut method_stream: Vec<String> = Vec::new();
+        let mut constructor_stream: Vec<String> = Vec::new();
+        //virtual methods go to traits, methods of an interface to its trait
+        let mut virtuals: Vec<&Function> = Vec::new();
+        //overloaded operators go to impls of the std traits
+        let mut operators: Vec<(Function, bool)> = Vec::new();
+        let mut friend_stream: Vec<String> = Vec::new();
+        //static data members, associated consts and statics beside the struct
+        let mut consts: Vec<String> = Vec::new();
+        let mut statics: Vec<String> = Vec::new();
+        for member in &record.members {
+            match member.kind {
+                MemberKind::Field(ref decl) if decl.is_static => {
+                    for field in &decl.declarators {
+                        let item = self.gen_static_member(record, decl, field, member.access);
+                        if decl.is_const {
+                            consts.push(item);
+                        } else {
+                            statics.push(item);
+                        }
+                    }
+                }
+                MemberKind::Field(ref decl) => {
+                    for field in &decl.declarators {
+                        //fields of a struct are used outside of the module of a namespace
+                        if member.access == Access::Public
+                            && (record.kind == RecordKind::Class || self.exported().is_some())
+                        {
+                            fields.push("pub".to_string());
+                        }
+                        let pointer = Some(field_pointer(field)).filter(|_| field.ty.is_pointer());
+                        if let Some(ref info) = pointer {
+                            fields.push(self.gen_pointer_doc(info));
+                        }
+                        fields.push(field.name.clone());
+                        fields.push(":".to_string());
+                        match pointer {
+                            Some(ref info) => fields.push(self.gen_pointer_type(info)),
+                            None => fields.push(self.gen_type(&field.ty)),
+                        }
+                        fields.push(",".to_string());
+                    }
+                }
+                MemberKind::Method(ref method) if method.operator().is_some() => {
+                    operators.push((method.clone(), true));
+                }
+                MemberKind::Method(ref method)
+                    if interface || declaring(&records, record, &method.name).is_some() =>
+                {
+                    virtuals.push(method);
+                }
+                MemberKind::Friend(ref function) if function.operator().is_some() => {
+                    operators.push((function.clone(), false));
+                }
+                //friend functions defined in the class are free functions
+                MemberKind::Friend(ref function) => {
+                    friend_stream.append(&mut self.gen_function(function));
+                }
+                MemberKind::Method(ref method) => {
+                    method_stream.append(&mut self.gen_method(
+                        record,
+                        method,
+                        Some(member.access),
+                        None,
+                    ));
+                }
+                MemberKind::Constructor(ref constructor) => {
+                    constructor_stream.append(&mut self.gen_constructor(
+                        record,
+                        constructor,
+                        member.access,
+                    ));
+                }
+                MemberKind::Destructor(_) => {}
+                MemberKind::Comment(ref comment) => fields.push(comment.clone() + "\n"),
+                MemberKind::Unsupported(ref unsupported) => {
+                    fields.append(&mut gen_unsupported(unsupported))
+                }
+            }
+        }
+
+        for function in self.free_operators.clone() {
+            if self.operator_record(&function).as_ref() == Some(&record.name) {
+                operators.push((function, false));
+            }
+        }
+
+        if !interface {
+            if let Some(derive) = self.gen_derive(record, &operators) {
+                stream.push(derive);
+            }
+            stream.extend(self.exported());
+            stream.push("struct".to_string());
+            stream.push(record.name.clone() + &self.gen_generics(&record.template, false));
+            stream.push("{".to_string());
+            if let Some(base) = base {
+                stream.push(format!("base : {} ,", base.name));
+            }
+            stream.append(&mut fields);
+            stream.push("}\n".to_string());
+        } else {
+            stream.append(&mut fields);
+        }
+
+        stream.append(&mut statics);
+        //constructors are listed ahead of the methods
+        let mut impl_stream = consts;
+        impl_stream.append(&mut constructor_stream);
+        impl_stream.append(&mut method_stream);
+        if !impl_stream.is_empty() {
+            stream.push(
+                "\n\n/**Method declarations are wrapped inside the impl block \
+                 \n * Which implements the corresponding structure\
+                 \n **/\n"
+                    .to_string(),
+            );
+            stream.push("impl".to_string() + &self.gen_generics(&record.template, false));
+            stream.push(self.gen_type(&CType::Named(record.name.clone())));
+            stream.push("{\n".to_string());
+            stream.append(&mut impl_stream);
+            stream.push("}\n".to_string());
+        }
+        if let Some(base) = base {
+            stream.append(&mut gen_deref(&record.name, &base.name));
+        }
+        if let Some(destructor) = destructor(record) {
+            stream.append(&mut self.gen_drop(record, destructor));
+        }
+        stream.append(&mut self.gen_traits(&records, record, &virtuals));
+        stream.append(&mut self.gen_operators(Some(record), &operators));
+        stream.append(&mut friend_stream);
+        self.leave_template(outer);
+        stream
+    }
+
+    /**
+     * gen_static_member:
+     * static const int max = 10; => pub const max: i32 = 10; in the impl,
+     * other static data members are statics named after their class,
+     * static int count; => static mut Counter_count: i32 = 0;
+     */
+    fn gen_static_member(
+        &self,
+        record: &Record,
+        decl: &Declaration,
+        field: &Declarator,
+        access: Access,
+    ) -> String {
+        let path = format!("{}::{}", record.name, field.name);
+        let value = match field.init {
+            Some(ref init) => self.gen_converted(&field.ty, init),
+            None => match self.static_values.iter().find(|value| value.0 == path) {
+                Some(value) => self.gen_converted(&field.ty, &value.1),
+                None => self.default_value(&field.ty),
+            },
+        };
+        let visibility = if access == Access::Public { "pub " } else { "" };
+        if decl.is_const {
+            format!(
+                "{}const {} : {} = {} ;\n",
+                visibility,
+                field.name,
+                self.gen_type(&field.ty),
+                value
+            )
+        } else {
+            format!(
+                "//{}, shared by every {}\n{}static mut {} : {} = {} ;\n",
+                path,
+                record.name,
+                visibility,
+                static_name(&record.name, &field.name),
+                self.gen_type(&field.ty),
+                value
+            )
+        }
+    }
+
+    // class and declaration of the static data member A::count
+    fn static_member(&self, path: &str) -> Option<(&Record, &Declaration)> {
+        let index = path.rfind("::")?;
+        let (class, member) = (&path[..index], &path[index + 2..]);
+        let record = self.find_record(class)?;
+        record
+            .statics()
+            .into_iter()
+            .find(|field| field.1.name == member)
+            .map(|field| (record, field.0))
+    }
+
+    /**
+     * gen_static_path:
+     * static data member named in an expression, count inside its class and
+     * A::count anywhere, Self::max for consts and the static otherwise
+     */
+    fn gen_static_path(&self, name: &str) -> Option<String> {
+        let path = match self.class {
+            Some(ref class) if !name.contains("::") => {
+                //locals and parameters shadow the members
+                if self.variables.iter().any(|variable| variable.0 == name) {
+                    return None;
+                }
+                format!("{}::{}", class, name)
+            }
+            _ => name.to_string(),
+        };
+        let (record, decl) = self.static_member(&path)?;
+        let member = &path[path.rfind("::")? + 2..];
+        if !decl.is_const {
+            return Some(static_name(&record.name, member));
+        }
+        match self.class {
+            Some(ref class) if *class == record.name => Some(format!("Self::{}", member)),
+            _ => Some(path.clone()),
+        }
+    }
+
+    /**
+     * gen_constructor:
+     * constructor => associated function returning the new value, the
+     * members start out with their initializer or default value and
+     * the body runs on the value before it is returned
+     */
+    fn gen_constructor(
+        &mut self,
+        record: &Record,
+        constructor: &Function,
+        access: Access,
+    ) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let name = constructor_names(record)
+            .into_iter()
+            .find(|named| std::ptr::eq(named.1, constructor))
+            .map(|named| named.0)
+            .unwrap_or_else(|| "new".to_string());
+        self.pointers = Some(analyze_function(constructor, &self.tables, &self.receivers));
+        self.enter_scope(constructor);
+        stream.append(&mut self.gen_signature_docs());
+        if access == Access::Public {
+            stream.push("pub".to_string());
+        }
+        stream.push("fn".to_string());
+        stream.push(name);
+        stream.push("(".to_string());
+        stream.append(&mut self.gen_params(&constructor.params));
+        stream.push(")".to_string());
+        stream.push("-> Self".to_string());
+        stream.push(self.gen_where(record, constructor));
+        stream.push("{".to_string());
+        stream.push(self.gen_binding(false, false));
+        stream.push("this =".to_string());
+        stream.push(self.gen_constructed(record, &constructor.initializers));
+        stream.push(";".to_string());
+        if let Some(ref body) = constructor.body {
+            self.enter_members(record, constructor, "this");
+            stream.append(&mut self.gen_stmts(body));
+            self.leave_members();
+        }
+        stream.push("this".to_string());
+        stream.push("}".to_string());
+        self.pointers = None;
+        stream
+    }
+
+    // value built before the constructor body runs, from the member initializers
+    fn gen_constructed(&self, record: &Record, initializers: &[Initializer]) -> String {
+        let initializer = |member: &str| {
+            initializers
+                .iter()
+                .find(|initializer| initializer.member == member)
+        };
+        let mut literal = format!("{} {{", record.name);
+        if let Some(base) = composed(&self.records, record) {
+            let value = match initializer(&base.name) {
+                Some(initializer) => self.gen_construction(&base.name, &initializer.args),
+                None => self.default_value(&CType::Named(base.name.clone())),
+            };
+            literal.push_str(&format!(" base : {} ,", value));
+        }
+        for field in record.fields() {
+            let value = match initializer(&field.name) {
+                Some(initializer) => match field.ty {
+                    CType::Named(ref class) if self.has_constructors(class) => {
+                        self.gen_construction(class, &initializer.args)
+                    }
+                    _ if initializer.args.len() == 1 && field.ty.is_pointer() => {
+                        self.gen_pointer_value(&field_pointer(field), &initializer.args[0])
+                    }
+                    _ if initializer.args.len() == 1 => self.gen_expr(&initializer.args[0]),
+                    _ => self.default_value(&field.ty),
+                },
+                None => self.default_value(&field.ty),
+            };
+            literal.push_str(&format!(" {} : {} ,", field.name, value));
+        }
+        literal.push_str(" }");
+        literal
+    }
+
+    // A(args) => A::new(args), the overload is picked by the types of the arguments
+    fn gen_construction(&self, class: &str, args: &[Expr]) -> String {
+        let types: Vec<Option<CType>> = args.iter().map(|arg| self.arg_type(arg)).collect();
+        let name = self
+            .find_record(class)
+            .and_then(|record| constructor_for(record, &types))
+            .unwrap_or_else(|| "new".to_string());
+        let args: Vec<String> = args.iter().map(|arg| self.gen_expr(arg)).collect();
+        format!("{}::{}({})", class, name, args.join(", "))
+    }
+
+    fn is_std_type(&self, ty: &CType) -> bool {
+        matches!(*ty, CType::Named(ref name) | CType::Generic(ref name, _) if self.std_name(name, std_type).is_some())
+    }
+
+    fn is_constructed(&self, ty: &CType) -> bool {
+        matches!(*ty, CType::Named(ref class) | CType::Generic(ref class, _) if self.has_constructors(class))
+    }
+
+    fn has_constructors(&self, class: &str) -> bool {
+        self.find_record(class)
+            .is_some_and(|record| !constructors(record).is_empty())
+    }
+
+    /**
+     * gen_drop:
+     * destructor => impl Drop, run when the value goes out of scope.
+     * Interfaces only become traits, which can't implement Drop, an
+     * empty destructor is left out and any other is reported
+     */
+    fn gen_drop(&mut self, record: &Record, destructor: &Function) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let body = match destructor.body {
+            Some(ref body) => body,
+            None => return stream,
+        };
+        //out of class definitions carry a record of their own
+        if self.find_record(&record.name).is_some_and(is_interface) {
+            if body.is_empty() {
+                return stream;
+            }
+            return gen_unsupported(&Unsupported {
+                reason: format!(
+                    "`{}` only becomes a trait, traits can not implement Drop",
+                    record.name
+                ),
+                text: format!("{}() {{ ... }}", destructor.name),
+                span: destructor.span,
+            });
+        }
+        self.pointers = Some(analyze_function(destructor, &self.tables, &self.receivers));
+        self.enter_scope(destructor);
+        //Drop impls can't add bounds the struct doesn't have
+        stream.push(format!(
+            "impl{} Drop for {} {{",
+            self.gen_generics(&record.template, false),
+            self.gen_type(&CType::Named(record.name.clone()))
+        ));
+        stream.push("fn drop(&mut self) {".to_string());
+        self.enter_members(record, destructor, "self");
+        stream.append(&mut self.gen_stmts(body));
+        self.leave_members();
+        stream.push("}".to_string());
+        stream.push("}\n".to_string());
+        self.pointers = None;
+        stream
+    }
+
+    // bare member names in the function body are reached through the receiver
+    fn enter_members(&mut self, record: &Record, function: &Function, receiver: &str) {
+        let mut shadowed: Vec<String> = function
+            .params
+            .iter()
+            .filter_map(|param| param.name.clone())
+            .collect();
+        for stmt in function.body.iter().flatten() {
+            stmt_locals(stmt, &mut shadowed);
+        }
+        //static methods have no object to reach members through
+        if !function.is_static {
+            self.members = member_names(&self.records, record)
+                .into_iter()
+                .filter(|member| !shadowed.contains(member))
+                .collect();
+        }
+        let mut methods: Vec<(String, Receiver)> = Vec::new();
+        let mut class = Some(record);
+        while let Some(current) = class {
+            for entry in self
+                .receivers
+                .iter()
+                .filter(|entry| entry.0 == current.name)
+            {
+                methods.push((entry.1.clone(), entry.2));
+            }
+            class = composed(&self.records, current);
+        }
+        self.methods = methods;
+        self.class = Some(record.name.clone());
+        self.receiver = receiver.to_string();
+    }
+
+    fn leave_members(&mut self) {
+        self.members.clear();
+        self.methods.clear();
+        self.class = None;
+        self.receiver = "self".to_string();
+    }
+
+    /**
+     * gen_traits:
+     * trait declaring the virtual methods the class introduces and impls
+     * of the traits of its ancestors, overrides replace the methods and
+     * the others are forwarded to the base class
+     */
+    fn gen_traits(
+        &mut self,
+        records: &[Record],
+        record: &Record,
+        virtuals: &[&Function],
+    ) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let own = declared(records, record);
+        let interface = is_interface(record);
+        //abstract classes can't implement their trait, bodies become defaults
+        let defaults = is_abstract(record);
+        if !own.is_empty() || interface {
+            stream.extend(self.exported());
+            stream.push("trait".to_string());
+            stream.push(trait_name(record));
+            let supertraits: Vec<String> = bases(records, record)
+                .iter()
+                .filter_map(|base| object_trait(records, &base.name))
+                .collect();
+            if !supertraits.is_empty() {
+                stream.push(format!(": {}", supertraits.join(" + ")));
+            }
+            stream.push("{\n".to_string());
+            for method in virtuals {
+                if !interface && !own.iter().any(|own| own.name == method.name) {
+                    continue;
+                }
+                if defaults || method.is_pure {
+                    stream.append(&mut self.gen_method(record, method, None, None));
+                } else {
+                    let signature = Function {
+                        body: None,
+                        ..(*method).clone()
+                    };
+                    stream.append(&mut self.gen_method(record, &signature, None, None));
+                }
+            }
+            stream.push("}\n".to_string());
+            if !defaults {
+                stream.push(format!(
+                    "impl {} for {} {{\n",
+                    trait_name(record),
+                    record.name
+                ));
+                for method in &own {
+                    stream.append(&mut self.gen_method(record, method, None, None));
+                }
+                stream.push("}\n".to_string());
+            }
+        }
+        if interface {
+            return stream;
+        }
+        let base = composed(records, record);
+        for ancestor in trait_ancestors(records, record) {
+            stream.push(format!(
+                "impl {} for {} {{\n",
+                trait_name(ancestor),
+                record.name
+            ));
+            for declaration in declared(records, ancestor) {
+                match virtuals
+                    .iter()
+                    .find(|method| method.name == declaration.name)
+                {
+                    Some(method) => {
+                        stream.append(&mut self.gen_method(record, method, None, Some(declaration)))
+                    }
+                    //inherited implementation of a concrete base class
+                    None if base.is_some_and(|base| !is_abstract(base)) => {
+                        stream.append(&mut self.gen_forward(ancestor, declaration))
+                    }
+                    None => {}
+                }
+            }
+            stream.push("}\n".to_string());
+        }
+        stream
+    }
+
+    /**
+     * gen_method:
+     * method of the impl block of the class, or of a trait without access,
+     * overrides take the pointer translation of the declaration they override
+     */
+    fn gen_method(
+        &mut self,
+        record: &Record,
+        method: &Function,
+        access: Option<Access>,
+        declaration: Option<&Function>,
+    ) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        //methods without body are only declared by traits
+        if method.body.is_none() && access.is_some() {
+            return stream;
+        }
+        let mut table = analyze_function(method, &self.tables, &self.receivers);
+        if let Some(declaration) = declaration {
+            let declared = analyze_function(declaration, &self.tables, &self.receivers);
+            if declared.params.len() == table.params.len() {
+                table.params = declared.params;
+                table.ret = declared.ret;
+            }
+        }
+        self.pointers = Some(table);
+        self.enter_scope(method);
+        let receiver = self.receiver_of(&record.name, &method.name);
+        stream.append(&mut self.gen_signature_docs());
+        if method.is_inline {
+            stream.push("#[inline]".to_string());
+        }
+        if access == Some(Access::Public) {
+            stream.push("pub".to_string());
+        }
+        //overloads in the impl block are told apart by their names
+        let name = match access {
+            Some(_) => method_names(record)
+                .into_iter()
+                .find(|named| {
+                    std::ptr::eq(named.1, method)
+                        || (named.1.name == method.name && named.1.params == method.params)
+                })
+                .map(|named| named.0)
+                .unwrap_or_else(|| method.name.clone()),
+            None => method.name.clone(),
+        };
+        stream.push("fn".to_string());
+        stream.push(name + &self.gen_generics(&method.template, true));
+        stream.push("(".to_string());
+        stream.push(receiver.as_str().to_string());
+        //patterns like `mut a` are only allowed in methods with a body
+        stream.extend(
+            self.gen_params(&method.params)
+                .into_iter()
+                .filter(|token| method.body.is_some() || token != "mut"),
+        );
+        stream.push(")".to_string());
+        stream.append(&mut self.gen_return_type(&method.return_type));
+        stream.push(self.gen_where(record, method));
+        match method.body {
+            Some(ref body) => {
+                stream.push("{".to_string());
+                self.enter_members(record, method, "self");
+                stream.append(&mut self.gen_body(body));
+                self.leave_members();
+                stream.push("}".to_string());
+            }
+            None => stream.push(";".to_string()),
+        }
+        self.pointers = None;
+        stream
+    }
+
+    fn receiver_of(&self, class: &str, method: &str) -> Receiver {
+        receiver_of(&self.receivers, &self.records, class, method).unwrap_or(Receiver::Shared)
+    }
+
+    // virtual method the derived class doesn't override, calls the one of its base
+    fn gen_forward(&mut self, ancestor: &Record, declaration: &Function) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        self.pointers = Some(analyze_function(declaration, &self.tables, &self.receivers));
+        self.enter_scope(declaration);
+        stream.push("fn".to_string());
+        stream.push(declaration.name.clone());
+        stream.push("(".to_string());
+        let receiver = self.receiver_of(&ancestor.name, &declaration.name);
+        stream.push(receiver.as_str().to_string());
+        stream.append(&mut self.gen_params(&declaration.params));
+        stream.push(")".to_string());
+        stream.append(&mut self.gen_return_type(&declaration.return_type));
+        let args: Vec<String> = declaration
+            .params
+            .iter()
+            .map(|param| param.name.clone().unwrap_or_else(|| "_".to_string()))
+            .collect();
+        stream.push(format!(
+            "{{ self.base.{}({}) }}",
+            declaration.name,
+            args.join(", ")
+        ));
+        self.pointers = None;
+        stream
+    }
+
+    /**
+     * gen_definition:
+     * member function defined outside of its class, it is generated with
+     * the class unless the class isn't declared in the translation unit,
+     * then it gets an impl block of its own
+     */
+    fn gen_definition(&mut self, definition: &Function) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        if declaration_of(&self.records, definition).is_some() {
+            return stream;
+        }
+        let class = definition.class.clone().unwrap_or_default();
+        let function = Function {
+            class: None,
+            ..definition.clone()
+        };
+        let kind = if function.name == class {
+            MemberKind::Constructor(function)
+        } else if function.name.starts_with('~') {
+            MemberKind::Destructor(function)
+        } else {
+            MemberKind::Method(function)
+        };
+        let record = Record {
+            kind: RecordKind::Class,
+            name: class.clone(),
+            bases: Vec::new(),
+            template: Vec::new(),
+            members: vec![Member {
+                access: Access::Public,
+                kind,
+            }],
+            span: definition.span,
+        };
+        let mut impl_stream = match record.members[0].kind {
+            MemberKind::Constructor(ref constructor) => {
+                self.gen_constructor(&record, constructor, Access::Public)
+            }
+            MemberKind::Destructor(ref destructor) => return self.gen_drop(&record, destructor),
+            MemberKind::Method(ref method) => {
+                self.gen_method(&record, method, Some(Access::Public), None)
+            }
+            _ => Vec::new(),
+        };
+        stream.push("impl".to_string());
+        stream.push(class);
+        stream.push("{\n".to_string());
+        stream.append(&mut impl_stream);
+        stream.push("}\n".to_string());
+        stream
+    }
+
+    // class of the translation unit a free operator takes as its first or second operand
+    fn operator_record(&self, function: &Function) -> Option<String> {
+        function
+            .params
+            .iter()
+            .take(2)
+            .find_map(|param| match *param.ty.referred() {
+                CType::Named(ref name) if record_named(&self.records, name).is_some() => {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+    }
+
+    /**
+     * gen_derive:
+     * traits the operators of the class need derived, operators taking
+     * the class by value leave a copy of plain data behind and PartialOrd
+     * requires PartialEq
+     */
+    fn gen_derive(&self, record: &Record, operators: &[(Function, bool)]) -> Option<String> {
+        let kinds: Vec<OperatorKind> = operators
+            .iter()
+            .filter_map(|&(ref function, is_member)| trait_of(function, is_member))
+            .map(|op| op.kind)
+            .collect();
+        let mut derives: Vec<&str> = Vec::new();
+        let by_value = kinds
+            .iter()
+            .any(|kind| *kind == OperatorKind::Binary || *kind == OperatorKind::Unary);
+        if by_value && self.is_plain(record) {
+            derives.push("Clone");
+            derives.push("Copy");
+        } else if !record.template.is_empty() && self.is_plain(record) {
+            //values of class templates are copied like in c++, where T: Clone
+            derives.push("Clone");
+        }
+        if kinds.contains(&OperatorKind::Ordering) && !kinds.contains(&OperatorKind::Equality) {
+            derives.push("PartialEq");
+        }
+        if derives.is_empty() {
+            return None;
+        }
+        Some(format!("#[derive({})]\n", derives.join(", ")))
+    }
+
+    // class of numbers and plain classes without destructor, it can be Copy
+    fn is_plain(&self, record: &Record) -> bool {
+        let plain = |ty: &CType| match *ty.base() {
+            _ if ty.is_pointer() || ty.is_reference() => false,
+            CType::Primitive(..) => true,
+            CType::Named(ref name) if record.template.iter().any(|param| param.name == *name) => {
+                true
+            }
+            CType::Named(ref name) => {
+                record_named(&self.records, name).is_some_and(|named| self.is_plain(named))
+            }
+            _ => false,
+        };
+        record.bases.is_empty()
+            && destructor(record).is_none()
+            && record
+                .fields()
+                .iter()
+                .all(|field| plain(&field.ty) && !matches!(field.ty, CType::Array(_, None)))
+    }
+
+    /**
+     * gen_operators:
+     * impls of the std traits for overloaded operators, taking the class
+     * or free ones. == and != share a PartialEq impl, the ordering
+     * operators a PartialOrd impl and [] is Index and IndexMut
+     */
+    fn gen_operators(
+        &mut self,
+        record: Option<&Record>,
+        operators: &[(Function, bool)],
+    ) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        //(kind, object, right operand) of the impls generated so far
+        let mut generated: Vec<(OperatorKind, String, String)> = Vec::new();
+        for &(ref function, is_member) in operators {
+            let op = match trait_of(function, is_member) {
+                Some(op) if function.body.is_some() => op,
+                _ => continue,
+            };
+            let (object, rhs) = self.operator_operands(record, function, is_member, op.kind);
+            let grouped = matches!(
+                op.kind,
+                OperatorKind::Equality | OperatorKind::Ordering | OperatorKind::Index
+            );
+            let key = (op.kind, object.clone(), rhs.clone());
+            if grouped && generated.contains(&key) {
+                continue;
+            }
+            generated.push(key);
+            //operators of the same group, defined for the same operands
+            let group: Vec<(&Function, bool, OperatorTrait)> = operators
+                .iter()
+                .filter_map(|&(ref other, is_member)| {
+                    let other_op = trait_of(other, is_member)?;
+                    let same = other.body.is_some()
+                        && other_op.kind == op.kind
+                        && self.operator_operands(record, other, is_member, op.kind)
+                            == (object.clone(), rhs.clone());
+                    Some((other, is_member, other_op)).filter(|_| same)
+                })
+                .collect();
+            let generics = if rhs == object || op.kind == OperatorKind::Unary {
+                String::new()
+            } else {
+                format!("<{}>", rhs)
+            };
+            match op.kind {
+                OperatorKind::Binary | OperatorKind::Unary => {
+                    let output = self.gen_type(function.return_type.referred());
+                    let receiver = match record {
+                        Some(record)
+                            if is_member
+                                && self.receiver_of(&record.name, &function.name)
+                                    == Receiver::Mutable =>
+                        {
+                            "mut self"
+                        }
+                        _ => "self",
+                    };
+                    stream.push(format!(
+                        "impl {}{} for {} {{ type Output = {};",
+                        op.name, generics, object, output
+                    ));
+                    stream.push(format!(
+                        "fn {}({}{}) -> {} {{",
+                        op.method,
+                        receiver,
+                        self.gen_operand_param(function, is_member, &rhs, ""),
+                        output
+                    ));
+                    stream.append(&mut self.gen_operator_body(record, function, is_member, true));
+                    stream.push("} }\n".to_string());
+                }
+                OperatorKind::Assign => {
+                    //a += b; has no value in rust, the object is not returned
+                    let mut assign = function.clone();
+                    if let Some(body) = assign.body.as_mut() {
+                        let returns_object = body.last().is_some_and(|stmt| {
+                            matches!(stmt.kind, StmtKind::Return(Some(ref value)) if returns_this(value))
+                        });
+                        if returns_object {
+                            body.pop();
+                        }
+                    }
+                    assign.return_type = CType::Primitive(TokenType::Void, Modifier::Default);
+                    stream.push(format!("impl {}{} for {} {{", op.name, generics, object));
+                    stream.push(format!(
+                        "fn {}(&mut self{}) {{",
+                        op.method,
+                        self.gen_operand_param(function, is_member, &rhs, "")
+                    ));
+                    stream.append(&mut self.gen_operator_body(record, &assign, is_member, true));
+                    stream.push("} }\n".to_string());
+                }
+                OperatorKind::Equality | OperatorKind::Ordering => {
+                    stream.push(format!("impl {}{} for {} {{", op.name, generics, object));
+                    if op.kind == OperatorKind::Ordering {
+                        stream.push(format!(
+                            "fn partial_cmp(&self, other: &{}) -> Option<std::cmp::Ordering> {{ {} }}",
+                            rhs,
+                            partial_cmp_body(group[0].2.method)
+                        ));
+                    } else if group.iter().all(|entry| entry.2.method == "ne") {
+                        stream.push(format!(
+                            "fn eq(&self, other: &{}) -> bool {{ !self.ne(other) }}",
+                            rhs
+                        ));
+                    }
+                    for &(function, is_member, op) in &group {
+                        stream.push(format!(
+                            "fn {}(&self{}) -> bool {{",
+                            op.method,
+                            self.gen_operand_param(function, is_member, &rhs, "&")
+                        ));
+                        stream.append(
+                            &mut self.gen_operator_body(record, function, is_member, false),
+                        );
+                        stream.push("}".to_string());
+                    }
+                    stream.push("}\n".to_string());
+                }
+                OperatorKind::Index => {
+                    let element = self.gen_type(function.return_type.referred());
+                    let shared = group
+                        .iter()
+                        .find(|entry| entry.0.is_const)
+                        .unwrap_or(&group[0]);
+                    let mutable = group.iter().find(|entry| !entry.0.is_const);
+                    stream.push(format!(
+                        "impl std::ops::Index<{}> for {} {{ type Output = {};",
+                        rhs, object, element
+                    ));
+                    stream.push(format!(
+                        "fn index(&self{}) -> &{} {{",
+                        self.gen_operand_param(shared.0, shared.1, &rhs, ""),
+                        element
+                    ));
+                    self.returned_borrow = "&".to_string();
+                    stream.append(&mut self.gen_operator_body(record, shared.0, shared.1, true));
+                    stream.push("} }\n".to_string());
+                    if let Some(&(function, is_member, _)) = mutable {
+                        stream.push(format!(
+                            "impl std::ops::IndexMut<{}> for {} {{",
+                            rhs, object
+                        ));
+                        stream.push(format!(
+                            "fn index_mut(&mut self{}) -> &mut {} {{",
+                            self.gen_operand_param(function, is_member, &rhs, ""),
+                            element
+                        ));
+                        self.returned_borrow = "&mut ".to_string();
+                        stream
+                            .append(&mut self.gen_operator_body(record, function, is_member, true));
+                        stream.push("} }\n".to_string());
+                    }
+                    self.returned_borrow.clear();
+                }
+                OperatorKind::Display => {
+                    let out = function.params[0]
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| "f".to_string());
+                    stream.push(format!("impl std::fmt::Display for {} {{", object));
+                    stream.push(format!(
+                        "fn fmt(&self, {}: &mut std::fmt::Formatter) -> std::fmt::Result {{",
+                        out
+                    ));
+                    self.stream = Some(out);
+                    stream.append(&mut self.gen_operator_body(record, function, is_member, true));
+                    let returns = function
+                        .body
+                        .iter()
+                        .flatten()
+                        .last()
+                        .is_some_and(|stmt| matches!(stmt.kind, StmtKind::Return(_)));
+                    if !returns {
+                        stream.push("Ok(())".to_string());
+                    }
+                    self.stream = None;
+                    stream.push("} }\n".to_string());
+                }
+            }
+        }
+        stream
+    }
+
+    // rust types of the object and the right operand of an operator
+    fn operator_operands(
+        &self,
+        record: Option<&Record>,
+        function: &Function,
+        is_member: bool,
+        kind: OperatorKind,
+    ) -> (String, String) {
+        let mut operands: Vec<String> = Vec::new();
+        if is_member {
+            operands.push(record.map(|record| record.name.clone()).unwrap_or_default());
+        }
+        for param in &function.params {
+            operands.push(self.gen_type(param.ty.referred()));
+        }
+        match kind {
+            //out << value
+            OperatorKind::Display => (operands[1].clone(), String::new()),
+            //rust indexes with usize
+            OperatorKind::Index
+                if matches!(
+                    *function.params[0].ty.referred(),
+                    CType::Primitive(token_type, _)
+                        if !matches!(token_type, TokenType::Float | TokenType::Double)
+                ) =>
+            {
+                (operands[0].clone(), "usize".to_string())
+            }
+            _ => (
+                operands[0].clone(),
+                operands.get(1).cloned().unwrap_or_default(),
+            ),
+        }
+    }
+
+    // `, b: B` for the right operand, borrowed by the comparison traits
+    fn gen_operand_param(
+        &self,
+        function: &Function,
+        is_member: bool,
+        rhs: &str,
+        borrow: &str,
+    ) -> String {
+        let index = if is_member { 0 } else { 1 };
+        match function.params.get(index) {
+            Some(param) => {
+                let name = param.name.clone().unwrap_or_else(|| "_".to_string());
+                let binding = if self.strict || !borrow.is_empty() {
+                    ""
+                } else {
+                    "mut "
+                };
+                format!(", {}{} : {}{}", binding, name, borrow, rhs)
+            }
+            None => String::new(),
+        }
+    }
+
+    /**
+     * gen_operator_body:
+     * body of the trait method of an operator, `self` stands for the
+     * object operand. Operands taken by value are not references anymore
+     * and a compared number is copied out of its borrow
+     */
+    fn gen_operator_body(
+        &mut self,
+        record: Option<&Record>,
+        function: &Function,
+        is_member: bool,
+        by_value: bool,
+    ) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let body = match function.body {
+            Some(ref body) => body,
+            None => return stream,
+        };
+        let mut table = analyze_function(function, &self.tables, &self.receivers);
+        let display = self.stream.is_some();
+        let object = if is_member || display {
+            None
+        } else {
+            function.params.first()
+        };
+        let value = if display {
+            function.params.get(1)
+        } else {
+            None
+        };
+        let rhs = function.params.get(if is_member { 0 } else { 1 });
+        let binding = if self.strict { "let" } else { "let mut" };
+        for param in object.iter().chain(value.iter()) {
+            if let Some(ref name) = param.name {
+                stream.push(format!("{} {} = self;", binding, name));
+                table.variables.retain(|info| info.name != *name);
+            }
+        }
+        if let Some(Param {
+            name: Some(ref name),
+            ref ty,
+        }) = rhs
+        {
+            if by_value || display {
+                table.variables.retain(|info| info.name != *name);
+            } else if !ty.is_reference() && !matches!(*ty, CType::Named(_)) {
+                stream.push(format!("{} {} = *{};", binding, name, name));
+            }
+        }
+        if let Some(Param {
+            name: Some(ref name),
+            ..
+        }) = function.params.first().filter(|_| display)
+        {
+            table.variables.retain(|info| info.name != *name);
+        }
+        self.pointers = Some(table);
+        self.enter_scope(function);
+        match record {
+            Some(record) if is_member => self.enter_members(record, function, "self"),
+            _ => {}
+        }
+        stream.append(&mut self.gen_body(body));
+        self.leave_members();
+        self.pointers = None;
+        stream
+    }
+
+    /**
+     * gen_write:
+     * out << a << "text" to the stream of Display::fmt => write!(out, "{}text", a)?
+     * and std::cout << a => print!("{}", a), which uses the Display impl of a
+     */
+    fn gen_write(&self, expr: &Expr) -> Option<String> {
+        let mut operands: Vec<&Expr> = Vec::new();
+        let mut current = expr;
+        while let ExprKind::Binary(BinaryOp::Shl, ref lhs, ref rhs) = current.kind {
+            operands.push(rhs);
+            current = lhs;
+        }
+        let target = match current.kind {
+            ExprKind::Ident(ref name) if !operands.is_empty() => name,
+            _ => return None,
+        };
+        let (open, close) = match target.as_str() {
+            _ if Some(target) == self.stream.as_ref() => (format!("write!({}, ", target), "?;"),
+            "cout" | "std::cout" => ("print!(".to_string(), ";"),
+            "cerr" | "std::cerr" | "clog" | "std::clog" => ("eprint!(".to_string(), ";"),
+            _ => return None,
+        };
+        let mut format = String::new();
+        let mut args: Vec<String> = Vec::new();
+        for operand in operands.into_iter().rev() {
+            match operand.kind {
+                ExprKind::Literal(Literal::Str(ref text)) => format.push_str(
+                    &text[1..text.len() - 1]
+                        .replace('{', "{{")
+                        .replace('}', "}}"),
+                ),
+                ExprKind::Literal(Literal::Char(ref text)) => {
+                    let text = &text[1..text.len() - 1];
+                    match text {
+                        "\"" => format.push_str("\\\""),
+                        "{" => format.push_str("{{"),
+                        "}" => format.push_str("}}"),
+                        _ => format.push_str(text),
+                    }
+                }
+                ExprKind::Ident(ref name) if name == "endl" || name == "std::endl" => {
+                    format.push_str("\\n")
+                }
+                _ => {
+                    format.push_str("{}");
+                    args.push(format!(", {}", self.gen_int_operand(operand, 0)));
+                }
+            }
+        }
+        Some(format!("{}\"{}\"{}){}", open, format, args.concat(), close))
+    }
+
+    /*-------------------------- statements --------------------------*/
+
+    fn gen_stmt(&mut self, stmt: &Stmt) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        match stmt.kind {
+            StmtKind::Declaration(ref decl) => {
+                stream.append(&mut self.gen_declaration(decl, false));
+            }
+            StmtKind::Expr(ref expr) => stream.append(&mut self.gen_expr_stmt(expr)),
+            StmtKind::Block(ref stmts) => {
+                stream.push("{".to_string());
+                stream.append(&mut self.gen_stmts(stmts));
+                stream.push("}".to_string());
+            }
+            StmtKind::If {
+                ref cond,
+                ref then,
+                ref otherwise,
+            } => {
+                stream.push("if".to_string());
+                stream.push(self.gen_cond(cond));
+                stream.append(&mut self.gen_braced(then));
+                if let Some(ref otherwise) = *otherwise {
+                    stream.push("else".to_string());
+                    if let StmtKind::If { .. } = otherwise.kind {
+                        stream.append(&mut self.gen_stmt(otherwise));
+                    } else {
+                        stream.append(&mut self.gen_braced(otherwise));
+                    }
+                }
+            }
+            StmtKind::While { ref cond, ref body } => {
+                let labels = self.enter_loop(body, &mut stream);
+                if is_always_true(cond) {
+                    stream.push("loop".to_string());
+                } else {
+                    stream.push("while".to_string());
+                    stream.push(self.gen_cond(cond));
+                }
+                stream.append(&mut self.gen_braced(body));
+                self.leave_loop(labels);
+            }
+            StmtKind::DoWhile { ref body, ref cond } => {
+                let labels = self.enter_loop(body, &mut stream);
+                if is_always_true(cond) {
+                    stream.push("loop".to_string());
+                    stream.append(&mut self.gen_braced(body));
+                } else {
+                    //body runs as part of the condition evaluation
+                    stream.push("while".to_string());
+                    stream.push("{".to_string());
+                    stream.append(&mut self.gen_unbraced(body));
+                    stream.push(self.gen_cond(cond));
+                    stream.push("}".to_string());
+                    stream.push("{".to_string());
+                    stream.push("}".to_string());
+                }
+                self.leave_loop(labels);
+            }
+            StmtKind::For {
+                ref init,
+                ref cond,
+                ref step,
+                ref body,
+            } => {
+                stream.append(&mut self.gen_for(init, cond, step, body));
+            }
+            StmtKind::Switch {
+                ref cond,
+                ref cases,
+            } => {
+                stream.append(&mut self.gen_switch(cond, cases));
+            }
+            StmtKind::Break => match self.break_label {
+                Some(ref label) => stream.push(format!("break {};", label)),
+                None => stream.push("break;".to_string()),
+            },
+            StmtKind::Continue => match (&self.continue_block, &self.continue_label) {
+                (Some(block), _) => stream.push(format!("break {};", block)),
+                (None, Some(label)) => stream.push(format!("continue {};", label)),
+                (None, None) => stream.push("continue;".to_string()),
+            },
+            StmtKind::Return(ref value) => match *value {
+                Some(ref value) if self.in_main => {
+                    stream.push(format!(
+                        "std::process::exit({});",
+                        self.gen_int_operand(value, 0)
+                    ));
+                }
+                Some(ref value) => stream.push(format!("return {};", self.gen_return_value(value))),
+                None => stream.push("return;".to_string()),
+            },
+            StmtKind::Goto(ref label) => {
+                match self.gotos.iter().rev().find(|entry| entry.0 == *label) {
+                    Some(&(_, true)) => stream.push(format!("continue '{};", label)),
+                    Some(&(_, false)) => stream.push(format!("break '{};", label)),
+                    None => stream.append(&mut self.gen_unstructured_goto(label, stmt.span)),
+                }
+            }
+            //gen_labelled places the block or loop of the label
+            StmtKind::Label(_) => {}
+            StmtKind::Comment(ref comment) => stream.push(comment.clone() + "\n"),
+            StmtKind::Unsupported(ref unsupported) => {
+                stream.append(&mut gen_unsupported(unsupported))
+            }
+            StmtKind::Empty => {}
+        }
+        stream
+    }
+
+    fn gen_stmts(&mut self, stmts: &[Stmt]) -> Vec<String> {
+        if stmts.iter().any(|stmt| label_of(stmt).is_some()) {
+            return self.gen_labelled(stmts);
+        }
+        let mut stream: Vec<String> = Vec::new();
+        for stmt in stmts {
+            stream.append(&mut self.gen_stmt(stmt));
+        }
+        stream
+    }
+
+    // statement as a braced block, single statements get braces added
+    fn gen_braced(&mut self, stmt: &Stmt) -> Vec<String> {
+        let mut stream: Vec<String> = vec!["{".to_string()];
+        stream.append(&mut self.gen_unbraced(stmt));
+        stream.push("}".to_string());
+        stream
+    }
+
+    // contents of a block statement without its braces
+    fn gen_unbraced(&mut self, stmt: &Stmt) -> Vec<String> {
+        match stmt.kind {
+            StmtKind::Block(ref stmts) => self.gen_stmts(stmts),
+            _ => self.gen_stmt(stmt),
+        }
+    }
+
+    /**
+     * gen_for:
+     * for (int i = a; i < b; i++) => for i in a..b, a loop walking an array
+     * by its index iterates the array. Other loops become
+     * init; while cond { body step; } with the body in a block continue
+     * breaks out of, so the step still runs. A declaring init gets a block
+     * of its own around the loop.
+     * Identify infinite loops and replace for with loop{}
+     */
+    fn gen_for(
+        &mut self,
+        init: &Option<Box<Stmt>>,
+        cond: &Option<Expr>,
+        step: &Option<Expr>,
+        body: &Stmt,
+    ) -> Vec<String> {
+        if let Some(counting) = counting_loop(init, cond, step, body, &self.variables) {
+            return self.gen_counting(&counting, body);
+        }
+        let mut stream: Vec<String> = Vec::new();
+        //a counter declared in init is only in scope inside the loop, the
+        //block keeps it from shadowing an outer variable of the same name
+        let scope = self.variables.len();
+        let declares = matches!(
+            init.as_ref().map(|init| &init.kind),
+            Some(StmtKind::Declaration(..))
+        );
+        if declares {
+            stream.push("{".to_string());
+        }
+        if let Some(ref init) = *init {
+            stream.append(&mut self.gen_stmt(init));
+        }
+        //an unlabelled break can't leave the loop from inside the block
+        let continue_block = step.is_some() && continues(body);
+        if continue_block && breaks(body) && self.loop_label.is_none() {
+            self.loop_label = Some("'outer".to_string());
+        }
+        let labels = self.enter_loop(body, &mut stream);
+        match *cond {
+            Some(ref cond) if !is_always_true(cond) => {
+                stream.push("while".to_string());
+                stream.push(self.gen_cond(cond));
+            }
+            _ => stream.push("loop".to_string()),
+        }
+        stream.push("{".to_string());
+        if continue_block {
+            self.continue_block = Some("'body".to_string());
+            stream.push("'body:".to_string());
+            stream.append(&mut self.gen_braced(body));
+            self.continue_block = None;
+        } else {
+            stream.append(&mut self.gen_unbraced(body));
+        }
+        if let Some(ref step) = *step {
+            stream.append(&mut self.gen_expr_stmt(step));
+        }
+        stream.push("}".to_string());
+        self.leave_loop(labels);
+        if declares {
+            stream.push("}".to_string());
+        }
+        self.variables.truncate(scope);
+        stream
+    }
+
+    /**
+     * gen_counting:
+     * counting loop as a rust for loop, over a range or over the elements
+     * of the array the counter indexes, a[i] => *item
+     */
+    fn gen_counting(&mut self, counting: &Counting, body: &Stmt) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        let counter = &counting.counter.name;
+        let ty = counting.counter.ty.clone();
+        //the counter and the element are only in scope inside the loop
+        let scope = self.variables.len();
+        self.variables.push((counter.clone(), ty.clone()));
+        let array = indexed_array(counting, body).and_then(|array| {
+            let local = self.variables.iter().rev().find(|local| local.0 == array);
+            match local.map(|local| &local.1) {
+                Some(CType::Array(inner, Some(length)))
+                    if matches!(**inner, CType::Primitive(..))
+                        && self.gen_expr(length) == self.gen_expr(counting.end) =>
+                {
+                    Some((array, (**inner).clone()))
+                }
+                _ => None,
+            }
+        });
+        let labels = self.enter_loop(body, &mut stream);
+        stream.push("for".to_string());
+        match array {
+            Some((array, element)) => {
+                let item = if uses(body, "item") || self.is_variable("item") {
+                    format!("{}_item", array)
+                } else {
+                    "item".to_string()
+                };
+                let iter = if writes(body, &array) {
+                    "iter_mut"
+                } else {
+                    "iter"
+                };
+                let body = replace_element(body, &array, counter, &item);
+                self.variables
+                    .push((item.clone(), CType::Pointer(Box::new(element))));
+                let index = if uses(&body, counter) { counter } else { "_" };
+                stream.push(format!("({}, {})", index, item));
+                stream.push("in".to_string());
+                stream.push(format!("{}.{}().enumerate()", array, iter));
+                stream.push("{".to_string());
+                //enumerate counts in usize
+                if index != "_" && self.gen_type(&ty) != "usize" {
+                    stream.push(format!(
+                        "let {} = {} as {};",
+                        counter,
+                        counter,
+                        self.gen_type(&ty)
+                    ));
+                }
+                stream.append(&mut self.gen_unbraced(&body));
+                stream.push("}".to_string());
+            }
+            None => {
+                stream.push(counter.clone());
+                stream.push("in".to_string());
+                stream.push(self.gen_range(counting));
+                stream.append(&mut self.gen_braced(body));
+            }
+        }
+        self.variables.truncate(scope);
+        self.leave_loop(labels);
+        stream
+    }
+
+    /**
+     * gen_range:
+     * a..b and a..=b counting up, (b..=a).rev() counting down and
+     * .step_by(k) for steps other than one
+     */
+    fn gen_range(&self, counting: &Counting) -> String {
+        let bound = BinaryOp::Or.rust_precedence();
+        let ty = self.gen_type(&counting.counter.ty);
+        //enumerators are cast to the type of the counter
+        let gen_bound = |expr: &Expr, precedence: u8| match self.enum_of(expr) {
+            Some(_) => format!("{} as {}", self.gen_operand(expr, CAST), ty),
+            None => self.gen_operand(expr, precedence),
+        };
+        let mut start = gen_bound(counting.start, bound);
+        //the literal a range starts at gives the type of the counter
+        if start.chars().all(|c| c.is_ascii_digit()) && ty != "i32" {
+            start.push_str(&ty);
+        }
+        let end = gen_bound(counting.end, bound);
+        let range = match (counting.step > 0, counting.inclusive) {
+            (true, false) => format!("{}..{}", start, end),
+            (true, true) => format!("{}..={}", start, end),
+            (false, true) => format!("({}..={}).rev()", end, start),
+            (false, false) => {
+                let after = match counting.end.kind {
+                    ExprKind::Literal(Literal::Integer(ref number)) if number.value().is_some() => {
+                        (number.value().unwrap_or_default() + 1).to_string()
+                    }
+                    _ => format!(
+                        "{} + 1",
+                        gen_bound(counting.end, BinaryOp::Add.rust_precedence())
+                    ),
+                };
+                format!("({}..={}).rev()", after, start)
+            }
+        };
+        match counting.step.abs() {
+            1 => range,
+            step if counting.step > 0 => format!("({}).step_by({})", range, step),
+            step => format!("{}.step_by({})", range, step),
+        }
+    }
+
+    /**
+     * enter_loop:
+     * breaks inside a loop body leave the loop, a loop whose continue
+     * crosses a labelled switch block is labelled itself. Returns the
+     * labels of the enclosing statement to restore afterwards
+     */
+    fn enter_loop(&mut self, body: &Stmt, stream: &mut Vec<String>) -> Labels {
+        let mut label = self.loop_label.take();
+        if label.is_none() && (continues_through_switch(body) || leaves_goto_block(body)) {
+            label = Some("'outer".to_string());
+        }
+        if let Some(ref label) = label {
+            stream.push(format!("{}:", label));
+        }
+        let enclosing = (
+            self.break_label.take(),
+            self.continue_label.take(),
+            self.continue_block.take(),
+        );
+        self.break_label = label.clone();
+        self.continue_label = label;
+        enclosing
+    }
+
+    fn leave_loop(&mut self, enclosing: Labels) {
+        self.break_label = enclosing.0;
+        self.continue_label = enclosing.1;
+        self.continue_block = enclosing.2;
+    }
+
+    /**
+     * gen_labelled:
+     * statements with goto labels among them. The statements from the
+     * first goto jumping forward to a label up to the label become a
+     * labelled block the gotos break out of, and the statements from a
+     * label up to the last goto jumping back to it a labelled loop the
+     * gotos continue
+     */
+    fn gen_labelled(&mut self, stmts: &[Stmt]) -> Vec<String> {
+        for end in (0..stmts.len()).rev() {
+            let label = match label_of(&stmts[end]) {
+                Some(label) => label,
+                None => continue,
+            };
+            let mut start = match stmts[..end].iter().position(|stmt| jumps_to(stmt, label)) {
+                Some(start) => start,
+                None => continue,
+            };
+            //a goto to a label inside the block starts the block earlier
+            while let Some(earlier) = stmts[..start].iter().position(|stmt| {
+                stmts[start..end]
+                    .iter()
+                    .filter_map(label_of)
+                    .any(|inner| jumps_to(stmt, inner))
+            }) {
+                start = earlier;
+            }
+            let mut stream = self.gen_stmts(&stmts[..start]);
+            stream.append(&mut self.gen_goto_block(label, &stmts[start..end], false));
+            stream.append(&mut self.gen_stmts(&stmts[end..]));
+            return stream;
+        }
+        for start in 0..stmts.len() {
+            let label = match label_of(&stmts[start]) {
+                Some(label) => label,
+                None => continue,
+            };
+            let end = match stmts.iter().rposition(|stmt| jumps_to(stmt, label)) {
+                Some(end) if end > start => end,
+                _ => continue,
+            };
+            let mut stream = self.gen_stmts(&stmts[..start]);
+            stream.append(&mut self.gen_goto_block(label, &stmts[start + 1..=end], true));
+            stream.append(&mut self.gen_stmts(&stmts[end + 1..]));
+            return stream;
+        }
+        let mut stream: Vec<String> = Vec::new();
+        for stmt in stmts {
+            stream.append(&mut self.gen_stmt(stmt));
+        }
+        stream
+    }
+
+    /**
+     * gen_goto_block:
+     * 'label: { stmts } for gotos jumping forward, a single loop is
+     * labelled itself, and 'label: loop { stmts break; } for gotos
+     * jumping back
+     */
+    fn gen_goto_block(&mut self, label: &str, stmts: &[Stmt], back: bool) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        self.gotos.push((label.to_string(), back));
+        let single_loop = match *stmts {
+            [ref stmt] => matches!(stmt.kind, StmtKind::While { .. } | StmtKind::For { .. }),
+            _ => false,
+        };
+        if single_loop && !back {
+            self.loop_label = Some(format!("'{}", label));
+            stream.append(&mut self.gen_stmts(stmts));
+        } else {
+            stream.push(format!("'{}:", label));
+            if back {
+                stream.push("loop".to_string());
+            }
+            stream.push("{".to_string());
+            stream.append(&mut self.gen_stmts(stmts));
+            if back {
+                stream.push("break;".to_string());
+            }
+            stream.push("}".to_string());
+        }
+        self.gotos.pop();
+        stream
+    }
+
+    // goto to a label it can't reach by a labelled block or loop
+    fn gen_unstructured_goto(&self, label: &str, span: Span) -> Vec<String> {
+        let target = match self.labels.iter().find(|entry| entry.0 == label) {
+            Some(entry) => format!("{} ({})", label, location(entry.1)),
+            None => format!("{} (not found)", label),
+        };
+        let visible = self
+            .visible
+            .iter()
+            .find(|entry| entry.0 == span)
+            .is_some_and(|entry| entry.1.iter().any(|name| name == label));
+        //the label is in reach, the block or loop of another label is in the way
+        let reason = if visible {
+            "its label is in reach, but the jumps to it overlap the jumps to another label\
+             \n * and neither labelled block nor loop can be nested in the other."
+        } else {
+            "its label is neither in the same block as the goto nor in a block around it."
+        };
+        vec![
+            GOTO.get_doc().to_string(),
+            format!("\n * {}\n * Label: {}\n */\n", reason, target),
+            format!("// goto {};\n", label),
+        ]
+    }
+
+    /**
+     * gen_switch:
+     * switch becomes match, with an empty default arm
+     * added when the switch does not handle default. Grouped labels
+     * share an arm, case 1: case 2: => 1 | 2, and a break that doesn't
+     * end its arm leaves a labelled block around the match
+     */
+    fn gen_switch(&mut self, cond: &Expr, cases: &[SwitchCase]) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        self.switches += 1;
+        let enclosing = self.break_label.take();
+        if needs_label(cases) {
+            let label = match self.switches {
+                1 => "'switch".to_string(),
+                depth => format!("'switch{}", depth),
+            };
+            stream.push(format!("{}:", label));
+            stream.push("{".to_string());
+            self.break_label = Some(label);
+        }
+
+        //switch (n) { case RED: } matches the enum value of the integer
+        let labels = cases.iter().filter_map(|case| match case.label {
+            CaseLabel::Value(ref value) => Some(value),
+            _ => None,
+        });
+        let label_enum = labels
+            .clone()
+            .find_map(|value| self.enum_of(uncast(value)))
+            .filter(|_| self.enum_of(cond).is_none())
+            .map(|def| (def.name.clone().unwrap_or_default(), self.enum_repr(def)));
+        stream.push("match".to_string());
+        match label_enum {
+            Some((ref name, ref repr)) if repr == "i32" => {
+                stream.push(format!("{}::try_from({})", name, self.gen_expr(cond)))
+            }
+            Some((ref name, ref repr)) => stream.push(format!(
+                "{}::try_from({} as {})",
+                name,
+                self.gen_operand(cond, CAST),
+                repr
+            )),
+            None => stream.push(self.gen_expr(cond)),
+        }
+        stream.push("{".to_string());
+        //an enum with every enumerator matched needs no catch-all arm
+        let exhaustive = self
+            .enum_of(cond)
+            .is_some_and(|def| self.is_exhaustive(def, &labels.collect::<Vec<&Expr>>()));
+        //default matches only values no case does, its arm goes last
+        let mut arms = switch_arms(cases);
+        arms.sort_by_key(|arm| arm.is_default());
+        let has_default = arms.iter().any(|arm| arm.is_default());
+        for arm in &arms {
+            let patterns: Vec<String> = arm
+                .labels
+                .iter()
+                .map(|label| self.gen_case_label(label, label_enum.is_some()))
+                .collect();
+            if arm.is_default() {
+                stream.push("_".to_string());
+            } else {
+                stream.push(patterns.join(" | "));
+            }
+            stream.push("=>".to_string());
+            stream.push("{".to_string());
+            let body: Vec<Stmt> = arm
+                .body
+                .iter()
+                .flat_map(|stmt| match stmt.kind {
+                    StmtKind::Block(ref stmts) => stmts.clone(),
+                    _ => vec![stmt.clone()],
+                })
+                .collect();
+            stream.append(&mut self.gen_stmts(&body));
+            stream.push("}".to_string());
+        }
+        if !has_default && !exhaustive {
+            stream.push("_".to_string());
+            stream.push("=>".to_string());
+            stream.push("{".to_string());
+            stream.push("}".to_string());
+        }
+        stream.push("}".to_string());
+        if self.break_label.is_some() {
+            stream.push("}".to_string());
+        }
+        self.break_label = enclosing;
+        self.switches -= 1;
+        stream
+    }
+
+    // pattern of a case label, GNU case ranges 1 ... 5 become 1..=5
+    fn gen_case_label(&self, label: &CaseLabel, try_from: bool) -> String {
+        match *label {
+            CaseLabel::Value(ref value) if try_from => {
+                format!("Ok({})", self.gen_expr(uncast(value)))
+            }
+            CaseLabel::Value(ref value) => self.gen_expr(value),
+            CaseLabel::Range(ref low, ref high) => {
+                format!("{}..={}", self.gen_expr(low), self.gen_expr(high))
+            }
+            CaseLabel::Default => "_".to_string(),
+        }
+    }
+
+    /**
+     * gen_expr_stmt:
+     * expression used as a statement, increments are written as
+     * compound assignment and comma separated expressions are split
+     */
+    fn gen_expr_stmt(&mut self, expr: &Expr) -> Vec<String> {
+        let mut stream: Vec<String> = Vec::new();
+        match expr.kind {
+            ExprKind::Comma(ref exprs) => {
+                for expr in exprs {
+                    stream.append(&mut self.gen_expr_stmt(expr));
+                }
+            }
+            _ if self.gen_write(expr).is_some() => {
+                stream.push(self.gen_write(expr).expect("stream output checked above"));
+            }
+            //Box::from_raw of memory malloc handed out is undefined behavior
+            _ if self.unboxed_release(expr).is_some() => {
+                stream.push(format!(
+                    "\n//`{}` does not hold memory of a Box, free it with libc::free ({})\n",
+                    self.unboxed_release(expr).expect("pointer checked above"),
+                    location(expr.span)
+                ));
+                stream.push(format!("// {};\n", self.gen_effect(expr)));
+            }
+            //check if overloaded operators is in effect like << >>,
+            //pointers to data members have no rust equivalent either
+            _ if is_stream_output(expr) || uses_data_member_pointer(expr) => {
+                stream.push(format!(
+                    "\n//This statement need to be handled manually ({})\n",
+                    location(expr.span)
+                ));
+                stream.push(format!("// {};\n", self.gen_effect(expr)));
+            }
+            _ => stream.push(self.gen_effect(expr) + ";"),
+        }
+        stream
+    }
+
+    // expression evaluated only for its side effect, value is discarded
+    fn gen_effect(&self, expr: &Expr) -> String {
+        if let Some(step) = self.step_of(expr) {
+            return step;
+        }
+        let (place, effect) = match expr.kind {
+            //p += n; => p = &p[n..];
+            ExprKind::Assign(Some(op), ref lhs, ref rhs)
+                if (op == BinaryOp::Add || op == BinaryOp::Sub)
+                    && self
+                        .pointer(lhs)
+                        .and_then(|info| self.gen_step(info, Some(rhs), op == BinaryOp::Sub))
+                        .is_some() =>
+            {
+                let info = self.pointer(lhs).expect("pointer checked above");
+                return self
+                    .gen_step(info, Some(rhs), op == BinaryOp::Sub)
+                    .expect("pointer checked above");
+            }
+            //*p++ = x; => p[0] = x; p = &mut std::mem::take(&mut p)[1..];
+            ExprKind::Assign(op, ref lhs, ref rhs) if self.stepped_place(lhs).is_some() => {
+                let (pointer, step) = self.stepped_place(lhs).expect("pointer checked above");
+                let place = Expr::new(
+                    ExprKind::Unary(UnaryOp::Deref, Box::new(pointer.clone())),
+                    lhs.span,
+                );
+                let assign = Expr::new(
+                    ExprKind::Assign(op, Box::new(place), rhs.clone()),
+                    expr.span,
+                );
+                return match lhs.kind {
+                    ExprKind::Unary(UnaryOp::Deref, ref operand) if is_postfix(operand) => {
+                        format!("{}; {}", self.gen_effect(&assign), step)
+                    }
+                    _ => format!("{}; {}", step, self.gen_effect(&assign)),
+                };
+            }
+            ExprKind::Postfix(PostfixOp::Increment, ref operand)
+            | ExprKind::Unary(UnaryOp::PreIncrement, ref operand) => {
+                (operand, format!("{} += 1", self.gen_place(operand)))
+            }
+            ExprKind::Postfix(PostfixOp::Decrement, ref operand)
+            | ExprKind::Unary(UnaryOp::PreDecrement, ref operand) => {
+                (operand, format!("{} -= 1", self.gen_place(operand)))
+            }
+            //p = NULL; => p = None;, assigning to a reference writes through it
+            ExprKind::Assign(None, ref lhs, ref rhs)
+                if self
+                    .pointer(lhs)
+                    .is_some_and(|info| !info.ty.is_reference()) =>
+            {
+                let info = self.pointer(lhs).expect("pointer checked above");
+                if let Some(resize) = self.gen_realloc(info, rhs) {
+                    return resize;
+                }
+                return format!("{} = {}", info.name, self.gen_pointer_value(info, rhs));
+            }
+            //o.f = g; and table[i] = g; => Some(g)
+            ExprKind::Assign(None, ref lhs, ref rhs) if self.is_nullable_function(lhs) => (
+                lhs,
+                format!(
+                    "{} = {}",
+                    self.gen_place(lhs),
+                    self.gen_function_value(PointerKind::NullableFunction, rhs)
+                ),
+            ),
+            //o.next = p; casts references to the raw pointer of the field
+            ExprKind::Assign(None, ref lhs, ref rhs)
+                if matches!(lhs.kind, ExprKind::Member(..))
+                    && self
+                        .expr_type(lhs)
+                        .is_some_and(|ty| ty.is_pointer() && !ty.is_function_pointer()) =>
+            {
+                let info = PointerInfo {
+                    name: String::new(),
+                    ty: self.expr_type(lhs).expect("type checked above"),
+                    kind: PointerKind::Raw,
+                    mutable: true,
+                    boxed: false,
+                    reason: String::new(),
+                };
+                (
+                    lhs,
+                    format!(
+                        "{} = {}",
+                        self.gen_place(lhs),
+                        self.gen_pointer_value(&info, rhs)
+                    ),
+                )
+            }
+            ExprKind::Assign(op, ref lhs, ref rhs) => {
+                let op = match op {
+                    Some(op) => format!("{}=", op.as_str()),
+                    None => "=".to_string(),
+                };
+                let value = match self.expr_type(lhs) {
+                    Some(ref ty) => self.gen_converted(ty, rhs),
+                    None => self.gen_expr(rhs),
+                };
+                (lhs, format!("{} {} {}", self.gen_place(lhs), op, value))
+            }
+            //free(p); and delete p; => drop(p); for owned pointers
+            ExprKind::Call(..) | ExprKind::Delete(_) if self.gen_release(expr).is_some() => {
+                return self.gen_release(expr).expect("pointer checked above");
+            }
+            _ => return self.gen_expr(expr),
+        };
+        //writes through raw pointers are unsafe
+        if self.derefs_raw(place) {
+            format!("unsafe {{ {} }}", effect)
+        } else {
+            effect
+        }
+    }
+
+    // *p++ and *++p as a place, the pointer and the statement moving it
+    fn stepped_place<'b>(&self, place: &'b Expr) -> Option<(&'b Expr, String)> {
+        match place.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => {
+                let step = self.step_of(operand)?;
+                Some((stepped(operand), step))
+            }
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_release:
+     * free(p); and delete p; => drop(p); for owned pointers, a raw pointer
+     * that holds a box taken apart by Box::into_raw is put back together
+     */
+    fn gen_release(&self, expr: &Expr) -> Option<String> {
+        let info = released(expr).and_then(|name| self.pointer_info(name))?;
+        match info.kind {
+            PointerKind::Owned | PointerKind::NullableOwned | PointerKind::Vec => {
+                Some(format!("drop({})", info.name))
+            }
+            PointerKind::Raw if info.boxed => {
+                Some(format!("drop(unsafe {{ Box::from_raw({}) }})", info.name))
+            }
+            _ => None,
+        }
+    }
+
+    // raw pointer released that may hold memory that no Box allocated
+    fn unboxed_release<'e>(&self, expr: &'e Expr) -> Option<&'e str> {
+        let name = released(expr)?;
+        match self.pointer_info(name) {
+            Some(info) if info.kind == PointerKind::Raw && !info.boxed => Some(name),
+            _ => None,
+        }
+    }
+
+    /**
+     * gen_place:
+     * assigned or incremented expression, writes through nullable
+     * pointers unwrap them mutably and raw pointers are dereferenced
+     * without the unsafe block gen_effect puts around the statement
+     */
+    fn gen_place(&self, place: &Expr) -> String {
+        if let Some((info, index, backwards)) = self.element(place) {
+            return self.gen_element(info, index, backwards, true);
+        }
+        match place.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => {
+                format!("*{}", self.gen_place_operand(operand))
+            }
+            ExprKind::Ident(ref name) if self.is_reference(name) => format!("*{}", name),
+            ExprKind::Member(ref base, ref member, arrow) => {
+                let base = match self.pointer(base) {
+                    Some(info) if arrow && info.kind.is_nullable() => {
+                        format!("{}.as_deref_mut().unwrap()", info.name)
+                    }
+                    Some(info)
+                        if arrow
+                            && (info.kind == PointerKind::Slice
+                                || info.kind == PointerKind::Vec) =>
+                    {
+                        format!("{}[0]", info.name)
+                    }
+                    Some(info) if arrow && info.kind == PointerKind::Raw => {
+                        format!("(*{})", info.name)
+                    }
+                    _ => self.gen_place_operand(base),
+                };
+                format!("{}.{}", base, member)
+            }
+            ExprKind::Index(ref base, ref index) => {
+                format!(
+                    "{}[{}]",
+                    self.gen_place_operand(base),
+                    self.gen_index(index)
+                )
+            }
+            _ => self.gen_operand(place, ATOM),
+        }
+    }
+
+    fn gen_place_operand(&self, place: &Expr) -> String {
+        match place.kind {
+            ExprKind::Unary(UnaryOp::Deref, _) => format!("({})", self.gen_place(place)),
+            ExprKind::Member(..) | ExprKind::Index(..) => self.gen_place(place),
+            _ => self.gen_operand(place, ATOM),
+        }
+    }
+
+    // whether the place is reached through a raw pointer
+    fn derefs_raw(&self, place: &Expr) -> bool {
+        match place.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref base) | ExprKind::Member(ref base, _, true) => {
+                match self.pointer(stepped(base)) {
+                    Some(info) => info.kind == PointerKind::Raw,
+                    None => self.derefs_raw(base),
+                }
+            }
+            ExprKind::Index(ref base, _) if self.pointer(base).is_some() => self
+                .pointer(base)
+                .is_some_and(|info| info.kind == PointerKind::Raw),
+            ExprKind::Member(ref base, _, false) | ExprKind::Index(ref base, _) => {
+                self.derefs_raw(base)
+            }
+            _ => false,
+        }
+    }
+
+    /*-------------------------- expressions --------------------------*/
+
+    /**
+     * gen_expr:
+     * rust expression for c/c++ expression
+     */
+    pub fn gen_expr(&self, expr: &Expr) -> String {
+        match expr.kind {
+            //a copy of a value of a type parameter
+            _ if self.bounds.clones.contains(&expr.span) => {
+                let place = Expr::new(expr.kind.clone(), Span::default());
+                format!("{}.clone()", self.gen_operand(&place, ATOM))
+            }
+            ExprKind::Literal(ref literal) => match *literal {
+                Literal::Integer(ref number) => gen_integer(number),
+                Literal::Float(ref number) => gen_float(number),
+                Literal::Char(ref value) | Literal::Str(ref value) => value.clone(),
+                Literal::Bool(value) => value.to_string(),
+                Literal::Null => "std::ptr::null_mut()".to_string(),
+            },
+            ExprKind::Ident(ref name) if self.macro_params.contains(name) => format!("${}", name),
+            ExprKind::Ident(ref name) if self.gen_static_path(name).is_some() => self
+                .gen_static_path(name)
+                .expect("static member checked above"),
+            ExprKind::Ident(ref name) if self.members.contains(name) => {
+                format!("{}.{}", self.receiver, name)
+            }
+            //references to values are dereferenced where they are used
+            ExprKind::Ident(ref name) if self.is_reference(name) => {
+                match *self
+                    .pointer_info(name)
+                    .expect("reference checked above")
+                    .ty
+                    .referred()
+                {
+                    CType::Named(ref class) if self.find_record(class).is_some() => name.clone(),
+                    _ => format!("(*{})", name),
+                }
+            }
+            ExprKind::Ident(ref name) if self.const_param(name).is_some() => {
+                let ty = self.const_param(name).expect("parameter checked above");
+                match self.gen_type(ty) {
+                    ref rust if *rust == self.const_param_type(ty) => name.clone(),
+                    rust => format!("({} as {})", name, rust),
+                }
+            }
+            //enumerators of a c enum are named through their enum
+            ExprKind::Ident(ref name)
+                if !name.contains("::")
+                    && self.enumerator(name).is_some()
+                    && !self.is_variable(name) =>
+            {
+                let def = self.enumerator(name).expect("enumerator checked above");
+                format!("{}::{}", def.name.clone().unwrap_or_default(), name)
+            }
+            ExprKind::Ident(ref name) => name.clone(),
+            ExprKind::Generic(ref name, ref args) => {
+                format!("{}::{}", name, self.gen_template_args(args))
+            }
+            ExprKind::This => self.receiver.clone(),
+            ExprKind::Unary(UnaryOp::Not, ref operand)
+                if self.gen_null_test(operand, true).is_some() =>
+            {
+                self.gen_null_test(operand, true)
+                    .expect("pointer checked above")
+            }
+            ExprKind::Unary(UnaryOp::Deref, _) | ExprKind::Index(..)
+                if self.element(expr).is_some() =>
+            {
+                let (info, index, backwards) = self.element(expr).expect("pointer checked above");
+                self.gen_element(info, index, backwards, false)
+            }
+            //*p++ reads the element before moving on, *++p after
+            ExprKind::Unary(UnaryOp::Deref, ref operand) if self.step_of(operand).is_some() => {
+                let step = self.step_of(operand).expect("pointer checked above");
+                let info = self
+                    .pointer(stepped(operand))
+                    .expect("pointer checked above");
+                let value = self.gen_element(info, None, false, false);
+                if is_postfix(operand) {
+                    format!("{{ let value = {}; {}; value }}", value, step)
+                } else {
+                    format!("{{ {}; {} }}", step, value)
+                }
+            }
+            ExprKind::Unary(op, ref operand) => {
+                let operand_str = match op {
+                    UnaryOp::Plus | UnaryOp::Minus | UnaryOp::BitNot => {
+                        self.gen_int_operand(operand, UNARY)
+                    }
+                    _ => self.gen_operand(operand, UNARY),
+                };
+                //-(x == 0) as i32 would negate the comparison
+                let operand_str = if self.is_number_not(operand) {
+                    format!("({})", operand_str)
+                } else {
+                    operand_str
+                };
+                match op {
+                    UnaryOp::Plus => operand_str,
+                    UnaryOp::Minus => format!("-{}", operand_str),
+                    //c's ! yields the int 1 for 0 and 0 for any other number
+                    UnaryOp::Not if self.is_number(operand) => format!(
+                        "({} == 0) as i32",
+                        self.gen_int_operand(operand, BinaryOp::Eq.rust_precedence() + 1)
+                    ),
+                    //rust uses ! for both logical and bitwise negation
+                    UnaryOp::Not | UnaryOp::BitNot => format!("!{}", operand_str),
+                    UnaryOp::Deref => format!("*{}", operand_str),
+                    //&f is the function f itself
+                    UnaryOp::AddressOf if matches!(operand.kind, ExprKind::Ident(ref name) if self.is_function_name(name)) => {
+                        operand_str
+                    }
+                    UnaryOp::AddressOf => {
+                        if self.strict {
+                            format!("&{}", operand_str)
+                        } else {
+                            format!("&mut {}", operand_str)
+                        }
+                    }
+                    //value after the update
+                    UnaryOp::PreIncrement | UnaryOp::PreDecrement => {
+                        format!("{{ {}; {} }}", self.gen_effect(expr), operand_str)
+                    }
+                }
+            }
+            //value before the update
+            ExprKind::Postfix(op, ref operand) => {
+                let operand_str = self.gen_operand(operand, ATOM);
+                let undo = match op {
+                    PostfixOp::Increment => "-",
+                    PostfixOp::Decrement => "+",
+                };
+                format!(
+                    "{{ {}; {} {} 1 }}",
+                    self.gen_effect(expr),
+                    operand_str,
+                    undo
+                )
+            }
+            //p == NULL => p.is_none()
+            ExprKind::Binary(BinaryOp::Eq, ref pointer, ref null)
+            | ExprKind::Binary(BinaryOp::Eq, ref null, ref pointer)
+            | ExprKind::Binary(BinaryOp::Ne, ref pointer, ref null)
+            | ExprKind::Binary(BinaryOp::Ne, ref null, ref pointer)
+                if is_null(null) && self.gen_null_test(pointer, true).is_some() =>
+            {
+                let is_eq = matches!(expr.kind, ExprKind::Binary(BinaryOp::Eq, _, _));
+                self.gen_null_test(pointer, is_eq)
+                    .expect("pointer checked above")
+            }
+            ExprKind::Binary(BinaryOp::Sub, ..) if self.gen_difference(expr, None).is_some() => {
+                self.gen_difference(expr, None)
+                    .expect("pointers checked above")
+            }
+            ExprKind::Binary(..)
+                if self
+                    .gen_pointer_offset(
+                        expr,
+                        self.pointer(stepped(expr)).is_some_and(|info| info.mutable),
+                    )
+                    .is_some() =>
+            {
+                self.gen_pointer_offset(
+                    expr,
+                    self.pointer(stepped(expr)).is_some_and(|info| info.mutable),
+                )
+                .expect("pointer checked above")
+            }
+            ExprKind::Binary(op, ref lhs, ref rhs) if op == BinaryOp::And || op == BinaryOp::Or => {
+                //pointers used as operands are tested for NULL
+                let operand = |operand: &Expr, precedence: u8| {
+                    self.gen_null_test(operand, false)
+                        .unwrap_or_else(|| self.gen_operand(operand, precedence))
+                };
+                format!(
+                    "{} {} {}",
+                    operand(lhs, op.rust_precedence()),
+                    op.as_str(),
+                    operand(rhs, op.rust_precedence() + 1)
+                )
+            }
+            ExprKind::Binary(op, ref lhs, ref rhs) => {
+                //comparisons are non associative in rust, a < b < c needs parentheses
+                let left_precedence = if op.is_comparison() {
+                    op.rust_precedence() + 1
+                } else {
+                    op.rust_precedence()
+                };
+                //values of the same enum compare as they are, others as integers
+                let same_enum = op.is_comparison()
+                    && self.enum_of(lhs).is_some()
+                    && self.enum_of(lhs).map(|def| &def.name)
+                        == self.enum_of(rhs).map(|def| &def.name);
+                let operand = |operand: &Expr, precedence: u8| {
+                    if same_enum {
+                        self.gen_operand(operand, precedence)
+                    } else {
+                        self.gen_int_operand(operand, precedence)
+                    }
+                };
+                let mut lhs_str = operand(lhs, left_precedence);
+                //`x as T < y` would be read as generic arguments of T
+                if (op == BinaryOp::Lt || op == BinaryOp::Shl)
+                    && !lhs_str.starts_with('(')
+                    && (ends_with_cast(lhs)
+                        || self.is_number_not(lhs)
+                        || (!same_enum && self.enum_of(lhs).is_some()))
+                {
+                    lhs_str = format!("({})", lhs_str);
+                }
+                format!(
+                    "{} {} {}",
+                    lhs_str,
+                    op.as_str(),
+                    operand(rhs, op.rust_precedence() + 1)
+                )
+            }
+            //assignment as a value : the assigned value is the result
+            ExprKind::Assign(_, ref lhs, _) => {
+                format!("{{ {}; {} }}", self.gen_effect(expr), self.gen_expr(lhs))
+            }
+            ExprKind::Call(ref callee, ref expr_args) => {
+                let args = self.gen_args(callee, expr_args);
+                match callee.kind {
+                    ExprKind::Ident(ref name) if self.macro_rules.contains(name) => {
+                        format!("{}!({})", name, args.join(", "))
+                    }
+                    //T() is the default value of a type parameter
+                    ExprKind::Ident(ref name)
+                        if expr_args.is_empty()
+                            && self.is_type_param(&CType::Named(name.clone())) =>
+                    {
+                        format!("{}::default()", name)
+                    }
+                    //A(1, 2) constructs a value of class A
+                    ExprKind::Ident(ref name) if self.has_constructors(name) => {
+                        self.gen_construction(name, expr_args)
+                    }
+                    //other methods of the class are called on the same object
+                    ExprKind::Ident(ref name)
+                        if self.methods.iter().any(|method| method.0 == *name) =>
+                    {
+                        let receiver = self
+                            .methods
+                            .iter()
+                            .find(|method| method.0 == *name)
+                            .map(|method| method.1);
+                        let overload = self
+                            .class
+                            .as_ref()
+                            .and_then(|class| self.overload(class, name, expr_args));
+                        //arguments are passed to the parameters of the chosen overload
+                        let (name, args) = match overload {
+                            Some((name, function)) => {
+                                (name, self.gen_overload_args(function, expr_args))
+                            }
+                            None => (name.clone(), args),
+                        };
+                        match receiver {
+                            Some(Receiver::Static) => {
+                                format!("Self::{}({})", name, args.join(", "))
+                            }
+                            _ => format!("{}.{}({})", self.receiver, name, args.join(", ")),
+                        }
+                    }
+                    //(obj.*method)(args) calls the method with obj as receiver
+                    ExprKind::MemberPointer(ref object, ref method, arrow) => {
+                        let receiver = if arrow {
+                            self.gen_expr(object)
+                        } else if self.strict {
+                            format!("&{}", self.gen_operand(object, UNARY))
+                        } else {
+                            format!("&mut {}", self.gen_operand(object, UNARY))
+                        };
+                        let mut args = args;
+                        args.insert(0, receiver);
+                        format!("{}({})", self.gen_operand(method, ATOM), args.join(", "))
+                    }
+                    //obj.add(1, 2) => obj.add_i32_i32(1, 2) for overloaded methods
+                    ExprKind::Member(ref object, ref method, arrow)
+                        if self.method_overload(object, method, expr_args).is_some() =>
+                    {
+                        let (name, function) = self
+                            .method_overload(object, method, expr_args)
+                            .expect("overload checked above");
+                        let args = self.gen_overload_args(function, expr_args);
+                        let callee = Expr {
+                            kind: ExprKind::Member(object.clone(), name, arrow),
+                            span: callee.span,
+                        };
+                        format!("{}({})", self.gen_callee(&callee), args.join(", "))
+                    }
+                    _ => format!("{}({})", self.gen_callee(callee), args.join(", ")),
+                }
+            }
+            ExprKind::Index(ref base, ref index) => {
+                format!(
+                    "{}[{}]",
+                    self.gen_operand(base, ATOM),
+                    self.gen_index(index)
+                )
+            }
+            ExprKind::Member(ref base, ref member, true) if self.pointer(base).is_some() => {
+                let info = self.pointer(base).expect("pointer checked above");
+                match info.kind {
+                    PointerKind::Nullable | PointerKind::NullableOwned => {
+                        format!("{}.as_deref().unwrap().{}", info.name, member)
+                    }
+                    PointerKind::Raw => format!("unsafe {{ (*{}).{} }}", info.name, member),
+                    PointerKind::Slice | PointerKind::Vec => format!("{}[0].{}", info.name, member),
+                    _ => format!("{}.{}", info.name, member),
+                }
+            }
+            //rust auto dereferences on member access
+            ExprKind::Member(ref base, ref member, _) => {
+                format!("{}.{}", self.gen_operand(base, ATOM), member)
+            }
+            //left as written, the statement is handled manually
+            ExprKind::MemberPointer(ref object, ref member, arrow) => format!(
+                "{}{}{}",
+                self.gen_operand(object, ATOM),
+                if arrow { "->*" } else { ".*" },
+                self.gen_operand(member, ATOM)
+            ),
+            //(Color)1 converts with TryFrom
+            ExprKind::Cast(ref ty, ref operand) if self.enum_named(ty).is_some() => {
+                self.gen_converted(ty, operand)
+            }
+            ExprKind::Cast(ref ty, ref operand) => {
+                format!(
+                    "{} as {}",
+                    self.gen_operand(operand, CAST),
+                    self.gen_type(ty)
+                )
+            }
+            ExprKind::Conditional(ref cond, ref then, ref otherwise) => format!(
+                "if {} {{ {} }} else {{ {} }}",
+                self.gen_cond(cond),
+                self.gen_expr(then),
+                self.gen_expr(otherwise)
+            ),
+            ExprKind::SizeOfType(ref ty) => format!("std::mem::size_of::<{}>()", self.gen_type(ty)),
+            ExprKind::SizeOfExpr(ref operand) => {
+                format!(
+                    "std::mem::size_of_val(&{})",
+                    self.gen_operand(operand, UNARY)
+                )
+            }
+            ExprKind::Comma(ref exprs) => {
+                //evaluates every expression, value of the last one is the result
+                let mut parts: Vec<String> = Vec::new();
+                for (index, expr) in exprs.iter().enumerate() {
+                    if index + 1 == exprs.len() {
+                        parts.push(self.gen_expr(expr));
+                    } else {
+                        parts.push(self.gen_effect(expr));
+                    }
+                }
+                format!("{{ {} }}", parts.join("; "))
+            }
+            ExprKind::InitList(ref values) => {
+                let values: Vec<String> = values.iter().map(|value| self.gen_expr(value)).collect();
+                format!("[{}]", values.join(", "))
+            }
+            ExprKind::New(ref ty, ref args) => self.gen_new(ty, args),
+            //the box taken apart by Box::into_raw is freed again
+            ExprKind::Delete(_) if self.gen_release(expr).is_some() => {
+                self.gen_release(expr).expect("pointer checked above")
+            }
+            ExprKind::Delete(ref pointer) => format!("drop({})", self.gen_expr(pointer)),
+        }
+    }
+
+    // arguments of a call, passed to pointer parameters the way the callee takes them
+    fn gen_args(&self, callee: &Expr, args: &[Expr]) -> Vec<String> {
+        let params = match callee.kind {
+            ExprKind::Ident(ref name) => self
+                .tables
+                .iter()
+                .find(|table| table.function == *name)
+                .map(|table| table.params.clone())
+                .or_else(|| self.method_params(name)),
+            ExprKind::Member(_, ref method, _) => self.method_params(method),
+            _ => None,
+        };
+        self.gen_args_for(params, self.param_types(callee), args)
+    }
+
+    // arguments of a call to the overload of a method, converted to its parameters
+    fn gen_overload_args(&self, function: &Function, args: &[Expr]) -> Vec<String> {
+        let params = analyze_function(function, &self.tables, &self.receivers).params;
+        let types = function
+            .params
+            .iter()
+            .map(|param| param.ty.clone())
+            .collect();
+        self.gen_args_for(Some(params), Some(types), args)
+    }
+
+    // arguments converted to the pointer translations and types of the parameters
+    fn gen_args_for(
+        &self,
+        params: Option<Vec<Option<PointerInfo>>>,
+        types: Option<Vec<CType>>,
+        args: &[Expr],
+    ) -> Vec<String> {
+        args.iter()
+            .enumerate()
+            .map(
+                |(index, arg)| match params.as_ref().and_then(|params| params.get(index)) {
+                    Some(Some(param)) => self.gen_pointer_value(param, arg),
+                    _ => match types.as_ref().and_then(|types| types.get(index)) {
+                        Some(ty) => self.gen_converted(ty, arg),
+                        //variable arguments, printf("%d", RED)
+                        None => self.gen_int_operand(arg, 0),
+                    },
+                },
+            )
+            .collect()
+    }
+
+    // declared parameter types of the called function or method
+    fn param_types(&self, callee: &Expr) -> Option<Vec<CType>> {
+        let name = match callee.kind {
+            ExprKind::Ident(ref name) | ExprKind::Member(_, ref name, _) => name,
+            _ => return None,
+        };
+        let function = self
+            .prototypes
+            .iter()
+            .find(|function| function.name == *name && matches!(callee.kind, ExprKind::Ident(_)))
+            .or_else(|| {
+                self.records
+                    .iter()
+                    .flat_map(|record| methods(record))
+                    .find(|method| method.name == *name)
+            })?;
+        Some(
+            function
+                .params
+                .iter()
+                .map(|param| param.ty.clone())
+                .collect(),
+        )
+    }
+
+    // pointer and reference parameters of the method with the name, in any class
+    fn method_params(&self, name: &str) -> Option<Vec<Option<PointerInfo>>> {
+        let method = self
+            .records
+            .iter()
+            .flat_map(|record| methods(record))
+            .find(|method| method.name == name)?;
+        Some(analyze_function(method, &self.tables, &self.receivers).params)
+    }
+
+    // index of an array access, rust indexes with usize
+    fn gen_index(&self, index: &Expr) -> String {
+        match index.kind {
+            ExprKind::Literal(Literal::Integer(_)) => self.gen_expr(index),
+            _ => format!("{} as usize", self.gen_operand(index, CAST)),
+        }
+    }
+
+    /**
+     * gen_operand:
+     * operand of an operator binding with the given rust precedence,
+     * parenthesized when the operand itself binds looser
+     */
+    fn gen_operand(&self, expr: &Expr, precedence: u8) -> String {
+        let operand = self.gen_expr(expr);
+        //a block starting a statement would end it, { a += 1; a - 1 } + b
+        if rust_precedence(expr) < precedence || is_block(&operand) {
+            format!("({})", operand)
+        } else {
+            operand
+        }
+    }
+}
+
+// binding power of the generated rust expression, see BinaryOp::rust_precedence
+fn rust_precedence(expr: &Expr) -> u8 {
+    match expr.kind {
+        ExprKind::Binary(op, _, _) => op.rust_precedence(),
+        ExprKind::Cast(..) => CAST,
+        //increments are generated as blocks, gen_operand parenthesizes them
+        ExprKind::Unary(UnaryOp::PreIncrement, _) | ExprKind::Unary(UnaryOp::PreDecrement, _) => {
+            ATOM
+        }
+        ExprKind::Unary(..) => UNARY,
+        //if expression has to be parenthesized to be used as an operand
+        ExprKind::Conditional(..) => 0,
+        _ => ATOM,
+    }
+}
+
+// increments and raw pointer accesses are generated as blocks
+fn is_block(rust: &str) -> bool {
+    rust.starts_with('{') || rust.starts_with("unsafe {")
+}
+
+// derived struct dereferences to its base for inherited fields and methods
+fn gen_deref(name: &str, base: &str) -> Vec<String> {
+    vec![
+        format!(
+            "impl std::ops::Deref for {} {{ type Target = {}; fn deref(&self) -> &{} {{ &self.base }} }}\n",
+            name, base, base
+        ),
+        format!(
+            "impl std::ops::DerefMut for {} {{ fn deref_mut(&mut self) -> &mut {} {{ &mut self.base }} }}\n",
+            name, base
+        ),
+    ]
+}
+
+// integer literal 0, in any notation
+fn is_zero(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Literal(Literal::Integer(ref number)) => number
+            .digits
+            .chars()
+            .all(|digit| digit == '0' || digit == '_'),
+        _ => false,
+    }
+}
+
+// static standing in for the static data member count of Counter, Counter_count
+fn static_name(class: &str, member: &str) -> String {
+    format!("{}_{}", class, member)
+}
+
+// diagnostic comment holding the text of a construct crust can not translate
+fn gen_unsupported(unsupported: &Unsupported) -> Vec<String> {
+    //rust block comments nest, comment delimiters of the source are broken up
+    let text = unsupported.text.replace("/*", "/ *").replace("*/", "* /");
+    vec![
+        UNSUPPORTED.get_doc().to_string(),
+        format!(
+            "{} ({})\n{}\n */\n",
+            unsupported.reason,
+            location(unsupported.span),
+            text
+        ),
+    ]
+}
+
+// position of a construct in the c/c++ source, for warnings
+fn location(span: Span) -> String {
+    format!("line {}, column {}", span.line, span.column)
+}
+
+// integer literal in rust notation, 017 => 0o17 and 10UL => 10u64
+fn gen_integer(number: &Number) -> String {
+    let prefix = match number.radix {
+        Radix::Decimal => "",
+        Radix::Hexadecimal => "0x",
+        Radix::Octal => "0o",
+        Radix::Binary => "0b",
+    };
+    format!(
+        "{}{}{}",
+        prefix,
+        number.digits,
+        number_suffix(number.suffix)
+    )
+}
+
+// floating literal in rust notation, rust needs digits around the `.`, 1. => 1.0
+fn gen_float(number: &Number) -> String {
+    let mut digits = number.digits.clone();
+    if digits.starts_with('.') {
+        digits.insert(0, '0');
+    }
+    if let Some(dot) = digits.find('.') {
+        if !digits[dot + 1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+            digits.insert(dot + 1, '0');
+        }
+    }
+    format!("{}{}", digits, number_suffix(number.suffix))
+}
+
+// name of a class or enum type, Stack of Stack<int> as well
+fn type_name(ty: &CType) -> Option<String> {
+    match *ty.unqualified() {
+        CType::Named(ref name) | CType::Generic(ref name, _) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+// integer, floating or char type an enum value is cast to
+fn is_arithmetic(ty: &CType) -> bool {
+    match *ty {
+        CType::Primitive(token_type, _) => !matches!(
+            token_type,
+            TokenType::Void | TokenType::StringValue | TokenType::Auto
+        ),
+        _ => false,
+    }
+}
+
+// operand of a cast, case (int)RED: labels the enumerator
+fn uncast(expr: &Expr) -> &Expr {
+    match expr.kind {
+        ExprKind::Cast(_, ref operand) => uncast(operand),
+        _ => expr,
+    }
+}
+
+// p++ and p-- yield the pointer before the step
+fn is_postfix(expr: &Expr) -> bool {
+    matches!(expr.kind, ExprKind::Postfix(..))
+}
+
+// whether the generated expression ends with `as T`
+
+fn ends_with_cast(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Cast(..) => true,
+        ExprKind::Binary(_, _, ref rhs) => ends_with_cast(rhs),
+        ExprKind::Unary(UnaryOp::Minus, ref operand)
+        | ExprKind::Unary(UnaryOp::Not, ref operand)
+        | ExprKind::Unary(UnaryOp::BitNot, ref operand)
+        | ExprKind::Unary(UnaryOp::Deref, ref operand)
+        | ExprKind::Unary(UnaryOp::AddressOf, ref operand) => ends_with_cast(operand),
+        _ => false,
+    }
+}
+
+// obj.*ptr used other than as the callee of a call
+fn uses_data_member_pointer(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::MemberPointer(..) => true,
+        ExprKind::Call(ref callee, ref args) => {
+            let callee_uses = match callee.kind {
+                ExprKind::MemberPointer(ref object, ref method, _) => {
+                    uses_data_member_pointer(object) || uses_data_member_pointer(method)
+                }
+                _ => uses_data_member_pointer(callee),
+            };
+            callee_uses || args.iter().any(uses_data_member_pointer)
+        }
+        _ => expr.children().into_iter().any(uses_data_member_pointer),
+    }
+}
+
+// while(1) and while(true)
+fn is_always_true(cond: &Expr) -> bool {
+    match cond.kind {
+        ExprKind::Literal(Literal::Bool(true)) => true,
+        ExprKind::Literal(Literal::Integer(ref number)) => number.value() == Some(1),
+        _ => false,
+    }
+}
+
+// while(0), the condition of do { } while (0) statement macros
+// return *this; of an assignment operator
+fn returns_this(value: &Expr) -> bool {
+    match value.kind {
+        ExprKind::This => true,
+        ExprKind::Unary(UnaryOp::Deref, ref operand) => operand.kind == ExprKind::This,
+        _ => false,
+    }
+}
+
+fn is_always_false(cond: &Expr) -> bool {
+    match cond.kind {
+        ExprKind::Literal(Literal::Bool(false)) => true,
+        ExprKind::Literal(Literal::Integer(ref number)) => number.value() == Some(0),
+        _ => false,
+    }
+}
+
+// shift with a string or char operand, cout << "text"
+fn is_stream_output(expr: &Expr) -> bool {
+    matches!(
+        expr.kind,
+        ExprKind::Binary(BinaryOp::Shl, _, _) | ExprKind::Binary(BinaryOp::Shr, _, _)
+    ) && is_stream_expr(expr)
+}
+
+fn is_stream_expr(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Shl, ref lhs, ref rhs)
+        | ExprKind::Binary(BinaryOp::Shr, ref lhs, ref rhs) => {
+            is_stream_expr(lhs) || is_stream_expr(rhs)
+        }
+        ExprKind::Literal(Literal::Str(_)) | ExprKind::Literal(Literal::Char(_)) => true,
+        _ => false,
+    }
+}
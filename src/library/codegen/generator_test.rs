@@ -0,0 +1,2013 @@
+use library::codegen::generator::init_generator;
+use library::lexer::tokenizer::Tokenizer;
+use library::parser::parser::init_parser;
+
+// translated code with whitespace collapsed, doc comments included
+fn translate(text: &str, strict: bool) -> String {
+    let tokens = Tokenizer::new(text).tokenize().expect("lexing failed");
+    let (unit, errors) = init_parser(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let stream = init_generator(&unit, strict);
+    stream
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[test]
+fn test_function_with_shorthand_return() {
+    let rust = translate("int add(int a, int b){ return a + b; }", false);
+    assert!(
+        rust.contains("fn add ( mut a : i32 , mut b : i32 , ) -> i32 {"),
+        "{}",
+        rust
+    );
+    assert!(rust.ends_with("a + b }"), "{}", rust);
+}
+
+#[test]
+fn test_return_in_main_exits() {
+    let rust = translate("int main(){ return 3; }", false);
+    assert!(rust.contains("std::process::exit(3);"), "{}", rust);
+}
+
+#[test]
+fn test_declarations_follow_mode() {
+    let loose = translate("int main(){ int a = 1; }", false);
+    assert!(loose.contains("let mut a : i32 = 1 ;"), "{}", loose);
+
+    let strict = translate("int main(){ int a = 1; }", true);
+    assert!(strict.contains("let a : i32 = 1 ;"), "{}", strict);
+
+    let global = translate("int a;", false);
+    assert!(global.contains("static mut a : i32 = 0i32 ;"), "{}", global);
+}
+
+#[test]
+fn test_array_declaration() {
+    let rust = translate("int main(){ int a[] = {1, 2, 3}; char b[4]; }", false);
+    assert!(
+        rust.contains("let mut a : [i32 ; 3] = [1, 2, 3] ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut b : [char ; 4] = ['_' ; 4] ;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_increments() {
+    let rust = translate("int main(){ i++; --j; a = b++; }", false);
+    assert!(rust.contains("i += 1;"), "{}", rust);
+    assert!(rust.contains("j -= 1;"), "{}", rust);
+    assert!(rust.contains("a = { b += 1; b - 1 };"), "{}", rust);
+}
+
+#[test]
+fn test_nested_binary_is_parenthesized() {
+    let rust = translate("int main(){ a = (b + c) * d; }", false);
+    assert!(rust.contains("a = (b + c) * d;"), "{}", rust);
+}
+
+#[test]
+fn test_block_operands_are_parenthesized() {
+    let rust = translate(
+        "int f(int a, int b) { return a++ + b; }\nint g(int *p) { return p[0] + p[-1]; }\n",
+        false,
+    );
+    assert!(rust.contains("({ a += 1; a - 1 }) + b }"), "{}", rust);
+    assert!(
+        rust.contains("(unsafe { *p }) + (unsafe { *p.offset(-1) }) }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_loops() {
+    let rust = translate(
+        "int main(){ while(1){ x--; } for(int i = 0; i < 10; i++){ f(i); } }",
+        false,
+    );
+    assert!(rust.contains("loop { x -= 1; }"), "{}", rust);
+    assert!(rust.contains("for i in 0..10 { f(i); }"), "{}", rust);
+}
+
+#[test]
+fn test_counting_loops_become_ranges() {
+    let rust = translate(
+        "int f(int n) { int s = 0;\n\
+         for (int i = 1; i <= n; i += 2) s += i;\n\
+         for (int i = n - 1; i >= 0; i--) s += i;\n\
+         for (int i = n; i > 0; i -= 3) s += i;\n\
+         for (long i = 0; i < n; i++) s++;\n\
+         for (int i = 0; i < n; i++) { n--; }\n\
+         for (unsigned u = 10; u >= 0; u--) s++;\n\
+         return s; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("for i in (1..=n).step_by(2) { s += i; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("for i in (0..=n - 1).rev() { s += i; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("for i in (1..=n).rev().step_by(3) { s += i; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("for i in 0i64..n { s += 1; }"), "{}", rust);
+    //u >= 0 holds for every unsigned value, the loop never ends
+    assert!(
+        rust.contains("let mut u : u32 = 10 ; while u >= 0 {"),
+        "{}",
+        rust
+    );
+    //the body changes the bound, it is checked on every iteration
+    assert!(
+        rust.contains("let mut i : i32 = 0 ; while i < n { n -= 1; i += 1; } }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_enumerator_bounds_are_cast_to_the_counter() {
+    let rust = translate(
+        "enum Color { RED, GREEN, COUNT };\n\
+         int f() { int s = 0;\n\
+         for (int k = RED; k < COUNT; k++) s += k;\n\
+         for (int k = COUNT; k > RED; k--) s += k;\n\
+         return s; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("for k in Color::RED as i32..Color::COUNT as i32 { s += k; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("for k in (Color::RED as i32 + 1..=Color::COUNT as i32).rev() { s += k; }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_loop_counter_does_not_shadow_outer_variable() {
+    let rust = translate(
+        "int f(int n) { int i = 100;\n\
+         for (int i = 0; i != n; i++) { n--; }\n\
+         return i; }\n",
+        false,
+    );
+    //the counter is declared in a block of its own around the loop
+    assert!(rust.contains("let mut i : i32 = 100 ; { /*"), "{}", rust);
+    assert!(
+        rust.contains("let mut i : i32 = 0 ; while i != n { n -= 1; i += 1; } }"),
+        "{}",
+        rust
+    );
+    assert!(rust.ends_with("i }"), "{}", rust);
+}
+
+#[test]
+fn test_indexed_array_loops_iterate() {
+    let rust = translate(
+        "int main() { int a[5]; int total = 0;\n\
+         for (int i = 0; i < 5; i++) { a[i] = i * i; }\n\
+         for (int i = 0; i < 5; i++) { total += a[i]; }\n\
+         return total; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "for (i, item) in a.iter_mut().enumerate() { let i = i as i32; *item = i * i; }"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("for (_, item) in a.iter().enumerate() { total += *item; }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_continue_runs_the_step() {
+    let rust = translate(
+        "int f(int n) { int s = 0; int i;\n\
+         for (i = 0; i < n; i++) { if (i % 2 == 0) continue; if (i > 15) break; s += i; }\n\
+         return s; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "i = 0; 'outer: while i < n { 'body: { if i % 2 == 0 { break 'body; } if i > 15 { break 'outer; } s += i; } i += 1; }"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_switch_becomes_match() {
+    let rust = translate(
+        "int main(){ switch(a){ case 1: b = 1; break; case 2: b = 2; break; } }",
+        false,
+    );
+    assert!(
+        rust.contains("match a { 1 => { b = 1; } 2 => { b = 2; } _ => { } }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_grouped_cases_and_fallthrough() {
+    let rust = translate(
+        "int main(){ switch(a){ case 1: case 2: b = 1; break; default: b = 0; case 3: b += 3; case 4 ... 6: b += 4; } }",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "match a { 1 | 2 => { b = 1; } 3 => { b += 3; b += 4; } 4..=6 => { b += 4; } _ => { b = 0; b += 3; b += 4; } }"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_break_inside_case_leaves_labelled_block() {
+    let rust = translate(
+        "int main(){ while (a) { switch(a){ case 1: if (b) break; b = 1; break; case 2: for (;;) { break; } continue; } a--; } }",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "'outer: while a { 'switch: { match a { 1 => { if b { break 'switch; } b = 1; } 2 => { loop { break; } continue 'outer; } _ => { } } }"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_struct_and_variable() {
+    let rust = translate(
+        "struct A { int a; float b; }; int main(){ struct A x; }",
+        false,
+    );
+    assert!(
+        rust.contains("struct A { a : i32 , b : f32 , }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut x : A = A { a : 0i32 , b : 0.0f32 , } ;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_struct_initializer_sets_fields_in_order() {
+    let rust = translate(
+        "struct P { int x; int y; }; struct L { struct P a; struct P b; };\n\
+         int main(){ struct P p = {1, 2}; struct P q = {3}; struct L l = {{1, 2}, {3, 4}}; }",
+        false,
+    );
+    assert!(
+        rust.contains("let mut p : P = P { x : 1 , y : 2 , } ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut q : P = P { x : 3 , y : 0i32 , } ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "let mut l : L = L { a : P { x : 1 , y : 2 , } , b : P { x : 3 , y : 4 , } , } ;"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_class_methods_in_impl() {
+    let rust = translate("class A { int a; public: int get(){ return a; } };", false);
+    assert!(rust.contains("struct A { a : i32 , }"), "{}", rust);
+    assert!(
+        rust.contains("impl A { pub fn get ( &self, ) -> i32 {"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_typedef_alias() {
+    let rust = translate("typedef unsigned int uint; typedef struct S S;", false);
+    assert!(rust.contains("type uint = u32 ;"), "{}", rust);
+    assert!(!rust.contains("type S"), "{}", rust);
+}
+
+#[test]
+fn test_sizeof_and_cast() {
+    let rust = translate("int main(){ x = (double)y + sizeof(long); }", false);
+    assert!(
+        rust.contains("x = y as f64 + std::mem::size_of::<i64>();"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_parentheses_follow_rust_precedence() {
+    let rust = translate(
+        "int main(){ x = a & b == c; y = (a & b) == c; z = a - (b - c); w = a * b + c; }",
+        false,
+    );
+    assert!(rust.contains("x = a & (b == c);"), "{}", rust);
+    assert!(rust.contains("y = a & b == c;"), "{}", rust);
+    assert!(rust.contains("z = a - (b - c);"), "{}", rust);
+    assert!(rust.contains("w = a * b + c;"), "{}", rust);
+}
+
+#[test]
+fn test_comparison_chain_is_parenthesized() {
+    let rust = translate("int main(){ x = a < b == c; }", false);
+    assert!(rust.contains("x = (a < b) == c;"), "{}", rust);
+}
+
+#[test]
+fn test_cast_before_less_than() {
+    let rust = translate("int main(){ x = (long)a < b; y = -(int)c; }", false);
+    assert!(rust.contains("x = (a as i64) < b;"), "{}", rust);
+    assert!(rust.contains("y = -(c as i32);"), "{}", rust);
+}
+
+#[test]
+fn test_conditional_becomes_if_expression() {
+    let rust = translate(
+        "int main(){ x = a > b ? a : b; y = 1 + (c ? 2 : 3); }",
+        false,
+    );
+    assert!(rust.contains("x = if a > b { a } else { b };"), "{}", rust);
+    assert!(
+        rust.contains("y = 1 + (if c { 2 } else { 3 });"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_assignment_used_as_value() {
+    let rust = translate("int main(){ a = b = 0; if ((c = f()) > 0) {} }", false);
+    assert!(rust.contains("a = { b = 0; b };"), "{}", rust);
+    assert!(rust.contains("if ({ c = f(); c }) > 0 {"), "{}", rust);
+}
+
+#[test]
+fn test_comma_used_as_value() {
+    let rust = translate("int main(){ x = (a++, b); }", false);
+    assert!(rust.contains("x = { a += 1; b };"), "{}", rust);
+}
+
+#[test]
+fn test_macro_constants() {
+    let rust = translate(
+        "#define MAX_LEN 256\n#define PI 3.14\n#define NAME \"crust\"\n#define MASK (MAX_LEN - 1)\n#define VERBOSE MAX_LEN > 10\nint a[MAX_LEN];\n",
+        false,
+    );
+    assert!(rust.contains("const MAX_LEN : i32 = 256 ;"), "{}", rust);
+    assert!(rust.contains("const PI : f64 = 3.14 ;"), "{}", rust);
+    assert!(rust.contains("const NAME : &str = \"crust\" ;"), "{}", rust);
+    assert!(
+        rust.contains("const MASK : i32 = MAX_LEN - 1 ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("const VERBOSE : bool = MAX_LEN > 10 ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("[i32 ; MAX_LEN as usize]"), "{}", rust);
+}
+
+#[test]
+fn test_function_like_macros() {
+    let rust = translate(
+        "#define KB(x) ((x) * 1024)\n#define HALF(x) ((x) / 2.0)\n#define SQUARE(x) ((x) * (x))\n#define SWAP(a, b) do { int t = a; a = b; b = t; } while (0)\n#define TWICE(x) (x * 2)\nint main(){ SWAP(x, y); y = SQUARE(x + 1) + KB(2) + TWICE(1 + 2); }",
+        false,
+    );
+    assert!(
+        rust.contains("#[inline] fn KB ( x : i32 , ) -> i32 { x * 1024 }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("fn HALF ( x : f64 , ) -> f64 { x / 2.0 }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("macro_rules! SQUARE { ($x:expr) => { $x * $x } ; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "macro_rules! SWAP { ($a:expr, $b:expr) => { { /*Avoid using mutable variables unless it is necessary to do so */ let mut t : i32 = $a ; $a = $b; $b = t; } } ; }"
+        ),
+        "{}",
+        rust
+    );
+    //an unparenthesized parameter keeps the argument grouped as macro_rules!
+    assert!(
+        rust.contains("macro_rules! TWICE { ($x:expr) => { $x * 2 } ; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("SWAP!(x, y);"), "{}", rust);
+    assert!(
+        rust.contains("y = SQUARE!(x + 1) + KB(2) + TWICE!(1 + 2);"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_macro_using_caller_local_is_reported() {
+    let rust = translate(
+        "#define SWAP(a, b) do { t = a; a = b; b = t; } while (0)\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "Reason: the body uses `t` from the caller's scope, macro_rules! hygiene would not resolve it"
+        ),
+        "{}",
+        rust
+    );
+    assert!(!rust.contains("macro_rules! SWAP"), "{}", rust);
+}
+
+#[test]
+fn test_macro_using_file_scope_names_is_translated() {
+    let rust = translate(
+        "enum { LIMIT = 8 };\nconst int step = 2;\n#define CAP(x) ((x) > LIMIT ? LIMIT : (x) * step)\n",
+        false,
+    );
+    assert!(rust.contains("macro_rules! CAP"), "{}", rust);
+    assert!(!rust.contains("caller's scope"), "{}", rust);
+}
+
+#[test]
+fn test_untranslated_macro_diagnostic() {
+    let rust = translate("#define STR(x) #x\n#define GUARD_H\n", false);
+    assert!(
+        rust.contains(
+            "Reason: the `#` and `##` operators have no rust equivalent * #define STR(x) #x (line 1, column 1) **/"
+        ),
+        "{}",
+        rust
+    );
+    assert!(!rust.contains("GUARD_H"), "{}", rust);
+}
+
+#[test]
+fn test_numeric_literals() {
+    let rust = translate(
+        "int main(){ a = 017 + 0x1Fu + 0b101; b = 10UL + 1'000 + 7ll; c = 1e-9 + .5f + 2. + 3.0L; }",
+        false,
+    );
+    assert!(rust.contains("a = 0o17 + 0x1Fu32 + 0b101;"), "{}", rust);
+    assert!(rust.contains("b = 10u64 + 1_000 + 7i64;"), "{}", rust);
+    assert!(
+        rust.contains("c = 1e-9 + 0.5f32 + 2.0 + 3.0f64;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_macro_constant_types_follow_literals() {
+    let rust = translate(
+        "#define BIG 0x100000000\n#define MASK 0xFFFFFFFF\n#define RATE 0.5f\n#define SIZE 16UL\n",
+        false,
+    );
+    assert!(rust.contains("const BIG : i64 = 0x100000000 ;"), "{}", rust);
+    assert!(rust.contains("const MASK : u32 = 0xFFFFFFFF ;"), "{}", rust);
+    assert!(rust.contains("const RATE : f32 = 0.5f32 ;"), "{}", rust);
+    assert!(rust.contains("const SIZE : u64 = 16u64 ;"), "{}", rust);
+}
+
+#[test]
+fn test_manual_statement_points_at_source() {
+    let rust = translate("int main(){\n    out << \"hi\";\n}", false);
+    assert!(
+        rust.contains("//This statement need to be handled manually (line 2, column 5)"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_bitwise_operators() {
+    let rust = translate(
+        "void f(){ int r = a ^ b & c | d; r ^= 1; r &= ~MASK; r |= 0x80; r <<= 2; r >>= n; p = &r; }",
+        false,
+    );
+    assert!(
+        rust.contains("let mut r : i32 = a ^ b & c | d ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("r ^= 1; r &= !MASK; r |= 0x80;"), "{}", rust);
+    assert!(rust.contains("r <<= 2; r >>= n; p = &mut r;"), "{}", rust);
+}
+
+#[test]
+fn test_logical_not_on_numbers() {
+    let rust = translate(
+        "int f(int x, bool ok){ int b = !x; int c = -!x; if (!x) return b; if (!ok) return c; return !(x & 4); }",
+        false,
+    );
+    assert!(
+        rust.contains("let mut b : i32 = (x == 0) as i32 ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut c : i32 = -((x == 0) as i32) ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("if x == 0 { return b; }"), "{}", rust);
+    assert!(rust.contains("if !ok { return c; }"), "{}", rust);
+    //& binds tighter than == in rust
+    assert!(rust.contains("(x & 4 == 0) as i32"), "{}", rust);
+}
+
+#[test]
+fn test_member_pointers() {
+    let rust = translate(
+        "void f(){ (obj.*handler)(1); (ptr->*handler)(); x = obj.*field; }",
+        false,
+    );
+    assert!(rust.contains("handler(&mut obj, 1);"), "{}", rust);
+    assert!(rust.contains("handler(ptr);"), "{}", rust);
+    assert!(rust.contains("// x = obj.*field;"), "{}", rust);
+}
+
+#[test]
+fn test_variadic_function() {
+    let rust = translate("int log(const char *fmt, ...){ return 0; }", false);
+    assert!(
+        rust.contains("the variable arguments `...` of the function below were dropped"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("fn log ("), "{}", rust);
+}
+
+#[test]
+fn test_unsupported_construct_diagnostic() {
+    let rust = translate(
+        "namespace fs = std::filesystem;\nint main(){ goto *target; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "Crust could not translate the C/C++ code below, * it has to be handled manually. \
+             * Reason: namespace aliases are not supported (line 1, column 1) \
+             namespace fs = std::filesystem; */"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "Reason: computed `goto` is not supported (line 2, column 13) goto *target; */"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_forward_goto_becomes_labelled_block() {
+    let rust = translate(
+        "int f(int n) { int rc = -1; if (n < 0) goto out; rc = n; out: return rc; }\n\
+         int g(int n) { for (int i = 0; i < n; i++) { while (n > 0) { if (i == n) goto found; n--; } } found: return n; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("'out: { if n < 0 { break 'out; } rc = n; } return rc;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("'found: while i < n { while n > 0 { if i == n { break 'found; }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_backward_goto_becomes_loop() {
+    let rust = translate(
+        "int f(int n) { int tries = 0; again: tries++; if (tries < n) goto again; return tries; }\n\
+         int g(int n) { if (n) goto inside; { inside: n++; } return n; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "'again: loop { tries += 1; if tries < n { continue 'again; } break; } return tries;"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "Crust could not turn the goto below into a labelled block or loop, \
+             * its label is neither in the same block as the goto nor in a block around it. \
+             * Label: inside (line 2, column 38) */ // goto inside;"
+        ),
+        "{}",
+        rust
+    );
+
+    let rust = translate(
+        "int f(int n) { goto a; b: n++; goto c; a: n--; goto b; c: return n; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "* its label is in reach, but the jumps to it overlap the jumps to another label \
+             * and neither labelled block nor loop can be nested in the other. \
+             * Label: b (line 1, column 24) */ // goto b;"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_keywords_with_rust_equivalent() {
+    let rust = translate(
+        "extern int shared;\ninline int id(int x) { return x; }\nint *p = nullptr;\nregister int r = 0;\n",
+        false,
+    );
+    assert!(
+        rust.contains("extern \"C\" { static mut shared: i32; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("#[inline] fn id ("), "{}", rust);
+    assert!(rust.contains("std::ptr::null_mut()"), "{}", rust);
+    assert!(rust.contains("static mut r : i32 = 0 ;"), "{}", rust);
+
+    let rust = translate(
+        "class A { public: int x; int get() { return this->x; } };\nvolatile int flag;\n",
+        false,
+    );
+    assert!(rust.contains("self.x"), "{}", rust);
+    assert!(rust.contains("rust has no volatile variables"), "{}", rust);
+}
+
+#[test]
+fn test_extern_declarations() {
+    let rust = translate(
+        "extern int count;\nextern const int limit;\nextern int x = 5;\nint f() { return count + limit + x; }\nint count = 1;\n",
+        false,
+    );
+    assert!(
+        !rust.contains("extern \"C\" { static mut count"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("static mut count : i32 = 1 ;"), "{}", rust);
+    assert!(
+        rust.contains("extern \"C\" { static limit: i32; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("static mut x : i32 = 5 ;"), "{}", rust);
+}
+
+#[test]
+fn test_extern_function_prototypes_are_linked() {
+    let rust = translate(
+        "extern int ext_fn(int);\nextern \"C\" { int c_fn(const char *s, ...); int f(int a); }\n\
+         int helper(int);\nint f(int a) { return a; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("extern \"C\" { fn ext_fn(_: i32) -> i32; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("extern \"C\" { fn c_fn(s: *const char, ...) -> i32; }"),
+        "{}",
+        rust
+    );
+    //defined in the unit, or a prototype without extern
+    assert!(!rust.contains("fn f(a: i32)"), "{}", rust);
+    assert!(!rust.contains("helper"), "{}", rust);
+}
+
+#[test]
+fn test_unparsed_region_is_kept_in_a_comment() {
+    let tokens = Tokenizer::new("int f(){ a = 1 +; /* why */ }\nint g(){ return 2; }\n")
+        .tokenize()
+        .expect("lexing failed");
+    let (unit, errors) = init_parser(&tokens);
+    assert_eq!(errors.len(), 1);
+    let rust = init_generator(&unit, false).join(" ");
+    assert!(
+        rust.contains("Reason: expected expression, found `;` (line 1, column 10)\na = 1 +;\n */"),
+        "{}",
+        rust
+    );
+    //the comment after the region is kept as it is
+    assert!(rust.contains("/* why */"), "{}", rust);
+    assert!(rust.contains("fn g ( ) -> i32 {"), "{}", rust);
+}
+
+#[test]
+fn test_pointer_parameters_become_references() {
+    let rust = translate(
+        "void swap(int *a, int *b){ int t = *a; *a = *b; *b = t; }\n\
+         int get(const int *p){ return *p; }\n\
+         int main(){ int x = 1, y = 2; int *p = &x; swap(p, &y); return get(&x); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn swap ( mut a : &mut i32 , mut b : &mut i32 , )"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("//a: &mut i32, never NULL, written through (line 1)"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("fn get ( mut p : &i32 , ) -> i32"),
+        "{}",
+        rust
+    );
+    //p is passed on to a parameter that is written through
+    assert!(
+        rust.contains(
+            "//p: &mut i32, never NULL, passed to `swap`, which writes through it (line 3)"
+        ),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("let mut p : &mut i32 = &mut x ;"), "{}", rust);
+    assert!(rust.contains("swap(p, &mut y);"), "{}", rust);
+    assert!(rust.contains("std::process::exit(get(&x));"), "{}", rust);
+}
+
+#[test]
+fn test_nullable_pointer_becomes_option() {
+    let rust = translate(
+        "void reset(int *p){ if (p != NULL) { *p = 0; } }\n\
+         int main(){ int y = 1; int *q = NULL; if (!q) q = &y; reset(q); reset(NULL); }\n\
+         int first(struct Node *node){ return node ? node->value : 0; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn reset ( mut p : Option<&mut i32> , )"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("if p.is_some() { *p.as_deref_mut().unwrap() = 0; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut q : Option<&mut i32> = None ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("if q.is_none() { q = Some(&mut y); } reset(q); reset(None);"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("if node.is_some() { node.as_deref().unwrap().value } else { 0 }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_pointers_passed_null_become_option() {
+    let rust = translate(
+        "struct Node { int val; struct Node *next; };\n\
+         struct Node *push(struct Node *head, int v) {\n\
+           struct Node *n = (struct Node *)malloc(sizeof(struct Node));\n\
+           n->next = head; return n; }\n\
+         int get(int *p) { return *p; }\n\
+         int main() { struct Node *list = NULL; list = push(list, 1); return get(NULL); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn get ( mut p : Option<&i32> , )"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("get(None)"), "{}", rust);
+    assert!(
+        rust.contains("fn push ( mut head : Option<&Node> , mut v : i32 , )"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "n.next = head.map_or(std::ptr::null_mut(), |head| head as *const Node as *mut Node);"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("list = Some(push(list.as_deref(), 1));"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_allocated_pointer_becomes_box() {
+    let rust = translate(
+        "int main(){ int *h = (int *) malloc(sizeof(int)); *h = 5; free(h); }\n\
+         struct Node *make(){ struct Node *n = calloc(1, sizeof(struct Node)); return n; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("//h: Box<i32>, owns the memory allocated with `malloc` (line 1)"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut h : Box<i32> = Box::new(0i32) ; *h = 5; drop(h);"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("fn make ( ) -> Box<Node> {"), "{}", rust);
+    assert!(
+        rust.contains("let mut n : Box<Node> = Box::new(Default::default()) ;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_raw_pointer_is_the_last_resort() {
+    let rust = translate(
+        "struct List { int value; struct List *next; };\n\
+         int *last;\n\
+         void f(int *p, void *data){ p--; *p = 1; int x = *p; p->value = x; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("Reason: next: struct fields can't hold borrowed references without lifetimes */ next : *mut List ,"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("Reason: last: global pointers can't hold borrowed references */ static mut last : *mut i32 = std::ptr::null_mut() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("Reason: p: moved backwards (line 3)"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("Reason: data: `void *` has no rust equivalent"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("fn f ( mut p : *mut i32 , mut data : *const std::ffi::c_void , )"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("p = unsafe { p.sub(1) }; unsafe { *p = 1 };"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut x : i32 = unsafe { *p } ; unsafe { (*p).value = x };"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_raw_pointer_is_borrowed_for_references() {
+    let rust = translate(
+        "void set(int *r) { *r = 1; }\n\
+         int get(int *r) { if (r) { return *r; } return 0; }\n\
+         int main(){ int x = 0; int *p = &x; int **pp = &p; set(p); int *q = p; *q = 2; return get(p); }\n",
+        false,
+    );
+    assert!(rust.contains("let mut p : *mut i32 = &mut x ;"), "{}", rust);
+    assert!(rust.contains("set(unsafe { &mut *p });"), "{}", rust);
+    assert!(
+        rust.contains("let mut q : &mut i32 = unsafe { &mut *p } ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("get(unsafe { p.as_ref() })"), "{}", rust);
+}
+
+#[test]
+fn test_decayed_array_becomes_slice() {
+    let rust = translate(
+        "int main(){ int arr[3] = {1, 2, 3}; int *p = arr; int i = 1; p[i] = 4; *(p + 2) = 5; int x = *p; return x; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("let mut p : &mut [i32] = &mut arr[..] ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("p[i as usize] = 4;"), "{}", rust);
+    assert!(rust.contains("p[2] = 5;"), "{}", rust);
+    assert!(rust.contains("let mut x : i32 = p[0] ;"), "{}", rust);
+}
+
+#[test]
+fn test_slice_parameter_takes_arrays() {
+    let rust = translate(
+        "int sum(const int *a, int n){ int total = 0; while (n > 0) { total += *a; a++; n--; } return total; }\n\
+         int main(){ int arr[2] = {1, 2}; return sum(arr + 1, 1) + sum(arr, 2); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn sum ( mut a : &[i32] , mut n : i32 , ) -> i32"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("total += a[0]; a = &a[1..];"), "{}", rust);
+    assert!(
+        rust.contains("sum(&arr[1..], 1) + sum(&arr[..], 2)"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_string_cursor_walks_str() {
+    let rust = translate(
+        "int length(const char *s){ int n = 0; while (*s) { s++; n++; } return n; }\n\
+         char first(const char *s){ return *s; }\n",
+        false,
+    );
+    assert!(rust.contains("fn length ( mut s : &str , )"), "{}", rust);
+    assert!(
+        rust.contains("while !s.is_empty() { s = &s[1..]; n += 1; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("s.chars().next().unwrap_or('\\0')"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_copy_loop_steps_both_slices() {
+    let rust = translate(
+        "void copy(int *dst, const int *src, int n){ while (n > 0) { *dst++ = *src++; n--; } }\n",
+        false,
+    );
+    assert!(
+        rust.contains("dst[0] = { let value = src[0]; src = &src[1..]; value }; dst = &mut std::mem::take(&mut dst)[1..];"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_raw_pointer_arithmetic_uses_offsets() {
+    let rust = translate(
+        "void f(int *p, int *q, int i){ p -= 2; p[i] = 1; int x = *(p + i); int d = p - q; }\n\
+         int g(int *p, int n){ int *end = p + n; int s = p[0] + *(p - 1); while (p < end) *p++ = 0; return s; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("Reason: p: moved backwards (line 1)"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("p = unsafe { p.offset(-2) };"), "{}", rust);
+    assert!(
+        rust.contains("unsafe { *p.offset(i as isize) = 1 };"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut x : i32 = unsafe { *p.offset(i as isize) } ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut d : i32 = unsafe { p.offset_from(q) as i32 } ;"),
+        "{}",
+        rust
+    );
+    //compared pointers agree on *mut, p[0] is *p and p[-1] the same offset as *(p - 1)
+    assert!(
+        rust.contains("let mut end : *mut i32 = unsafe { p.offset(n as isize) } ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("(unsafe { *p }) + (unsafe { *p.offset(-1) })"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_decayed_array_offsets_into_raw_pointers() {
+    let rust = translate(
+        "int back(int *p) { return p[-1]; }\n\
+         int first(int *p) { return *p; }\n\
+         int dist(int *p) { int arr[5]; p = arr; p = p + 1; return &arr[4] - p; }\n\
+         int main(){ int arr[4]; first(arr + 2); return back(arr + 1); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("Reason: p: moved backwards (line 1)"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("unsafe { *p.offset(-1) }"), "{}", rust);
+    assert!(
+        rust.contains("unsafe { arr.as_ptr().add(4).offset_from(p) as i32 }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("first(&arr[2]);"), "{}", rust);
+    assert!(
+        rust.contains("back(unsafe { arr.as_ptr().add(1) })"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_allocated_array_becomes_vec() {
+    let rust = translate(
+        "int *make(int n){ int *a = (int *)malloc(n * sizeof(int)); a[0] = n; return a; }\n\
+         int main(){ int *a = make(3); a = (int *)realloc(a, 5 * sizeof(int)); char *text = (char *)calloc(4, sizeof(char)); free(text); free(a); return 0; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn make ( mut n : i32 , ) -> Vec<i32>"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut a : Vec<i32> = vec![0i32 ; n as usize] ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut a : Vec<i32> = make(3) ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("a.resize(5, 0i32);"), "{}", rust);
+    assert!(
+        rust.contains("let mut text : Vec<char> = vec!["),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("drop(text); drop(a);"), "{}", rust);
+}
+
+#[test]
+fn test_new_and_delete_become_box_and_drop() {
+    let rust = translate(
+        "class Point { public: int x; };\n\
+         int main(){ Point *p = new Point(1); int *v = new int(5); int *values = new int[4]; values[1] = *v + p->x; delete p; delete v; delete[] values; return 0; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("let mut p : Box<Point> = Box::new(Point::new(1)) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut v : Box<i32> = Box::new(5) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut values : Vec<i32> = vec![0i32 ; 4] ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("values[1] = *v + p.x;"), "{}", rust);
+    assert!(rust.contains("drop(p); drop(v); drop(values);"), "{}", rust);
+}
+
+#[test]
+fn test_use_after_free_is_reported() {
+    let rust = translate(
+        "int main(){ int *q = (int *)malloc(sizeof(int)); *q = 1; free(q); *q = 2; int *r = new int; delete r; r = NULL; return 0; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("Reason: q: used after it was freed on line 1 (line 1)"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut q : *mut i32 = Box::into_raw(Box::new(0i32)) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("drop(unsafe { Box::from_raw(q) }); unsafe { *q = 2 };"),
+        "{}",
+        rust
+    );
+    //assigning the freed pointer again is not a use
+    assert!(
+        rust.contains("//r: Option<Box<i32>>, owns the memory allocated with `new` (line 1)"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_freed_buffer_is_not_rebuilt_as_box() {
+    let rust = translate(
+        "int f(int n){ int *a = (int *)malloc(n * sizeof(int)); a[0] = 1; free(a); a[0] = 2; return 0; }\n",
+        false,
+    );
+    assert!(!rust.contains("Box::from_raw"), "{}", rust);
+    assert!(
+        rust.contains(
+            "//`a` does not hold memory of a Box, free it with libc::free (line 1, column 66) // free(a);"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_function_pointers_become_fn_types() {
+    let rust = translate(
+        "typedef int (*binop)(int, int);\n\
+         int add(int a, int b) { return a + b; }\n\
+         int main(){ int (*op)(int, int) = &add; int (*maybe)(int, int) = NULL; if (maybe == NULL) { maybe = op; } return (*maybe)(1, 2); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("type binop = fn(i32, i32) -> i32 ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut op : fn(i32, i32) -> i32 = add ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut maybe : Option<fn(i32, i32) -> i32> = None ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("if maybe.is_none()"), "{}", rust);
+    assert!(rust.contains("maybe = Some(op);"), "{}", rust);
+    assert!(rust.contains("maybe.unwrap()(1, 2)"), "{}", rust);
+}
+
+#[test]
+fn test_called_parameter_becomes_callback() {
+    let rust = translate(
+        "int apply(int (*f)(int), int x) { return f(x); }\n\
+         int twice(int x) { return x * 2; }\n\
+         int main(){ return apply(twice, 3); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn apply ( mut f : &dyn Fn(i32) -> i32 , mut x : i32 , ) -> i32"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("apply(&twice, 3)"), "{}", rust);
+}
+
+#[test]
+fn test_function_pointer_fields_and_tables() {
+    let rust = translate(
+        "struct ops { void (*log)(int); };\n\
+         void show(int x) { }\n\
+         void (*handlers[3])(int);\n\
+         void (*fixed[2])(int) = { show, show };\n\
+         int main(){ struct ops o; o.log = show; if (o.log) { o.log(1); } handlers[1] = show; handlers[1](2); fixed[0](3); return 0; }\n",
+        false,
+    );
+    assert!(rust.contains("log: Option<fn(i32)>,"), "{}", rust);
+    assert!(rust.contains("log : None ,"), "{}", rust);
+    assert!(
+        rust.contains("static mut handlers : [Option<fn(i32)> ; 3] = [None ; 3] ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("static mut fixed : [fn(i32) ; 2] = [show, show] ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("o.log = Some(show);"), "{}", rust);
+    assert!(rust.contains("if o.log.is_some()"), "{}", rust);
+    assert!(rust.contains("o.log.unwrap()(1);"), "{}", rust);
+    assert!(rust.contains("handlers[1] = Some(show);"), "{}", rust);
+    assert!(rust.contains("handlers[1].unwrap()(2);"), "{}", rust);
+    assert!(rust.contains("fixed[0](3);"), "{}", rust);
+}
+
+#[test]
+fn test_typedef_function_pointer_table() {
+    let rust = translate(
+        "typedef int (*binop)(int, int);\n\
+         int add(int a, int b) { return a + b; }\n\
+         binop ops[] = { add, NULL };\n\
+         int main(){ if (ops[1]) { return ops[1](1, 2); } return ops[0](1, 2); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("static mut ops : [Option<binop> ; 2] = [Some(add), None] ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("ops[1].is_some()"), "{}", rust);
+    assert!(rust.contains("ops[1].unwrap()(1, 2)"), "{}", rust);
+}
+
+#[test]
+fn test_typedef_function_pointers_are_translated_as_pointers() {
+    let rust = translate(
+        "typedef int (*binop)(int, int);\n\
+         int add(int a, int b) { return a + b; }\n\
+         struct Op { binop op; };\n\
+         int run(binop f) { return f(1, 2); }\n\
+         int main(){ binop maybe = NULL; struct Op o; o.op = add; if (maybe != NULL) { maybe(1, 2); } return o.op(1, 1) + run(add); }\n",
+        false,
+    );
+    assert!(rust.contains("op : Option<binop> ,"), "{}", rust);
+    assert!(
+        rust.contains("let mut maybe : Option<binop> = None ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("if maybe.is_some()"), "{}", rust);
+    assert!(rust.contains("maybe.unwrap()(1, 2);"), "{}", rust);
+    assert!(rust.contains("o.op = Some(add);"), "{}", rust);
+    assert!(rust.contains("o.op.unwrap()(1, 1)"), "{}", rust);
+    assert!(
+        rust.contains("fn run ( mut f : &dyn Fn(i32, i32) -> i32 , )"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_const_void_pointer_stays_const() {
+    let rust = translate(
+        "int g(const void *p) { const int *q = (const int *)p; return *q; }\n\
+         struct S { const void *data; void *raw; };\n",
+        false,
+    );
+    assert!(
+        rust.contains("mut p : *const std::ffi::c_void ,"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("data : *const std::ffi::c_void ,"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("raw : *mut std::ffi::c_void ,"), "{}", rust);
+}
+
+#[test]
+fn test_pointers_to_const_stay_const() {
+    let rust = translate(
+        "struct Person { const char *name; const struct Person *boss; struct Person *next; };\n\
+         int main() { struct Person p; p.boss = NULL; return 0; }\n",
+        false,
+    );
+    assert!(rust.contains("name : *const char ,"), "{}", rust);
+    assert!(rust.contains("boss : *const Person ,"), "{}", rust);
+    assert!(rust.contains("next : *mut Person ,"), "{}", rust);
+    assert!(rust.contains("p.boss = std::ptr::null();"), "{}", rust);
+}
+
+#[test]
+fn test_abstract_class_becomes_trait() {
+    let rust = translate(
+        "class Shape { public: virtual int area() = 0; virtual int sides() { return 0; } };\n\
+         class Square : public Shape { public: int side; int area() override { return side * side; } };\n\
+         int total(Shape *s) { return s->area(); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("trait Shape { fn area ( &self, ) -> i32 ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("fn sides ( &self, ) -> i32 {"), "{}", rust);
+    assert!(!rust.contains("struct Shape"), "{}", rust);
+    assert!(
+        rust.contains("struct Square { pub side : i32 , }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("impl Shape for Square {"), "{}", rust);
+    assert!(!rust.contains("Rust has no inheritance"), "{}", rust);
+    assert!(
+        rust.contains("fn total ( mut s : &dyn Shape , )"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_non_virtual_members_keep_the_base_class() {
+    let rust = translate(
+        "class Animal { public: int legs; int getLegs() { return legs; } virtual int speak() { return 0; } };\n\
+         class Dog : public Animal { public: int speak() { return 1; } };\n\
+         int count(Animal *a) { return a->getLegs(); }\n\
+         int loud(Animal *a) { return a->speak(); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("//a: &Animal, never NULL, only read through, reaches `getLegs`, which is not virtual (line 3)"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("fn count ( mut a : &Animal , )"), "{}", rust);
+    assert!(
+        rust.contains("fn loud ( mut a : &dyn AnimalTrait , )"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_derived_class_composes_its_base() {
+    let rust = translate(
+        "class Named { public: int id; virtual int tag(int extra) { return extra; } };\n\
+         class Labelled : public Named { public: int size; };\n\
+         int main(){ Named *n = new Labelled(); Labelled l; return n->tag(1) + l.id; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("trait NamedTrait { fn tag ( &self, extra : i32 , ) -> i32 ; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("impl NamedTrait for Named {"), "{}", rust);
+    assert_eq!(
+        rust.matches("Rust has no inheritance").count(),
+        1,
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("struct Labelled { base : Named , pub size : i32 , }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl std::ops::Deref for Labelled { type Target = Named;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl NamedTrait for Labelled { fn tag ( &self, mut extra : i32 , ) -> i32 { self.base.tag(extra) }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut n : Box<dyn NamedTrait> ="),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("Labelled { base : Named { id : 0i32 , } , size : 0i32 , }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_constructors_become_associated_functions() {
+    let rust = translate(
+        "class Counter { public: int count; int step;\n\
+         Counter() : count(0), step(1) { }\n\
+         Counter(int start, int step) : count(start), step(step) { count = count + 1; }\n\
+         };\n\
+         int main(){ Counter a; Counter b = Counter(); Counter c(5, 3); Counter *d = new Counter(1, 1); delete d; return c.count; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "pub fn new ( ) -> Self { let mut this = Counter { count : 0 , step : 1 , } ; this }"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("pub fn with_start_step ( mut start : i32 , mut step : i32 , ) -> Self { let mut this = Counter { count : start , step : step , } ; this.count = this.count + 1; this }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut a : Counter = Counter::new() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut b : Counter = Counter::new() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut c : Counter = Counter::with_start_step(5, 3) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("Box::new(Counter::with_start_step(1, 1))"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_constructor_overloads_follow_argument_types() {
+    let rust = translate(
+        "class Point { public: int x; double y;\n\
+         Point() { }\n\
+         Point(int a) : x(a) { }\n\
+         Point(double a) : y(a) { }\n\
+         };\n\
+         int main(){ double d = 1.5; Point e(2.5); Point f(2); Point g(d); return f.x; }\n",
+        false,
+    );
+    assert!(rust.contains("pub fn with_a ( mut a : i32 , )"), "{}", rust);
+    assert!(
+        rust.contains("pub fn with_a_f64 ( mut a : f64 , )"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut e : Point = Point::with_a_f64(2.5) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut f : Point = Point::with_a(2) ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut g : Point = Point::with_a_f64(d) ;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_destructor_becomes_drop() {
+    let rust = translate(
+        "class File { public: int fd; File(int f) : fd(f) { } ~File() { close(fd); fd = -1; } };\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "impl Drop for File { fn drop(&mut self) { close(self.fd); self.fd = -1; } }"
+        ),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_interface_destructor_has_no_drop() {
+    let rust = translate(
+        "class Shape { public: virtual ~Shape() {} virtual double area() const = 0; };\n\
+         class Log { public: virtual ~Log() { puts(\"bye\"); } virtual void put(int v) = 0; };\n",
+        false,
+    );
+    assert!(rust.contains("trait Shape"), "{}", rust);
+    assert!(!rust.contains("impl Drop"), "{}", rust);
+    assert!(
+        rust.contains("Reason: `Log` only becomes a trait, traits can not implement Drop"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_method_receivers() {
+    let rust = translate(
+        "class Counter { public: int count;\n\
+         int get() const { return count; }\n\
+         void bump() { count++; }\n\
+         void twice() { bump(); bump(); }\n\
+         static int zero() { return 0; }\n\
+         int reset() { count = zero(); return get(); }\n\
+         };\n",
+        false,
+    );
+    assert!(rust.contains("pub fn get ( &self, ) -> i32 {"), "{}", rust);
+    assert!(rust.contains("pub fn bump ( &mut self, )"), "{}", rust);
+    assert!(
+        rust.contains("pub fn twice ( &mut self, ) { self.bump(); self.bump(); }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("self.count }"), "{}", rust);
+    assert!(rust.contains("pub fn zero ( ) -> i32"), "{}", rust);
+    assert!(rust.contains("self.count = Self::zero()"), "{}", rust);
+}
+
+#[test]
+fn test_pointer_calling_mutable_method_is_mutable() {
+    let rust = translate(
+        "class Counter { public: int count; void bump() { count++; } int get() { return count; } };\n\
+         int run(Counter *c, Counter *d) { c->bump(); return d->get(); }\n",
+        false,
+    );
+    assert!(rust.contains("mut c : &mut Counter"), "{}", rust);
+    assert!(rust.contains("mut d : &Counter"), "{}", rust);
+}
+
+#[test]
+fn test_member_definitions_merged_into_impl() {
+    let rust = translate(
+        "class Account { public: Account(int start); int balance() const;\n\
+         void add(int amount); void add(int amount, int times);\n\
+         private: int total; int audit(int); };\n\
+         Account::Account(int start) : total(start) { }\n\
+         int Account::balance() const { return total; }\n\
+         void Account::add(int amount) { total += audit(amount); }\n\
+         void Account::add(int amount, int times) { total += amount * times; }\n\
+         int Account::audit(int amount) { return amount; }\n\
+         int main(){ Account a(1); a.add(2); a.add(3, 4); return a.balance(); }\n",
+        false,
+    );
+    assert_eq!(rust.matches("impl Account").count(), 1, "{}", rust);
+    assert!(
+        rust.contains("pub fn new ( mut start : i32 , ) -> Self"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "pub fn add ( &mut self, mut amount : i32 , ) { self.total += self.audit(amount); }"
+        ),
+        "{}",
+        rust
+    );
+    //the overloads can't share a name in the impl block
+    assert!(
+        rust.contains("pub fn add_i32_i32 ( &mut self, mut amount : i32 , mut times : i32 , )"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("a.add(2); a.add_i32_i32(3, 4);"), "{}", rust);
+    assert!(
+        rust.contains(" fn audit ( &self, mut amount : i32 , )"),
+        "{}",
+        rust
+    );
+    assert!(!rust.contains("pub fn audit"), "{}", rust);
+}
+
+#[test]
+fn test_overloaded_method_arguments_follow_the_chosen_overload() {
+    let rust = translate(
+        "class P { public: int x; };\nclass A {\npublic:\n  int n;\n  void put(int v) { n = v; }\n\
+         void put(const P &o) { n = o.x; }\n  void again(const P &o) { put(o); }\n};\n\
+         int main() { A a; P b; a.put(b); a.put(1); return 0; }\n",
+        false,
+    );
+    assert!(rust.contains("a.put_p(&b); a.put(1);"), "{}", rust);
+    assert!(rust.contains("self.put_p(o);"), "{}", rust);
+}
+
+#[test]
+fn test_member_definition_of_unknown_class() {
+    let rust = translate("int Missing::get(int a) { return a; }\n", false);
+    assert!(
+        rust.contains("impl Missing { pub fn get ( &self, mut a : i32 , ) -> i32"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_operators_become_trait_impls() {
+    let rust = translate(
+        "class V {\npublic:\n  int x;\n  V operator+(const V &o) const { V r; r.x = x + o.x; return r; }\n\
+         V &operator+=(const V &o) { x += o.x; return *this; }\n\
+         bool operator<(const V &o) const { return x < o.x; }\n\
+         int operator[](int i) const { return x; }\n};\n\
+         bool operator!=(const V &a, const V &b) { return a.x != b.x; }\n",
+        false,
+    );
+    assert!(rust.contains("#[derive(Clone, Copy)]"), "{}", rust);
+    assert!(
+        rust.contains("impl std::ops::Add for V { type Output = V;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("fn add_assign(&mut self, mut o : V) { self.x += o.x; }"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("impl PartialOrd for V {"), "{}", rust);
+    assert!(rust.contains("fn lt(&self, o : &V) -> bool {"), "{}", rust);
+    assert!(
+        rust.contains("impl std::ops::Index<usize> for V { type Output = i32;"),
+        "{}",
+        rust
+    );
+    assert!(!rust.contains("IndexMut"), "{}", rust);
+    assert!(
+        rust.contains("fn eq(&self, other: &V) -> bool { !self.ne(other) }"),
+        "{}",
+        rust
+    );
+    assert!(!rust.contains("fn operator"), "{}", rust);
+}
+
+#[test]
+fn test_index_operator_borrows_each_branch() {
+    let rust = translate(
+        "class P {\npublic:\n  int x; int y;\n  int &operator[](int i) { return i == 0 ? x : y; }\n\
+         int operator[](int i) const { return i == 0 ? x : y; }\n};\n",
+        false,
+    );
+    assert!(
+        rust.contains("**/ if i == 0 { &self.x } else { &self.y } }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("if i == 0 { &mut self.x } else { &mut self.y }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_console_output_uses_display() {
+    let rust = translate(
+        "#include <iostream>\nstruct P { int x; };\n\
+         std::ostream &operator<<(std::ostream &os, const P &p) { os << p.x; return os; }\n\
+         int main(){ P c; std::cout << c; std::cout << \"c = \" << c << std::endl; std::cerr << \"done\"; }\n",
+        false,
+    );
+    assert!(rust.contains("print!(\"{}\", c);"), "{}", rust);
+    assert!(rust.contains("print!(\"c = {}\\n\", c);"), "{}", rust);
+    assert!(rust.contains("eprint!(\"done\");"), "{}", rust);
+}
+
+#[test]
+fn test_ostream_operator_becomes_display() {
+    let rust = translate(
+        "#include <iostream>\nstruct P { int x; };\n\
+         std::ostream &operator<<(std::ostream &os, const P &p) { os << \"P(\" << p.x << ')'; return os; }\n",
+        false,
+    );
+    assert!(rust.contains("impl std::fmt::Display for P {"), "{}", rust);
+    assert!(
+        rust.contains("fn fmt(&self, os: &mut std::fmt::Formatter) -> std::fmt::Result {"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("write!(os, \"P({})\", p.x)?;"), "{}", rust);
+}
+
+#[test]
+fn test_function_templates_infer_bounds() {
+    let rust = translate(
+        "template <typename T> T larger(T a, T b) { if (a > b) { return a; } return b; }\n\
+         template <typename T> T square(T x) { return x * x; }\n\
+         template <typename T> void exchange(T &a, T &b) { T tmp = a; a = b; b = tmp; }\n\
+         template int square<int>(int);\n\
+         int main() { return larger<int>(square(2), 3); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("fn larger<T: PartialOrd> ( mut a : T , mut b : T , ) -> T"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("fn square<T: std::ops::Mul<Output = T> + Clone>"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("x.clone() * x.clone()"), "{}", rust);
+    assert!(rust.contains("fn exchange<T: Clone>"), "{}", rust);
+    assert!(
+        rust.contains("let mut tmp : T = (*a).clone() ; *a = (*b).clone(); *b = tmp;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("// template int square<int>(int); rust instantiates generics"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("larger::<i32>(square(2), 3)"), "{}", rust);
+}
+
+#[test]
+fn test_class_templates_become_generic_structs() {
+    let rust = translate(
+        "template <typename T, int N>\nclass Stack {\npublic:\n  T items[N];\n  int top;\n\
+         Stack() : top(0) { }\n  void push(T v) { items[top] = v; top++; }\n\
+         bool full() const { return top == N; }\n\
+         T sum() const { T total = T(); for (int i = 0; i < top; i++) { total += items[i]; } return total; }\n};\n\
+         int main() { Stack<int, 4> s; s.push(1); return s.sum(); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("struct Stack<T, const N: usize> { pub items : [T ; N] ,"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl<T, const N: usize> Stack<T, N>"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("items : std::array::from_fn(|_| Default::default())"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("-> Self where T: Default {"), "{}", rust);
+    assert!(rust.contains("self.top == (N as i32)"), "{}", rust);
+    assert!(
+        rust.contains("-> T where T: Default + std::ops::AddAssign + Clone {"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("T::default()"), "{}", rust);
+    assert!(
+        rust.contains("let mut s : Stack<i32, 4> = Stack::new() ;"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_namespaces_become_modules() {
+    let rust = translate(
+        "namespace geo {\nstruct Point { int x; int y; };\n\
+         namespace detail { int square(int v) { return v * v; } }\n\
+         int norm2(int x, int y) { return detail::square(x) + detail::square(y); }\n}\n\
+         namespace geo { int twice(int v) { return 2 * v; } }\n\
+         namespace { int hidden = 3; }\n\
+         using geo::twice;\nusing namespace geo::detail;\n\
+         int main() { geo::Point p; p.x = 1; return geo::norm2(p.x, 2) + twice(square(hidden)); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("mod geo { use super::*; pub struct Point { pub x : i32 , pub y : i32 , }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("pub mod detail { use super::*; pub fn square ("),
+        "{}",
+        rust
+    );
+    //the reopened namespace is generated with the first one
+    assert_eq!(rust.matches("mod geo").count(), 1, "{}", rust);
+    assert!(rust.contains("pub fn twice ("), "{}", rust);
+    assert!(rust.contains("static mut hidden : i32 = 3"), "{}", rust);
+    assert!(rust.contains("use crate::geo::twice;"), "{}", rust);
+    assert!(rust.contains("use crate::geo::detail::*;"), "{}", rust);
+    assert!(
+        rust.contains("let mut p : geo::Point = geo::Point {"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("geo::norm2(p.x, 2)"), "{}", rust);
+}
+
+#[test]
+fn test_std_names_are_mapped() {
+    let rust = translate(
+        "using namespace std;\n\
+         int main() { std::vector<int> v; map<int, long> m; std::string s;\n\
+         std::vector<std::string> names; std::map<int, std::string> labels; std::unordered_map<std::string, int> counts;\n\
+         return std::max(1, min(2, 3)); }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "// using namespace std; rust names of the standard library are used instead"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut v : Vec<i32> = Default::default() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut m : std::collections::BTreeMap<i32, i64> = Default::default() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut s : String = Default::default() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut names : Vec<String> = Default::default() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "let mut labels : std::collections::BTreeMap<i32, String> = Default::default() ;"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "let mut counts : std::collections::HashMap<String, i32> = Default::default() ;"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("std::cmp::max(1, std::cmp::min(2, 3))"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_enums_have_discriminants_and_conversions() {
+    let rust = translate(
+        "enum Color { RED, GREEN = 4, BLUE, ALSO = 4 };\n\
+         int main() { enum Color c = 5; int n = c + 1; c = GREEN; return n; }\n",
+        false,
+    );
+    assert!(
+        rust.contains(
+            "#[repr(i32)] #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)] enum Color { RED = 0 , GREEN = 4 , BLUE = 5 , }"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl Color { pub const ALSO : Color = Color::GREEN ; }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl TryFrom<i32> for Color { type Error = i32;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut c : Color = Color::try_from(5).unwrap() ;"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("let mut n : i32 = c as i32 + 1 ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("c = Color::GREEN;"), "{}", rust);
+}
+
+#[test]
+fn test_named_casts() {
+    let rust = translate(
+        "enum class Color { Red, Green };\n\
+         int main(){ Color c = Color::Red; int i = 1; c = static_cast<Color>(i);\n\
+         long l = reinterpret_cast<long>(i); int j = static_cast<int>(c); return j; }\n",
+        false,
+    );
+    assert!(
+        rust.contains("c = Color::try_from(i).unwrap();"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("let mut l : i64 = i as i64 ;"), "{}", rust);
+    assert!(rust.contains("let mut j : i32 = c as i32 ;"), "{}", rust);
+}
+
+#[test]
+fn test_switch_over_enum_is_exhaustive() {
+    let rust = translate(
+        "enum class Suit : unsigned char { Hearts = 1, Spades };\n\
+         int rank(Suit s) { switch (s) { case Suit::Hearts: return 1; case Suit::Spades: return 2; } return 0; }\n\
+         int value(int n) { switch (n) { case (int)Suit::Hearts: return 1; default: return 0; } }\n",
+        false,
+    );
+    assert!(rust.contains("#[repr(u8)]"), "{}", rust);
+    assert!(
+        rust.contains("match s { Suit::Hearts => { return 1; } Suit::Spades => { return 2; } }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("match Suit::try_from(n as u8) { Ok(Suit::Hearts) => { return 1; } _ => { return 0; } }"),
+        "{}",
+        rust
+    );
+}
+
+#[test]
+fn test_static_data_members() {
+    let rust = translate(
+        "class Counter { public: static int count; static const int max = 10; int id;\n\
+         static int get() { return count; } void bump() { count++; id = count + max; } };\n\
+         int Counter::count = 5;\n\
+         int main(){ Counter::count = 3; int m = Counter::max; return Counter::get(); }\n",
+        false,
+    );
+    assert!(
+        rust.contains("struct Counter { pub id : i32 , }"),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains(
+            "//Counter::count, shared by every Counter pub static mut Counter_count : i32 = 5 ;"
+        ),
+        "{}",
+        rust
+    );
+    assert!(
+        rust.contains("impl Counter { pub const max : i32 = 10 ;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("Counter_count }"), "{}", rust);
+    assert!(
+        rust.contains("Counter_count += 1; self.id = Counter_count + Self::max;"),
+        "{}",
+        rust
+    );
+    assert!(rust.contains("Counter_count = 3;"), "{}", rust);
+    assert!(
+        rust.contains("let mut m : i32 = Counter::max ;"),
+        "{}",
+        rust
+    );
+}
@@ -0,0 +1,75 @@
+use library::ast::*;
+use library::codegen::loops::{breaks, continues};
+
+// whether the statement or one nested in it jumps to the label
+pub fn jumps_to(stmt: &Stmt, label: &str) -> bool {
+    match stmt.kind {
+        StmtKind::Goto(ref target) => target == label,
+        _ => stmt
+            .children()
+            .into_iter()
+            .any(|child| jumps_to(child, label)),
+    }
+}
+
+// label of a label statement, cleanup of cleanup:
+pub fn label_of(stmt: &Stmt) -> Option<&str> {
+    match stmt.kind {
+        StmtKind::Label(ref name) => Some(name),
+        _ => None,
+    }
+}
+
+// every label of a function body and where it is, for diagnostics
+pub fn labels(stmts: &[Stmt], found: &mut Vec<(String, Span)>) {
+    for stmt in stmts {
+        if let Some(name) = label_of(stmt) {
+            found.push((name.to_string(), stmt.span));
+        }
+        for child in stmt.children() {
+            labels(std::slice::from_ref(child), found);
+        }
+    }
+}
+
+/**
+ * visible_labels:
+ * every goto of the statements with the labels of its own block and of
+ * the blocks around it, the labels a labelled block or loop can reach
+ */
+pub fn visible_labels(stmts: &[Stmt], outer: &[String], found: &mut Vec<(Span, Vec<String>)>) {
+    let mut visible = outer.to_vec();
+    visible.extend(stmts.iter().filter_map(label_of).map(str::to_string));
+    for stmt in stmts {
+        visit_gotos(stmt, &visible, found);
+    }
+}
+
+fn visit_gotos(stmt: &Stmt, visible: &[String], found: &mut Vec<(Span, Vec<String>)>) {
+    match stmt.kind {
+        StmtKind::Goto(_) => found.push((stmt.span, visible.to_vec())),
+        StmtKind::Block(ref stmts) => visible_labels(stmts, visible, found),
+        _ => {
+            for child in stmt.children() {
+                visit_gotos(child, visible, found);
+            }
+        }
+    }
+}
+
+/**
+ * leaves_goto_block:
+ * a break or continue of a loop inside the labelled block or loop a goto
+ * becomes has to name its loop, rust would take it for one of the block
+ */
+pub fn leaves_goto_block(body: &Stmt) -> bool {
+    has_label(body) && (breaks(body) || continues(body))
+}
+
+fn has_label(stmt: &Stmt) -> bool {
+    match stmt.kind {
+        StmtKind::Label(_) => true,
+        StmtKind::While { .. } | StmtKind::DoWhile { .. } | StmtKind::For { .. } => false,
+        _ => stmt.children().into_iter().any(has_label),
+    }
+}
@@ -0,0 +1,789 @@
+use library::ast::*;
+use library::codegen::rust_type::parse_type;
+use library::lexeme::definition::TokenType;
+
+/**
+ * record_named:
+ * class, struct or union of the translation unit with the given name
+ */
+pub fn record_named<'r>(records: &'r [Record], name: &str) -> Option<&'r Record> {
+    records.iter().find(|record| record.name == name)
+}
+
+// base classes defined in the translation unit, in declaration order
+pub fn bases<'r>(records: &'r [Record], record: &Record) -> Vec<&'r Record> {
+    record
+        .bases
+        .iter()
+        .filter_map(|base| record_named(records, base))
+        .collect()
+}
+
+// class with a pure virtual method, it can't be created on its own
+pub fn is_abstract(record: &Record) -> bool {
+    methods(record).iter().any(|method| method.is_pure)
+}
+
+/**
+ * is_interface:
+ * abstract class without data members or bases, only a trait
+ * is generated for it and no struct
+ */
+pub fn is_interface(record: &Record) -> bool {
+    is_abstract(record) && record.fields().is_empty() && record.bases.is_empty()
+}
+
+// base class kept as the `base` field of the derived struct
+pub fn composed<'r>(records: &'r [Record], record: &Record) -> Option<&'r Record> {
+    bases(records, record)
+        .into_iter()
+        .next()
+        .filter(|base| !is_interface(base))
+}
+
+pub fn methods(record: &Record) -> Vec<&Function> {
+    record
+        .members
+        .iter()
+        .filter_map(|member| match member.kind {
+            MemberKind::Method(ref method) => Some(method),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+ * declaring:
+ * class and declaration of the virtual method the name refers to,
+ * the topmost class of the hierarchy declaring it virtual
+ */
+pub fn declaring<'r>(
+    records: &'r [Record],
+    record: &'r Record,
+    name: &str,
+) -> Option<(&'r Record, &'r Function)> {
+    for base in bases(records, record) {
+        if let Some(found) = declaring(records, base, name) {
+            return Some(found);
+        }
+    }
+    methods(record)
+        .into_iter()
+        .find(|method| method.name == name && method.is_virtual)
+        .map(|method| (record, method))
+}
+
+// virtual methods first declared by the class, they make up its trait
+pub fn declared<'r>(records: &'r [Record], record: &'r Record) -> Vec<&'r Function> {
+    methods(record)
+        .into_iter()
+        .filter(|method| {
+            declaring(records, record, &method.name)
+                .is_some_and(|(declarer, _)| declarer.name == record.name)
+        })
+        .collect()
+}
+
+// the class itself names the trait of an interface, other traits are suffixed
+pub fn trait_name(record: &Record) -> String {
+    if is_interface(record) {
+        record.name.clone()
+    } else {
+        format!("{}Trait", record.name)
+    }
+}
+
+/**
+ * object_trait:
+ * trait for uses of the class through pointers, the trait of the
+ * nearest class in the hierarchy declaring virtual methods
+ */
+pub fn object_trait(records: &[Record], name: &str) -> Option<String> {
+    let record = record_named(records, name)?;
+    if !declared(records, record).is_empty() {
+        return Some(trait_name(record));
+    }
+    bases(records, record)
+        .into_iter()
+        .find_map(|base| object_trait(records, &base.name))
+}
+
+// ancestors declaring virtual methods, nearest first
+pub fn trait_ancestors<'r>(records: &'r [Record], record: &'r Record) -> Vec<&'r Record> {
+    let mut ancestors: Vec<&Record> = Vec::new();
+    for base in bases(records, record) {
+        if !declared(records, base).is_empty() {
+            ancestors.push(base);
+        }
+        for ancestor in trait_ancestors(records, base) {
+            if !ancestors.iter().any(|found| found.name == ancestor.name) {
+                ancestors.push(ancestor);
+            }
+        }
+    }
+    ancestors
+}
+
+// whether another class of the translation unit derives from the class
+pub fn is_base(records: &[Record], name: &str) -> bool {
+    records
+        .iter()
+        .any(|record| record.bases.iter().any(|base| base == name))
+}
+
+// constructors of the class in declaration order
+pub fn constructors(record: &Record) -> Vec<&Function> {
+    record
+        .members
+        .iter()
+        .filter_map(|member| match member.kind {
+            MemberKind::Constructor(ref constructor) => Some(constructor),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+ * constructor_names:
+ * rust names of the overloaded constructors, the default constructor
+ * or else the first one is `new` and the others are named after their
+ * parameters, A(int x, int y) => A::with_x_y(x, y). Overloads with the
+ * same parameter names add their types, A(double x) => A::with_x_f64
+ */
+pub fn constructor_names(record: &Record) -> Vec<(String, &Function)> {
+    let constructors = constructors(record);
+    let new = constructors
+        .iter()
+        .position(|constructor| constructor.params.is_empty())
+        .unwrap_or(0);
+    let mut names: Vec<(String, &Function)> = Vec::new();
+    for (index, constructor) in constructors.into_iter().enumerate() {
+        let name = if index == new {
+            "new".to_string()
+        } else {
+            let params: Vec<String> = constructor
+                .params
+                .iter()
+                .enumerate()
+                .map(|(position, param)| {
+                    param
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("arg{}", position))
+                })
+                .collect();
+            format!("with_{}", params.join("_"))
+        };
+        let name = overload_name(&names, name, constructor, index);
+        names.push((name, constructor));
+    }
+    names
+}
+
+/**
+ * overload_name:
+ * name of an overload that is not taken by the overloads named before
+ * it, the types of the parameters tell it apart, add(double) => add_f64
+ */
+pub fn overload_name(
+    named: &[(String, &Function)],
+    name: String,
+    overload: &Function,
+    index: usize,
+) -> String {
+    let taken = |name: &str| named.iter().any(|named| named.0 == name);
+    if !taken(&name) {
+        return name;
+    }
+    let types: Vec<String> = overload
+        .params
+        .iter()
+        .map(|param| type_suffix(&param.ty))
+        .collect();
+    let typed = if types.is_empty() {
+        name.clone()
+    } else {
+        format!("{}_{}", name, types.join("_"))
+    };
+    //T and const T& only differ in how the value is passed
+    if taken(&typed) {
+        format!("{}{}", typed, index)
+    } else {
+        typed
+    }
+}
+
+// part of an overload name standing for the type, unsigned int => u32
+fn type_suffix(ty: &CType) -> String {
+    match *ty {
+        CType::Primitive(token_type, modifier) => {
+            parse_type(token_type, modifier).unwrap_or_else(|| "arg".to_string())
+        }
+        CType::Named(ref name) | CType::Generic(ref name, _) => {
+            name.replace("::", "_").to_lowercase()
+        }
+        CType::Pointer(ref inner) => format!("{}_ptr", type_suffix(inner)),
+        CType::Reference(ref inner) | CType::Const(ref inner) => type_suffix(inner),
+        CType::Array(ref inner, _) => format!("{}_array", type_suffix(inner)),
+        CType::Function(..) => "fn".to_string(),
+    }
+}
+
+/**
+ * overload_for:
+ * overload called with arguments of the given types, None for types that
+ * are not known. Parameters of the same type win over ones of the same
+ * kind of number, the first overload taking as many arguments over the
+ * others and the first overload when none does
+ */
+pub fn overload_for(overloads: &[(String, &Function)], args: &[Option<CType>]) -> Option<String> {
+    let score = |function: &Function| -> usize {
+        function
+            .params
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| match *arg {
+                Some(ref arg) if same_type(arg.referred(), param.ty.referred()) => 2,
+                Some(ref arg) if number_kind(arg) == number_kind(param.ty.referred()) => 1,
+                _ => 0,
+            })
+            .sum()
+    };
+    let mut best: Option<(&String, usize)> = None;
+    for named in overloads
+        .iter()
+        .filter(|named| named.1.params.len() == args.len())
+    {
+        let score = score(named.1);
+        if best.is_none_or(|best| score > best.1) {
+            best = Some((&named.0, score));
+        }
+    }
+    best.map(|best| best.0)
+        .or_else(|| overloads.first().map(|named| &named.0))
+        .cloned()
+}
+
+// types that are the same apart from the const a pointer points to
+fn same_type(ty: &CType, other: &CType) -> bool {
+    match (ty, other) {
+        (CType::Pointer(_), CType::Pointer(_)) => same_type(ty.pointee(), other.pointee()),
+        _ => ty == other,
+    }
+}
+
+// Some(true) for floating types, Some(false) for integer types
+fn number_kind(ty: &CType) -> Option<bool> {
+    match *ty {
+        CType::Primitive(TokenType::Float, _) | CType::Primitive(TokenType::Double, _) => {
+            Some(true)
+        }
+        CType::Primitive(TokenType::Integer, _)
+        | CType::Primitive(TokenType::Short, _)
+        | CType::Primitive(TokenType::Long, _)
+        | CType::Primitive(TokenType::Character, _) => Some(false),
+        _ => None,
+    }
+}
+
+/**
+ * method_names:
+ * rust names of the methods of the class, the first of the overloads
+ * keeps the name and the others add their parameter types the way the
+ * constructors do, add(int, int) => add_i32_i32. Virtual methods and
+ * operators keep the name their trait gives them
+ */
+pub fn method_names(record: &Record) -> Vec<(String, &Function)> {
+    let mut names: Vec<(String, &Function)> = Vec::new();
+    for (index, method) in methods(record).into_iter().enumerate() {
+        let name = if method.is_virtual || method.operator().is_some() {
+            method.name.clone()
+        } else {
+            overload_name(&names, method.name.clone(), method, index)
+        };
+        names.push((name, method));
+    }
+    names
+}
+
+// rust name of the overloaded method called with arguments of the given types
+pub fn method_for(record: &Record, name: &str, args: &[Option<CType>]) -> Option<String> {
+    let overloads: Vec<(String, &Function)> = method_names(record)
+        .into_iter()
+        .filter(|named| named.1.name == name)
+        .collect();
+    if overloads.len() < 2 {
+        return None;
+    }
+    overload_for(&overloads, args)
+}
+
+// constructor called with arguments of the given types
+pub fn constructor_for(record: &Record, args: &[Option<CType>]) -> Option<String> {
+    overload_for(&constructor_names(record), args)
+}
+
+// destructor of the class, ~A()
+pub fn destructor(record: &Record) -> Option<&Function> {
+    record.members.iter().find_map(|member| match member.kind {
+        MemberKind::Destructor(ref destructor) => Some(destructor),
+        _ => None,
+    })
+}
+
+/**
+ * declaration_of:
+ * member of the class a function defined outside of it belongs to,
+ * overloads are told apart by their parameter types
+ */
+pub fn declaration_of<'r>(records: &'r [Record], definition: &Function) -> Option<&'r Function> {
+    let class = definition.class.as_ref()?;
+    record_named(records, class)?
+        .members
+        .iter()
+        .filter_map(|member| match member.kind {
+            MemberKind::Method(ref function)
+            | MemberKind::Constructor(ref function)
+            | MemberKind::Destructor(ref function) => Some(function),
+            _ => None,
+        })
+        .find(|declared| is_declared_as(declared, definition))
+}
+
+/**
+ * merge_definitions:
+ * moves member functions defined outside of their class, int A::f(int a),
+ * into the declarations of the class, which keep their access and the
+ * virtual and static specifiers only allowed inside the class
+ */
+pub fn merge_definitions(records: &mut [Record], definitions: &[&Function]) {
+    for definition in definitions {
+        let class = match definition.class {
+            Some(ref class) => class,
+            None => continue,
+        };
+        let record = match records.iter_mut().find(|record| record.name == *class) {
+            Some(record) => record,
+            None => continue,
+        };
+        let declared = record
+            .members
+            .iter_mut()
+            .filter_map(|member| match member.kind {
+                MemberKind::Method(ref mut function)
+                | MemberKind::Constructor(ref mut function)
+                | MemberKind::Destructor(ref mut function) => Some(function),
+                _ => None,
+            })
+            .find(|declared| is_declared_as(declared, definition));
+        if let Some(declared) = declared {
+            *declared = Function {
+                class: None,
+                is_inline: declared.is_inline || definition.is_inline,
+                is_virtual: declared.is_virtual,
+                is_static: declared.is_static,
+                is_const: declared.is_const || definition.is_const,
+                //a definition outside of a class template repeats the parameters of the class
+                template: declared.template.clone(),
+                ..(*definition).clone()
+            };
+        }
+    }
+}
+
+// same name and parameter types, the names of the parameters may differ
+fn is_declared_as(declared: &Function, definition: &Function) -> bool {
+    declared.name == definition.name
+        && declared.params.len() == definition.params.len()
+        && declared
+            .params
+            .iter()
+            .zip(&definition.params)
+            .all(|(declared, defined)| declared.ty == defined.ty)
+}
+
+/**
+ * member_names:
+ * data members reachable from methods of the class, inherited ones
+ * are reached through the base
+ */
+pub fn member_names(records: &[Record], record: &Record) -> Vec<String> {
+    let mut names: Vec<String> = record
+        .fields()
+        .iter()
+        .map(|field| field.name.clone())
+        .collect();
+    if let Some(base) = composed(records, record) {
+        names.extend(member_names(records, base));
+    }
+    names
+}
+
+//Receiver self parameter of a translated method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receiver {
+    //&self, the method only reads the object
+    Shared,
+    //&mut self, the method changes the object
+    Mutable,
+    //no self, c++ static member function
+    Static,
+}
+
+impl Receiver {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Receiver::Shared => "&self,",
+            Receiver::Mutable => "&mut self,",
+            Receiver::Static => "",
+        }
+    }
+}
+
+//Receivers receiver of every method, by class and method name
+pub type Receivers = Vec<(String, String, Receiver)>;
+
+/**
+ * infer_receivers:
+ * static methods take no receiver and const ones take &self, other
+ * methods take &mut self when they change a member, call a method
+ * taking &mut self or hand out the object, repeated until no method
+ * changes. Overrides take the receiver of the whole virtual method
+ */
+pub fn infer_receivers(records: &[Record]) -> Receivers {
+    let mut receivers: Receivers = Vec::new();
+    for record in records {
+        for method in methods(record) {
+            let receiver = if method.is_static {
+                Receiver::Static
+            } else {
+                Receiver::Shared
+            };
+            receivers.push((record.name.clone(), method.name.clone(), receiver));
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for record in records {
+            for method in methods(record) {
+                if method.is_static || method.is_const {
+                    continue;
+                }
+                let mutable = Mutation::new(records, record, method, &receivers).mutates(method)
+                    || overrides(records, record, method).iter().any(|other| {
+                        receiver_of(&receivers, records, &other.0.name, &other.1.name)
+                            == Some(Receiver::Mutable)
+                    });
+                let entry = receivers
+                    .iter_mut()
+                    .find(|entry| entry.0 == record.name && entry.1 == method.name)
+                    .expect("every method has a receiver");
+                if mutable && entry.2 != Receiver::Mutable {
+                    entry.2 = Receiver::Mutable;
+                    changed = true;
+                }
+            }
+        }
+    }
+    receivers
+}
+
+/**
+ * receiver_of:
+ * receiver of the method called through an object of the class,
+ * inherited methods are looked up in the base classes
+ */
+pub fn receiver_of(
+    receivers: &Receivers,
+    records: &[Record],
+    class: &str,
+    method: &str,
+) -> Option<Receiver> {
+    if let Some(entry) = receivers
+        .iter()
+        .find(|entry| entry.0 == class && entry.1 == method)
+    {
+        return Some(entry.2);
+    }
+    let record = record_named(records, class)?;
+    bases(records, record)
+        .into_iter()
+        .find_map(|base| receiver_of(receivers, records, &base.name, method))
+}
+
+// methods of the hierarchy sharing the virtual method, the declaration and overrides
+fn overrides<'r>(
+    records: &'r [Record],
+    record: &'r Record,
+    method: &Function,
+) -> Vec<(&'r Record, &'r Function)> {
+    let declarer = match declaring(records, record, &method.name) {
+        Some((declarer, _)) => declarer.name.clone(),
+        None => return Vec::new(),
+    };
+    let mut found = Vec::new();
+    for other in records {
+        let same = declaring(records, other, &method.name)
+            .is_some_and(|(declarer_of, _)| declarer_of.name == declarer);
+        if !same {
+            continue;
+        }
+        for function in methods(other) {
+            if function.name == method.name {
+                found.push((other, function));
+            }
+        }
+    }
+    found
+}
+
+//Mutation walks a method body for changes to the object
+struct Mutation<'r> {
+    records: &'r [Record],
+    record: &'r Record,
+    receivers: &'r Receivers,
+    //members not shadowed by parameters or locals
+    members: Vec<String>,
+}
+
+impl<'r> Mutation<'r> {
+    fn new(
+        records: &'r [Record],
+        record: &'r Record,
+        method: &Function,
+        receivers: &'r Receivers,
+    ) -> Mutation<'r> {
+        let mut shadowed: Vec<String> = method
+            .params
+            .iter()
+            .filter_map(|param| param.name.clone())
+            .collect();
+        for stmt in method.body.iter().flatten() {
+            stmt_locals(stmt, &mut shadowed);
+        }
+        let members = member_names(records, record)
+            .into_iter()
+            .filter(|member| !shadowed.contains(member))
+            .collect();
+        Mutation {
+            records,
+            record,
+            receivers,
+            members,
+        }
+    }
+
+    fn mutates(&self, method: &Function) -> bool {
+        method
+            .body
+            .iter()
+            .flatten()
+            .any(|stmt| self.stmt_mutates(stmt))
+    }
+
+    fn stmt_mutates(&self, stmt: &Stmt) -> bool {
+        stmt.exprs().iter().any(|expr| self.expr_mutates(expr))
+            || stmt.children().iter().any(|child| self.stmt_mutates(child))
+    }
+
+    fn expr_mutates(&self, expr: &Expr) -> bool {
+        let mutates = match expr.kind {
+            ExprKind::Assign(_, ref place, _)
+            | ExprKind::Postfix(_, ref place)
+            | ExprKind::Unary(UnaryOp::PreIncrement, ref place)
+            | ExprKind::Unary(UnaryOp::PreDecrement, ref place)
+            //a pointer to a member may be written through
+            | ExprKind::Unary(UnaryOp::AddressOf, ref place) => self.is_member_place(place),
+            ExprKind::Call(ref callee, ref args) => {
+                args.iter().any(|arg| arg.kind == ExprKind::This) || self.call_mutates(callee)
+            }
+            _ => false,
+        };
+        mutates || expr.children().iter().any(|child| self.expr_mutates(child))
+    }
+
+    // method called on the object or on one of its members
+    fn call_mutates(&self, callee: &Expr) -> bool {
+        let (object, method) = match callee.kind {
+            ExprKind::Ident(ref method) => (None, method),
+            //objects reached through pointers aren't part of this one
+            ExprKind::Member(ref object, _, true) if object.kind != ExprKind::This => return false,
+            ExprKind::Member(ref object, ref method, _) => (Some(&**object), method),
+            _ => return false,
+        };
+        let class = match object {
+            None => Some(self.record.name.clone()),
+            Some(&Expr {
+                kind: ExprKind::This,
+                ..
+            }) => Some(self.record.name.clone()),
+            Some(object) if self.is_member_place(object) => self.member_class(object),
+            Some(_) => return false,
+        };
+        match class {
+            Some(class) => {
+                match receiver_of(self.receivers, self.records, &class, method) {
+                    Some(receiver) => receiver == Receiver::Mutable,
+                    //functions outside the class or members of unknown classes
+                    None => object.is_some() && record_named(self.records, &class).is_none(),
+                }
+            }
+            //methods of a member whose class isn't translated may change it
+            None => true,
+        }
+    }
+
+    // class of the member the place names, when it is a class of the translation unit
+    fn member_class(&self, place: &Expr) -> Option<String> {
+        let name = match place.kind {
+            ExprKind::Ident(ref name) | ExprKind::Member(_, ref name, _) => name,
+            _ => return None,
+        };
+        match self.member_type(name)? {
+            CType::Named(class) => Some(class),
+            _ => None,
+        }
+    }
+
+    fn member_type(&self, name: &str) -> Option<CType> {
+        let mut record = Some(self.record);
+        while let Some(current) = record {
+            if let Some(field) = current.fields().iter().find(|field| field.name == name) {
+                return Some(field.ty.clone());
+            }
+            record = composed(self.records, current);
+        }
+        None
+    }
+
+    // place stored in the object itself, not reached through a pointer member
+    fn is_member_place(&self, place: &Expr) -> bool {
+        match place.kind {
+            ExprKind::Ident(ref name) => self.members.contains(name),
+            ExprKind::This => true,
+            ExprKind::Member(ref base, ref name, _) if base.kind == ExprKind::This => {
+                self.member_type(name).is_some()
+            }
+            ExprKind::Member(ref base, _, false) => self.is_member_place(base),
+            ExprKind::Unary(UnaryOp::Deref, ref base) => base.kind == ExprKind::This,
+            ExprKind::Index(ref base, _) => {
+                let through_pointer = match base.kind {
+                    ExprKind::Ident(ref name) | ExprKind::Member(_, ref name, _) => {
+                        self.member_type(name).is_some_and(|ty| ty.is_pointer())
+                    }
+                    _ => false,
+                };
+                !through_pointer && self.is_member_place(base)
+            }
+            _ => false,
+        }
+    }
+}
+
+// names declared by the statement and the statements nested inside
+pub fn stmt_locals(stmt: &Stmt, locals: &mut Vec<String>) {
+    if let StmtKind::Declaration(ref decl) = stmt.kind {
+        locals.extend(
+            decl.declarators
+                .iter()
+                .map(|declarator| declarator.name.clone()),
+        );
+    }
+    for child in stmt.children() {
+        stmt_locals(child, locals);
+    }
+}
+
+/**
+ * non_virtual_uses:
+ * pointers and references to a base class the statements reach a data
+ * member or non-virtual method through, with the first such use. Trait
+ * objects only have the virtual methods, these stay borrows of the class
+ * and virtual calls through them run the methods of the class itself
+ */
+pub fn non_virtual_uses(
+    records: &[Record],
+    variables: &[(String, CType)],
+    stmts: &[Stmt],
+) -> Vec<(String, String)> {
+    let mut variables = variables.to_vec();
+    let mut uses: Vec<(String, String)> = Vec::new();
+    let mut virtuals: Vec<(String, String)> = Vec::new();
+    for stmt in stmts {
+        stmt_members(records, stmt, &mut variables, &mut uses, &mut virtuals);
+    }
+    for found in uses.iter_mut() {
+        if let Some(call) = virtuals.iter().find(|call| call.0 == found.0) {
+            found.1 = format!("{}, {}", found.1, call.1);
+        }
+    }
+    uses
+}
+
+// members reached through pointers to base classes, non-virtual and virtual ones apart
+fn stmt_members(
+    records: &[Record],
+    stmt: &Stmt,
+    variables: &mut Vec<(String, CType)>,
+    uses: &mut Vec<(String, String)>,
+    virtuals: &mut Vec<(String, String)>,
+) {
+    if let StmtKind::Declaration(ref decl) = stmt.kind {
+        for declarator in &decl.declarators {
+            variables.push((declarator.name.clone(), declarator.ty.clone()));
+        }
+    }
+    for expr in stmt.exprs() {
+        expr_members(records, expr, variables, uses, virtuals);
+    }
+    for child in stmt.children() {
+        stmt_members(records, child, variables, uses, virtuals);
+    }
+}
+
+fn expr_members(
+    records: &[Record],
+    expr: &Expr,
+    variables: &[(String, CType)],
+    uses: &mut Vec<(String, String)>,
+    virtuals: &mut Vec<(String, String)>,
+) {
+    if let ExprKind::Member(ref base, ref member, arrow) = expr.kind {
+        let variable = match base.kind {
+            ExprKind::Ident(ref name) => variables.iter().rev().find(|var| var.0 == *name),
+            _ => None,
+        };
+        let class = variable.and_then(|var| match (arrow, &var.1) {
+            (true, &CType::Pointer(ref inner)) | (false, &CType::Reference(ref inner)) => {
+                match *inner.unqualified() {
+                    CType::Named(ref class) => record_named(records, class),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+        if let (Some(variable), Some(record)) = (variable, class) {
+            if is_base(records, &record.name) && object_trait(records, &record.name).is_some() {
+                let line = expr.span.line;
+                let (found, reason) = if declaring(records, record, member).is_some() {
+                    let reason = format!(
+                        "the virtual `{}` (line {}) runs the one of `{}`",
+                        member, line, record.name
+                    );
+                    (&mut *virtuals, reason)
+                } else {
+                    let reason =
+                        format!("reaches `{}`, which is not virtual (line {})", member, line);
+                    (&mut *uses, reason)
+                };
+                if !found.iter().any(|found| found.0 == variable.0) {
+                    found.push((variable.0.clone(), reason));
+                }
+            }
+        }
+    }
+    for child in expr.children() {
+        expr_members(records, child, variables, uses, virtuals);
+    }
+}
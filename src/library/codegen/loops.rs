@@ -0,0 +1,409 @@
+use library::ast::*;
+use library::lexeme::definition::TokenType;
+
+//Counting for loop, for (int i = a; i < b; i += k) walks a range of integers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counting<'a> {
+    pub counter: &'a Declarator,
+    pub start: &'a Expr,
+    pub end: &'a Expr,
+    //i <= b and i >= b reach the end
+    pub inclusive: bool,
+    //amount added to the counter, negative when it counts down
+    pub step: i64,
+}
+
+/**
+ * counting_loop:
+ * for loop whose counter is declared by the loop, compared with a bound
+ * and stepped by a constant, with neither the counter nor the bound
+ * changed by the body. Rust evaluates the bound of a range once
+ */
+pub fn counting_loop<'a>(
+    init: &'a Option<Box<Stmt>>,
+    cond: &'a Option<Expr>,
+    step: &'a Option<Expr>,
+    body: &Stmt,
+    locals: &[(String, CType)],
+) -> Option<Counting<'a>> {
+    let counter = match init.as_ref()?.kind {
+        StmtKind::Declaration(ref decl) if decl.declarators.len() == 1 => &decl.declarators[0],
+        _ => return None,
+    };
+    let is_integer = match counter.ty {
+        CType::Primitive(token_type, _) => matches!(
+            token_type,
+            TokenType::Integer | TokenType::Short | TokenType::Long
+        ),
+        _ => false,
+    };
+    let start = counter.init.as_ref()?;
+    let (op, end) = match cond.as_ref()?.kind {
+        ExprKind::Binary(op, ref lhs, ref end) if is_ident(lhs, &counter.name) => (op, &**end),
+        _ => return None,
+    };
+    let step = step_of(step.as_ref()?, &counter.name)?;
+    let inclusive = match op {
+        BinaryOp::Lt if step > 0 => false,
+        BinaryOp::Le if step > 0 => true,
+        BinaryOp::Gt if step < 0 => false,
+        BinaryOp::Ge if step < 0 => true,
+        _ => return None,
+    };
+    //an unsigned counter is never below 0, u >= 0 never ends the loop
+    let never_ends = op == BinaryOp::Ge
+        && matches!(counter.ty, CType::Primitive(_, Modifier::Unsigned))
+        && matches!(end.kind, ExprKind::Literal(Literal::Integer(ref number)) if number.value() == Some(0));
+    if !is_integer || never_ends || writes(body, &counter.name) || !is_invariant(end, body, locals)
+    {
+        return None;
+    }
+    Some(Counting {
+        counter,
+        start,
+        end,
+        inclusive,
+        step,
+    })
+}
+
+/**
+ * indexed_array:
+ * array a counting loop from 0 to its length reads or writes a[i] of,
+ * when the body uses the array in no other way it can be iterated
+ */
+pub fn indexed_array(counting: &Counting, body: &Stmt) -> Option<String> {
+    let zero = matches!(
+        counting.start.kind,
+        ExprKind::Literal(Literal::Integer(ref number)) if number.value() == Some(0)
+    );
+    if !zero || counting.step != 1 || counting.inclusive {
+        return None;
+    }
+    let mut arrays: Vec<String> = Vec::new();
+    indexed_by(body, &counting.counter.name, &mut arrays);
+    let array = arrays.first()?;
+    let only_array = arrays.iter().all(|other| other == array);
+    if only_array && array_uses(body, array, &counting.counter.name) {
+        Some(array.clone())
+    } else {
+        None
+    }
+}
+
+// body of an array loop with a[i] replaced by *item
+pub fn replace_element(stmt: &Stmt, array: &str, counter: &str, item: &str) -> Stmt {
+    let mut stmt = stmt.clone();
+    for expr in stmt_exprs_mut(&mut stmt) {
+        replace_in_expr(expr, array, counter, item);
+    }
+    stmt
+}
+
+// whether the body writes the variable, its elements or fields
+pub fn writes(stmt: &Stmt, name: &str) -> bool {
+    stmt.exprs().into_iter().any(|expr| expr_writes(expr, name))
+        || stmt.children().into_iter().any(|child| writes(child, name))
+}
+
+// whether the variable is used in the statement at all
+pub fn uses(stmt: &Stmt, name: &str) -> bool {
+    let declares = match stmt.kind {
+        StmtKind::Declaration(ref decl) => decl
+            .declarators
+            .iter()
+            .any(|declarator| declarator.name == name),
+        _ => false,
+    };
+    declares
+        || stmt.exprs().into_iter().any(|expr| mentions(expr, name))
+        || stmt.children().into_iter().any(|child| uses(child, name))
+}
+
+// break of the loop, not of a loop or switch nested in it
+pub fn breaks(stmt: &Stmt) -> bool {
+    match stmt.kind {
+        StmtKind::Break => true,
+        StmtKind::While { .. }
+        | StmtKind::DoWhile { .. }
+        | StmtKind::For { .. }
+        | StmtKind::Switch { .. } => false,
+        _ => stmt.children().into_iter().any(breaks),
+    }
+}
+
+// continue of the loop, not of a loop nested in it
+pub fn continues(stmt: &Stmt) -> bool {
+    match stmt.kind {
+        StmtKind::Continue => true,
+        StmtKind::While { .. } | StmtKind::DoWhile { .. } | StmtKind::For { .. } => false,
+        _ => stmt.children().into_iter().any(continues),
+    }
+}
+
+// i++, ++i, i += 2, i--, --i and i -= 2
+fn step_of(step: &Expr, counter: &str) -> Option<i64> {
+    match step.kind {
+        ExprKind::Postfix(PostfixOp::Increment, ref operand)
+        | ExprKind::Unary(UnaryOp::PreIncrement, ref operand)
+            if is_ident(operand, counter) =>
+        {
+            Some(1)
+        }
+        ExprKind::Postfix(PostfixOp::Decrement, ref operand)
+        | ExprKind::Unary(UnaryOp::PreDecrement, ref operand)
+            if is_ident(operand, counter) =>
+        {
+            Some(-1)
+        }
+        ExprKind::Assign(Some(op), ref place, ref amount) if is_ident(place, counter) => {
+            let amount = match amount.kind {
+                ExprKind::Literal(Literal::Integer(ref number)) => number.value()? as i64,
+                _ => return None,
+            };
+            match op {
+                BinaryOp::Add if amount > 0 => Some(amount),
+                BinaryOp::Sub if amount > 0 => Some(-amount),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/**
+ * is_invariant:
+ * bound the body can't change, locals it doesn't write, and fields,
+ * globals and elements only when the body calls nothing and writes no
+ * place but locals
+ */
+fn is_invariant(end: &Expr, body: &Stmt, locals: &[(String, CType)]) -> bool {
+    let mut names: Vec<&str> = Vec::new();
+    if !is_pure(end, &mut names) || names.iter().any(|name| writes(body, name)) {
+        return false;
+    }
+    let only_locals = !has_place(end)
+        && names
+            .iter()
+            .all(|name| locals.iter().any(|local| local.0 == *name));
+    only_locals || !calls_or_writes_places(body)
+}
+
+// expression without calls or side effects, the names it reads are collected
+fn is_pure<'a>(expr: &'a Expr, names: &mut Vec<&'a str>) -> bool {
+    match expr.kind {
+        ExprKind::Call(..)
+        | ExprKind::Assign(..)
+        | ExprKind::Postfix(..)
+        | ExprKind::Unary(UnaryOp::PreIncrement, _)
+        | ExprKind::Unary(UnaryOp::PreDecrement, _) => false,
+        ExprKind::Ident(ref name) => {
+            names.push(name);
+            true
+        }
+        _ => expr
+            .children()
+            .into_iter()
+            .all(|child| is_pure(child, names)),
+    }
+}
+
+// field, element or pointee read by the expression
+fn has_place(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Member(..) | ExprKind::Index(..) | ExprKind::Unary(UnaryOp::Deref, _) => true,
+        _ => expr.children().into_iter().any(has_place),
+    }
+}
+
+fn calls_or_writes_places(stmt: &Stmt) -> bool {
+    stmt.exprs().into_iter().any(expr_calls_or_writes_places)
+        || stmt.children().into_iter().any(calls_or_writes_places)
+}
+
+fn expr_calls_or_writes_places(expr: &Expr) -> bool {
+    let place = match expr.kind {
+        ExprKind::Call(..) => return true,
+        ExprKind::Assign(_, ref place, _)
+        | ExprKind::Postfix(_, ref place)
+        | ExprKind::Unary(UnaryOp::PreIncrement, ref place)
+        | ExprKind::Unary(UnaryOp::PreDecrement, ref place) => Some(place),
+        _ => None,
+    };
+    let writes_place = place.is_some_and(|place| !matches!(place.kind, ExprKind::Ident(_)));
+    writes_place || expr.children().into_iter().any(expr_calls_or_writes_places)
+}
+
+fn expr_writes(expr: &Expr, name: &str) -> bool {
+    let written = match expr.kind {
+        ExprKind::Assign(_, ref place, _)
+        | ExprKind::Postfix(_, ref place)
+        | ExprKind::Unary(UnaryOp::PreIncrement, ref place)
+        | ExprKind::Unary(UnaryOp::PreDecrement, ref place)
+        | ExprKind::Unary(UnaryOp::AddressOf, ref place) => place_root(place) == Some(name),
+        _ => false,
+    };
+    written
+        || expr
+            .children()
+            .into_iter()
+            .any(|child| expr_writes(child, name))
+}
+
+// variable a place is part of, a of a[i] and a.x
+fn place_root(place: &Expr) -> Option<&str> {
+    match place.kind {
+        ExprKind::Ident(ref name) => Some(name),
+        ExprKind::Index(ref base, _) | ExprKind::Member(ref base, _, false) => place_root(base),
+        _ => None,
+    }
+}
+
+fn mentions(expr: &Expr, name: &str) -> bool {
+    is_ident(expr, name)
+        || expr
+            .children()
+            .into_iter()
+            .any(|child| mentions(child, name))
+}
+
+// arrays the body indexes by the counter, a of a[i]
+fn indexed_by(stmt: &Stmt, counter: &str, arrays: &mut Vec<String>) {
+    fn walk(expr: &Expr, counter: &str, arrays: &mut Vec<String>) {
+        if let ExprKind::Index(ref base, ref index) = expr.kind {
+            if let (ExprKind::Ident(ref name), true) = (&base.kind, is_ident(index, counter)) {
+                arrays.push(name.clone());
+            }
+        }
+        for child in expr.children() {
+            walk(child, counter, arrays);
+        }
+    }
+    for expr in stmt.exprs() {
+        walk(expr, counter, arrays);
+    }
+    for child in stmt.children() {
+        indexed_by(child, counter, arrays);
+    }
+}
+
+// whether every use of the array in the body is a[i]
+fn array_uses(stmt: &Stmt, array: &str, counter: &str) -> bool {
+    fn walk(expr: &Expr, array: &str, counter: &str) -> bool {
+        match expr.kind {
+            ExprKind::Index(ref base, ref index) if is_ident(base, array) => {
+                is_ident(index, counter)
+            }
+            ExprKind::Ident(ref name) => name != array,
+            _ => expr
+                .children()
+                .into_iter()
+                .all(|child| walk(child, array, counter)),
+        }
+    }
+    let declares = match stmt.kind {
+        StmtKind::Declaration(ref decl) => decl
+            .declarators
+            .iter()
+            .any(|declarator| declarator.name == array),
+        _ => false,
+    };
+    !declares
+        && stmt
+            .exprs()
+            .into_iter()
+            .all(|expr| walk(expr, array, counter))
+        && stmt
+            .children()
+            .into_iter()
+            .all(|child| array_uses(child, array, counter))
+}
+
+fn replace_in_expr(expr: &mut Expr, array: &str, counter: &str, item: &str) {
+    let element = match expr.kind {
+        ExprKind::Index(ref base, ref index) => is_ident(base, array) && is_ident(index, counter),
+        _ => false,
+    };
+    if element {
+        let span = expr.span;
+        let item = Expr {
+            kind: ExprKind::Ident(item.to_string()),
+            span,
+        };
+        expr.kind = ExprKind::Unary(UnaryOp::Deref, Box::new(item));
+        return;
+    }
+    for child in expr.children_mut() {
+        replace_in_expr(child, array, counter, item);
+    }
+}
+
+// expressions of a statement and the statements nested in it
+fn stmt_exprs_mut(stmt: &mut Stmt) -> Vec<&mut Expr> {
+    let mut exprs: Vec<&mut Expr> = Vec::new();
+    match stmt.kind {
+        StmtKind::Declaration(ref mut decl) => {
+            for declarator in &mut decl.declarators {
+                exprs.extend(declarator.init.iter_mut());
+            }
+        }
+        StmtKind::Expr(ref mut expr) | StmtKind::Return(Some(ref mut expr)) => exprs.push(expr),
+        StmtKind::Block(ref mut stmts) => {
+            for stmt in stmts {
+                exprs.extend(stmt_exprs_mut(stmt));
+            }
+        }
+        StmtKind::If {
+            ref mut cond,
+            ref mut then,
+            ref mut otherwise,
+        } => {
+            exprs.push(cond);
+            exprs.extend(stmt_exprs_mut(then));
+            if let Some(ref mut otherwise) = *otherwise {
+                exprs.extend(stmt_exprs_mut(otherwise));
+            }
+        }
+        StmtKind::While {
+            ref mut cond,
+            ref mut body,
+        }
+        | StmtKind::DoWhile {
+            ref mut cond,
+            ref mut body,
+        } => {
+            exprs.push(cond);
+            exprs.extend(stmt_exprs_mut(body));
+        }
+        StmtKind::For {
+            ref mut init,
+            ref mut cond,
+            ref mut step,
+            ref mut body,
+        } => {
+            if let Some(ref mut init) = *init {
+                exprs.extend(stmt_exprs_mut(init));
+            }
+            exprs.extend(cond.iter_mut());
+            exprs.extend(step.iter_mut());
+            exprs.extend(stmt_exprs_mut(body));
+        }
+        StmtKind::Switch {
+            ref mut cond,
+            ref mut cases,
+        } => {
+            exprs.push(cond);
+            for case in cases {
+                for stmt in &mut case.body {
+                    exprs.extend(stmt_exprs_mut(stmt));
+                }
+            }
+        }
+        _ => {}
+    }
+    exprs
+}
+
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(expr.kind, ExprKind::Ident(ref ident) if ident == name)
+}
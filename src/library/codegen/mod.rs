@@ -0,0 +1,15 @@
+pub mod enumeration;
+pub mod generator;
+pub mod goto;
+pub mod hierarchy;
+pub mod loops;
+pub mod namespace;
+pub mod operator;
+pub mod pointer;
+pub mod rust_type;
+pub mod standard;
+pub mod switch;
+pub mod template;
+
+#[cfg(test)]
+mod generator_test;
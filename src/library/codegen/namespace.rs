@@ -0,0 +1,64 @@
+use library::ast::*;
+
+/**
+ * merge_namespaces:
+ * a c++ namespace can be reopened while a rust module can be defined only
+ * once, the items of every namespace of the same name go to the first one
+ */
+pub fn merge_namespaces(items: &[Item]) -> Vec<Item> {
+    let mut merged: Vec<Item> = Vec::new();
+    for item in items {
+        if let ItemKind::Namespace(Namespace {
+            name: Some(ref name),
+            ref items,
+        }) = item.kind
+        {
+            let opened = merged.iter_mut().find_map(|opened| match opened.kind {
+                ItemKind::Namespace(ref mut namespace) if namespace.name.as_ref() == Some(name) => {
+                    Some(namespace)
+                }
+                _ => None,
+            });
+            if let Some(namespace) = opened {
+                namespace.items.extend(items.iter().cloned());
+                continue;
+            }
+        }
+        merged.push(item.clone());
+    }
+    merged
+}
+
+// paths of every named namespace, a::b for b nested in a
+pub fn module_paths(items: &[Item], parent: &[String], paths: &mut Vec<Vec<String>>) {
+    for item in items {
+        if let ItemKind::Namespace(ref namespace) = item.kind {
+            let mut path = parent.to_vec();
+            path.extend(namespace.name.iter().cloned());
+            if !paths.contains(&path) && namespace.name.is_some() {
+                paths.push(path.clone());
+            }
+            module_paths(&namespace.items, &path, paths);
+        }
+    }
+}
+
+/**
+ * resolve_path:
+ * absolute rust path of a qualified name used inside the namespace scope,
+ * c++ looks its first namespace up from the innermost enclosing namespace
+ * outwards
+ */
+pub fn resolve_path(paths: &[Vec<String>], scope: &[String], path: &str) -> String {
+    let first = path.split("::").next().unwrap_or_default();
+    for depth in (0..scope.len() + 1).rev() {
+        let mut candidate = scope[..depth].to_vec();
+        candidate.push(first.to_string());
+        if paths.contains(&candidate) {
+            let mut segments = scope[..depth].to_vec();
+            segments.push(path.to_string());
+            return format!("crate::{}", segments.join("::"));
+        }
+    }
+    format!("crate::{}", path)
+}
@@ -0,0 +1,143 @@
+use library::ast::*;
+
+//OperatorKind how the trait of an overloaded operator takes its operands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorKind {
+    //a + b, self and the right operand by value, with an Output type
+    Binary,
+    //a += b, &mut self
+    Assign,
+    //-a and !a, self by value
+    Unary,
+    //a == b and a != b, &self and a borrowed right operand
+    Equality,
+    //a < b, PartialOrd methods next to the partial_cmp they are built into
+    Ordering,
+    //a[i], Index and IndexMut
+    Index,
+    //out << a to an ostream, formats the value for Display
+    Display,
+}
+
+//OperatorTrait std trait and method an overloaded operator implements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorTrait {
+    pub kind: OperatorKind,
+    pub name: &'static str,
+    pub method: &'static str,
+}
+
+/**
+ * operator_trait:
+ * trait implemented for the operator symbol applied to the number of
+ * operands, the object counts as one for member operators
+ */
+pub fn operator_trait(op: &str, operands: usize) -> Option<OperatorTrait> {
+    let (kind, name, method) = match (op, operands) {
+        ("-", 1) => (OperatorKind::Unary, "std::ops::Neg", "neg"),
+        ("!", 1) | ("~", 1) => (OperatorKind::Unary, "std::ops::Not", "not"),
+        ("+", 2) => (OperatorKind::Binary, "std::ops::Add", "add"),
+        ("-", 2) => (OperatorKind::Binary, "std::ops::Sub", "sub"),
+        ("*", 2) => (OperatorKind::Binary, "std::ops::Mul", "mul"),
+        ("/", 2) => (OperatorKind::Binary, "std::ops::Div", "div"),
+        ("%", 2) => (OperatorKind::Binary, "std::ops::Rem", "rem"),
+        ("&", 2) => (OperatorKind::Binary, "std::ops::BitAnd", "bitand"),
+        ("|", 2) => (OperatorKind::Binary, "std::ops::BitOr", "bitor"),
+        ("^", 2) => (OperatorKind::Binary, "std::ops::BitXor", "bitxor"),
+        ("<<", 2) => (OperatorKind::Binary, "std::ops::Shl", "shl"),
+        (">>", 2) => (OperatorKind::Binary, "std::ops::Shr", "shr"),
+        ("+=", 2) => (OperatorKind::Assign, "std::ops::AddAssign", "add_assign"),
+        ("-=", 2) => (OperatorKind::Assign, "std::ops::SubAssign", "sub_assign"),
+        ("*=", 2) => (OperatorKind::Assign, "std::ops::MulAssign", "mul_assign"),
+        ("/=", 2) => (OperatorKind::Assign, "std::ops::DivAssign", "div_assign"),
+        ("%=", 2) => (OperatorKind::Assign, "std::ops::RemAssign", "rem_assign"),
+        ("&=", 2) => (
+            OperatorKind::Assign,
+            "std::ops::BitAndAssign",
+            "bitand_assign",
+        ),
+        ("|=", 2) => (
+            OperatorKind::Assign,
+            "std::ops::BitOrAssign",
+            "bitor_assign",
+        ),
+        ("^=", 2) => (
+            OperatorKind::Assign,
+            "std::ops::BitXorAssign",
+            "bitxor_assign",
+        ),
+        ("<<=", 2) => (OperatorKind::Assign, "std::ops::ShlAssign", "shl_assign"),
+        (">>=", 2) => (OperatorKind::Assign, "std::ops::ShrAssign", "shr_assign"),
+        ("==", 2) => (OperatorKind::Equality, "PartialEq", "eq"),
+        ("!=", 2) => (OperatorKind::Equality, "PartialEq", "ne"),
+        ("<", 2) => (OperatorKind::Ordering, "PartialOrd", "lt"),
+        (">", 2) => (OperatorKind::Ordering, "PartialOrd", "gt"),
+        ("<=", 2) => (OperatorKind::Ordering, "PartialOrd", "le"),
+        (">=", 2) => (OperatorKind::Ordering, "PartialOrd", "ge"),
+        ("[]", 2) => (OperatorKind::Index, "std::ops::Index", "index"),
+        _ => return None,
+    };
+    Some(OperatorTrait { kind, name, method })
+}
+
+/**
+ * trait_of:
+ * trait of an overloaded operator function, out << value with an
+ * ostream on the left formats the value
+ */
+pub fn trait_of(function: &Function, is_member: bool) -> Option<OperatorTrait> {
+    let op = function.operator()?;
+    let operands = function.params.len() + usize::from(is_member);
+    if op == "<<"
+        && !is_member
+        && function
+            .params
+            .first()
+            .is_some_and(|param| is_ostream(&param.ty))
+    {
+        return Some(OperatorTrait {
+            kind: OperatorKind::Display,
+            name: "std::fmt::Display",
+            method: "fmt",
+        });
+    }
+    operator_trait(op, operands)
+}
+
+// std::ostream & and the other output streams
+pub fn is_ostream(ty: &CType) -> bool {
+    match *ty.referred() {
+        CType::Named(ref name) => name.ends_with("ostream"),
+        _ => false,
+    }
+}
+
+/**
+ * partial_cmp_body:
+ * partial_cmp built from the one ordering operator the class defines,
+ * the values compare equal when == says so
+ */
+pub fn partial_cmp_body(method: &str) -> String {
+    let (first, last) = match method {
+        "lt" | "le" => ("Less", "Greater"),
+        _ => ("Greater", "Less"),
+    };
+    let ordering = |name: &str| format!("Some(std::cmp::Ordering::{})", name);
+    if method == "lt" || method == "gt" {
+        format!(
+            "if self.{}(other) {{ {} }} else if self == other {{ {} }} else {{ {} }}",
+            method,
+            ordering(first),
+            ordering("Equal"),
+            ordering(last)
+        )
+    } else {
+        format!(
+            "if self == other {{ {} }} else if self.{}(other) {{ {} }} else {{ {} }}",
+            ordering("Equal"),
+            method,
+            ordering(first),
+            ordering(last)
+        )
+    }
+}
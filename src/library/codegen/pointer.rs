@@ -0,0 +1,1491 @@
+#![allow(dead_code)]
+
+use library::ast::*;
+use library::codegen::hierarchy::{Receiver, Receivers};
+use library::lexeme::definition::TokenType;
+
+//PointerKind rust type a c/c++ pointer is translated to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerKind {
+    //&T or &mut T
+    Borrowed,
+    //Option<&T> or Option<&mut T>, the pointer may be NULL
+    Nullable,
+    //Box<T>, the pointer owns a heap allocation
+    Owned,
+    //Option<Box<T>>
+    NullableOwned,
+    //&str, a char pointer to text that is only read
+    Str,
+    //&[T] or &mut [T], a pointer into an array that is indexed or advanced
+    Slice,
+    //Vec<T>, the pointer owns memory for several values from malloc or new[]
+    Vec,
+    //fn(A) -> R, a pointer to a function
+    Function,
+    //Option<fn(A) -> R>
+    NullableFunction,
+    //&dyn Fn(A) -> R, a function pointer parameter that is only called
+    Callback,
+    //*const T or *mut T, dereferenced inside unsafe blocks
+    Raw,
+}
+
+impl PointerKind {
+    pub fn is_nullable(self) -> bool {
+        matches!(
+            self,
+            PointerKind::Nullable | PointerKind::NullableOwned | PointerKind::NullableFunction
+        )
+    }
+
+    pub fn is_function(self) -> bool {
+        matches!(
+            self,
+            PointerKind::Function | PointerKind::NullableFunction | PointerKind::Callback
+        )
+    }
+
+    pub fn is_owned(self) -> bool {
+        matches!(self, PointerKind::Owned | PointerKind::NullableOwned)
+    }
+}
+
+//PointerInfo translation chosen for one pointer and the reason for the choice
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerInfo {
+    pub name: String,
+    //declared c/c++ pointer type
+    pub ty: CType,
+    pub kind: PointerKind,
+    //written through, &mut T instead of &T
+    pub mutable: bool,
+    //raw pointer only ever set to values Box::into_raw took apart, or NULL
+    pub boxed: bool,
+    pub reason: String,
+}
+
+//PointerTable pointer translations of one function
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerTable {
+    pub function: String,
+    //(function, parameter, reason) for NULL or pointers that may be NULL passed to functions
+    pub nulls: Vec<(String, usize, String)>,
+    //one entry per parameter, None for parameters that are not pointers
+    pub params: Vec<Option<PointerInfo>>,
+    //set when the function returns a pointer
+    pub ret: Option<PointerInfo>,
+    //pointer parameters and local variables
+    pub variables: Vec<PointerInfo>,
+}
+
+impl PointerTable {
+    pub fn find(&self, name: &str) -> Option<&PointerInfo> {
+        self.variables.iter().find(|info| info.name == name)
+    }
+}
+
+// what a function body does with one pointer variable, first occurrence of each use
+struct Usage {
+    name: String,
+    ty: CType,
+    is_param: bool,
+    null: Option<String>,
+    written: Option<String>,
+    heap: Option<String>,
+    //allocated for several values
+    buffer: Option<String>,
+    //indexing or advancing, which a slice can express
+    slice: Option<String>,
+    //advancing, which an owned buffer can't express
+    moved: Option<String>,
+    //used after free or delete
+    freed: Option<String>,
+    //stored or returned rather than only called, for function pointers
+    stored: Option<String>,
+    //use that only a raw pointer can express
+    raw: Option<String>,
+    string: Option<String>,
+    //assigned single allocations, which a raw pointer holds as Box::into_raw
+    boxed: bool,
+    //assigned anything else than such an allocation or NULL
+    unboxed: bool,
+}
+
+impl Usage {
+    fn new(name: &str, ty: &CType, is_param: bool) -> Usage {
+        Usage {
+            name: name.to_string(),
+            ty: ty.clone(),
+            is_param,
+            null: None,
+            written: None,
+            heap: None,
+            buffer: None,
+            slice: None,
+            moved: None,
+            freed: None,
+            stored: None,
+            raw: None,
+            string: None,
+            boxed: false,
+            unboxed: is_param,
+        }
+    }
+}
+
+//Fact what a value stored in a pointer tells about it
+enum Fact {
+    Null(String),
+    Heap(String),
+    Buffer(String),
+    Slice(String),
+    Raw(String),
+    Text(String),
+}
+
+// records the first reason only
+fn note(fact: &mut Option<String>, reason: String) {
+    if fact.is_none() {
+        *fact = Some(reason);
+    }
+}
+
+struct Analysis<'a> {
+    //tables of the other functions, from the previous round
+    known: &'a [PointerTable],
+    //receivers of the methods, calls to &mut self methods write through the object
+    receivers: &'a Receivers,
+    usages: Vec<Usage>,
+    //local arrays, pointers to them are pointer arithmetic
+    arrays: Vec<String>,
+    //(q, p) for q = p, q borrows what p points to
+    aliases: Vec<(String, String)>,
+    //(p, q) for pointers compared with each other, both need the same mutability
+    compared: Vec<(String, String)>,
+    returns: Vec<Expr>,
+    //(function, parameter, p, line) for pointers passed to functions, None for NULL
+    arguments: Vec<(String, usize, Option<String>, u32)>,
+    //(p, line) for pointers freed and not assigned since
+    freed: Vec<(String, u32)>,
+}
+
+/**
+ * resolve_function_aliases:
+ * the unit with typedef names of function pointers replaced by the
+ * pointer type in declarations, parameters, fields and return types,
+ * typedef int (*binop)(int, int); binop f; is analyzed as int (*f)(int, int).
+ * Along with the typedef names that were replaced
+ */
+pub fn resolve_function_aliases(unit: &TranslationUnit) -> (TranslationUnit, Vec<String>) {
+    let aliases: Vec<(String, CType)> = unit
+        .all_items()
+        .into_iter()
+        .filter_map(|item| match item.kind {
+            ItemKind::Typedef(ref typedef) => Some((typedef.name.clone(), typedef.ty.clone())),
+            _ => None,
+        })
+        .collect();
+    let mut unit = unit.clone();
+    let mut resolved: Vec<String> = Vec::new();
+    if aliases.iter().any(|alias| alias.1.is_function_pointer()) {
+        resolve_items(&mut unit.items, &aliases, &mut resolved);
+    }
+    (unit, resolved)
+}
+
+fn resolve_items(items: &mut [Item], aliases: &[(String, CType)], resolved: &mut Vec<String>) {
+    for item in items {
+        match item.kind {
+            ItemKind::Function(ref mut function) => resolve_function(function, aliases, resolved),
+            ItemKind::Declaration(ref mut decl) => resolve_declaration(decl, aliases, resolved),
+            ItemKind::Record(ref mut record) => {
+                for member in &mut record.members {
+                    match member.kind {
+                        MemberKind::Field(ref mut decl) => {
+                            resolve_declaration(decl, aliases, resolved)
+                        }
+                        MemberKind::Method(ref mut function)
+                        | MemberKind::Constructor(ref mut function)
+                        | MemberKind::Destructor(ref mut function)
+                        | MemberKind::Friend(ref mut function) => {
+                            resolve_function(function, aliases, resolved)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ItemKind::Namespace(ref mut namespace) => {
+                resolve_items(&mut namespace.items, aliases, resolved)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn resolve_function(
+    function: &mut Function,
+    aliases: &[(String, CType)],
+    resolved: &mut Vec<String>,
+) {
+    resolve_type(&mut function.return_type, aliases, resolved);
+    for param in &mut function.params {
+        resolve_type(&mut param.ty, aliases, resolved);
+    }
+    for stmt in function.body.iter_mut().flatten() {
+        resolve_stmt(stmt, aliases, resolved);
+    }
+}
+
+fn resolve_declaration(
+    decl: &mut Declaration,
+    aliases: &[(String, CType)],
+    resolved: &mut Vec<String>,
+) {
+    for declarator in &mut decl.declarators {
+        resolve_type(&mut declarator.ty, aliases, resolved);
+    }
+}
+
+fn resolve_stmt(stmt: &mut Stmt, aliases: &[(String, CType)], resolved: &mut Vec<String>) {
+    match stmt.kind {
+        StmtKind::Declaration(ref mut decl) => resolve_declaration(decl, aliases, resolved),
+        StmtKind::Block(ref mut stmts) => {
+            for stmt in stmts {
+                resolve_stmt(stmt, aliases, resolved);
+            }
+        }
+        StmtKind::If {
+            ref mut then,
+            ref mut otherwise,
+            ..
+        } => {
+            resolve_stmt(then, aliases, resolved);
+            if let Some(ref mut otherwise) = *otherwise {
+                resolve_stmt(otherwise, aliases, resolved);
+            }
+        }
+        StmtKind::While { ref mut body, .. } | StmtKind::DoWhile { ref mut body, .. } => {
+            resolve_stmt(body, aliases, resolved)
+        }
+        StmtKind::For {
+            ref mut init,
+            ref mut body,
+            ..
+        } => {
+            if let Some(ref mut init) = *init {
+                resolve_stmt(init, aliases, resolved);
+            }
+            resolve_stmt(body, aliases, resolved);
+        }
+        StmtKind::Switch { ref mut cases, .. } => {
+            for stmt in cases.iter_mut().flat_map(|case| case.body.iter_mut()) {
+                resolve_stmt(stmt, aliases, resolved);
+            }
+        }
+        _ => {}
+    }
+}
+
+// binop => int (*)(int, int), through typedefs of typedefs and in arrays of them
+fn resolve_type(ty: &mut CType, aliases: &[(String, CType)], resolved: &mut Vec<String>) {
+    match *ty {
+        CType::Named(ref name) => {
+            let mut pointer = match aliases.iter().find(|alias| alias.0 == *name) {
+                Some(alias) if alias.1 != *ty => alias.1.clone(),
+                _ => return,
+            };
+            resolve_type(&mut pointer, aliases, resolved);
+            if pointer.is_function_pointer() {
+                if !resolved.contains(name) {
+                    resolved.push(name.clone());
+                }
+                *ty = pointer;
+            }
+        }
+        CType::Array(ref mut inner, _) | CType::Pointer(ref mut inner) => {
+            resolve_type(inner, aliases, resolved)
+        }
+        _ => {}
+    }
+}
+
+/**
+ * analyze_pointers:
+ * pointer translations of every function defined in the unit. A pointer
+ * passed to a function that writes through it has to be mutable as well,
+ * so the functions are analyzed until none of the tables change
+ */
+pub fn analyze_pointers(unit: &TranslationUnit, receivers: &Receivers) -> Vec<PointerTable> {
+    let functions: Vec<&Function> = unit
+        .all_items()
+        .into_iter()
+        .filter_map(|item| match item.kind {
+            //member functions defined outside of their class are analyzed with it
+            ItemKind::Function(ref function)
+                if function.body.is_some() && function.class.is_none() =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+        .collect();
+    let mut tables: Vec<PointerTable> = Vec::new();
+    //every round can only make more pointers mutable
+    for _ in 0..functions.len() + 1 {
+        let next: Vec<PointerTable> = functions
+            .iter()
+            .map(|function| analyze_function(function, &tables, receivers))
+            .collect();
+        if next == tables {
+            break;
+        }
+        tables = next;
+    }
+    tables
+}
+
+/**
+ * analyze_function:
+ * collects the uses of every pointer parameter and local variable of
+ * the function and picks the safest rust type that allows all of them
+ */
+pub fn analyze_function(
+    function: &Function,
+    known: &[PointerTable],
+    receivers: &Receivers,
+) -> PointerTable {
+    let mut analysis = Analysis {
+        known,
+        receivers,
+        usages: Vec::new(),
+        arrays: Vec::new(),
+        aliases: Vec::new(),
+        compared: Vec::new(),
+        returns: Vec::new(),
+        arguments: Vec::new(),
+        freed: Vec::new(),
+    };
+    for param in &function.params {
+        if let Some(ref name) = param.name {
+            analysis.declare(name, &param.ty, true);
+        }
+    }
+    if let Some(ref body) = function.body {
+        for stmt in body {
+            analysis.visit_stmt(stmt);
+        }
+    }
+    analysis.propagate_writes();
+    //callers passing NULL make the parameter nullable
+    for (index, param) in function.params.iter().enumerate() {
+        let null = known
+            .iter()
+            .flat_map(|table| &table.nulls)
+            .find(|null| null.0 == function.name && null.1 == index);
+        let usage = match param.name {
+            Some(ref name) if param.ty.is_pointer() => {
+                analysis.usages.iter_mut().find(|usage| usage.name == *name)
+            }
+            _ => None,
+        };
+        if let (Some(null), Some(usage)) = (null, usage) {
+            note(&mut usage.null, null.2.clone());
+        }
+    }
+
+    let variables: Vec<PointerInfo> = analysis.usages.iter().map(classify).collect();
+    let nulls = analysis
+        .arguments
+        .iter()
+        .filter_map(|(callee, index, pointer, line)| {
+            let reason = match *pointer {
+                None => format!("`{}` passes NULL (line {})", function.name, line),
+                Some(ref name)
+                    if variables
+                        .iter()
+                        .any(|info| info.name == *name && info.kind.is_nullable()) =>
+                {
+                    format!(
+                        "`{}` passes `{}`, which may be NULL (line {})",
+                        function.name, name, line
+                    )
+                }
+                Some(_) => return None,
+            };
+            Some((callee.clone(), *index, reason))
+        })
+        .collect();
+    let params = function
+        .params
+        .iter()
+        .map(|param| match (&param.name, &param.ty) {
+            (Some(name), CType::Pointer(_)) | (Some(name), CType::Reference(_)) => {
+                variables.iter().find(|info| info.name == *name).cloned()
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let ret = if function.return_type.is_pointer() {
+        Some(analysis.classify_return(&function.return_type, &params, &variables))
+    } else {
+        None
+    };
+    PointerTable {
+        function: function.name.clone(),
+        nulls,
+        params,
+        ret,
+        variables,
+    }
+}
+
+/**
+ * global_pointer:
+ * translation of a pointer declared outside of functions, rust statics
+ * can only borrow other statics
+ */
+pub fn global_pointer(global: &Declarator) -> PointerInfo {
+    let (kind, reason) = match global.init {
+        _ if !global.ty.is_function_pointer() => (
+            PointerKind::Raw,
+            "global pointers can't hold borrowed references",
+        ),
+        Some(ref init) if !is_null(init) => (PointerKind::Function, "initialized with a function"),
+        _ => (PointerKind::NullableFunction, "starts out as NULL"),
+    };
+    PointerInfo {
+        name: global.name.clone(),
+        ty: global.ty.clone(),
+        kind,
+        mutable: true,
+        boxed: false,
+        reason: reason.to_string(),
+    }
+}
+
+/**
+ * field_pointer:
+ * translation of a pointer member of a struct or class, borrowed
+ * references in fields would need lifetime parameters on the struct
+ */
+pub fn field_pointer(field: &Declarator) -> PointerInfo {
+    let (kind, reason) = if field.ty.is_function_pointer() {
+        (
+            PointerKind::NullableFunction,
+            "function pointer fields start out as NULL",
+        )
+    } else {
+        (
+            PointerKind::Raw,
+            "struct fields can't hold borrowed references without lifetimes",
+        )
+    };
+    PointerInfo {
+        name: field.name.clone(),
+        ty: field.ty.clone(),
+        kind,
+        mutable: true,
+        boxed: false,
+        reason: reason.to_string(),
+    }
+}
+
+impl<'a> Analysis<'a> {
+    fn declare(&mut self, name: &str, ty: &CType, is_param: bool) {
+        match *ty {
+            CType::Pointer(_) | CType::Reference(_) if self.find(name).is_none() => {
+                self.usages.push(Usage::new(name, ty, is_param))
+            }
+            CType::Array(..) => self.arrays.push(name.to_string()),
+            _ => {}
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&Usage> {
+        self.usages.iter().find(|usage| usage.name == name)
+    }
+
+    // usage of the pointer variable the expression names, references can only be written
+    fn usage(&mut self, expr: &Expr) -> Option<&mut Usage> {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self
+                .usages
+                .iter_mut()
+                .find(|usage| usage.name == *name && !usage.ty.is_reference()),
+            _ => None,
+        }
+    }
+
+    // usage of the pointer or reference variable the expression names
+    fn reference(&mut self, expr: &Expr) -> Option<&mut Usage> {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self.usages.iter_mut().find(|usage| usage.name == *name),
+            _ => None,
+        }
+    }
+
+    fn is_reference(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Ident(ref name) => {
+                self.find(name).is_some_and(|usage| usage.ty.is_reference())
+            }
+            _ => false,
+        }
+    }
+
+    fn is_array(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self.arrays.contains(name),
+            _ => false,
+        }
+    }
+
+    fn is_pointer(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self
+                .find(name)
+                .is_some_and(|usage| !usage.ty.is_reference()),
+            _ => false,
+        }
+    }
+
+    fn table(&self, name: &str) -> Option<&'a PointerTable> {
+        self.known.iter().find(|table| table.function == name)
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt.kind {
+            StmtKind::Declaration(ref decl) => {
+                for declarator in &decl.declarators {
+                    self.declare(&declarator.name, &declarator.ty, false);
+                    if let Some(ref init) = declarator.init {
+                        if declarator.ty.is_pointer() {
+                            self.assigned(&declarator.name, init);
+                        }
+                        self.stored(init, "stored in `{}`", &declarator.name);
+                        self.visit_expr(init);
+                    }
+                }
+            }
+            StmtKind::Expr(ref expr) => self.visit_expr(expr),
+            StmtKind::Block(ref stmts) => {
+                for stmt in stmts {
+                    self.visit_stmt(stmt);
+                }
+            }
+            StmtKind::If {
+                ref cond,
+                ref then,
+                ref otherwise,
+            } => {
+                self.visit_cond(cond);
+                self.visit_stmt(then);
+                if let Some(ref otherwise) = *otherwise {
+                    self.visit_stmt(otherwise);
+                }
+            }
+            StmtKind::While { ref cond, ref body } | StmtKind::DoWhile { ref body, ref cond } => {
+                self.visit_cond(cond);
+                self.visit_stmt(body);
+            }
+            StmtKind::For {
+                ref init,
+                ref cond,
+                ref step,
+                ref body,
+            } => {
+                if let Some(ref init) = *init {
+                    self.visit_stmt(init);
+                }
+                if let Some(ref cond) = *cond {
+                    self.visit_cond(cond);
+                }
+                if let Some(ref step) = *step {
+                    self.visit_expr(step);
+                }
+                self.visit_stmt(body);
+            }
+            StmtKind::Switch {
+                ref cond,
+                ref cases,
+            } => {
+                self.visit_expr(cond);
+                for case in cases {
+                    for stmt in &case.body {
+                        self.visit_stmt(stmt);
+                    }
+                }
+            }
+            StmtKind::Return(Some(ref value)) => {
+                self.stored(value, "returned", "");
+                self.returns.push(value.clone());
+                self.visit_expr(value);
+            }
+            _ => {}
+        }
+    }
+
+    // if (p) tests the pointer for NULL
+    fn visit_cond(&mut self, cond: &Expr) {
+        let line = cond.span.line;
+        if let Some(usage) = self.usage(cond) {
+            note(&mut usage.null, format!("checked for NULL (line {})", line));
+        }
+        self.visit_expr(cond);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        let line = expr.span.line;
+        match expr.kind {
+            ExprKind::Assign(_, ref lhs, ref rhs) if self.is_function_pointer(rhs) => {
+                self.stored(rhs, "stored in `{}`", &lhs.idents().join("."));
+                if let ExprKind::Ident(ref name) = lhs.kind {
+                    self.assigned(name, rhs);
+                }
+            }
+            ExprKind::InitList(ref values) => {
+                for value in values {
+                    self.stored(value, "stored in an initializer", "");
+                }
+            }
+            ExprKind::Assign(op, ref lhs, ref rhs) => match (op, &lhs.kind) {
+                (None, ExprKind::Ident(name)) if self.is_pointer(lhs) => self.assigned(name, rhs),
+                (Some(BinaryOp::Add), _) => self.advanced(lhs, line),
+                (Some(BinaryOp::Sub), _) => self.moved_back(lhs, line),
+                (Some(_), _) if self.is_pointer(lhs) => self.arithmetic(lhs, line),
+                _ => self.written(lhs, format!("written through (line {})", line)),
+            },
+            ExprKind::Unary(UnaryOp::PreIncrement, ref operand)
+            | ExprKind::Unary(UnaryOp::PreDecrement, ref operand)
+            | ExprKind::Postfix(_, ref operand) => {
+                if self.is_pointer(operand) {
+                    match expr.kind {
+                        ExprKind::Unary(UnaryOp::PreIncrement, _)
+                        | ExprKind::Postfix(PostfixOp::Increment, _) => {
+                            self.advanced(operand, line)
+                        }
+                        _ => self.moved_back(operand, line),
+                    }
+                } else {
+                    self.written(operand, format!("written through (line {})", line));
+                }
+            }
+            ExprKind::Unary(UnaryOp::AddressOf, ref operand) => {
+                if let Some(usage) = self.usage(operand) {
+                    note(
+                        &mut usage.raw,
+                        format!("its own address is taken (line {})", line),
+                    );
+                }
+            }
+            ExprKind::Unary(UnaryOp::Not, ref operand) => {
+                if let Some(usage) = self.usage(operand) {
+                    note(&mut usage.null, format!("checked for NULL (line {})", line));
+                }
+            }
+            ExprKind::Binary(op, ref lhs, ref rhs) => match op {
+                _ if op.is_comparison() && self.is_pointer(lhs) && self.is_pointer(rhs) => {
+                    if let (ExprKind::Ident(ref lhs), ExprKind::Ident(ref rhs)) =
+                        (&lhs.kind, &rhs.kind)
+                    {
+                        self.compared.push((lhs.clone(), rhs.clone()));
+                    }
+                    if op != BinaryOp::Eq && op != BinaryOp::Ne {
+                        for operand in &[lhs, rhs] {
+                            if let Some(usage) = self.usage(operand) {
+                                note(
+                                    &mut usage.raw,
+                                    format!("compared by address (line {})", line),
+                                );
+                            }
+                        }
+                    }
+                }
+                BinaryOp::Eq | BinaryOp::Ne => {
+                    for &(pointer, other) in &[(lhs, rhs), (rhs, lhs)] {
+                        if is_null(other) {
+                            if let Some(usage) = self.usage(pointer) {
+                                note(
+                                    &mut usage.null,
+                                    format!("compared with NULL (line {})", line),
+                                );
+                            }
+                        }
+                    }
+                }
+                BinaryOp::And | BinaryOp::Or => {
+                    for operand in &[lhs, rhs] {
+                        if let Some(usage) = self.usage(operand) {
+                            note(&mut usage.null, format!("checked for NULL (line {})", line));
+                        }
+                    }
+                }
+                //p + i is the slice of p starting at i
+                BinaryOp::Add => {
+                    for operand in &[lhs, rhs] {
+                        if let Some(usage) = self.usage(operand) {
+                            note(&mut usage.slice, format!("offset (line {})", line));
+                        }
+                    }
+                }
+                BinaryOp::Sub if self.is_pointer(rhs) => {
+                    for operand in &[lhs, rhs] {
+                        if let Some(usage) = self.usage(operand) {
+                            note(
+                                &mut usage.raw,
+                                format!("subtracted from another pointer (line {})", line),
+                            );
+                        }
+                    }
+                }
+                BinaryOp::Sub => self.moved_back(lhs, line),
+                _ if op.is_comparison() => {
+                    for operand in &[lhs, rhs] {
+                        if let Some(usage) = self.usage(operand) {
+                            note(
+                                &mut usage.raw,
+                                format!("compared by address (line {})", line),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            },
+            ExprKind::Conditional(ref cond, _, _) => {
+                if let Some(usage) = self.usage(cond) {
+                    note(&mut usage.null, format!("checked for NULL (line {})", line));
+                }
+            }
+            //p[-1] reaches before the start, slices can't
+            ExprKind::Index(ref base, ref index) if is_negative(index) => {
+                self.moved_back(base, line)
+            }
+            ExprKind::Index(ref base, _) => {
+                if let Some(usage) = self.usage(base) {
+                    note(&mut usage.slice, format!("indexed (line {})", line));
+                }
+            }
+            ExprKind::Cast(_, ref operand) => {
+                if let Some(usage) = self.usage(operand) {
+                    note(
+                        &mut usage.raw,
+                        format!("cast to another type (line {})", line),
+                    );
+                }
+            }
+            ExprKind::Call(ref callee, ref args) => {
+                self.called(callee, line);
+                self.passed(callee, args)
+            }
+            ExprKind::Ident(ref name) => {
+                if let Some(&(_, freed)) = self.freed.iter().find(|freed| freed.0 == *name) {
+                    if let Some(usage) = self.usage(expr) {
+                        note(
+                            &mut usage.freed,
+                            format!("used after it was freed on line {} (line {})", freed, line),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+        for child in expr.children() {
+            self.visit_expr(child);
+        }
+        //after the operand of free(p) itself was visited
+        if let Some(name) = released(expr) {
+            self.freed.push((name.to_string(), line));
+        }
+    }
+
+    fn arithmetic(&mut self, operand: &Expr, line: u32) {
+        if let Some(usage) = self.usage(operand) {
+            note(
+                &mut usage.raw,
+                format!("pointer arithmetic (line {})", line),
+            );
+        }
+    }
+
+    fn is_function_pointer(&self, expr: &Expr) -> bool {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self
+                .find(name)
+                .is_some_and(|usage| usage.ty.is_function_pointer()),
+            _ => false,
+        }
+    }
+
+    // function pointer used as a value, `{}` in how is replaced by target
+    fn stored(&mut self, value: &Expr, how: &str, target: &str) {
+        if !self.is_function_pointer(value) {
+            return;
+        }
+        let line = value.span.line;
+        if let Some(usage) = self.usage(value) {
+            let how = how.replace("{}", target);
+            note(&mut usage.stored, format!("{} (line {})", how, line));
+        }
+    }
+
+    // p++ and p += n, the slice starting further on
+    fn advanced(&mut self, operand: &Expr, line: u32) {
+        if let Some(usage) = self.usage(operand) {
+            note(&mut usage.slice, format!("advanced (line {})", line));
+            note(&mut usage.moved, format!("advanced (line {})", line));
+        }
+    }
+
+    // slices can't grow at the front, p-- and p - n need a raw pointer
+    fn moved_back(&mut self, operand: &Expr, line: u32) {
+        if let Some(usage) = self.usage(operand) {
+            note(&mut usage.raw, format!("moved backwards (line {})", line));
+        }
+    }
+
+    // p->f() and r.f() write through p or r when f takes &mut self
+    fn called(&mut self, callee: &Expr, line: u32) {
+        let (base, method) = match callee.kind {
+            ExprKind::Member(ref base, ref method, arrow) if arrow || self.is_reference(base) => {
+                (base, method)
+            }
+            _ => return,
+        };
+        let class = match self.reference(base) {
+            Some(usage) => match *usage.ty.pointee().referred() {
+                CType::Named(ref class) => class.clone(),
+                _ => return,
+            },
+            None => return,
+        };
+        let mutating = self
+            .receivers
+            .iter()
+            .any(|entry| entry.0 == class && entry.1 == *method && entry.2 == Receiver::Mutable);
+        if mutating {
+            self.written(callee, format!("calls `{}` (line {})", method, line));
+        }
+    }
+
+    // *p = .., p->x = .. and p->a[i] = .. write through p
+    fn written(&mut self, place: &Expr, reason: String) {
+        let pointer = match place.kind {
+            ExprKind::Unary(UnaryOp::Deref, ref operand) if self.is_pointer(stepped(operand)) => {
+                stepped(operand)
+            }
+            ExprKind::Member(ref base, _, true) if self.is_pointer(base) => base,
+            ExprKind::Index(ref base, _) if self.is_pointer(base) => base,
+            ExprKind::Ident(_) if self.is_reference(place) => place,
+            ExprKind::Unary(UnaryOp::Deref, ref inner)
+            | ExprKind::Member(ref inner, _, _)
+            | ExprKind::Index(ref inner, _) => return self.written(inner, reason),
+            _ => return,
+        };
+        if let Some(usage) = self.reference(pointer) {
+            note(&mut usage.written, reason);
+        }
+    }
+
+    // pointers passed to functions that write through the parameter
+    fn passed(&mut self, callee: &Expr, args: &[Expr]) {
+        let name = match callee.kind {
+            ExprKind::Ident(ref name) => name,
+            _ => return,
+        };
+        for (index, arg) in args.iter().enumerate() {
+            let line = arg.span.line;
+            match arg.kind {
+                _ if is_null(arg) => self.arguments.push((name.clone(), index, None, line)),
+                ExprKind::Ident(ref pointer) if self.find(pointer).is_some() => self
+                    .arguments
+                    .push((name.clone(), index, Some(pointer.clone()), line)),
+                _ => {}
+            }
+        }
+        let table = self.table(name);
+        //a callback parameter takes any function
+        for (index, arg) in args.iter().enumerate() {
+            let param = table.and_then(|table| table.params.get(index));
+            if !matches!(param, Some(Some(param)) if param.kind == PointerKind::Callback) {
+                self.stored(arg, "passed to `{}`", name);
+            }
+        }
+        let table = match table {
+            Some(table) => table,
+            None => return,
+        };
+        for (arg, param) in args.iter().zip(table.params.iter()) {
+            match *param {
+                Some(ref param) if param.mutable => {
+                    let reason = format!(
+                        "passed to `{}`, which writes through it (line {})",
+                        name, arg.span.line
+                    );
+                    if let Some(usage) = self.reference(arg) {
+                        note(&mut usage.written, reason.clone());
+                    }
+                    if let ExprKind::Unary(UnaryOp::AddressOf, ref operand) = arg.kind {
+                        self.written(operand, reason);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /**
+     * assigned:
+     * what the value stored in the pointer tells about it, p = NULL,
+     * p = malloc(sizeof(T)), p = "text" or p = q
+     */
+    fn assigned(&mut self, name: &str, value: &Expr) {
+        let line = value.span.line;
+        let mut fact: Option<Fact> = None;
+        //free(p); p = NULL; is not a use of the freed memory
+        self.freed.retain(|freed| freed.0 != name);
+        //a function pointer holds a function or NULL
+        if self
+            .find(name)
+            .is_some_and(|usage| usage.ty.is_function_pointer())
+        {
+            for value in branches(value) {
+                if is_null(value) {
+                    let reason = format!("set to NULL (line {})", value.span.line);
+                    if let Some(usage) = self.usages.iter_mut().find(|usage| usage.name == name) {
+                        note(&mut usage.null, reason);
+                    }
+                }
+            }
+            return;
+        }
+        match value.kind {
+            _ if is_null(value) => fact = Some(Fact::Null(format!("set to NULL (line {})", line))),
+            ExprKind::Conditional(_, ref then, ref otherwise) => {
+                self.assigned(name, then);
+                self.assigned(name, otherwise);
+            }
+            ExprKind::Literal(Literal::Str(_)) => {
+                fact = Some(Fact::Text(format!(
+                    "points to a string literal (line {})",
+                    line
+                )))
+            }
+            ExprKind::Unary(UnaryOp::AddressOf, _) => {}
+            ExprKind::Ident(ref other) if self.find(other).is_some() => {
+                self.aliases.push((name.to_string(), other.clone()))
+            }
+            ExprKind::Ident(ref other) if self.arrays.contains(other) => {
+                fact = Some(Fact::Slice(format!(
+                    "points into the array `{}` (line {})",
+                    other, line
+                )))
+            }
+            //p = arr + 1, p = q + 1
+            ExprKind::Binary(BinaryOp::Add, ref base, _)
+            | ExprKind::Binary(BinaryOp::Add, _, ref base)
+                if self.is_pointer(base) || self.is_array(base) =>
+            {
+                fact = Some(Fact::Slice(format!(
+                    "points into `{}` (line {})",
+                    base.idents().join(""),
+                    line
+                )))
+            }
+            _ => {
+                if let Some(allocator) = allocation(value) {
+                    let pointee = match self.find(name).map(|usage| &usage.ty) {
+                        Some(ty) if ty.is_pointer() => ty.pointee().clone(),
+                        _ => return,
+                    };
+                    fact = Some(if is_single_allocation(value, &pointee) {
+                        Fact::Heap(format!(
+                            "owns the memory allocated with `{}` (line {})",
+                            allocator, line
+                        ))
+                    } else if allocated_count(value, &pointee).is_some() {
+                        Fact::Buffer(format!(
+                            "owns the values allocated with `{}` (line {})",
+                            allocator, line
+                        ))
+                    } else {
+                        Fact::Raw(format!(
+                            "holds memory allocated with `{}` that is not one value (line {})",
+                            allocator, line
+                        ))
+                    });
+                } else if let ExprKind::Call(ref callee, _) = value.kind {
+                    fact = Some(self.returned_by(callee, line));
+                } else if !matches!(value.kind, ExprKind::Binary(..)) {
+                    fact = Some(Fact::Raw(format!(
+                        "assigned an address crust can't follow (line {})",
+                        line
+                    )));
+                }
+            }
+        }
+        let usage = match self.usages.iter_mut().find(|usage| usage.name == name) {
+            Some(usage) => usage,
+            None => return,
+        };
+        let single = usage.ty.is_pointer()
+            && allocation(value).is_some()
+            && is_single_allocation(value, usage.ty.pointee());
+        match value.kind {
+            _ if single => usage.boxed = true,
+            //the branches were assigned one by one
+            ExprKind::Conditional(..) => {}
+            _ if is_null(value) => {}
+            _ => usage.unboxed = true,
+        }
+        match fact {
+            Some(Fact::Null(reason)) => note(&mut usage.null, reason),
+            Some(Fact::Heap(reason)) => note(&mut usage.heap, reason),
+            Some(Fact::Buffer(reason)) => note(&mut usage.buffer, reason),
+            Some(Fact::Slice(reason)) => note(&mut usage.slice, reason),
+            Some(Fact::Raw(reason)) => note(&mut usage.raw, reason),
+            Some(Fact::Text(reason)) => note(&mut usage.string, reason),
+            None => {}
+        }
+    }
+
+    // fact about a pointer assigned the result of the call
+    fn returned_by(&self, callee: &Expr, line: u32) -> Fact {
+        let name = match callee.kind {
+            ExprKind::Ident(ref name) => name.clone(),
+            _ => String::new(),
+        };
+        let ret = self.table(&name).and_then(|table| table.ret.as_ref());
+        match ret.map(|ret| ret.kind) {
+            Some(PointerKind::Owned) | Some(PointerKind::NullableOwned) => Fact::Heap(format!(
+                "owns the memory returned by `{}` (line {})",
+                name, line
+            )),
+            Some(PointerKind::Vec) => Fact::Buffer(format!(
+                "owns the values returned by `{}` (line {})",
+                name, line
+            )),
+            Some(PointerKind::Nullable) => {
+                Fact::Null(format!("`{}` may return NULL (line {})", name, line))
+            }
+            _ => Fact::Raw(format!(
+                "assigned the pointer returned by `{}` (line {})",
+                name, line
+            )),
+        }
+    }
+
+    /**
+     * propagate_writes:
+     * q = p; *q = 1; needs p to be mutable as well, and raw pointers
+     * compared with each other have to agree on *mut or *const
+     */
+    fn propagate_writes(&mut self) {
+        let mut links: Vec<(String, String, String)> = self
+            .aliases
+            .iter()
+            .map(|(alias, name)| {
+                let reason = format!("`{}` writes through it", alias);
+                (alias.clone(), name.clone(), reason)
+            })
+            .collect();
+        for (lhs, rhs) in &self.compared {
+            for (from, to) in [(lhs, rhs), (rhs, lhs)] {
+                let reason = format!("compared with `{}`, which is written through", from);
+                links.push((from.clone(), to.clone(), reason));
+            }
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (alias, name, reason) in &links {
+                let written = self
+                    .find(alias)
+                    .is_some_and(|usage| usage.written.is_some());
+                let usage = match self.usages.iter_mut().find(|usage| usage.name == *name) {
+                    Some(usage) => usage,
+                    None => continue,
+                };
+                if written && usage.written.is_none() {
+                    usage.written = Some(reason.clone());
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    /**
+     * classify_return:
+     * a returned pointer can be owned, or borrowed from the only
+     * reference parameter, everything else is returned as raw pointer
+     */
+    fn classify_return(
+        &self,
+        ty: &CType,
+        params: &[Option<PointerInfo>],
+        variables: &[PointerInfo],
+    ) -> PointerInfo {
+        let mut null: Option<u32> = None;
+        let mut heap: Option<String> = None;
+        let mut buffer: Option<String> = None;
+        let mut string = false;
+        let mut borrowed: Vec<&PointerInfo> = Vec::new();
+        let mut raw: Option<String> = None;
+        for value in self.returns.iter().flat_map(branches) {
+            let line = value.span.line;
+            let variable = match value.kind {
+                ExprKind::Ident(ref name) => variables.iter().find(|info| info.name == *name),
+                _ => None,
+            };
+            match (variable, &value.kind) {
+                _ if is_null(value) => null = null.or(Some(line)),
+                (_, &ExprKind::Literal(Literal::Str(_))) => string = true,
+                (Some(info), _) if info.kind == PointerKind::Vec => note(
+                    &mut buffer,
+                    format!("returns `{}` (line {})", info.name, line),
+                ),
+                (Some(info), _) if info.kind.is_owned() => {
+                    if info.kind.is_nullable() {
+                        null = null.or(Some(line));
+                    }
+                    note(
+                        &mut heap,
+                        format!("returns `{}` (line {})", info.name, line),
+                    )
+                }
+                (Some(info), _) if info.kind != PointerKind::Raw && self.is_param(&info.name) => {
+                    if info.kind.is_nullable() {
+                        null = null.or(Some(line));
+                    }
+                    borrowed.push(info)
+                }
+                _ if ty.is_pointer()
+                    && allocation(value).is_some()
+                    && is_single_allocation(value, ty.pointee()) =>
+                {
+                    note(
+                        &mut heap,
+                        format!("returns newly allocated memory (line {})", line),
+                    )
+                }
+                _ if ty.is_pointer()
+                    && allocation(value).is_some()
+                    && allocated_count(value, ty.pointee()).is_some() =>
+                {
+                    note(
+                        &mut buffer,
+                        format!("returns newly allocated values (line {})", line),
+                    )
+                }
+                _ => note(
+                    &mut raw,
+                    format!("returns an address crust can't follow (line {})", line),
+                ),
+            }
+        }
+
+        let references: Vec<&PointerInfo> = params
+            .iter()
+            .flatten()
+            .filter(|info| info.kind != PointerKind::Raw && !info.kind.is_owned())
+            .collect();
+        let (kind, mutable, reason) = if let Some(reason) = raw {
+            (PointerKind::Raw, true, reason)
+        } else if let Some(reason) = buffer
+            .clone()
+            .filter(|_| heap.is_none() && borrowed.is_empty() && !string && null.is_none())
+        {
+            (PointerKind::Vec, true, reason)
+        } else if let Some(reason) = heap
+            .clone()
+            .filter(|_| buffer.is_none() && borrowed.is_empty() && !string)
+        {
+            let kind = if null.is_some() {
+                PointerKind::NullableOwned
+            } else {
+                PointerKind::Owned
+            };
+            (kind, true, reason)
+        } else if !borrowed.is_empty()
+            && !string
+            && references.len() == 1
+            && borrowed.iter().all(|info| info.name == references[0].name)
+        {
+            let param = references[0];
+            let kind = match param.kind {
+                PointerKind::Str if null.is_none() => PointerKind::Str,
+                PointerKind::Slice if null.is_none() => PointerKind::Slice,
+                _ if null.is_some() || param.kind.is_nullable() => PointerKind::Nullable,
+                _ => PointerKind::Borrowed,
+            };
+            let reason = format!("borrows from the only reference parameter `{}`", param.name);
+            (kind, param.mutable, reason)
+        } else if string && borrowed.is_empty() && heap.is_none() && null.is_none() {
+            (
+                PointerKind::Str,
+                false,
+                "returns string literals".to_string(),
+            )
+        } else {
+            (
+                PointerKind::Raw,
+                borrowed.iter().all(|info| info.mutable),
+                "the returned pointer can't borrow from a single parameter".to_string(),
+            )
+        };
+        PointerInfo {
+            name: String::new(),
+            ty: ty.clone(),
+            kind,
+            mutable,
+            boxed: false,
+            reason,
+        }
+    }
+
+    fn is_param(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|usage| usage.is_param)
+    }
+}
+
+/**
+ * classify:
+ * rust type for the pointer from its uses, raw pointers are the last resort,
+ * then owned, string and borrowed pointers wrapped in Option if they can be NULL
+ */
+fn classify(usage: &Usage) -> PointerInfo {
+    let inner = usage.ty.pointee();
+    let mutable = usage.written.is_some();
+    let with_writes = |reason: &str| match usage.written {
+        Some(ref written) => format!("{}, {}", reason, written),
+        None => reason.to_string(),
+    };
+    let is_char = matches!(*inner, CType::Primitive(TokenType::Character, _));
+    let (kind, reason) = if usage.ty.is_reference() {
+        match usage.written {
+            Some(ref written) => (PointerKind::Borrowed, format!("a reference, {}", written)),
+            None => (PointerKind::Borrowed, "a reference, only read".to_string()),
+        }
+    } else if let CType::Function(..) = *inner {
+        match (&usage.null, &usage.stored) {
+            (Some(null), _) => (PointerKind::NullableFunction, null.clone()),
+            (None, Some(stored)) => (PointerKind::Function, stored.clone()),
+            (None, None) if usage.is_param => (
+                PointerKind::Callback,
+                "a parameter that is only called".to_string(),
+            ),
+            (None, None) => (PointerKind::Function, "never NULL".to_string()),
+        }
+    } else if let CType::Primitive(TokenType::Void, _) = *inner {
+        (
+            PointerKind::Raw,
+            "`void *` has no rust equivalent".to_string(),
+        )
+    } else if inner.is_pointer() {
+        (PointerKind::Raw, "points to another pointer".to_string())
+    } else if let Some(ref reason) = usage.freed {
+        (PointerKind::Raw, reason.clone())
+    } else if let Some(ref reason) = usage.raw {
+        (PointerKind::Raw, reason.clone())
+    } else if let Some(ref reason) = usage.buffer {
+        //a Vec can't be NULL, moved forward or replaced by a single value
+        match usage
+            .heap
+            .as_ref()
+            .or(usage.moved.as_ref())
+            .or(usage.null.as_ref())
+        {
+            Some(other) => (PointerKind::Raw, format!("{}, {}", reason, other)),
+            None => (PointerKind::Vec, reason.clone()),
+        }
+    } else if let Some(ref reason) = usage.heap {
+        match (&usage.slice, &usage.null) {
+            (Some(slice), _) => (PointerKind::Raw, format!("{}, {}", reason, slice)),
+            (None, Some(null)) => (PointerKind::NullableOwned, format!("{}, {}", reason, null)),
+            (None, None) => (PointerKind::Owned, reason.clone()),
+        }
+    } else if is_char
+        && !mutable
+        && usage.null.is_none()
+        && (usage.string.is_some() || usage.is_param)
+    {
+        //text walked with s++ is the rest of the string
+        let reason = match usage.string {
+            Some(ref reason) => reason.clone(),
+            None => "a char parameter that is only read".to_string(),
+        };
+        match usage.slice {
+            Some(ref slice) => (PointerKind::Str, format!("{}, {}", reason, slice)),
+            None => (PointerKind::Str, reason),
+        }
+    } else if let Some(ref slice) = usage.slice {
+        match usage.null {
+            Some(ref null) => (PointerKind::Raw, format!("{}, {}", slice, null)),
+            None => (PointerKind::Slice, with_writes(slice)),
+        }
+    } else if let Some(ref null) = usage.null {
+        (PointerKind::Nullable, with_writes(null))
+    } else if mutable {
+        (PointerKind::Borrowed, with_writes("never NULL"))
+    } else {
+        (
+            PointerKind::Borrowed,
+            "never NULL, only read through".to_string(),
+        )
+    };
+    PointerInfo {
+        name: usage.name.clone(),
+        ty: usage.ty.clone(),
+        kind,
+        mutable,
+        boxed: kind == PointerKind::Raw && usage.boxed && !usage.unboxed,
+        reason,
+    }
+}
+
+/**
+ * stepped:
+ * pointer behind *p++, *++p and *(p + i), the expression itself otherwise
+ */
+pub fn stepped(expr: &Expr) -> &Expr {
+    match expr.kind {
+        ExprKind::Postfix(_, ref pointer)
+        | ExprKind::Unary(UnaryOp::PreIncrement, ref pointer)
+        | ExprKind::Unary(UnaryOp::PreDecrement, ref pointer)
+        | ExprKind::Binary(BinaryOp::Add, ref pointer, _)
+        | ExprKind::Binary(BinaryOp::Sub, ref pointer, _) => pointer,
+        _ => expr,
+    }
+}
+
+// values an expression can evaluate to, c ? a : b gives a and b
+fn branches(expr: &Expr) -> Vec<&Expr> {
+    match expr.kind {
+        ExprKind::Conditional(_, ref then, ref otherwise) => {
+            let mut values = branches(then);
+            values.append(&mut branches(otherwise));
+            values
+        }
+        _ => vec![expr],
+    }
+}
+
+// NULL, nullptr and the literal 0
+pub fn is_null(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Literal(Literal::Null) => true,
+        ExprKind::Literal(Literal::Integer(ref number)) => number.value() == Some(0),
+        ExprKind::Cast(_, ref operand) => is_null(operand),
+        _ => false,
+    }
+}
+
+// -1, a literal index before the start of what the pointer points to
+fn is_negative(index: &Expr) -> bool {
+    matches!(
+        index.kind,
+        ExprKind::Unary(UnaryOp::Minus, ref operand)
+            if matches!(operand.kind, ExprKind::Literal(Literal::Integer(_)))
+    )
+}
+
+// name of the c allocator called by the expression or new, looking through casts
+pub fn allocation(expr: &Expr) -> Option<&str> {
+    match expr.kind {
+        ExprKind::Cast(_, ref operand) => allocation(operand),
+        ExprKind::Call(ref callee, _) => match callee.kind {
+            ExprKind::Ident(ref name)
+                if name == "malloc" || name == "calloc" || name == "realloc" =>
+            {
+                Some(name.as_str())
+            }
+            _ => None,
+        },
+        ExprKind::New(CType::Array(..), _) => Some("new[]"),
+        ExprKind::New(..) => Some("new"),
+        _ => None,
+    }
+}
+
+// malloc(sizeof(T)) and calloc(1, sizeof(T)) allocate one value of the pointed type
+pub fn is_single_allocation(expr: &Expr, pointee: &CType) -> bool {
+    let args = match expr.kind {
+        ExprKind::Cast(_, ref operand) => return is_single_allocation(operand, pointee),
+        ExprKind::New(ref ty, _) => return !ty.is_array(),
+        ExprKind::Call(_, ref args) => args,
+        _ => return false,
+    };
+    let size = match (args.len(), args.first()) {
+        (1, Some(size)) => size,
+        (
+            2,
+            Some(&Expr {
+                kind: ExprKind::Literal(Literal::Integer(ref count)),
+                ..
+            }),
+        ) if count.value() == Some(1) => &args[1],
+        _ => return false,
+    };
+    is_size_of(size, pointee)
+}
+
+/**
+ * allocated_count:
+ * number of values of the pointed type allocated by the expression, n for
+ * malloc(n * sizeof(T)), calloc(n, sizeof(T)), realloc(p, n * sizeof(T))
+ * and new T[n]. The size given to malloc(n) counts chars
+ */
+pub fn allocated_count<'e>(expr: &'e Expr, pointee: &CType) -> Option<&'e Expr> {
+    let size = match expr.kind {
+        ExprKind::Cast(_, ref operand) => return allocated_count(operand, pointee),
+        ExprKind::New(CType::Array(ref inner, Some(ref length)), _) if **inner == *pointee => {
+            return Some(length)
+        }
+        ExprKind::Call(_, ref args) => match (allocation(expr), args.len()) {
+            (Some("malloc"), 1) => &args[0],
+            (Some("realloc"), 2) => &args[1],
+            (Some("calloc"), 2) if is_size_of(&args[1], pointee) => return Some(&args[0]),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match size.kind {
+        ExprKind::Binary(BinaryOp::Mul, ref count, ref unit)
+        | ExprKind::Binary(BinaryOp::Mul, ref unit, ref count)
+            if is_size_of(unit, pointee) =>
+        {
+            Some(count)
+        }
+        _ if matches!(*pointee, CType::Primitive(TokenType::Character, _)) => Some(size),
+        _ => None,
+    }
+}
+
+// sizeof(T) or sizeof *p for the pointed type T
+fn is_size_of(size: &Expr, pointee: &CType) -> bool {
+    match size.kind {
+        ExprKind::SizeOfType(ref ty) => ty == pointee,
+        //sizeof *p
+        ExprKind::SizeOfExpr(ref operand) => {
+            matches!(operand.kind, ExprKind::Unary(UnaryOp::Deref, _))
+        }
+        _ => false,
+    }
+}
+
+// pointer variable released by free(p) or delete p
+pub fn released(expr: &Expr) -> Option<&str> {
+    let pointer = match expr.kind {
+        ExprKind::Call(ref callee, ref args)
+            if args.len() == 1 && callee.kind == ExprKind::Ident("free".to_string()) =>
+        {
+            &args[0]
+        }
+        ExprKind::Delete(ref pointer) => pointer,
+        _ => return None,
+    };
+    match pointer.kind {
+        ExprKind::Ident(ref name) => Some(name),
+        _ => None,
+    }
+}
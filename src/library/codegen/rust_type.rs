@@ -0,0 +1,102 @@
+use library::ast::{Literal, Modifier};
+use library::lexeme::definition::TokenType::*;
+use library::lexeme::definition::{NumberSuffix, Radix, TokenType};
+
+/**
+ * fn parse_type:
+ * takes the integer value of type Type
+ * returns either the equivalent Rust type as a string or
+ * None, if does not correspond to any c/c++ type
+ */
+pub fn parse_type(c_type: TokenType, modifier: Modifier) -> Option<String> {
+    match (modifier, c_type) {
+        //unsigned types
+        (Modifier::Unsigned, Character) => Some("u8".to_string()),
+        (Modifier::Unsigned, Short) => Some("u16".to_string()),
+        (Modifier::Unsigned, Integer) => Some("u32".to_string()),
+        (Modifier::Unsigned, Long) => Some("u64".to_string()),
+
+        //signed types
+        (_, Short) => Some("i16".to_string()),
+        (_, Integer) => Some("i32".to_string()),
+        (_, Long) => Some("i64".to_string()),
+
+        //type without modifiers
+        (_, Float) => Some("f32".to_string()),
+        (_, Double) => Some("f64".to_string()),
+        (_, Character) => Some("char".to_string()),
+        (_, Boolean) => Some("bool".to_string()),
+        (_, Void) => Some("void".to_string()),
+        (_, Auto) => Some("_".to_string()),
+        (_, StringValue) => Some("String".to_string()),
+        (_, _) => None,
+    }
+}
+
+/**
+ * fn literal_type:
+ * rust type of a c/c++ literal. Integer literals take the first of
+ * int, unsigned int (hexadecimal and octal only) and long their value
+ * fits in, suffixes make them unsigned or long. Floating literals are
+ * double unless suffixed with f
+ */
+pub fn literal_type(literal: &Literal) -> Option<String> {
+    match *literal {
+        Literal::Integer(ref number) => {
+            let value = number.value().unwrap_or(u64::MAX);
+            let fits_int = value <= i32::MAX as u64;
+            let fits_unsigned = value <= u32::MAX as u64;
+            //hexadecimal and octal literals may be unsigned int before long
+            let unsigned_first = number.radix != Radix::Decimal;
+            match number.suffix {
+                NumberSuffix::None if fits_int => parse_type(Integer, Modifier::Default),
+                NumberSuffix::None if fits_unsigned && unsigned_first => {
+                    parse_type(Integer, Modifier::Unsigned)
+                }
+                NumberSuffix::Unsigned if fits_unsigned => parse_type(Integer, Modifier::Unsigned),
+                NumberSuffix::Unsigned
+                | NumberSuffix::UnsignedLong
+                | NumberSuffix::UnsignedLongLong => parse_type(Long, Modifier::Unsigned),
+                _ => parse_type(Long, Modifier::Default),
+            }
+        }
+        Literal::Float(ref number) if number.suffix == NumberSuffix::Float => {
+            parse_type(Float, Modifier::Default)
+        }
+        Literal::Float(_) => parse_type(Double, Modifier::Default),
+        Literal::Char(_) => parse_type(Character, Modifier::Default),
+        Literal::Bool(_) => parse_type(Boolean, Modifier::Default),
+        Literal::Str(_) => Some("&str".to_string()),
+        Literal::Null => None,
+    }
+}
+
+/**
+ * fn number_suffix:
+ * rust type suffix for the suffix of a c/c++ numeric literal,
+ * 10UL => 10u64 and 1.5f => 1.5f32
+ */
+pub fn number_suffix(suffix: NumberSuffix) -> &'static str {
+    match suffix {
+        NumberSuffix::None => "",
+        NumberSuffix::Unsigned => "u32",
+        NumberSuffix::Long | NumberSuffix::LongLong => "i64",
+        NumberSuffix::UnsignedLong | NumberSuffix::UnsignedLongLong => "u64",
+        NumberSuffix::Float => "f32",
+        NumberSuffix::LongDouble => "f64",
+    }
+}
+
+pub fn get_default_value_for(c_type: TokenType) -> String {
+    let value = match c_type {
+        Integer => "0i32",
+        Short => "0i16",
+        Long => "0i64",
+        Float => "0.0f32",
+        Double => "0.0f64",
+        Character => "'_'",
+        Boolean => "false",
+        _ => "Default::default()",
+    };
+    String::from(value)
+}
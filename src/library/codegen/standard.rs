@@ -0,0 +1,61 @@
+//TYPES types of the c++ standard library and the rust types they map to
+const TYPES: [(&str, &str); 10] = [
+    ("string", "String"),
+    ("vector", "Vec"),
+    ("deque", "std::collections::VecDeque"),
+    ("list", "std::collections::LinkedList"),
+    ("map", "std::collections::BTreeMap"),
+    ("set", "std::collections::BTreeSet"),
+    ("unordered_map", "std::collections::HashMap"),
+    ("unordered_set", "std::collections::HashSet"),
+    ("size_t", "usize"),
+    ("ptrdiff_t", "isize"),
+];
+
+//FUNCTIONS functions of the c++ standard library and their rust equivalents
+const FUNCTIONS: [(&str, &str); 3] = [
+    ("max", "std::cmp::max"),
+    ("min", "std::cmp::min"),
+    ("exit", "std::process::exit"),
+];
+
+// rust type of a standard library type, std:: is already stripped
+pub fn std_type(name: &str) -> Option<&'static str> {
+    TYPES
+        .iter()
+        .find(|entry| entry.0 == name)
+        .map(|entry| entry.1)
+}
+
+// rust function of a standard library function, std:: is already stripped
+pub fn std_function(name: &str) -> Option<&'static str> {
+    FUNCTIONS
+        .iter()
+        .find(|entry| entry.0 == name)
+        .map(|entry| entry.1)
+}
+
+// standard library types that take template arguments, std::vector<int>
+pub fn is_std_template(name: &str) -> bool {
+    std_type(name).is_some() && !matches!(name, "string" | "size_t" | "ptrdiff_t")
+}
+
+/**
+ * std_names:
+ * names a using declaration of the standard library brings in scope,
+ * all of them for using namespace std;
+ */
+pub fn std_names(path: &str, namespace: bool) -> Vec<&'static str> {
+    TYPES
+        .iter()
+        .chain(FUNCTIONS.iter())
+        .map(|entry| entry.0)
+        .filter(|name| {
+            if namespace {
+                path == "std"
+            } else {
+                path.strip_prefix("std::") == Some(*name)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,145 @@
+use library::ast::*;
+use library::codegen::loops::continues;
+
+//Arm case labels sharing one match arm and the statements the arm runs,
+//those of the cases it falls through to included
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm<'a> {
+    pub labels: Vec<&'a CaseLabel>,
+    pub body: Vec<Stmt>,
+}
+
+impl<'a> Arm<'a> {
+    // default: arms are matched last, whatever their place in the switch
+    pub fn is_default(&self) -> bool {
+        self.labels.contains(&&CaseLabel::Default)
+    }
+}
+
+/**
+ * switch_arms:
+ * match arms of the cases of a switch, labels without statements of
+ * their own are grouped with the next case and the statements of the
+ * cases a case falls through to are copied into its arm. A break
+ * ending an arm is dropped since match arms don't fall through
+ */
+pub fn switch_arms(cases: &[SwitchCase]) -> Vec<Arm<'_>> {
+    let mut arms: Vec<Arm> = Vec::new();
+    let mut labels: Vec<&CaseLabel> = Vec::new();
+    for (index, case) in cases.iter().enumerate() {
+        labels.push(&case.label);
+        if case.body.is_empty() && index + 1 < cases.len() {
+            continue;
+        }
+        let mut body: Vec<Stmt> = Vec::new();
+        for case in &cases[index..] {
+            body.extend(case.body.iter().cloned());
+            if !falls_through(&case.body) {
+                break;
+            }
+        }
+        strip_tail_break(&mut body);
+        arms.push(Arm {
+            labels: std::mem::take(&mut labels),
+            body,
+        });
+    }
+    arms
+}
+
+// a break left in the middle of an arm leaves the switch through a labelled block
+pub fn needs_label(cases: &[SwitchCase]) -> bool {
+    switch_arms(cases)
+        .iter()
+        .any(|arm| arm.body.iter().any(breaks_out))
+}
+
+/**
+ * continues_through_switch:
+ * a continue inside a labelled switch block has to name the loop it
+ * continues, the loop whose body this is gets a label then
+ */
+pub fn continues_through_switch(body: &Stmt) -> bool {
+    match body.kind {
+        StmtKind::While { .. } | StmtKind::DoWhile { .. } | StmtKind::For { .. } => false,
+        StmtKind::Switch { ref cases, .. } if needs_label(cases) => body
+            .children()
+            .into_iter()
+            .any(|child| continues(child) || continues_through_switch(child)),
+        _ => body.children().into_iter().any(continues_through_switch),
+    }
+}
+
+// whether control reaches the end of the statements of a case
+fn falls_through(stmts: &[Stmt]) -> bool {
+    match stmts.iter().rev().find(|stmt| !is_trivia(stmt)) {
+        Some(stmt) => match stmt.kind {
+            StmtKind::Break | StmtKind::Continue | StmtKind::Goto(_) | StmtKind::Return(_) => false,
+            StmtKind::Block(ref stmts) => falls_through(stmts),
+            StmtKind::If {
+                ref then,
+                otherwise: Some(ref otherwise),
+                ..
+            } => {
+                falls_through(std::slice::from_ref(then))
+                    || falls_through(std::slice::from_ref(otherwise))
+            }
+            _ => true,
+        },
+        None => true,
+    }
+}
+
+// removes the breaks control leaves the arm with, if (a) { f(); break; }
+fn strip_tail_break(stmts: &mut Vec<Stmt>) {
+    let last = match stmts.iter().rposition(|stmt| !is_trivia(stmt)) {
+        Some(last) => last,
+        None => return,
+    };
+    match stmts[last].kind {
+        StmtKind::Break => {
+            stmts.remove(last);
+        }
+        StmtKind::Block(ref mut inner) => strip_tail_break(inner),
+        StmtKind::If {
+            ref mut then,
+            ref mut otherwise,
+            ..
+        } => {
+            strip_branch_break(then);
+            if let Some(ref mut otherwise) = *otherwise {
+                strip_branch_break(otherwise);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_branch_break(branch: &mut Stmt) {
+    match branch.kind {
+        StmtKind::Break => branch.kind = StmtKind::Block(Vec::new()),
+        StmtKind::Block(ref mut stmts) => strip_tail_break(stmts),
+        StmtKind::If { .. } => {
+            let mut stmts = vec![branch.clone()];
+            strip_tail_break(&mut stmts);
+            *branch = stmts.remove(0);
+        }
+        _ => {}
+    }
+}
+
+// break of the switch itself, not of a loop or switch nested in it
+fn breaks_out(stmt: &Stmt) -> bool {
+    match stmt.kind {
+        StmtKind::Break => true,
+        StmtKind::While { .. }
+        | StmtKind::DoWhile { .. }
+        | StmtKind::For { .. }
+        | StmtKind::Switch { .. } => false,
+        _ => stmt.children().into_iter().any(breaks_out),
+    }
+}
+
+fn is_trivia(stmt: &Stmt) -> bool {
+    matches!(stmt.kind, StmtKind::Comment(_) | StmtKind::Empty)
+}
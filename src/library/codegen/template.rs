@@ -0,0 +1,352 @@
+use library::ast::*;
+use library::codegen::hierarchy::record_named;
+
+//Bounds traits the type parameters of a template need and the copies
+//of their values, as found from the operations of the template bodies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bounds {
+    //type parameter and its traits, in the order they were found
+    pub traits: Vec<(String, Vec<String>)>,
+    //places whose value is copied, rust moves them unless cloned
+    pub clones: Vec<Span>,
+}
+
+impl Bounds {
+    // traits of a type parameter, PartialOrd covers PartialEq
+    pub fn of(&self, param: &str) -> Vec<String> {
+        let traits = self
+            .traits
+            .iter()
+            .find(|entry| entry.0 == param)
+            .map(|entry| entry.1.clone())
+            .unwrap_or_default();
+        let ordered = traits.iter().any(|name| name == "PartialOrd");
+        traits
+            .into_iter()
+            .filter(|name| !(ordered && name == "PartialEq"))
+            .collect()
+    }
+
+    fn add(&mut self, param: &str, name: String) {
+        match self.traits.iter_mut().find(|entry| entry.0 == param) {
+            Some(entry) if entry.1.contains(&name) => {}
+            Some(entry) => entry.1.push(name),
+            None => self.traits.push((param.to_string(), vec![name])),
+        }
+    }
+}
+
+/**
+ * infer_bounds:
+ * bounds of the type parameters of a template from the bodies of its
+ * functions, arithmetic needs std::ops::Add<Output = T> and the like,
+ * comparisons PartialOrd or PartialEq, copies Clone and values left
+ * uninitialized Default
+ */
+pub fn infer_bounds(
+    params: &[TemplateParam],
+    functions: &[&Function],
+    fields: &[&Declarator],
+    records: &[Record],
+) -> Bounds {
+    let mut inference = Inference {
+        records,
+        params: params
+            .iter()
+            .filter(|param| param.ty.is_none())
+            .map(|param| param.name.as_str())
+            .collect(),
+        fields: fields
+            .iter()
+            .map(|field| (field.name.clone(), field.ty.clone()))
+            .collect(),
+        variables: Vec::new(),
+        uses: Vec::new(),
+        bounds: Bounds::default(),
+    };
+    for function in functions {
+        inference.function(function);
+    }
+    inference.bounds
+}
+
+struct Inference<'a> {
+    records: &'a [Record],
+    params: Vec<&'a str>,
+    fields: Vec<(String, CType)>,
+    //parameters and locals of the function being walked
+    variables: Vec<(String, CType)>,
+    //values of by value variables, a variable used twice is cloned
+    uses: Vec<(String, String, Vec<Span>)>,
+    bounds: Bounds,
+}
+
+impl<'a> Inference<'a> {
+    fn function(&mut self, function: &Function) {
+        self.variables = function
+            .params
+            .iter()
+            .filter_map(|param| Some((param.name.clone()?, param.ty.clone())))
+            .collect();
+        self.uses.clear();
+        //fields not set by the initializer list start out with their default value
+        if function.return_type == CType::Named(function.name.clone()) {
+            for (name, ty) in self.fields.clone() {
+                let initialized = function
+                    .initializers
+                    .iter()
+                    .any(|initializer| initializer.member == name);
+                if !initialized {
+                    self.uninitialized(&ty);
+                }
+            }
+        }
+        if let Some(ref body) = function.body {
+            for stmt in body {
+                self.stmt(stmt);
+            }
+        }
+        for (_, param, spans) in std::mem::take(&mut self.uses) {
+            if spans.len() > 1 {
+                for span in spans {
+                    self.clone_value(&param, span);
+                }
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt.kind {
+            StmtKind::Declaration(ref decl) => {
+                for declarator in &decl.declarators {
+                    self.variables
+                        .push((declarator.name.clone(), declarator.ty.clone()));
+                    match declarator.init {
+                        Some(ref init) => {
+                            self.expr(init);
+                            self.value(init);
+                        }
+                        None => self.uninitialized(&declarator.ty),
+                    }
+                }
+            }
+            StmtKind::Return(Some(ref value)) => {
+                self.expr(value);
+                self.value(value);
+            }
+            _ => {
+                for expr in stmt.exprs() {
+                    self.expr(expr);
+                }
+            }
+        }
+        for child in stmt.children() {
+            self.stmt(child);
+        }
+    }
+
+    // T x; and T a[4]; start out with T::default()
+    fn uninitialized(&mut self, ty: &CType) {
+        if ty.is_pointer() || ty.is_reference() {
+            return;
+        }
+        if let Some(param) = self.param_of_type(ty.base()) {
+            self.bounds.add(&param, "Default".to_string());
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr.kind {
+            ExprKind::Binary(op, ref lhs, ref rhs) => {
+                let param = self.param_of(lhs).or_else(|| self.param_of(rhs));
+                if let Some(param) = param {
+                    match op {
+                        BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => {
+                            self.bounds.add(&param, "PartialOrd".to_string())
+                        }
+                        BinaryOp::Eq | BinaryOp::Ne => {
+                            self.bounds.add(&param, "PartialEq".to_string())
+                        }
+                        _ => {
+                            if let Some(name) = ops_trait(op) {
+                                self.bounds
+                                    .add(&param, format!("std::ops::{}<Output = {}>", name, param));
+                                self.value(lhs);
+                                self.value(rhs);
+                            }
+                        }
+                    }
+                }
+            }
+            ExprKind::Assign(op, ref lhs, ref rhs) => {
+                if let (Some(op), Some(param)) = (op, self.param_of(lhs)) {
+                    if let Some(name) = ops_trait(op) {
+                        self.bounds.add(&param, format!("std::ops::{}Assign", name));
+                    }
+                }
+                self.value(rhs);
+            }
+            //T() value initializes
+            ExprKind::Call(ref callee, ref args) if args.is_empty() => {
+                if let ExprKind::Ident(ref name) = callee.kind {
+                    if let Some(param) = self.param_of_type(&CType::Named(name.clone())) {
+                        self.bounds.add(&param, "Default".to_string());
+                    }
+                }
+            }
+            ExprKind::Unary(UnaryOp::Minus, ref operand) => {
+                if let Some(param) = self.param_of(operand) {
+                    self.bounds
+                        .add(&param, format!("std::ops::Neg<Output = {}>", param));
+                    self.value(operand);
+                }
+            }
+            _ => {}
+        }
+        for child in expr.children() {
+            self.expr(child);
+        }
+    }
+
+    /**
+     * value:
+     * a value of a type parameter copied out of a place, fields,
+     * elements and references are cloned, by value variables only when
+     * their value is used more than once
+     */
+    fn value(&mut self, expr: &Expr) {
+        let param = match self.param_of(expr) {
+            Some(param) => param,
+            None => return,
+        };
+        match expr.kind {
+            ExprKind::Ident(ref name) => match self.local(name) {
+                Some(ty) if !ty.is_reference() => {
+                    match self.uses.iter_mut().find(|entry| entry.0 == *name) {
+                        Some(entry) => entry.2.push(expr.span),
+                        None => self.uses.push((name.clone(), param, vec![expr.span])),
+                    }
+                }
+                _ => self.clone_value(&param, expr.span),
+            },
+            ExprKind::Member(..) | ExprKind::Index(..) | ExprKind::Unary(UnaryOp::Deref, _) => {
+                self.clone_value(&param, expr.span)
+            }
+            ExprKind::Conditional(_, ref then, ref otherwise) => {
+                self.value(then);
+                self.value(otherwise);
+            }
+            _ => {}
+        }
+    }
+
+    fn clone_value(&mut self, param: &str, span: Span) {
+        self.bounds.add(param, "Clone".to_string());
+        self.bounds.clones.push(span);
+    }
+
+    fn local(&self, name: &str) -> Option<&CType> {
+        self.variables
+            .iter()
+            .rev()
+            .find(|entry| entry.0 == name)
+            .map(|entry| &entry.1)
+    }
+
+    // local or field, fields are used without this-> in methods
+    fn variable(&self, name: &str) -> Option<&CType> {
+        self.variables
+            .iter()
+            .rev()
+            .chain(self.fields.iter())
+            .find(|entry| entry.0 == name)
+            .map(|entry| &entry.1)
+    }
+
+    // type of a field of a class, with the arguments of a class template substituted
+    fn field_of(&self, class: &str, args: &[TemplateArg], name: &str) -> Option<CType> {
+        let record = record_named(self.records, class)?;
+        let field = record
+            .fields()
+            .into_iter()
+            .find(|field| field.name == name)?;
+        let ty = record.template.iter().zip(args).find_map(|(param, arg)| {
+            match (arg, field.ty.referred()) {
+                (TemplateArg::Type(ref ty), CType::Named(ref named)) if *named == param.name => {
+                    Some(ty.clone())
+                }
+                _ => None,
+            }
+        });
+        Some(ty.unwrap_or_else(|| field.ty.clone()))
+    }
+
+    // type parameter an expression has a value of
+    fn param_of(&self, expr: &Expr) -> Option<String> {
+        let ty = self.type_of(expr)?;
+        self.param_of_type(ty.referred())
+    }
+
+    fn param_of_type(&self, ty: &CType) -> Option<String> {
+        match *ty {
+            CType::Named(ref name) if self.params.contains(&name.as_str()) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    fn type_of(&self, expr: &Expr) -> Option<CType> {
+        match expr.kind {
+            ExprKind::Ident(ref name) => self.variable(name).cloned(),
+            ExprKind::Member(ref base, ref name, arrow) => {
+                let class = self.type_of(base).map(|ty| match ty {
+                    ty @ CType::Pointer(_) if arrow => ty.pointee().clone(),
+                    CType::Reference(inner) => *inner,
+                    ty => ty,
+                });
+                match class {
+                    Some(CType::Named(ref class)) => self.field_of(class, &[], name),
+                    Some(CType::Generic(ref class, ref args)) => self.field_of(class, args, name),
+                    //this->x and members of the class itself
+                    _ => self
+                        .fields
+                        .iter()
+                        .find(|field| field.0 == *name)
+                        .map(|field| field.1.clone()),
+                }
+            }
+            ExprKind::Unary(UnaryOp::Deref, ref operand) => match self.type_of(operand)? {
+                ty @ CType::Pointer(_) => Some(ty.pointee().clone()),
+                _ => None,
+            },
+            ExprKind::Index(ref base, _) => match self.type_of(base)? {
+                ty @ CType::Pointer(_) => Some(ty.pointee().clone()),
+                CType::Array(inner, _) => Some(*inner),
+                _ => None,
+            },
+            ExprKind::Unary(UnaryOp::Minus, ref operand) => self.type_of(operand),
+            ExprKind::Binary(op, ref lhs, ref rhs) if ops_trait(op).is_some() => self
+                .type_of(lhs)
+                .filter(|ty| self.param_of_type(ty.referred()).is_some())
+                .or_else(|| self.type_of(rhs)),
+            ExprKind::Assign(_, ref lhs, _) => self.type_of(lhs),
+            ExprKind::Conditional(_, ref then, _) => self.type_of(then),
+            ExprKind::Cast(ref ty, _) => Some(ty.clone()),
+            _ => None,
+        }
+    }
+}
+
+// std::ops trait of an arithmetic or bitwise operator
+fn ops_trait(op: BinaryOp) -> Option<&'static str> {
+    match op {
+        BinaryOp::Add => Some("Add"),
+        BinaryOp::Sub => Some("Sub"),
+        BinaryOp::Mul => Some("Mul"),
+        BinaryOp::Div => Some("Div"),
+        BinaryOp::Rem => Some("Rem"),
+        BinaryOp::BitAnd => Some("BitAnd"),
+        BinaryOp::BitOr => Some("BitOr"),
+        BinaryOp::BitXor => Some("BitXor"),
+        _ => None,
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Clone, Copy)]
 pub enum DocType {
     NO_STRICT,
@@ -8,11 +8,18 @@ pub enum DocType {
     NO_RETURN,
     INCLUDE_STMT,
     NO_POINTER,
-    CONSTRUCTOR,
     CRUST,
     DEFAULT,
     UNION,
     UNION_DECL,
+    MACRO,
+    VARIADIC,
+    UNSUPPORTED,
+    VOLATILE,
+    INHERITANCE,
+    GOTO,
+    EXTERN,
+    EXTERN_FUNCTION,
 }
 
 impl DocType {
@@ -40,13 +47,9 @@ impl DocType {
                  can translate header file with Crust to produce Rust code. * \n"
             }
             DocType::NO_POINTER => {
-                "\n/** Crust doesn't support pointers yet,\
-                \n * Please handle the pointers manually with rust refernce, Rc, Arc or RefCell \
-                \n * ======> "
-            }
-            DocType::CONSTRUCTOR => {
-                "\n/** Rust structures do not support constructors\
-                \n * Please handle them with static builder functions\n * >>>>>>>>"
+                "\n/* Crust found no safe rust equivalent for the pointer below,\
+                \n * it is a raw pointer and is dereferenced inside unsafe blocks.\
+                \n * Reason:"
             }
             DocType::CRUST => {
                 "/*************************************************************************\
@@ -65,6 +68,45 @@ impl DocType {
 				"\n/* Union declaration must be translated to Rust equivalent Some type varients\
 				\n * Make changes before compilation \n */"
 			}
+            DocType::MACRO => {
+                "\n/** Crust could not translate the macro below into Rust,\
+                \n * it was expanded in place wherever it is used.\
+                \n * Reason:"
+            }
+            DocType::VARIADIC => {
+                "\n/** Rust functions take a fixed number of arguments,\
+                \n * the variable arguments `...` of the function below were dropped.\
+                \n * Pass them as a slice or write a macro_rules! macro instead.\
+                \n **/\n"
+            }
+            DocType::UNSUPPORTED => {
+                "\n/* Crust could not translate the C/C++ code below,\
+                \n * it has to be handled manually.\
+                \n * Reason:"
+            }
+            DocType::VOLATILE => {
+                "\n/* The variable below was declared volatile, rust has no volatile variables.\
+                \n * Read and write it with std::ptr::read_volatile and std::ptr::write_volatile\
+                \n */\n"
+            }
+            DocType::INHERITANCE => {
+                "\n/* Rust has no inheritance, the struct below holds its base class in the `base` field\
+                \n * and dereferences to it, virtual methods are declared by traits implemented for it.\
+                \n */\n"
+            }
+            DocType::GOTO => {
+                "\n/* Crust could not turn the goto below into a labelled block or loop,"
+            }
+            DocType::EXTERN => {
+                "\n/* The variable below is defined in another file and linked as a C symbol,\
+                \n * reading or writing it needs unsafe.\
+                \n */\n"
+            }
+            DocType::EXTERN_FUNCTION => {
+                "\n/* The function below is defined in another file and linked as a C symbol,\
+                \n * calling it needs unsafe.\
+                \n */\n"
+            }
             _ => "//Doc Not Found. Please Report bug",
         }
     }
@@ -48,8 +48,14 @@ pub enum TokenType {
     MultiplyEqual,
     DivideEqual,
     ModuleEqual,
+    BitwiseAndEqual,
+    BitwiseOrEqual,
+    BitwiseXorEqual,
+    LeftShiftEqual,
+    RightShiftEqual,
     BitwiseAnd,
     BitwiseOr,
+    BitwiseXor,
     BitwiseNegate,
     BitwiseLeftShift,
     BitwiseRightShift,
@@ -90,6 +96,12 @@ pub enum TokenType {
     MultilineComment,
     Identifier,
     Arrow,
+    Dot,
+    //pointer to member access obj.*ptr and obj->*ptr
+    DotStar,
+    ArrowStar,
+    //variable arguments `...`
+    Ellipsis,
     ScopeResolution,
 
     //Identifiers an=d keywords
@@ -114,6 +126,25 @@ pub enum TokenType {
     KeywordStruct,
     KeywordEnum,
     KeywordUnion,
+    KeywordGoto,
+    KeywordExtern,
+    KeywordInline,
+    KeywordVolatile,
+    KeywordRegister,
+    KeywordConstexpr,
+    KeywordExplicit,
+    KeywordTemplate,
+    KeywordTypename,
+    KeywordNamespace,
+    KeywordUsing,
+    KeywordVirtual,
+    KeywordFriend,
+    KeywordOperator,
+    KeywordThis,
+    KeywordDelete,
+    KeywordTry,
+    KeywordCatch,
+    KeywordThrow,
 
     HeaderDefine,
     HeaderInclude,
@@ -123,3 +154,25 @@ pub enum TokenType {
     Null,
     Others,
 }
+
+//Radix base a numeric literal is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Decimal,
+    Hexadecimal,
+    Octal,
+    Binary,
+}
+
+//NumberSuffix type suffix of a numeric literal, u l ul ll ull for integers, f l for floats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberSuffix {
+    None,
+    Unsigned,
+    Long,
+    UnsignedLong,
+    LongLong,
+    UnsignedLongLong,
+    Float,
+    LongDouble,
+}
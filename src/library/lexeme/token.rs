@@ -1,4 +1,4 @@
-use library::lexeme::definition::{TokenKind, TokenType};
+use library::lexeme::definition::{NumberSuffix, Radix, TokenKind, TokenType};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Token {
@@ -7,6 +7,14 @@ pub struct Token {
     token_type: TokenType,
     line_number: u32,
     id: u32,
+    //column of the first character, counted from 1
+    column: u32,
+    //byte range [start, end) of the token in the source text
+    start: usize,
+    end: usize,
+    //base and type suffix of numeric literals
+    radix: Radix,
+    suffix: NumberSuffix,
 }
 
 impl Clone for Token {
@@ -30,6 +38,11 @@ impl Token {
             token_type,
             line_number,
             id,
+            column: 0,
+            start: 0,
+            end: 0,
+            radix: Radix::Decimal,
+            suffix: NumberSuffix::None,
         }
     }
 
@@ -57,6 +70,39 @@ impl Token {
         self.id
     }
 
+    pub fn get_column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    // records where the token was read from in the source text
+    pub fn set_location(&mut self, start: usize, end: usize, column: u32) {
+        self.start = start;
+        self.end = end;
+        self.column = column;
+    }
+
+    pub fn get_radix(&self) -> Radix {
+        self.radix
+    }
+
+    pub fn get_suffix(&self) -> NumberSuffix {
+        self.suffix
+    }
+
+    // records how a numeric literal was written
+    pub fn set_number_format(&mut self, radix: Radix, suffix: NumberSuffix) {
+        self.radix = radix;
+        self.suffix = suffix;
+    }
+
     pub fn set_token_value(&mut self, val: &str) {
         self.token = val.to_string();
     }
@@ -69,7 +115,7 @@ impl Token {
         self.token_kind = typ;
     }
 
-    fn set_token_ln(&mut self, ln: u32) {
+    pub fn set_token_ln(&mut self, ln: u32) {
         self.line_number = ln;
     }
     fn set_token_id(&mut self, id_: u32) {
@@ -108,7 +154,7 @@ mod test {
         );
         assert_eq!(token.get_token_value(), "\'");
 
-        token.set_token_value(&"\"");
+        token.set_token_value("\"");
         assert_eq!(token.get_token_value(), "\"");
     }
 
@@ -171,4 +217,38 @@ mod test {
         token.set_token_id(4);
         assert_eq!(token.get_token_id(), 4);
     }
+
+    #[test]
+    fn test_that_token_location_can_set_and_read_successfully() {
+        let mut token: Token = Token::new(
+            "count".to_string(),
+            TokenKind::Identifiers,
+            TokenType::Identifier,
+            2,
+            5,
+        );
+        assert_eq!(token.get_column(), 0);
+
+        token.set_location(14, 19, 5);
+        assert_eq!(token.get_start(), 14);
+        assert_eq!(token.get_end(), 19);
+        assert_eq!(token.get_column(), 5);
+    }
+
+    #[test]
+    fn test_that_number_format_can_set_and_read_successfully() {
+        let mut token: Token = Token::new(
+            "0x1Fu".to_string(),
+            TokenKind::Values,
+            TokenType::NumberInteger,
+            0,
+            0,
+        );
+        assert_eq!(token.get_radix(), Radix::Decimal);
+        assert_eq!(token.get_suffix(), NumberSuffix::None);
+
+        token.set_number_format(Radix::Hexadecimal, NumberSuffix::Unsigned);
+        assert_eq!(token.get_radix(), Radix::Hexadecimal);
+        assert_eq!(token.get_suffix(), NumberSuffix::Unsigned);
+    }
 }
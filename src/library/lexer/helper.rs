@@ -1,6 +1,6 @@
 use library::lexeme::definition::{TokenKind, TokenType};
 
-pub fn identify_token_type(tok: &Vec<char>) -> (TokenType, TokenKind) {
+pub fn identify_token_type(tok: &[char]) -> (TokenType, TokenKind) {
     let tok_value: String = tok.iter().collect();
     match tok_value.as_str() {
         "auto" => (TokenType::Auto, TokenKind::DataTypes),
@@ -41,6 +41,26 @@ pub fn identify_token_type(tok: &Vec<char>) -> (TokenType, TokenKind) {
         "struct" => (TokenType::KeywordStruct, TokenKind::Keyword),
         "string" => (TokenType::StringValue, TokenKind::DataTypes),
         "NULL" => (TokenType::Null, TokenKind::Keyword),
+        "nullptr" => (TokenType::Null, TokenKind::Keyword),
+        "goto" => (TokenType::KeywordGoto, TokenKind::Keyword),
+        "extern" => (TokenType::KeywordExtern, TokenKind::Modifiers),
+        "inline" => (TokenType::KeywordInline, TokenKind::Modifiers),
+        "volatile" => (TokenType::KeywordVolatile, TokenKind::Keyword),
+        "register" => (TokenType::KeywordRegister, TokenKind::Modifiers),
+        "constexpr" => (TokenType::KeywordConstexpr, TokenKind::Keyword),
+        "explicit" => (TokenType::KeywordExplicit, TokenKind::Modifiers),
+        "template" => (TokenType::KeywordTemplate, TokenKind::Keyword),
+        "typename" => (TokenType::KeywordTypename, TokenKind::Keyword),
+        "namespace" => (TokenType::KeywordNamespace, TokenKind::Keyword),
+        "using" => (TokenType::KeywordUsing, TokenKind::Keyword),
+        "virtual" => (TokenType::KeywordVirtual, TokenKind::Modifiers),
+        "friend" => (TokenType::KeywordFriend, TokenKind::Modifiers),
+        "operator" => (TokenType::KeywordOperator, TokenKind::Keyword),
+        "this" => (TokenType::KeywordThis, TokenKind::Keyword),
+        "delete" => (TokenType::KeywordDelete, TokenKind::Keyword),
+        "try" => (TokenType::KeywordTry, TokenKind::Keyword),
+        "catch" => (TokenType::KeywordCatch, TokenKind::Keyword),
+        "throw" => (TokenType::KeywordThrow, TokenKind::Keyword),
         "#include" => (TokenType::HeaderInclude, TokenKind::Preprocessors),
         "#define" => (TokenType::HeaderDefine, TokenKind::Preprocessors),
         "#ifdef" => (TokenType::HeaderIfDefineStart, TokenKind::Preprocessors),
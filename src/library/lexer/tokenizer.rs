@@ -1,11 +1,82 @@
 #![allow(dead_code)]
 
+use std::fmt;
 use std::str::Chars;
 
-use library::lexeme::definition::{TokenKind, TokenType, BLACK_HOLE};
+use library::lexeme::definition::{NumberSuffix, Radix, TokenKind, TokenType, BLACK_HOLE};
 use library::lexeme::token::Token;
 use library::lexer::helper::*;
 
+//LexErrorKind reason the tokenizer rejected a part of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexErrorKind {
+    UnterminatedString,
+    UnterminatedChar,
+    UnterminatedComment,
+    UnknownCharacter(char),
+    //8 in the octal 08 or 2 in the binary 0b102
+    InvalidDigit(char, Radix),
+    //0x and 0b without digits
+    MissingDigits(Radix),
+    //1.5u, 10q
+    InvalidSuffix,
+}
+
+impl fmt::Display for LexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LexErrorKind::UnterminatedString => write!(f, "missing terminating `\"` character"),
+            LexErrorKind::UnterminatedChar => write!(f, "missing terminating `'` character"),
+            LexErrorKind::UnterminatedComment => write!(f, "unterminated `/*` comment"),
+            LexErrorKind::UnknownCharacter(ch) => {
+                write!(f, "unknown character `{}`", ch.escape_default())
+            }
+            LexErrorKind::InvalidDigit(digit, radix) => {
+                write!(
+                    f,
+                    "invalid digit `{}` in {} literal",
+                    digit,
+                    radix_name(radix)
+                )
+            }
+            LexErrorKind::MissingDigits(radix) => {
+                write!(f, "{} literal has no digits", radix_name(radix))
+            }
+            LexErrorKind::InvalidSuffix => write!(f, "invalid suffix on numeric literal"),
+        }
+    }
+}
+
+fn radix_name(radix: Radix) -> &'static str {
+    match radix {
+        Radix::Decimal => "decimal",
+        Radix::Hexadecimal => "hexadecimal",
+        Radix::Octal => "octal",
+        Radix::Binary => "binary",
+    }
+}
+
+//LexError reason and start of the text the tokenizer failed on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+//errors are collected, so that every one of them is reported at once
+pub type LexResult<T> = Result<T, Vec<LexError>>;
+
 pub struct Tokenizer<'a> {
     line_no: u32,
     id: u32,
@@ -13,8 +84,17 @@ pub struct Tokenizer<'a> {
     current_char: char,
     token: Vec<char>,
     length: usize,
+    //byte offset and column of current_char
+    offset: usize,
+    column: u32,
+    //byte offset of the char after current_char
+    next_offset: usize,
+    //byte offset and column of the first char of the token being read
+    token_start: usize,
+    token_column: u32,
     input: Chars<'a>,
     pub token_buffer: Vec<Token>,
+    errors: Vec<LexError>,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -22,7 +102,7 @@ impl<'a> Tokenizer<'a> {
     // Create object of type Tokenizer
     // and returns it
     //
-    pub fn new(text: &str) -> Tokenizer {
+    pub fn new(text: &str) -> Tokenizer<'_> {
         let token: Vec<char> = Vec::new();
         let token_stream: Vec<Token> = Vec::new();
 
@@ -32,59 +112,58 @@ impl<'a> Tokenizer<'a> {
             id: 0,
             line_no: 1,
             current_char: BLACK_HOLE,
-            length: text.len(),
-            token: token,
+            //position counts chars, not bytes
+            length: text.chars().count(),
+            offset: 0,
+            column: 0,
+            next_offset: 0,
+            token_start: 0,
+            token_column: 0,
+            token,
             token_buffer: token_stream,
             input: text.chars(),
+            errors: Vec::new(),
         };
         self_object
     }
 
     // tokenize
     // function walks over given code text and
-    // returns the stream of tokens
-    // trait bound Clone
-    pub fn tokenize(&mut self) -> Vec<Token> {
+    // returns the stream of tokens, or every
+    // lexical error found in the text
+    pub fn tokenize(&mut self) -> LexResult<Vec<Token>> {
         self.current_char = self.get_next_char();
         loop {
             match self.current_char {
+                '\0' if self.at_end() => break,
+
                 '\n' => {
                     self.line_no += 1;
                     self.current_char = self.get_next_char();
                 }
 
-                ' ' | '\t' => {
+                ' ' | '\t' | '\r' | '\x0b' | '\x0c' => {
                     self.current_char = self.get_next_char();
                 }
 
                 '"' => {
-                    self.push_advance();
-
-                    while self.current_char != '"' {
+                    self.quoted('"');
+                    if self.current_char == '"' {
                         self.push_advance();
-                        if self.current_char == '\\' {
-                            // 	self.token.push('$');
-                            self.push_advance();
-                            self.push_advance();
-                        }
+                        self.push_to_tok_buffer(TokenType::StringValue, TokenKind::Values);
+                    } else {
+                        self.error(LexErrorKind::UnterminatedString);
                     }
-
-                    self.push_advance();
-                    self.push_to_tok_buffer(TokenType::StringValue, TokenKind::Values);
                 }
 
                 '\'' => {
-                    self.push_advance();
-
-                    while self.current_char != '\'' {
-                        if self.current_char == '\\' {
-                            self.push_advance();
-                        }
+                    self.quoted('\'');
+                    if self.current_char == '\'' {
                         self.push_advance();
+                        self.push_to_tok_buffer(TokenType::CharValue, TokenKind::Values);
+                    } else {
+                        self.error(LexErrorKind::UnterminatedChar);
                     }
-
-                    self.push_advance();
-                    self.push_to_tok_buffer(TokenType::CharValue, TokenKind::Values);
                 }
 
                 '{' => {
@@ -122,10 +201,18 @@ impl<'a> Tokenizer<'a> {
                     match self.current_char {
                         '<' => {
                             self.push_advance();
-                            self.push_to_tok_buffer(
-                                TokenType::BitwiseLeftShift,
-                                TokenKind::BinaryOperators,
-                            );
+                            if self.current_char == '=' {
+                                self.push_advance();
+                                self.push_to_tok_buffer(
+                                    TokenType::LeftShiftEqual,
+                                    TokenKind::AssignmentOperators,
+                                );
+                            } else {
+                                self.push_to_tok_buffer(
+                                    TokenType::BitwiseLeftShift,
+                                    TokenKind::BinaryOperators,
+                                );
+                            }
                         }
 
                         '=' => {
@@ -150,10 +237,18 @@ impl<'a> Tokenizer<'a> {
                     match self.current_char {
                         '>' => {
                             self.push_advance();
-                            self.push_to_tok_buffer(
-                                TokenType::BitwiseRightShift,
-                                TokenKind::BinaryOperators,
-                            );
+                            if self.current_char == '=' {
+                                self.push_advance();
+                                self.push_to_tok_buffer(
+                                    TokenType::RightShiftEqual,
+                                    TokenKind::AssignmentOperators,
+                                );
+                            } else {
+                                self.push_to_tok_buffer(
+                                    TokenType::BitwiseRightShift,
+                                    TokenKind::BinaryOperators,
+                                );
+                            }
                         }
 
                         '=' => {
@@ -192,42 +287,32 @@ impl<'a> Tokenizer<'a> {
 
                 '_' | 'a'..='z' | 'A'..='Z' => {
                     self.push_advance();
-                    loop {
-                        match self.current_char {
-                            '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' => {
-                                self.push_advance();
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
+                    while let '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' = self.current_char {
+                        self.push_advance();
                     }
                     let (token_type, base_type) = identify_token_type(&self.token);
                     self.push_to_tok_buffer(token_type, base_type);
                 }
 
                 '0'..='9' => {
-                    self.push_advance();
-                    let mut is_int: bool = true;
+                    self.tokenize_number();
+                }
 
-                    loop {
-                        match self.current_char {
-                            '0'..='9' => {
-                                self.push_advance();
-                            }
-                            '.' => {
-                                self.push_advance();
-                                is_int = false;
-                            }
-                            _ => {
-                                break;
-                            }
-                        };
-                    }
-                    if is_int {
-                        self.push_to_tok_buffer(TokenType::NumberInteger, TokenKind::Values);
+                '.' if self.peek_char(0).is_ascii_digit() => {
+                    self.tokenize_number();
+                }
+
+                '.' => {
+                    self.push_advance();
+                    if self.current_char == '.' && self.peek_char(0) == '.' {
+                        self.push_advance();
+                        self.push_advance();
+                        self.push_to_tok_buffer(TokenType::Ellipsis, TokenKind::SpecialChars);
+                    } else if self.current_char == '*' {
+                        self.push_advance();
+                        self.push_to_tok_buffer(TokenType::DotStar, TokenKind::SpecialChars);
                     } else {
-                        self.push_to_tok_buffer(TokenType::NumberFloat, TokenKind::Values);
+                        self.push_to_tok_buffer(TokenType::Dot, TokenKind::SpecialChars);
                     }
                 }
 
@@ -275,7 +360,15 @@ impl<'a> Tokenizer<'a> {
                         }
                         '>' => {
                             self.push_advance();
-                            self.push_to_tok_buffer(TokenType::Arrow, TokenKind::SpecialChars);
+                            if self.current_char == '*' {
+                                self.push_advance();
+                                self.push_to_tok_buffer(
+                                    TokenType::ArrowStar,
+                                    TokenKind::SpecialChars,
+                                );
+                            } else {
+                                self.push_to_tok_buffer(TokenType::Arrow, TokenKind::SpecialChars);
+                            }
                         }
                         _ => {
                             self.push_to_tok_buffer(TokenType::Minus, TokenKind::BinaryOperators);
@@ -323,9 +416,8 @@ impl<'a> Tokenizer<'a> {
                     self.push_to_tok_buffer(TokenType::BitwiseNegate, TokenKind::UnaryOperators);
                 }
 
-                // could be address or bitwise operator
-                // TODO : how to find address off operator?
-                //      If left side expression is not rvalue then it must be address off,
+                // could be address or bitwise operator,
+                // it is bitwise when it follows an operand
                 '&' => {
                     self.push_advance();
                     match self.current_char {
@@ -336,14 +428,27 @@ impl<'a> Tokenizer<'a> {
                                 TokenKind::BinaryOperators,
                             );
                         }
-                        _ => {
-                            //this is ambiguous resolution, should be validated at the parser side,
-                            //tokenizer has very limited knowledge of the expression
+                        '=' => {
+                            self.push_advance();
+                            self.push_to_tok_buffer(
+                                TokenType::BitwiseAndEqual,
+                                TokenKind::AssignmentOperators,
+                            );
+                        }
+                        //casts such as (int)&x are still resolved by the parser,
+                        //tokenizer has very limited knowledge of the expression
+                        _ if self.follows_operand() => {
                             self.push_to_tok_buffer(
                                 TokenType::BitwiseAnd,
                                 TokenKind::BinaryOperators,
                             );
                         }
+                        _ => {
+                            self.push_to_tok_buffer(
+                                TokenType::AddressOf,
+                                TokenKind::UnaryOperators,
+                            );
+                        }
                     };
                 }
 
@@ -357,6 +462,13 @@ impl<'a> Tokenizer<'a> {
                                 TokenKind::BinaryOperators,
                             );
                         }
+                        '=' => {
+                            self.push_advance();
+                            self.push_to_tok_buffer(
+                                TokenType::BitwiseOrEqual,
+                                TokenKind::AssignmentOperators,
+                            );
+                        }
                         _ => {
                             self.push_to_tok_buffer(
                                 TokenType::BitwiseOr,
@@ -366,6 +478,25 @@ impl<'a> Tokenizer<'a> {
                     };
                 }
 
+                '^' => {
+                    self.push_advance();
+                    match self.current_char {
+                        '=' => {
+                            self.push_advance();
+                            self.push_to_tok_buffer(
+                                TokenType::BitwiseXorEqual,
+                                TokenKind::AssignmentOperators,
+                            );
+                        }
+                        _ => {
+                            self.push_to_tok_buffer(
+                                TokenType::BitwiseXor,
+                                TokenKind::BinaryOperators,
+                            );
+                        }
+                    };
+                }
+
                 '!' => {
                     self.push_advance();
                     match self.current_char {
@@ -395,23 +526,22 @@ impl<'a> Tokenizer<'a> {
                             loop {
                                 self.push_advance();
 
-                                if self.current_char == '\n' {
+                                if self.at_end() {
+                                    self.error(LexErrorKind::UnterminatedComment);
+                                    self.line_no = line_num;
+                                    break;
+                                } else if self.current_char == '\n' {
                                     //count the new lines
                                     line_num += 1;
-                                } else if self.current_char == '*' {
+                                } else if self.current_char == '*' && self.peek_char(0) == '/' {
                                     self.push_advance();
-                                    if self.current_char == '/' {
-                                        self.push_advance();
-                                        self.push_to_tok_buffer(
-                                            TokenType::MultilineComment,
-                                            TokenKind::Comments,
-                                        );
-                                        self.line_no = line_num;
-                                        break;
-                                    } else if self.current_char == '\n' {
-                                        //count the new lines
-                                        line_num += 1;
-                                    }
+                                    self.push_advance();
+                                    self.push_to_tok_buffer(
+                                        TokenType::MultilineComment,
+                                        TokenKind::Comments,
+                                    );
+                                    self.line_no = line_num;
+                                    break;
                                 }
                             }
                         }
@@ -476,15 +606,8 @@ impl<'a> Tokenizer<'a> {
                 }
                 '#' => {
                     self.push_advance();
-                    loop {
-                        match self.current_char {
-                            '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' => {
-                                self.push_advance();
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
+                    while let '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' = self.current_char {
+                        self.push_advance();
                     }
                     let (token_type, base_type) = identify_token_type(&self.token);
 
@@ -494,6 +617,9 @@ impl<'a> Tokenizer<'a> {
                             '\n' => {
                                 break;
                             }
+                            _ if self.at_end() => {
+                                break;
+                            }
                             _ => {
                                 self.push_advance();
                             }
@@ -508,7 +634,7 @@ impl<'a> Tokenizer<'a> {
 
                 _ => {
                     self.push_advance();
-                    self.push_to_tok_buffer(TokenType::Others, TokenKind::None);
+                    self.error(LexErrorKind::UnknownCharacter(self.token[0]));
                 }
             };
 
@@ -517,8 +643,12 @@ impl<'a> Tokenizer<'a> {
             }
         } //loop
 
-        // return the stream clone to struct internal object
-        self.token_buffer.clone()
+        if self.errors.is_empty() {
+            // return the stream clone to struct internal object
+            Ok(self.token_buffer.clone())
+        } else {
+            Err(self.errors.clone())
+        }
     }
 
     // get_next_token:
@@ -527,10 +657,180 @@ impl<'a> Tokenizer<'a> {
     //
     fn get_next_char(&mut self) -> char {
         self.position += 1;
-        if let Some(ch) = self.input.next() {
-            ch
+        if self.current_char == '\n' {
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        let next = self.input.next();
+        self.offset = self.next_offset;
+        self.next_offset += next.map_or(0, |ch| ch.len_utf8());
+        next.unwrap_or('\0')
+    }
+
+    // follows_operand:
+    // true when the last token ends an operand, such as a name,
+    // a literal or a closing bracket, comments are skipped
+    //
+    fn follows_operand(&self) -> bool {
+        let last = self
+            .token_buffer
+            .iter()
+            .rev()
+            .find(|token| token.get_token_kind() != TokenKind::Comments);
+        matches!(
+            last.map(|token| token.get_token_type()),
+            Some(TokenType::Identifier)
+                | Some(TokenType::Main)
+                | Some(TokenType::NumberInteger)
+                | Some(TokenType::NumberFloat)
+                | Some(TokenType::CharValue)
+                | Some(TokenType::StringValue)
+                | Some(TokenType::True)
+                | Some(TokenType::False)
+                | Some(TokenType::Null)
+                | Some(TokenType::RightBracket)
+                | Some(TokenType::RightSquareBracket)
+                | Some(TokenType::Increment)
+                | Some(TokenType::Decrement)
+        )
+    }
+
+    // at_end:
+    // true once every char of the input is read
+    //
+    fn at_end(&self) -> bool {
+        self.position > self.length
+    }
+
+    // quoted:
+    // reads a string or char literal up to its closing quote,
+    // stopping at the end of the line when the quote is missing
+    //
+    fn quoted(&mut self, quote: char) {
+        self.push_advance();
+        while self.current_char != quote && self.current_char != '\n' && !self.at_end() {
+            if self.current_char == '\\' {
+                self.push_advance();
+            }
+            self.push_advance();
+        }
+    }
+
+    // error:
+    // records a lexical error at the start of the token being read,
+    // the token is dropped and reading goes on after it
+    //
+    fn error(&mut self, kind: LexErrorKind) {
+        self.errors.push(LexError {
+            kind,
+            line: self.line_no,
+            column: self.token_column,
+        });
+        self.token.clear();
+    }
+
+    // peek_char:
+    // returns the char n places after the current one
+    // without consuming anything
+    //
+    fn peek_char(&self, n: usize) -> char {
+        self.input.clone().nth(n).unwrap_or('\0')
+    }
+
+    // tokenize_number:
+    // reads an integer or floating literal such as 0x1F, 0b101, 017,
+    // 1'000, 1e-9, .5f or 10UL and records its radix and suffix
+    //
+    fn tokenize_number(&mut self) {
+        let mut radix = Radix::Decimal;
+        let mut is_int = true;
+        if self.current_char == '0' {
+            match self.peek_char(0) {
+                'x' | 'X' => radix = Radix::Hexadecimal,
+                'b' | 'B' => radix = Radix::Binary,
+                _ => {}
+            }
+            if radix != Radix::Decimal {
+                self.push_advance();
+                self.push_advance();
+            }
+        }
+
+        loop {
+            match self.current_char {
+                '0'..='9' => self.push_advance(),
+                'a'..='f' | 'A'..='F' if radix == Radix::Hexadecimal => self.push_advance(),
+                //digit separator 1'000
+                '\'' if self.peek_char(0).is_ascii_hexdigit() => self.push_advance(),
+                '.' if radix == Radix::Decimal => {
+                    is_int = false;
+                    self.push_advance();
+                }
+                'e' | 'E' if radix == Radix::Decimal => {
+                    let sign = matches!(self.peek_char(0), '+' | '-');
+                    if !self.peek_char(sign as usize).is_ascii_digit() {
+                        break;
+                    }
+                    is_int = false;
+                    self.push_advance();
+                    if sign {
+                        self.push_advance();
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let digits = self.token.len();
+        //the suffix runs on to the end of the word, 10UL or the invalid 10q
+        let mut suffix = String::new();
+        while self.current_char.is_ascii_alphanumeric() || self.current_char == '_' {
+            suffix.push(self.current_char.to_ascii_lowercase());
+            self.push_advance();
+        }
+        let suffix = match (is_int, suffix.as_str()) {
+            (_, "") => NumberSuffix::None,
+            (true, "u") => NumberSuffix::Unsigned,
+            (true, "l") => NumberSuffix::Long,
+            (true, "ul") | (true, "lu") => NumberSuffix::UnsignedLong,
+            (true, "ll") => NumberSuffix::LongLong,
+            (true, "ull") | (true, "llu") => NumberSuffix::UnsignedLongLong,
+            (false, "f") => NumberSuffix::Float,
+            (false, "l") => NumberSuffix::LongDouble,
+            _ => return self.error(LexErrorKind::InvalidSuffix),
+        };
+        //leading zero makes an integer octal, 017
+        if radix == Radix::Decimal && is_int && digits > 1 && self.token[0] == '0' {
+            radix = Radix::Octal;
+        }
+        let prefix = match radix {
+            Radix::Hexadecimal | Radix::Binary => 2,
+            _ => 0,
+        };
+        if digits == prefix {
+            return self.error(LexErrorKind::MissingDigits(radix));
+        }
+        let invalid = self.token[prefix..digits]
+            .iter()
+            .find(|digit| match radix {
+                Radix::Binary => matches!(digit, '2'..='9'),
+                Radix::Octal => matches!(digit, '8' | '9'),
+                _ => false,
+            })
+            .cloned();
+        if let Some(digit) = invalid {
+            return self.error(LexErrorKind::InvalidDigit(digit, radix));
+        }
+
+        let token_type = if is_int {
+            TokenType::NumberInteger
         } else {
-            '\0'
+            TokenType::NumberFloat
+        };
+        self.push_to_tok_buffer(token_type, TokenKind::Values);
+        if let Some(token) = self.token_buffer.last_mut() {
+            token.set_number_format(radix, suffix);
         }
     }
 
@@ -539,7 +839,8 @@ impl<'a> Tokenizer<'a> {
     fn push_to_tok_buffer(&mut self, token_type: TokenType, token_kind: TokenKind) {
         let token: String = self.token.iter().cloned().collect();
         if !token.is_empty() {
-            let token = Token::new(token, token_kind, token_type, self.line_no, self.id);
+            let mut token = Token::new(token, token_kind, token_type, self.line_no, self.id);
+            token.set_location(self.token_start, self.offset, self.token_column);
             self.token_buffer.push(token);
             self.id += 1;
         }
@@ -551,6 +852,10 @@ impl<'a> Tokenizer<'a> {
     // gets next char and stores it in self.current_char
     //
     fn push_advance(&mut self) {
+        if self.token.is_empty() {
+            self.token_start = self.offset;
+            self.token_column = self.column;
+        }
         self.token.push(self.current_char);
         self.current_char = self.get_next_char();
     }
@@ -582,9 +887,9 @@ mod test {
     use std::io::BufReader;
     use std::io::Read;
 
-    use library::lexeme::definition::{TokenKind, TokenType};
+    use library::lexeme::definition::{NumberSuffix, Radix, TokenKind, TokenType};
     use library::lexeme::token::Token;
-    use library::lexer::tokenizer::Tokenizer;
+    use library::lexer::tokenizer::{LexError, LexErrorKind, Tokenizer};
 
     fn read_file(path: &str) -> String {
         let file = match File::open(path) {
@@ -599,9 +904,17 @@ mod test {
         text
     }
 
+    // tokens with the source location cleared, expected tokens are built without one
+    fn without_location(mut tokens: Vec<Token>) -> Vec<Token> {
+        for token in &mut tokens {
+            token.set_location(0, 0, 0);
+        }
+        tokens
+    }
+
     #[test]
     fn test_get_next_char() {
-        let get_next_char = |x: &str| Tokenizer::new(&x).get_next_char();
+        let get_next_char = |x: &str| Tokenizer::new(x).get_next_char();
 
         assert_eq!('\0', get_next_char(""));
         assert_eq!(' ', get_next_char(" "));
@@ -840,8 +1153,148 @@ mod test {
                 20,
                 19,
             ),
+            Token::new(
+                String::from("nullptr"),
+                TokenKind::Keyword,
+                TokenType::Null,
+                21,
+                20,
+            ),
+            Token::new(
+                String::from("goto"),
+                TokenKind::Keyword,
+                TokenType::KeywordGoto,
+                22,
+                21,
+            ),
+            Token::new(
+                String::from("extern"),
+                TokenKind::Modifiers,
+                TokenType::KeywordExtern,
+                23,
+                22,
+            ),
+            Token::new(
+                String::from("inline"),
+                TokenKind::Modifiers,
+                TokenType::KeywordInline,
+                24,
+                23,
+            ),
+            Token::new(
+                String::from("volatile"),
+                TokenKind::Keyword,
+                TokenType::KeywordVolatile,
+                25,
+                24,
+            ),
+            Token::new(
+                String::from("register"),
+                TokenKind::Modifiers,
+                TokenType::KeywordRegister,
+                26,
+                25,
+            ),
+            Token::new(
+                String::from("constexpr"),
+                TokenKind::Keyword,
+                TokenType::KeywordConstexpr,
+                27,
+                26,
+            ),
+            Token::new(
+                String::from("explicit"),
+                TokenKind::Modifiers,
+                TokenType::KeywordExplicit,
+                28,
+                27,
+            ),
+            Token::new(
+                String::from("template"),
+                TokenKind::Keyword,
+                TokenType::KeywordTemplate,
+                29,
+                28,
+            ),
+            Token::new(
+                String::from("typename"),
+                TokenKind::Keyword,
+                TokenType::KeywordTypename,
+                30,
+                29,
+            ),
+            Token::new(
+                String::from("namespace"),
+                TokenKind::Keyword,
+                TokenType::KeywordNamespace,
+                31,
+                30,
+            ),
+            Token::new(
+                String::from("using"),
+                TokenKind::Keyword,
+                TokenType::KeywordUsing,
+                32,
+                31,
+            ),
+            Token::new(
+                String::from("virtual"),
+                TokenKind::Modifiers,
+                TokenType::KeywordVirtual,
+                33,
+                32,
+            ),
+            Token::new(
+                String::from("friend"),
+                TokenKind::Modifiers,
+                TokenType::KeywordFriend,
+                34,
+                33,
+            ),
+            Token::new(
+                String::from("operator"),
+                TokenKind::Keyword,
+                TokenType::KeywordOperator,
+                35,
+                34,
+            ),
+            Token::new(
+                String::from("this"),
+                TokenKind::Keyword,
+                TokenType::KeywordThis,
+                36,
+                35,
+            ),
+            Token::new(
+                String::from("delete"),
+                TokenKind::Keyword,
+                TokenType::KeywordDelete,
+                37,
+                36,
+            ),
+            Token::new(
+                String::from("try"),
+                TokenKind::Keyword,
+                TokenType::KeywordTry,
+                38,
+                37,
+            ),
+            Token::new(
+                String::from("catch"),
+                TokenKind::Keyword,
+                TokenType::KeywordCatch,
+                39,
+                38,
+            ),
+            Token::new(
+                String::from("throw"),
+                TokenKind::Keyword,
+                TokenType::KeywordThrow,
+                40,
+                39,
+            ),
         ];
-        assert_eq!(expected, tok.tokenize());
+        assert_eq!(expected, without_location(tok.tokenize().unwrap()));
     }
 
     #[test]
@@ -913,14 +1366,14 @@ mod test {
                 8,
             ),
         ];
-        assert_eq!(expected, tok.tokenize());
+        assert_eq!(expected, without_location(tok.tokenize().unwrap()));
     }
 
     #[test]
     fn test_tokenize_comments() {
         let text = read_file("src/test/resources/tokenize_comments.cpp");
         let mut tok = Tokenizer::new(&text);
-        let expected = vec![
+        let expected = [
             Token::new(
                 String::from("//Hello World"),
                 TokenKind::Comments,
@@ -943,7 +1396,7 @@ mod test {
                 2,
             ),
         ];
-        let result = tok.tokenize();
+        let result = without_location(tok.tokenize().unwrap());
         let mut index: usize = 0;
         while index < expected.len() {
             assert_eq!(
@@ -1081,8 +1534,8 @@ mod test {
             ),
             Token::new(
                 String::from("&"),
-                TokenKind::BinaryOperators,
-                TokenType::BitwiseAnd,
+                TokenKind::UnaryOperators,
+                TokenType::AddressOf,
                 18,
                 17,
             ),
@@ -1163,8 +1616,78 @@ mod test {
                 29,
                 28,
             ),
+            Token::new(
+                String::from("^"),
+                TokenKind::BinaryOperators,
+                TokenType::BitwiseXor,
+                30,
+                29,
+            ),
+            Token::new(
+                String::from("^="),
+                TokenKind::AssignmentOperators,
+                TokenType::BitwiseXorEqual,
+                31,
+                30,
+            ),
+            Token::new(
+                String::from("<<="),
+                TokenKind::AssignmentOperators,
+                TokenType::LeftShiftEqual,
+                32,
+                31,
+            ),
+            Token::new(
+                String::from(">>="),
+                TokenKind::AssignmentOperators,
+                TokenType::RightShiftEqual,
+                33,
+                32,
+            ),
+            Token::new(
+                String::from("&="),
+                TokenKind::AssignmentOperators,
+                TokenType::BitwiseAndEqual,
+                34,
+                33,
+            ),
+            Token::new(
+                String::from("|="),
+                TokenKind::AssignmentOperators,
+                TokenType::BitwiseOrEqual,
+                35,
+                34,
+            ),
+            Token::new(
+                String::from("."),
+                TokenKind::SpecialChars,
+                TokenType::Dot,
+                36,
+                35,
+            ),
+            Token::new(
+                String::from(".*"),
+                TokenKind::SpecialChars,
+                TokenType::DotStar,
+                37,
+                36,
+            ),
+            Token::new(
+                String::from("->*"),
+                TokenKind::SpecialChars,
+                TokenType::ArrowStar,
+                38,
+                37,
+            ),
+            Token::new(
+                String::from("..."),
+                TokenKind::SpecialChars,
+                TokenType::Ellipsis,
+                39,
+                38,
+            ),
         ];
-        let result = tok.tokenize();
+        let result = without_location(tok.tokenize().unwrap());
         let mut index = 0;
         while index < expected.len() {
             assert_eq!(
@@ -1245,7 +1768,7 @@ mod test {
                 8,
             ),
         ];
-        let result = tok.tokenize();
+        let result = without_location(tok.tokenize().unwrap());
         let mut index = 0;
         while index < expected.len() {
             assert_eq!(
@@ -1279,7 +1802,7 @@ mod test {
                        6),
             Token::new(String::from("true"), TokenKind::Values, TokenType::True, 8, 7),
             Token::new(String::from("false"), TokenKind::Values, TokenType::False, 9, 8)];
-        let result = tok.tokenize();
+        let result = without_location(tok.tokenize().unwrap());
         let mut index = 0;
         while index < expected.len() {
             assert_eq!(
@@ -1291,11 +1814,211 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_tokenize_numbers() {
+        let mut tok =
+            Tokenizer::new("0x1Fu 0b1010 017 0 1'000 1e-9 2.5E+3f .5 10UL 7llu 3.0L 1.e5\n");
+        let expected = [
+            (
+                "0x1Fu",
+                TokenType::NumberInteger,
+                Radix::Hexadecimal,
+                NumberSuffix::Unsigned,
+            ),
+            (
+                "0b1010",
+                TokenType::NumberInteger,
+                Radix::Binary,
+                NumberSuffix::None,
+            ),
+            (
+                "017",
+                TokenType::NumberInteger,
+                Radix::Octal,
+                NumberSuffix::None,
+            ),
+            (
+                "0",
+                TokenType::NumberInteger,
+                Radix::Decimal,
+                NumberSuffix::None,
+            ),
+            (
+                "1'000",
+                TokenType::NumberInteger,
+                Radix::Decimal,
+                NumberSuffix::None,
+            ),
+            (
+                "1e-9",
+                TokenType::NumberFloat,
+                Radix::Decimal,
+                NumberSuffix::None,
+            ),
+            (
+                "2.5E+3f",
+                TokenType::NumberFloat,
+                Radix::Decimal,
+                NumberSuffix::Float,
+            ),
+            (
+                ".5",
+                TokenType::NumberFloat,
+                Radix::Decimal,
+                NumberSuffix::None,
+            ),
+            (
+                "10UL",
+                TokenType::NumberInteger,
+                Radix::Decimal,
+                NumberSuffix::UnsignedLong,
+            ),
+            (
+                "7llu",
+                TokenType::NumberInteger,
+                Radix::Decimal,
+                NumberSuffix::UnsignedLongLong,
+            ),
+            (
+                "3.0L",
+                TokenType::NumberFloat,
+                Radix::Decimal,
+                NumberSuffix::LongDouble,
+            ),
+            (
+                "1.e5",
+                TokenType::NumberFloat,
+                Radix::Decimal,
+                NumberSuffix::None,
+            ),
+        ];
+        let result = tok.tokenize().unwrap();
+        assert_eq!(result.len(), expected.len());
+        for (token, &(value, token_type, radix, suffix)) in result.iter().zip(expected.iter()) {
+            assert_eq!(token.get_token_value(), value);
+            assert_eq!(token.get_token_type(), token_type, "{}", value);
+            assert_eq!(token.get_radix(), radix, "{}", value);
+            assert_eq!(token.get_suffix(), suffix, "{}", value);
+        }
+
+        let errors = Tokenizer::new("08 0x; 0b102 1.5u 10q 3f\n")
+            .tokenize()
+            .unwrap_err();
+        let error = |kind, column| LexError {
+            kind,
+            line: 1,
+            column,
+        };
+        assert_eq!(
+            errors,
+            vec![
+                error(LexErrorKind::InvalidDigit('8', Radix::Octal), 1),
+                error(LexErrorKind::MissingDigits(Radix::Hexadecimal), 4),
+                error(LexErrorKind::InvalidDigit('2', Radix::Binary), 8),
+                error(LexErrorKind::InvalidSuffix, 14),
+                error(LexErrorKind::InvalidSuffix, 19),
+                error(LexErrorKind::InvalidSuffix, 23),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "line 1, column 1: invalid digit `8` in octal literal"
+        );
+    }
+
+    #[test]
+    fn test_address_of_or_bitwise_and() {
+        let text = "x = a & b; p = &a; q = f() & (c) & 1; r = s[0] & &t; // c &\nm = &u;\n";
+        let result = Tokenizer::new(text).tokenize().unwrap();
+        let types: Vec<TokenType> = result
+            .iter()
+            .filter(|token| token.get_token_value() == "&")
+            .map(|token| token.get_token_type())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::BitwiseAnd,
+                TokenType::AddressOf,
+                TokenType::BitwiseAnd,
+                TokenType::BitwiseAnd,
+                TokenType::BitwiseAnd,
+                TokenType::AddressOf,
+                TokenType::AddressOf,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexical_errors() {
+        let text = "char *s = \"open;\nint a @ 1;\nchar c = 'x;\nint b;\n/* never closed\nint c;";
+        let errors = Tokenizer::new(text).tokenize().unwrap_err();
+        let error = |kind, line, column| LexError { kind, line, column };
+        assert_eq!(
+            errors,
+            vec![
+                error(LexErrorKind::UnterminatedString, 1, 11),
+                error(LexErrorKind::UnknownCharacter('@'), 2, 7),
+                error(LexErrorKind::UnterminatedChar, 3, 10),
+                error(LexErrorKind::UnterminatedComment, 5, 1),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "line 1, column 11: missing terminating `\"` character"
+        );
+    }
+
+    #[test]
+    fn test_escaped_quotes_and_comment_ends() {
+        let text = "s = \"\\\"\"; c = '\\''; /* a **/ x";
+        let result = Tokenizer::new(text).tokenize().unwrap();
+        let values: Vec<String> = result.iter().map(|token| token.get_token_value()).collect();
+        assert_eq!(
+            values,
+            vec!["s", "=", "\"\\\"\"", ";", "c", "=", "'\\''", ";", "/* a **/", "x"]
+        );
+        assert!(Tokenizer::new("").tokenize().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_token_locations() {
+        let text = "int a;\n  s = \"é\"; // note\n\tx+=10;\n";
+        let result = Tokenizer::new(text).tokenize().unwrap();
+        let expected = [
+            ("int", 1, 1, 0, 3),
+            ("a", 1, 5, 4, 5),
+            (";", 1, 6, 5, 6),
+            ("s", 2, 3, 9, 10),
+            ("=", 2, 5, 11, 12),
+            ("\"é\"", 2, 7, 13, 17),
+            (";", 2, 10, 17, 18),
+            ("// note", 2, 12, 19, 26),
+            ("x", 3, 2, 28, 29),
+            ("+=", 3, 3, 29, 31),
+            ("10", 3, 5, 31, 33),
+            (";", 3, 7, 33, 34),
+        ];
+        assert_eq!(result.len(), expected.len());
+        for (token, &(value, line, column, start, end)) in result.iter().zip(expected.iter()) {
+            assert_eq!(token.get_token_value(), value);
+            assert_eq!(token.get_token_line_num(), line, "{}", value);
+            assert_eq!(token.get_column(), column, "{}", value);
+            assert_eq!(
+                (token.get_start(), token.get_end()),
+                (start, end),
+                "{}",
+                value
+            );
+            assert_eq!(&text[start..end], value);
+        }
+    }
+
     #[test]
     fn test_tokenize_ids() {
         let text = read_file("src/test/resources/tokenize_ids.cpp");
         let mut tok = Tokenizer::new(&text);
-        let expected = vec![
+        let expected = [
             Token::new(
                 String::from("_"),
                 TokenKind::Identifiers,
@@ -1332,7 +2055,7 @@ mod test {
                 4,
             ),
         ];
-        let result = tok.tokenize();
+        let result = without_location(tok.tokenize().unwrap());
 
         let mut index = 0;
         while index < expected.len() {
@@ -1,4 +1,7 @@
+pub mod ast;
+pub mod codegen;
 pub mod doc;
 pub mod lexeme;
 pub mod lexer;
 pub mod parser;
+pub mod preprocessor;
@@ -1,6 +1,5 @@
-mod helper;
+#[allow(clippy::module_inception)]
 pub mod parser;
-mod rust_type;
 
 #[cfg(test)]
 mod parser_test;
@@ -1,2203 +1,2691 @@
 #![allow(dead_code)]
 
-use library::doc::DocType::*;
+use std::fmt;
+
+use library::ast;
+use library::ast::*;
+use library::codegen::standard::{is_std_template, std_names, std_type};
 use library::lexeme::definition::TokenType::*;
-use library::lexeme::definition::{TokenKind, TokenType};
+use library::lexeme::definition::{Radix, TokenKind, TokenType};
 use library::lexeme::token::Token;
-use library::parser::helper::*;
-use library::parser::rust_type::*;
-
-#[derive(Debug)]
-struct SymbolTable {
-    symbol_type: TokenType,
-    symbol_modifier: Modifier,
-    id_name: String,
-    is_assigned: bool,
-    is_ptr: bool,
-    assigned_val: String,
-}
+use library::lexer::tokenizer::Tokenizer;
 
-#[derive(Debug)]
-struct CStructMem {
-    name: String,
-    member_type: TokenType,
-    identifier: String,
+//ParseError reason and location of the construct the parser failed on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
 }
 
-impl Clone for SymbolTable {
-    fn clone(&self) -> SymbolTable {
-        let id = self.id_name.clone();
-        let val = self.assigned_val.clone();
-        SymbolTable {
-            assigned_val: val,
-            id_name: id,
-            symbol_modifier: self.symbol_modifier,
-            symbol_type: self.symbol_type,
-            is_ptr: self.is_ptr,
-            is_assigned: self.is_assigned,
-        }
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.span.line, self.span.column, self.message
+        )
     }
 }
 
-impl Clone for CStructMem {
-    fn clone(&self) -> CStructMem {
-        CStructMem {
-            name: self.name.clone(),
-            member_type: self.member_type,
-            identifier: self.identifier.clone(),
+pub type ParseResult<T> = Result<T, ParseError>;
+
+//Specifiers type and storage class read before a declarator
+struct Specifiers {
+    ty: CType,
+    is_static: bool,
+    is_const: bool,
+    is_extern: bool,
+    is_volatile: bool,
+    is_inline: bool,
+    is_virtual: bool,
+}
+
+impl Specifiers {
+    // type without storage class or qualifiers
+    fn of(ty: CType) -> Specifiers {
+        Specifiers {
+            ty,
+            is_static: false,
+            is_const: false,
+            is_extern: false,
+            is_volatile: false,
+            is_inline: false,
+            is_virtual: false,
         }
     }
 }
 
-struct Parser {
-    from: usize,
-    //for symbol table
-    once_warned: bool,
-    //default false
-    in_block_stmnt: bool,
-    //default false
-    in_expr: bool,
-    //default false
-    in_switch: bool,
-    //defalt false
-    strict: bool,
-    //default true
-    in_main: bool,
-    sym_tab: Vec<SymbolTable>,
-    // structure book keeping
-    struct_mem: Vec<CStructMem>,
-    typde_def_table: Vec<String>,
-    struct_in_body_declaration: bool,
+pub struct Parser<'a> {
+    lexeme: &'a [Token],
+    head: usize,
+    //index of the last consumed token, used to close spans
+    last: usize,
+    //names introduced by typedef, struct, union, class and enum
+    type_names: Vec<String>,
+    //names of function and class templates, max<int> and Stack<int> take arguments
+    templates: Vec<String>,
+    //`>>` closed two template argument lists, the outer one is closed already
+    closed_twice: bool,
+    //inside extern "C" { }, its prototypes are linked from other files
+    in_extern: bool,
+    //macros translated so far, in definition order
+    macros: Vec<MacroDef>,
+    //errors recovered from, their regions are kept as Unsupported nodes
+    errors: Vec<ParseError>,
 }
 
-pub fn init_parser(lexeme: &Vec<Token>, strict_parser: bool) -> Vec<String> {
-    let mut stream: Vec<String> = Vec::new();
-    stream.push(CRUST.get_doc().to_string());
-
-    let mut parser = Parser {
-        from: 0,
-        once_warned: false,
-        in_block_stmnt: false,
-        in_expr: false,
-        in_switch: false,
-        strict: strict_parser,
-        in_main: false,
-        sym_tab: Vec::new(),
-        struct_mem: Vec::new(),
-        typde_def_table: Vec::new(),
-        struct_in_body_declaration: false,
-    };
-    stream.append(&mut parser.parse_program(&lexeme));
-    stream
+/**
+ * init_parser:
+ * parses the token stream into a translation unit, along with the
+ * errors of the regions that could not be parsed
+ */
+pub fn init_parser(lexeme: &[Token]) -> (TranslationUnit, Vec<ParseError>) {
+    let mut parser = Parser::new(lexeme);
+    let unit = parser.parse_program();
+    (unit, parser.errors)
 }
 
-impl Parser {
-    fn parse_program(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        let mut lookahead: usize;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while head < lexeme.len() {
-            lookahead = head;
-            //match over token kind and token type
-            match lexeme[head].get_type() {
-                (TokenKind::Typedef, Typedef) => {
-                    //typedef STRUCT struct_t or
-                    //typedef struct typename {struct_def} new_type_name;
-                    if lexeme[head + 1].get_token_type() == KeywordStruct {
-                        //read in struct definition
-                        while lexeme[head].get_token_type() != RightCurlyBrace {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-                    }
-                    while lexeme[head].get_token_type() != Semicolon {
-                        temp_lexeme.push(lexeme[head].clone());
-                        head += 1;
-                    }
-                    temp_lexeme.push(lexeme[head].clone());
-                    stream.append(&mut self.parse_typdef(&temp_lexeme));
-                    head += 1;
-                    temp_lexeme.clear();
-                }
-                // matches any datatype
-                (TokenKind::DataTypes, _) => {
-                    //if token is modifiers , move lookahead pointer to next lexeme
-                    if lexeme[head].get_token_type() == Signed
-                        || lexeme[head].get_token_type() == Unsigned
-                    {
-                        lookahead += 1;
-                    }
-                    //To see whats after the given identifier
-                    //ex : int a = 0; int a;
-                    //     int foo(){}
-                    lookahead += 2;
-                    match lexeme[lookahead].get_token_type() {
-                        // function declaration
-                        LeftBracket => {
-                            //inside the function
-                            self.in_block_stmnt = true;
-                            //move till end of function argument declaration
-                            while lexeme[lookahead].get_token_type() != RightBracket {
-                                lookahead += 1;
-                            }
-                            //move ahead of )
-                            lookahead += 1;
-
-                            // skip function body declaration
-                            if lexeme[lookahead].get_token_type() != LeftCurlyBrace {
-                                lookahead += 1;
-                                head = lookahead;
-                                //FIXME : Why is continue here ?
-                                //continue; ??
-                            }
-                            // advance lookahead to end of block
-                            lookahead = skip_block(&lexeme, lookahead + 1);
-                            // collect entire function block
-                            while head < lookahead {
-                                let l: Token = lexeme[head].clone();
-                                temp_lexeme.push(l);
-                                head += 1;
-                            }
-
-                            //parse function block
-                            stream.append(&mut self.parse_function(&temp_lexeme));
-                            temp_lexeme.clear();
+impl<'a> Parser<'a> {
+    pub fn new(lexeme: &'a [Token]) -> Parser<'a> {
+        Parser {
+            lexeme,
+            head: 0,
+            last: 0,
+            type_names: Vec::new(),
+            templates: Vec::new(),
+            closed_twice: false,
+            in_extern: false,
+            macros: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
 
-                            self.in_block_stmnt = false;
-                        }
+    pub fn parse_program(&mut self) -> TranslationUnit {
+        let mut items: Vec<Item> = Vec::new();
+        while self.head < self.lexeme.len() {
+            self.parse_item_or_recover(&mut items);
+        }
+        TranslationUnit { items }
+    }
 
-                        //array declaration found
-                        LeftSquareBracket => {
-                            lookahead = skip_stmt(&lexeme, lookahead);
+    /**
+     * print_lexemes: DEBUG_ONLY
+     * prints the lexemes in the lexeme vector
+     * from index start to end
+     */
+    fn print_lexemes(lexeme: &[Token], start: usize, end: usize) {
+        println!("----------lexeme-start------------");
+        for (i, token) in lexeme.iter().enumerate().take(end).skip(start) {
+            println!(
+                "Line Num {} , {}> {:?}",
+                token.get_token_line_num(),
+                i,
+                token
+            );
+        }
+        println!("----------lexeme-end------------");
+    }
 
-                            // collect variable declaration
-                            while head != lookahead {
-                                let l: Token = lexeme[head].clone();
-                                temp_lexeme.push(l);
-                                head += 1;
-                            }
-                            // parse declaration
-                            stream.append(&mut self.parse_array_declaration(&temp_lexeme));
-                            temp_lexeme.clear();
-                        }
+    /*-------------------------- token cursor --------------------------*/
 
-                        // variable declaration or declaration + assignment
-                        Semicolon | Comma | Assignment => {
-                            lookahead = skip_stmt(&lexeme, lookahead);
+    // comment token at the head, comments are only kept at item and statement level
+    fn raw_comment(&self) -> Option<String> {
+        match self.lexeme.get(self.head) {
+            Some(token) if token.get_token_kind() == TokenKind::Comments => {
+                Some(token.get_token_value())
+            }
+            _ => None,
+        }
+    }
 
-                            // collect variable declaration
-                            while head != lookahead {
-                                let l: Token = lexeme[head].clone();
-                                temp_lexeme.push(l);
-                                head += 1;
-                            }
-                            // parse declaration
-                            stream.append(&mut self.parse_declaration(&temp_lexeme, false));
-                            temp_lexeme.clear();
-                        }
-                        Identifier => {
-                            //in case of pointer declaration : int *a;
-                            while lexeme[head].get_token_type() != Semicolon {
-                                temp_lexeme.push(lexeme[head].clone());
-                                head += 1;
-                            }
-                            temp_lexeme.push(lexeme[head].clone());
-                            stream.append(&mut self.parse_declaration(&temp_lexeme, false));
-                            head += 1;
-                            temp_lexeme.clear();
-                        }
+    // n-th token from the head, skipping comments
+    fn peek_nth(&self, n: usize) -> Option<&'a Token> {
+        let lexeme = self.lexeme;
+        lexeme[self.head.min(lexeme.len())..]
+            .iter()
+            .filter(|token| token.get_token_kind() != TokenKind::Comments)
+            .nth(n)
+    }
 
-                        _ => {}
-                    };
-                }
+    fn peek(&self) -> Option<&'a Token> {
+        self.peek_nth(0)
+    }
 
-                // matches if statement
-                (TokenKind::Keyword, KeywordIf) => {
-                    // let mut temp_lexeme: Vec<Token> = Vec::new();
+    fn peek_type(&self) -> Option<TokenType> {
+        self.peek().map(|token| token.get_token_type())
+    }
 
-                    // move lookahead past conditon
-                    while lexeme[lookahead].get_token_type() != RightBracket {
-                        lookahead += 1;
-                    }
-                    lookahead += 1;
+    fn check(&self, token_type: TokenType) -> bool {
+        self.check_nth(0, token_type)
+    }
 
-                    // move lookahead past block
-                    if lexeme[lookahead].get_token_type() == LeftCurlyBrace {
-                        lookahead = skip_block(&lexeme, lookahead + 1);
-                    }
-                    // move lookahead past block for 'if' without braces
-                    else {
-                        lookahead = skip_stmt(&lexeme, lookahead);
-                    }
-                    // collect if block
-                    while head < lookahead {
-                        let l: Token = lexeme[head].clone();
-                        temp_lexeme.push(l);
-                        head += 1;
-                    }
+    fn check_nth(&self, n: usize, token_type: TokenType) -> bool {
+        self.peek_nth(n)
+            .map(|token| token.get_token_type() == token_type)
+            .unwrap_or(false)
+    }
 
-                    // parse if
-                    stream.append(&mut self.parse_if(&temp_lexeme));
-                    temp_lexeme.clear();
-                }
+    fn at_end(&self) -> bool {
+        self.peek().is_none()
+    }
 
-                (TokenKind::Keyword, KeywordElse) => {
-                    stream.push("else".to_string());
-                    head += 1;
-                    lookahead = head;
-                    if lexeme[head].get_token_type() == KeywordIf {
-                        continue;
-                    } else {
-                        if lexeme[lookahead].get_token_type() == LeftCurlyBrace {
-                            head += 1;
-                            lookahead = skip_block(&lexeme, head) - 1;
-                        } else {
-                            lookahead = skip_stmt(&lexeme, lookahead);
-                        }
+    // consumes the next non comment token
+    fn bump(&mut self) -> ParseResult<Token> {
+        while let Some(token) = self.lexeme.get(self.head) {
+            if token.get_token_kind() != TokenKind::Comments {
+                self.last = self.head;
+                self.head += 1;
+                return Ok(token.clone());
+            }
+            self.head += 1;
+        }
+        self.error("unexpected end of input")
+    }
 
-                        while head < lookahead {
-                            let l: Token = lexeme[head].clone();
-                            temp_lexeme.push(l);
-                            head += 1;
-                        }
-                        //** parse else body
-                        stream.push("{".to_string());
-                        stream.append(&mut self.parse_program(&temp_lexeme));
-                        temp_lexeme.clear();
-                        stream.push("}".to_string());
-                    }
-                }
+    fn eat(&mut self, token_type: TokenType) -> bool {
+        self.check(token_type) && self.bump().is_ok()
+    }
 
-                (TokenKind::Keyword, KeywordSwitch) => {
-                    while lexeme[lookahead].get_token_type() != LeftCurlyBrace {
-                        lookahead += 1;
-                    }
-                    lookahead += 1;
+    fn expect(&mut self, token_type: TokenType, what: &str) -> ParseResult<Token> {
+        if self.check(token_type) {
+            self.bump()
+        } else {
+            self.error(&format!("expected {}", what))
+        }
+    }
 
-                    lookahead = skip_block(&lexeme, lookahead);
-                    while head < lookahead {
-                        let l: Token = lexeme[head].clone();
-                        temp_lexeme.push(l);
-                        head += 1;
-                    }
-                    self.in_switch = true;
+    fn expect_identifier(&mut self) -> ParseResult<String> {
+        match self.peek_type() {
+            Some(Identifier) | Some(Main) => Ok(self.bump()?.get_token_value()),
+            _ => self.error("expected identifier"),
+        }
+    }
 
-                    stream.append(&mut self.parse_switch(&temp_lexeme));
-                    temp_lexeme.clear();
-                    self.in_switch = false;
-                }
+    fn error<T>(&self, message: &str) -> ParseResult<T> {
+        let found = match self.peek() {
+            Some(token) => format!(", found `{}`", token.get_token_value()),
+            None => String::new(),
+        };
+        Err(ParseError {
+            message: format!("{}{}", message, found),
+            span: self.current_span(),
+        })
+    }
 
-                (TokenKind::Keyword, KeywordWhile) => {
-                    // let mut temp_lexeme: Vec<Token> = Vec::new();
+    // span of the next token, or of the last token at the end of input
+    fn current_span(&self) -> Span {
+        match self.peek().or_else(|| self.lexeme.last()) {
+            Some(token) => token_span(token),
+            None => Span::default(),
+        }
+    }
 
-                    // move lookahead past conditon
-                    while lexeme[lookahead].get_token_type() != RightBracket {
-                        lookahead += 1;
-                    }
-                    lookahead += 1;
+    // span from start to the last consumed token
+    fn span_from(&self, start: Span) -> Span {
+        match self.lexeme.get(self.last) {
+            Some(token) => start.to(token_span(token)),
+            None => start,
+        }
+    }
 
-                    // move lookahead past block
-                    if lexeme[lookahead].get_token_type() == LeftCurlyBrace {
-                        lookahead = skip_block(&lexeme, lookahead + 1);
-                    }
-                    // move lookahead past block for 'if' without braces
-                    else {
-                        lookahead = skip_stmt(&lexeme, lookahead);
-                    }
-                    // collect if block
-                    while head < lookahead {
-                        let l: Token = lexeme[head].clone();
-                        temp_lexeme.push(l);
-                        head += 1;
-                    }
+    /*-------------------------- recovery --------------------------*/
 
-                    let was_in_switch: bool;
-                    was_in_switch = self.in_switch;
-                    self.in_switch = false;
+    /**
+     * or_recover:
+     * value of a parse that started at head, when it failed the
+     * error is recorded and the region is skipped and wrapped instead
+     */
+    fn or_recover<T, F>(&mut self, head: usize, result: ParseResult<T>, wrap: F) -> T
+    where
+        F: FnOnce(Unsupported) -> T,
+    {
+        match result {
+            Ok(value) => value,
+            Err(err) => {
+                self.head = head;
+                let unsupported = self.skip_construct(err.message.clone());
+                self.errors.push(err);
+                wrap(unsupported)
+            }
+        }
+    }
 
-                    // parse if
-                    stream.append(&mut self.parse_while(&temp_lexeme));
-                    self.in_switch = was_in_switch;
-                    temp_lexeme.clear();
+    /**
+     * skip_construct:
+     * skips the declaration or statement at the head up to its `;` or
+     * its closing brace, try blocks take their catch clauses along
+     */
+    fn skip_construct(&mut self, reason: String) -> Unsupported {
+        let from = self.head;
+        let start = self.current_span();
+        let mut braces = 0;
+        //a brace inside parentheses means a `)` is missing, they are closed
+        let mut parens = 0;
+        while let Some(token_type) = self.peek_type() {
+            if token_type == RightCurlyBrace && braces == 0 && self.head != from {
+                //closing brace of the enclosing block
+                break;
+            }
+            if self.bump().is_err() {
+                break;
+            }
+            match token_type {
+                LeftBracket | LeftSquareBracket => parens += 1,
+                RightBracket | RightSquareBracket => parens = 0.max(parens - 1),
+                LeftCurlyBrace => {
+                    braces += 1;
+                    parens = 0;
                 }
-
-                // matches do while statement
-                (TokenKind::Keyword, KeywordDo) => {
-                    // move lookahead past block
-                    lookahead = skip_block(&lexeme, lookahead + 2);
-                    lookahead = skip_stmt(&lexeme, lookahead);
-
-                    // collect while block
-                    while head < lookahead {
-                        let l: Token = lexeme[head].clone();
-                        temp_lexeme.push(l);
-                        head += 1;
+                RightCurlyBrace if braces > 1 => braces -= 1,
+                RightCurlyBrace => {
+                    //a stray brace is skipped on its own
+                    if braces == 1 && self.check(KeywordCatch) {
+                        braces = 0;
+                        continue;
                     }
-                    // parse while
-                    let was_in_switch: bool;
-                    was_in_switch = self.in_switch;
-                    self.in_switch = false;
-
-                    stream.append(&mut self.parse_dowhile(&temp_lexeme));
-                    temp_lexeme.clear();
-
-                    self.in_switch = was_in_switch;
+                    if braces == 1 {
+                        self.eat(Semicolon);
+                    }
+                    break;
                 }
+                Semicolon if braces == 0 && parens == 0 => break,
+                _ => {}
+            }
+        }
+        Unsupported {
+            reason,
+            text: self.verbatim(from, self.head),
+            span: self.span_from(start),
+        }
+    }
 
-                // matches for statement
-                (_, KeywordFor) => {
-                    //read till end of for()
-                    while lexeme[lookahead].get_token_type() != RightBracket {
-                        lookahead += 1;
-                    }
-                    //forward the look ahead buffer if block statements found
-
-                    lookahead += 1;
-                    if lexeme[lookahead].get_token_type() == LeftCurlyBrace {
-                        /*
-                        for(x;y;z){
-                            c=a+b;
-                        }*/
-
-                        lookahead = skip_block(&lexeme, lookahead);
-                    } else {
-                        /*
-                        for(x;y;z)
-                            c=a+b;
-                            */
-                        //read till end of following expression/statement. i.e semicolon
-                        lookahead = skip_stmt(&lexeme, lookahead);
-                    }
+    /**
+     * verbatim:
+     * source text of the tokens in [from, to), tokens are laid out on
+     * their own lines and columns so the layout of the source is kept
+     */
+    fn verbatim(&self, from: usize, to: usize) -> String {
+        let mut text = String::new();
+        let mut line = 0;
+        let mut column = 0;
+        for token in &self.lexeme[from.min(to)..to] {
+            let value = token.get_token_value();
+            if text.is_empty() {
+                line = token.get_token_line_num();
+            } else if token.get_token_line_num() > line {
+                for _ in line..token.get_token_line_num() {
+                    text.push('\n');
+                }
+                line = token.get_token_line_num();
+                column = 1;
+            }
+            if !text.is_empty() {
+                //tokens made without a location are spaced apart
+                let gap = match token.get_column() {
+                    0 => 1,
+                    token_column => token_column.saturating_sub(column),
+                };
+                text.push_str(&" ".repeat(gap as usize));
+            }
+            text.push_str(&value);
+            //end of the token, block comments span lines
+            match value.rfind('\n') {
+                Some(index) => {
+                    line += value.matches('\n').count() as u32;
+                    column = value[index + 1..].chars().count() as u32 + 1;
+                }
+                None => column = token.get_column() + value.chars().count() as u32,
+            }
+        }
+        text
+    }
 
-                    while head < lookahead {
-                        let l: Token = lexeme[head].clone();
-                        temp_lexeme.push(l);
-                        head += 1;
-                    }
+    /*-------------------------- items --------------------------*/
 
-                    let was_in_switch = self.in_switch;
-                    self.in_switch = false;
+    // parses one item, a failed item is kept as an unsupported region
+    fn parse_item_or_recover(&mut self, items: &mut Vec<Item>) {
+        let head = self.head;
+        let count = items.len();
+        let result = self.parse_item(items);
+        //a record parsed ahead of its failed declarators is skipped along with them
+        if result.is_err() {
+            items.truncate(count);
+        }
+        if let Some(unsupported) = self.or_recover(head, result.map(|_| None), Some) {
+            items.push(Item {
+                span: unsupported.span,
+                kind: ItemKind::Unsupported(unsupported),
+            });
+        }
+    }
 
-                    stream.append(&mut self.parse_for(&temp_lexeme));
-                    temp_lexeme.clear();
-                    self.in_switch = was_in_switch;
-                }
+    /**
+     * parse_item:
+     * parses one top level construct and appends it to items,
+     * a record definition followed by variables yields two items
+     */
+    fn parse_item(&mut self, items: &mut Vec<Item>) -> ParseResult<()> {
+        if let Some(comment) = self.raw_comment() {
+            let span = token_span(&self.lexeme[self.head]);
+            self.head += 1;
+            items.push(Item {
+                kind: ItemKind::Comment(comment),
+                span,
+            });
+            return Ok(());
+        }
+        let start = self.current_span();
+        let token = match self.peek() {
+            Some(token) => token,
+            None => {
+                //only comments were left
+                self.head = self.lexeme.len();
+                return Ok(());
+            }
+        };
 
-                // matches single and multi-line comment
-                (TokenKind::Comments, _) => {
-                    stream.push(lexeme[head].get_token_value() + "\n");
-                    head += 1;
+        match token.get_type() {
+            (TokenKind::Preprocessors, HeaderDefine) => {
+                let text = self.bump()?.get_token_value();
+                let def = parse_macro(&text, &self.macros, &file_scope_names(items));
+                if def.is_translated() {
+                    self.macros.push(def.clone());
                 }
-
-                // assignment statements
-                (_, Identifier) => {
-                    // let mut temp_lexeme: Vec<Token> = Vec::new();
-                    //identifier = expr
-                    //identifier()
-                    //identifier+expr
-                    //identifier OP_INC|OP_DEC; =>postfix
-
-                    match lexeme[head + 1].get_type() {
-                        (TokenKind::Identifiers, Identifier) => {
-                            if self
-                                .typde_def_table
-                                .contains(&lexeme[head].get_token_value())
-                            {
-                                lookahead = skip_stmt(&lexeme, lookahead);
-
-                                // collect variable declaration
-                                while head != lookahead {
-                                    let l: Token = lexeme[head].clone();
-                                    temp_lexeme.push(l);
-                                    head += 1;
-                                }
-                                // parse declaration
-                                stream.append(&mut self.parse_declaration(&temp_lexeme, false));
-                            } else {
-                                while lexeme[head].get_token_type() != Semicolon {
-                                    temp_lexeme.push(lexeme[head].clone());
-                                    head += 1;
-                                }
-                                temp_lexeme.push(lexeme[head].clone());
-                                head += 1;
-                                stream.append(&mut self.parse_class_decl(&temp_lexeme));
-                            }
-                            temp_lexeme.clear();
-                        }
-
-                        (TokenKind::AssignmentOperators, Assignment) => {
-                            // move lookahead past statement
-                            if lexeme[head + 3].get_token_type() == Comma {
-                                lookahead = head + 3;
-                                while head < lookahead + 1 {
-                                    let l: Token = lexeme[head].clone();
-                                    temp_lexeme.push(l);
-                                    head += 1;
-                                }
-                                stream.append(&mut self.parse_assignment(&temp_lexeme));
-                                temp_lexeme.clear();
-                            } else {
-                                lookahead = skip_stmt(&lexeme, lookahead);
-                                // collect statement
-                                while head < lookahead {
-                                    let l: Token = lexeme[head].clone();
-                                    temp_lexeme.push(l);
-                                    head += 1;
-                                }
-
-                                // parse assignment
-                                stream.append(&mut self.parse_assignment(&temp_lexeme));
-                                temp_lexeme.clear();
-                            }
-                        }
-                        (TokenKind::UnaryOperators, _) => {
-                            if self.in_expr != true {
-                                stream.push(lexeme[head].get_token_value());
-                                stream.push(match lexeme[head + 1].get_token_type() {
-                                    Increment => "+=1".to_string(),
-                                    Decrement => "-=1".to_string(),
-                                    _ => " ;".to_string(),
-                                });
-                                head += 2;
-                            } else {
-                                head += 2;
-                            }
-                        }
-                        (TokenKind::BinaryOperators, _) => {
-                            lookahead = skip_stmt(&lexeme, lookahead);
-
-                            //check if overloaded operators is in effect like << >>
-                            if lexeme[head + 2].get_token_type() == StringValue
-                                || lexeme[head + 2].get_token_type() == CharValue
-                            {
-                                stream.push(
-                                    "\n//This statement need to be handled manually \n".to_string(),
-                                );
-                                while head < lookahead {
-                                    stream.push(lexeme[head].get_token_value());
-                                    head += 1;
-                                }
-                            } else {
-                                // move lookahead past statement
-                                // collect statement
-                                while head < lookahead {
-                                    let l: Token = lexeme[head].clone();
-                                    temp_lexeme.push(l);
-                                    head += 1;
-                                }
-
-                                // parse assignment
-                                stream.append(&mut self.parse_expr(&temp_lexeme));
-                                temp_lexeme.clear();
-                            }
-                        }
-                        (TokenKind::SpecialChars, LeftBracket) => {
-                            //function call?
-                            let mut temp_lexeme: Vec<Token> = Vec::new();
-                            stream.push(lexeme[head].get_token_value());
-                            stream.push(lexeme[head + 1].get_token_value());
-
-                            head += 2;
-
-                            let lookahead = skip_paranthised_block(&lexeme, head);
-                            while head < lookahead - 1 {
-                                temp_lexeme.push(lexeme[head].clone());
-                                head += 1;
-                            }
-                            let mut parsed_expr = self.parse_params(&temp_lexeme);
-                            stream.append(&mut parsed_expr);
-                            stream.push(lexeme[head].get_token_value());
-                            head += 1;
-                        }
-                        // (_, LEFT_SBRACKET) => {
-                        //     while lexeme[head].get_token_type() != RIGHT_SBRACKET {
-                        //         stream.push(lexeme[head].get_token_value());
-                        //         head += 1;
-                        //     }
-                        //     stream.push(lexeme[head].get)
-                        // }
-                        (_, _) => {
-                            if lexeme[head].get_token_type() != RightCurlyBrace {
-                                stream.push(lexeme[head].get_token_value());
-                            }
-                            head += 1;
+                items.push(Item {
+                    kind: ItemKind::Macro(def),
+                    span: start,
+                });
+            }
+            (TokenKind::Preprocessors, _) => {
+                let directive = self.bump()?.get_token_value();
+                items.push(Item {
+                    kind: ItemKind::Directive(directive),
+                    span: start,
+                });
+            }
+            (_, Semicolon) => {
+                self.bump()?;
+            }
+            (_, Typedef) => {
+                self.parse_typedef(items)?;
+            }
+            //linkage has no effect on the translation, extern "C" { items }
+            (_, KeywordExtern) if self.check_nth(1, StringValue) => {
+                self.bump()?;
+                self.bump()?;
+                if self.eat(LeftCurlyBrace) {
+                    let outer = self.in_extern;
+                    self.in_extern = true;
+                    while !self.check(RightCurlyBrace) {
+                        if self.raw_comment().is_none() && self.at_end() {
+                            self.in_extern = outer;
+                            return self.error("expected `}`");
                         }
-                    };
+                        self.parse_item_or_recover(items);
+                    }
+                    self.in_extern = outer;
+                    self.bump()?;
                 }
-
-                (TokenKind::UnaryOperators, _) => {
-                    stream.push(lexeme[head + 1].get_token_value());
-                    stream.push(match lexeme[head].get_token_type() {
-                        Increment => "+=1".to_string(),
-                        Decrement => "-=1".to_string(),
-                        _ => " ;".to_string(),
+            }
+            //template<> int max<int>(int a, int b) { }
+            (_, KeywordTemplate)
+                if self.check_nth(1, LessThan) && self.check_nth(2, GreaterThan) =>
+            {
+                let unsupported =
+                    self.skip_construct("explicit specializations are not supported".to_string());
+                items.push(Item {
+                    span: unsupported.span,
+                    kind: ItemKind::Unsupported(unsupported),
+                });
+            }
+            (_, KeywordTemplate) => {
+                self.parse_template(items)?;
+            }
+            //namespace fs = std::filesystem;
+            (_, KeywordNamespace) if self.check_nth(2, Assignment) => {
+                let unsupported =
+                    self.skip_construct("namespace aliases are not supported".to_string());
+                items.push(Item {
+                    span: unsupported.span,
+                    kind: ItemKind::Unsupported(unsupported),
+                });
+            }
+            (_, KeywordNamespace) => {
+                self.parse_namespace(items)?;
+            }
+            //using Int = int;
+            (_, KeywordUsing) if self.check_nth(2, Assignment) => {
+                let unsupported =
+                    self.skip_construct("alias declarations are not supported".to_string());
+                items.push(Item {
+                    span: unsupported.span,
+                    kind: ItemKind::Unsupported(unsupported),
+                });
+            }
+            (_, KeywordUsing) => {
+                self.parse_using(items)?;
+            }
+            _ if self.unsupported_ahead().is_some() => {
+                let unsupported = self.parse_unsupported();
+                items.push(Item {
+                    span: unsupported.span,
+                    kind: ItemKind::Unsupported(unsupported),
+                });
+            }
+            (_, KeywordStruct) | (_, KeywordUnion) | (_, KeywordClass)
+                if self.is_definition_ahead() =>
+            {
+                let record = self.parse_record()?;
+                let name = record.name.clone();
+                let span = record.span;
+                items.push(Item {
+                    kind: ItemKind::Record(record),
+                    span,
+                });
+                if !self.eat(Semicolon) {
+                    //struct A {...} a, b;
+                    let decl = self.parse_declaration_after(Specifiers::of(CType::Named(name)))?;
+                    let span = decl.span;
+                    items.push(Item {
+                        kind: ItemKind::Declaration(decl),
+                        span,
                     });
-                    head += 2;
                 }
-
-                (_, KeywordStruct) => {
-                    if lexeme[head + 2].get_token_type() == LeftCurlyBrace {
-                        //struct A{};
-                        while lexeme[head].get_token_type() != RightCurlyBrace {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-                        //push the right curly brace
-                        temp_lexeme.push(lexeme[head].clone());
-                        stream.append(&mut self.parse_struct(&temp_lexeme));
-                        temp_lexeme.clear();
-                        head += 2; //skip semicolon
-                    } else {
-                        //struct variable declaration
-
-                        while lexeme[head].get_token_type() != Semicolon {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-                        temp_lexeme.push(lexeme[head].clone());
-                        head += 1;
-                        stream.append(&mut self.parse_struct_decl(&temp_lexeme));
-                        temp_lexeme.clear();
-                    }
+            }
+            (_, KeywordEnum) if self.is_enum_definition_ahead() => {
+                let def = self.parse_enum()?;
+                let name = def.name.clone();
+                let span = def.span;
+                items.push(Item {
+                    kind: ItemKind::Enum(def),
+                    span,
+                });
+                if !self.eat(Semicolon) {
+                    let decl = self.parse_declaration_after(Specifiers::of(CType::Named(
+                        name.unwrap_or_default(),
+                    )))?;
+                    let span = decl.span;
+                    items.push(Item {
+                        kind: ItemKind::Declaration(decl),
+                        span,
+                    });
                 }
-
-                (_, KeywordUnion) => {
-                    if lexeme[head + 2].get_token_type() == LeftCurlyBrace {
-                        stream.push(UNION.get_doc().to_string());
-                        //struct A{};
-                        while lexeme[head].get_token_type() != RightCurlyBrace {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-                        //push the right curly brace
-                        temp_lexeme.push(lexeme[head].clone());
-                        stream.append(&mut self.parse_union(&temp_lexeme));
-                        temp_lexeme.clear();
-                        head += 2; //skip semicolon
-                    } else {
-                        //struct variable declaration
-
-                        while lexeme[head].get_token_type() != Semicolon {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-                        temp_lexeme.push(lexeme[head].clone());
-                        head += 1;
-                        stream.append(&mut self.parse_union_decl(&temp_lexeme));
-                        temp_lexeme.clear();
-                    }
+            }
+            //constructor or destructor defined outside of its class, A::A() { }
+            (_, Identifier)
+                if self.check_nth(self.skip_template_args(1), ScopeResolution)
+                    && (self.check_nth(self.skip_template_args(1) + 1, BitwiseNegate)
+                        || self.check_nth(self.skip_template_args(1) + 2, LeftBracket)) =>
+            {
+                let class = self.bump()?.get_token_value();
+                //Stack<T>::Stack() of a class template
+                if self.check(LessThan) {
+                    self.parse_template_args()?;
                 }
-
-                (_, KeywordClass) => {
-                    if lexeme[head + 2].get_token_type() == LeftCurlyBrace {
-                        //struct A{};
-                        while lexeme[head].get_token_type() != RightCurlyBrace
-                            || lexeme[head + 1].get_token_type() != Semicolon
-                        {
-                            temp_lexeme.push(lexeme[head].clone());
-                            head += 1;
-                        }
-
-                        //push the right curly brace
-                        temp_lexeme.push(lexeme[head].clone());
-                        stream.append(&mut self.parse_class(&temp_lexeme));
-                        temp_lexeme.clear();
-                        head += 2; //skip semicolon
-                    }
+                self.bump()?;
+                let (name, return_type) = if self.eat(BitwiseNegate) {
+                    let name = format!("~{}", self.expect_identifier()?);
+                    (name, CType::Primitive(Void, Modifier::Default))
+                } else {
+                    let name = self.expect_identifier()?;
+                    (name.clone(), CType::Named(name))
+                };
+                let mut function = self.parse_function_rest(name, return_type, start)?;
+                function.class = Some(class);
+                items.push(Item {
+                    span: function.span,
+                    kind: ItemKind::Function(function),
+                });
+            }
+            _ if self.is_declaration_start() => {
+                let spec = self.parse_specifiers()?;
+                if self.eat(Semicolon) {
+                    //forward declaration : struct A;
+                    return Ok(());
                 }
-
-                (_, KeywordEnum) => {
-                    while lexeme[head].get_token_type() != Semicolon {
-                        stream.push(lexeme[head].get_token_value());
-                        head += 1;
+                let (name, ty) = self.parse_declarator(&spec.ty)?;
+                if self.check(LeftBracket) {
+                    let mut function = self.parse_function_rest(name, ty, start)?;
+                    function.is_inline = spec.is_inline;
+                    function.is_extern = spec.is_extern || self.in_extern;
+                    //member function defined outside of its class, int A::f() { }
+                    if let Some(index) = function.name.rfind("::") {
+                        function.class = Some(function.name[..index].to_string());
+                        function.name = function.name[index + 2..].to_string();
                     }
-                    head += 1;
+                    self.check_operator(&function, function.class.is_some())?;
+                    items.push(Item {
+                        span: function.span,
+                        kind: ItemKind::Function(function),
+                    });
+                } else {
+                    //static data members are defined outside of their class, int A::count = 0;
+                    let decl = self.parse_declaration_rest(&spec, name, ty, start)?;
+                    items.push(Item {
+                        span: decl.span,
+                        kind: ItemKind::Declaration(decl),
+                    });
                 }
-                (_, KeywordReturn) => {
-                    let mut t = head;
-                    stream.push(NO_RETURN.get_doc().to_string());
-
-                    while lexeme[t].get_token_type() != Semicolon {
-                        t += 1;
-                    }
+            }
+            _ => return self.error("expected declaration"),
+        }
+        Ok(())
+    }
 
-                    if t != lexeme.len() - 1 {
-                        while lexeme[head].get_token_type() != Semicolon {
-                            stream.push(lexeme[head].get_token_value());
-                            head += 1;
-                        }
-                        stream.push(lexeme[head].get_token_value());
-                        head += 1;
-                    } else {
-                        //convert to shorthand notation
-                        head += 1;
-                        if self.in_main {
-                            stream.push("std::process::exit(".to_string());
-                            while lexeme[head].get_token_type() != Semicolon {
-                                stream.push(lexeme[head].get_token_value());
-                                head += 1;
-                            }
-                            stream.push(");".to_string());
-                        } else {
-                            while lexeme[head].get_token_type() != Semicolon {
-                                stream.push(lexeme[head].get_token_value());
-                                head += 1;
-                            }
-                        }
-                        head += 1;
-                    }
-                }
-                (TokenKind::Preprocessors, _) => {
-                    let line_num = lexeme[head].get_token_line_num();
-                    if self.once_warned == false {
-                        stream.push(INCLUDE_STMT.get_doc().to_string());
-                    } else {
-                        //if warned once is set, we have already started the preprocessor block and commented out using multi line comment.
-                        //which is ended at line L369 below. Need to pop that out before writing next pre-processor statement.
-                        stream.pop();
-                    }
+    // struct/union/class/enum keyword followed by an optional name and `{` or a base list
+    fn is_definition_ahead(&self) -> bool {
+        self.check_nth(1, LeftCurlyBrace)
+            || (self.check_nth(1, Identifier)
+                && (self.check_nth(2, LeftCurlyBrace) || self.check_nth(2, Colon)))
+    }
 
-                    //read in the current line as is.
-                    while lexeme[head].get_token_line_num() != line_num {
-                        stream.push(lexeme[head].get_token_value());
-                        head += 1;
-                    }
-                    stream.push(lexeme[head].get_token_value() + "\n");
-                    stream.push("**/\n".to_string());
-                    head += 1;
+    // enum [class] [name] [: type] followed by `{`
+    fn is_enum_definition_ahead(&self) -> bool {
+        let mut n = 1;
+        while let Some(token) = self.peek_nth(n) {
+            match token.get_token_type() {
+                LeftCurlyBrace => return true,
+                Semicolon | Assignment | LeftBracket | RightBracket | Comma => return false,
+                _ => n += 1,
+            }
+        }
+        false
+    }
 
-                    self.once_warned = true;
-                }
-                (TokenKind::SpecialChars, LeftBracket) => {
-                    let mut temp_lexeme: Vec<Token> = Vec::new();
-                    while lexeme[head].get_token_type() != RightBracket {
-                        temp_lexeme.push(lexeme[head].clone());
-                        head += 1;
-                    }
-                    temp_lexeme.push(lexeme[head].clone());
+    /**
+     * unsupported_ahead:
+     * keyword of a construct crust can not translate yet, when the
+     * construct at the head starts with one
+     */
+    fn unsupported_ahead(&self) -> Option<&'static str> {
+        let keyword = |token_type| match token_type {
+            KeywordNamespace => Some("namespace"),
+            KeywordUsing => Some("using"),
+            KeywordFriend => Some("friend"),
+            KeywordTry => Some("try"),
+            KeywordThrow => Some("throw"),
+            _ => None,
+        };
+        self.peek_type().and_then(keyword)
+    }
 
-                    stream.append(&mut self.parse_typecast(&temp_lexeme));
-                    head += 1;
-                }
+    // skips a construct introduced by a keyword crust can not translate yet
+    fn parse_unsupported(&mut self) -> Unsupported {
+        let keyword = self.unsupported_ahead().unwrap_or_default();
+        self.skip_construct(format!("`{}` is not supported", keyword))
+    }
 
-                // if all fails
-                (_, _) => {
-                    if lexeme[head].get_token_type() != RightCurlyBrace {
-                        if lexeme[head].get_token_type() == Comma {
-                            stream.push(";".to_string());
-                        } else if lexeme[head].get_token_type() == KeywordBreak {
-                            if !self.in_switch {
-                                stream.push(lexeme[head].get_token_value());
-                            }
-                        } else {
-                            stream.push(lexeme[head].get_token_value());
-                        }
-                    }
-                    head += 1;
-                }
+    /**
+     * parse_namespace:
+     * namespace a { items }, namespace a::b { } nests b inside a and
+     * an anonymous namespace has no name
+     */
+    fn parse_namespace(&mut self, items: &mut Vec<Item>) -> ParseResult<()> {
+        let start = self.current_span();
+        self.expect(KeywordNamespace, "`namespace`")?;
+        let mut names: Vec<String> = Vec::new();
+        if self.check(Identifier) {
+            names.push(self.expect_identifier()?);
+            while self.eat(ScopeResolution) {
+                names.push(self.expect_identifier()?);
+            }
+        }
+        self.expect(LeftCurlyBrace, "`{`")?;
+        let mut inner: Vec<Item> = Vec::new();
+        while !self.check(RightCurlyBrace) {
+            if self.raw_comment().is_none() && self.at_end() {
+                return self.error("expected `}`");
+            }
+            self.parse_item_or_recover(&mut inner);
+        }
+        self.bump()?;
+        let span = self.span_from(start);
+        let mut namespace = Namespace {
+            name: names.pop(),
+            items: inner,
+        };
+        while let Some(name) = names.pop() {
+            namespace = Namespace {
+                name: Some(name),
+                items: vec![Item {
+                    kind: ItemKind::Namespace(namespace),
+                    span,
+                }],
             };
         }
-        //return the rust lexeme to main
-        stream
+        items.push(Item {
+            kind: ItemKind::Namespace(namespace),
+            span,
+        });
+        Ok(())
     }
 
     /**
-     * print_lexemes: DEBUG_ONLY
-     * prints the lexemes in the lexeme vector
-     * from index start to end
+     * parse_using:
+     * using namespace a::b; or using a::b::f; the names it brings from
+     * the standard library become known types and templates
      */
-    fn print_lexemes(lexeme: &Vec<Token>, start: usize, end: usize) {
-        println!("----------lexeme-start------------");
-        for i in start..end {
-            println!(
-                "Line Num {} , {}> {:?}",
-                lexeme[i].get_token_line_num(),
-                i,
-                lexeme[i],
-            );
+    fn parse_using(&mut self, items: &mut Vec<Item>) -> ParseResult<()> {
+        let start = self.current_span();
+        self.expect(KeywordUsing, "`using`")?;
+        let namespace = self.eat(KeywordNamespace);
+        let mut path = self.expect_identifier()?;
+        while self.eat(ScopeResolution) {
+            path.push_str("::");
+            path.push_str(&self.expect_identifier()?);
+        }
+        self.expect(Semicolon, "`;`")?;
+        for name in std_names(&path, namespace) {
+            if std_type(name).is_some() && !self.type_names.iter().any(|known| known == name) {
+                self.type_names.push(name.to_string());
+            }
+            if is_std_template(name) && !self.templates.iter().any(|known| known == name) {
+                self.templates.push(name.to_string());
+            }
         }
-        println!("----------lexeme-end------------");
+        items.push(Item {
+            kind: ItemKind::Using(Using { path, namespace }),
+            span: self.span_from(start),
+        });
+        Ok(())
     }
 
     /**
-     * parse_function:
-     * parse c/c++ function into rust equivalent function
+     * parse_template:
+     * template<typename T, int N> before the function or class it
+     * declares, template class A<int>; instantiates one explicitly
      */
-    fn parse_function(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        let mut head: usize = 3;
-        let mut lookahead: usize = head;
-        let mut stream: Vec<String> = Vec::new();
-
-        stream.push("fn".to_string());
-        stream.push(lexeme[1].get_token_value());
-        stream.push("(".to_string());
-
-        // parse arguments differently for functions that are not main
-        // since rust does not have arguments or return type for main
-        if lexeme[1].get_token_type() != Main {
-            // collect arguments
-            while lexeme[lookahead].get_token_type() != RightBracket {
-                lookahead += 1;
-            }
-            while head < lookahead {
-                let l: Token = lexeme[head].clone();
-                temp_lexeme.push(l);
-                head += 1;
-            }
-            // parse arguments
-            stream.append(&mut self.parse_arguments(&temp_lexeme));
-            temp_lexeme.clear();
-
-            stream.push(")".to_string());
-
-            // parse return type
-            if let Some(rust_type) = parse_type(lexeme[0].get_token_type(), Modifier::Default) {
-                if rust_type != "void".to_string() {
-                    stream.push("->".to_string());
-                    stream.push(rust_type);
+    fn parse_template(&mut self, items: &mut Vec<Item>) -> ParseResult<()> {
+        let start = self.current_span();
+        let from = self.head;
+        self.expect(KeywordTemplate, "`template`")?;
+        if !self.check(LessThan) {
+            while !self.eat(Semicolon) {
+                if self.at_end() {
+                    return self.error("expected `;`");
                 }
+                self.bump()?;
+            }
+            items.push(Item {
+                kind: ItemKind::Instantiation(self.verbatim(from, self.head)),
+                span: self.span_from(start),
+            });
+            return Ok(());
+        }
+        let params = self.parse_template_params()?;
+        //the class can refer to itself as Stack<T> in its members
+        if self.check(KeywordClass) || self.check(KeywordStruct) {
+            if let Some(token) = self.peek_nth(1) {
+                self.templates.push(token.get_token_value());
             }
-
-            stream.push("{".to_string());
         }
-        // declare argc and argv inside main, if required
-        else {
-            //parsing main function
-            self.in_main = true;
-            stream.push(")".to_string());
-            stream.push("{".to_string());
-            if lexeme[head].get_token_type() != RightBracket {
-                if self.strict == false {
-                    stream.push(NO_STRICT.get_doc().to_string());
-                    stream.push("let mut argv: Vec<_> = std::env::args().collect();".to_string());
-                    stream.push("let mut argc = argv.len();".to_string());
-                } else {
-                    stream.push(STRICT.get_doc().to_string());
-
-                    stream.push("let argv: Vec<_> = std::env::args().collect();".to_string());
-                    stream.push("let argc = argv.len();".to_string());
+        let count = items.len();
+        let result = self.with_type_params(&params, |parser| parser.parse_item(items));
+        result?;
+        for item in &mut items[count..] {
+            match item.kind {
+                ItemKind::Function(ref mut function) => {
+                    function.template = params.clone();
+                    self.templates.push(function.name.clone());
                 }
+                ItemKind::Record(ref mut record) => record.template = params.clone(),
+                _ => return self.error("variable templates are not supported"),
             }
         }
+        Ok(())
+    }
 
-        while lexeme[head].get_token_type() != LeftCurlyBrace {
-            head += 1
+    // <typename T, class U, int N> of a template
+    fn parse_template_params(&mut self) -> ParseResult<Vec<TemplateParam>> {
+        self.expect(LessThan, "`<`")?;
+        let mut params: Vec<TemplateParam> = Vec::new();
+        while !self.eat(GreaterThan) {
+            if self.eat(KeywordTypename) || self.eat(KeywordClass) {
+                params.push(TemplateParam {
+                    name: self.expect_identifier()?,
+                    ty: None,
+                });
+            } else {
+                let spec = self.parse_specifiers()?;
+                let (name, ty) = self.parse_declarator(&spec.ty)?;
+                params.push(TemplateParam { name, ty: Some(ty) });
+            }
+            if self.check(Assignment) {
+                return self.error("default template arguments are not supported");
+            }
+            if !self.check(GreaterThan) {
+                self.expect(Comma, "`,` or `>` in template parameters")?;
+            }
         }
-        head += 1;
+        Ok(params)
+    }
 
-        // collect function body
-        // len - 1  so that '}' is excluded
-        while head < lexeme.len() - 1 {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
-        }
-        // parse function body
-        stream.append(&mut self.parse_program(&temp_lexeme));
-        stream.push("}".to_string());
-        self.in_main = false;
-        stream
+    // parses with the type parameters of a template known as type names
+    fn with_type_params<T, F>(&mut self, params: &[TemplateParam], parse: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Self) -> ParseResult<T>,
+    {
+        let names: Vec<String> = params
+            .iter()
+            .filter(|param| param.ty.is_none())
+            .map(|param| param.name.clone())
+            .collect();
+        let scope = self.type_names.len();
+        self.type_names.extend(names.iter().cloned());
+        let result = parse(self);
+        self.type_names.drain(scope..scope + names.len());
+        result
     }
 
     /**
-     * parse-arguments:
-     * parse c/c++ formal arguments in the function signature
-     * into rust equivalent arguments
+     * parse_template_args:
+     * <int, 4> after the name of a template, a `>>` closes the
+     * enclosing argument list as well
      */
-    fn parse_arguments(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        while head < lexeme.len() {
-            let mut declaration_lexeme: Vec<Token> = Vec::new();
-            while head < lexeme.len() && lexeme[head].get_token_type() != Comma {
-                //create a subset of argument declaration
-
-                declaration_lexeme.push(lexeme[head].clone());
-
-                head += 1;
+    fn parse_template_args(&mut self) -> ParseResult<Vec<TemplateArg>> {
+        self.expect(LessThan, "`<`")?;
+        let mut args: Vec<TemplateArg> = Vec::new();
+        loop {
+            if self.closed_twice {
+                self.closed_twice = false;
+                return Ok(args);
+            }
+            if self.eat(GreaterThan) {
+                return Ok(args);
+            }
+            if self.eat(BitwiseRightShift) {
+                self.closed_twice = true;
+                return Ok(args);
+            }
+            if !args.is_empty() {
+                self.expect(Comma, "`,` or `>` in template arguments")?;
+            }
+            if self.is_type_name_at(0) {
+                let spec = self.parse_specifiers()?;
+                //Box<Pair<int>> b; the declarator name follows the `>>`
+                let ty = if self.closed_twice {
+                    spec.ty.unqualified().clone()
+                } else {
+                    self.parse_abstract_declarator(&spec.ty)?.1
+                };
+                args.push(TemplateArg::Type(ty));
+            } else {
+                //shifts and comparisons would take the closing `>`
+                args.push(TemplateArg::Value(
+                    self.parse_binary(BinaryOp::Add.precedence())?,
+                ));
             }
-            head += 1;
-
-            let mut parsed_argument = self.parse_declaration(&declaration_lexeme, true);
-
-            stream.append(&mut parsed_argument);
-            stream.push(",".to_string());
         }
-        stream
     }
 
-    fn parse_params(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-
-        //create expression termination token ';'
-        let terminal_token = Token::new(";".to_string(), TokenKind::SpecialChars, Semicolon, 0, 0);
-        let mut head: usize = 0;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while head < lexeme.len() {
-            if lexeme[head].get_token_type() == Comma {
-                temp_lexeme.push(terminal_token.clone());
-
-                let mut parsed_expr = self.parse_expr(&temp_lexeme);
-                //pop the tailing semicolon
-                parsed_expr.pop();
-                stream.append(&mut parsed_expr);
-                stream.push(",".to_string());
-                temp_lexeme.clear();
-            } else {
-                temp_lexeme.push(lexeme[head].clone());
+    // position after the template arguments starting at the n-th token, if any
+    fn skip_template_args(&self, n: usize) -> usize {
+        if !self.check_nth(n, LessThan) || !self.is_template_at(n - 1) {
+            return n;
+        }
+        let mut depth = 0;
+        let mut n = n;
+        while let Some(token_type) = self.peek_nth(n).map(|token| token.get_token_type()) {
+            match token_type {
+                LessThan => depth += 1,
+                GreaterThan => depth -= 1,
+                BitwiseRightShift => depth -= 2,
+                Semicolon | LeftCurlyBrace => return n,
+                _ => {}
+            }
+            n += 1;
+            if depth <= 0 {
+                return n;
             }
-            head += 1;
         }
-        temp_lexeme.push(terminal_token.clone());
-        let mut parsed_expr = self.parse_expr(&temp_lexeme);
-        //pop the tailing semicolon
-        parsed_expr.pop();
-        stream.append(&mut parsed_expr);
+        n
+    }
 
-        return stream;
+    // name of a template followed by its arguments, std::vector<int> as well
+    fn is_template_at(&self, n: usize) -> bool {
+        let is_std = n > 1
+            && self.check_nth(n - 1, ScopeResolution)
+            && self
+                .peek_nth(n - 2)
+                .is_some_and(|token| token.get_token_value() == "std");
+        self.peek_nth(n).is_some_and(|token| {
+            let name = token.get_token_value();
+            self.templates.contains(&name) || (is_std && is_std_template(&name))
+        }) && self.check_nth(n + 1, LessThan)
     }
+
     /**
-     * parse_declaration:
-     * parse c/c++ declaration into rust
-     * equivalent statements */
-    fn parse_declaration(
-        &mut self,
-        lexeme: &Vec<Token>,
-        argument_declaration: bool,
-    ) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-
-        //  let mut sym_tab: Vec<SymbolTable> = Vec::new();
-        //self.sym_tab.clear();
-        let mut sym: SymbolTable = SymbolTable {
-            symbol_type: Others,
-            symbol_modifier: Modifier::Default,
-            id_name: "undefined_var".to_string(),
-            is_assigned: false,
-            is_ptr: false,
-            assigned_val: "NONE".to_string(),
+     * parse_typedef:
+     * typedef type alias;
+     * typedef struct tag {...} alias;
+     * a record defined in place is pushed as its own item before the alias
+     */
+    fn parse_typedef(&mut self, items: &mut Vec<Item>) -> ParseResult<()> {
+        let start = self.current_span();
+        self.expect(Typedef, "`typedef`")?;
+
+        let mut defined: Option<Item> = None;
+        let base = match self.peek_type() {
+            Some(KeywordStruct) | Some(KeywordUnion) | Some(KeywordClass)
+                if self.is_definition_ahead() =>
+            {
+                let record = self.parse_record()?;
+                let ty = CType::Named(record.name.clone());
+                defined = Some(Item {
+                    span: record.span,
+                    kind: ItemKind::Record(record),
+                });
+                ty
+            }
+            Some(KeywordEnum) if self.is_definition_ahead() => {
+                let def = self.parse_enum()?;
+                let ty = CType::Named(def.name.clone().unwrap_or_default());
+                defined = Some(Item {
+                    span: def.span,
+                    kind: ItemKind::Enum(def),
+                });
+                ty
+            }
+            _ => self.parse_specifiers()?.ty,
         };
 
-        //check if there is any modifier present
-
-        let (token_kind, token_type) = lexeme[0].get_type();
-
-        let mut type_index = 0;
-        if token_kind == TokenKind::Modifiers {
-            //type name can be found in next lexeme
-            type_index = 1;
-            match token_type {
-                Signed => {
-                    sym.symbol_modifier = Modifier::Signed;
+        let (alias, ty) = self.parse_declarator(&base)?;
+        self.expect(Semicolon, "`;` after typedef")?;
+        self.type_names.push(alias.clone());
+
+        if let Some(mut item) = defined {
+            //anonymous records take the name of the alias
+            let mut renamed = false;
+            match item.kind {
+                ItemKind::Record(ref mut record) if record.name.is_empty() => {
+                    record.name = alias.clone();
+                    renamed = true;
                 }
-                Unsigned => {
-                    sym.symbol_modifier = Modifier::Unsigned;
-                }
-                KeywordStatic => {
-                    sym.symbol_modifier = Modifier::Static;
-                }
-                KeywordConst => {
-                    sym.symbol_modifier = Modifier::Const;
+                ItemKind::Enum(ref mut def) if def.name.is_none() => {
+                    def.name = Some(alias.clone());
+                    renamed = true;
                 }
                 _ => {}
             }
+            items.push(item);
+            if renamed {
+                return Ok(());
+            }
+        }
+        items.push(Item {
+            kind: ItemKind::Typedef(ast::Typedef { name: alias, ty }),
+            span: self.span_from(start),
+        });
+        Ok(())
+    }
+
+    /**
+     * parse_record:
+     * parses struct, union and class definitions up to the closing brace
+     */
+    fn parse_record(&mut self) -> ParseResult<Record> {
+        let start = self.current_span();
+        let (kind, mut access) = match self.bump()?.get_token_type() {
+            KeywordStruct => (RecordKind::Struct, Access::Public),
+            KeywordUnion => (RecordKind::Union, Access::Public),
+            _ => (RecordKind::Class, Access::Private),
+        };
+        let name = if self.check(Identifier) {
+            self.bump()?.get_token_value()
+        } else {
+            String::new()
+        };
+        if !name.is_empty() {
+            self.type_names.push(name.clone());
+        }
+        //base classes, the access and virtual keywords have no rust equivalent
+        let mut bases: Vec<String> = Vec::new();
+        if self.eat(Colon) {
+            loop {
+                while self.eat(KeywordPublic)
+                    || self.eat(keywordPrivate)
+                    || self.eat(KeywordProtected)
+                    || self.eat(KeywordVirtual)
+                {}
+                bases.push(self.expect_identifier()?);
+                if !self.eat(Comma) {
+                    break;
+                }
+            }
         }
+        self.expect(LeftCurlyBrace, "`{`")?;
 
-        let type_token = &lexeme[type_index];
-        let typdef_type = type_token.get_token_value(); //get the type name
-
-        let mut head: usize = type_index + 1;
-        //let sym_idx:usize=0;
-        while head < lexeme.len() {
-            match lexeme[head].get_token_type() {
-                Identifier => sym.id_name = lexeme[head].get_token_value(),
-
-                Assignment => {
-                    sym.is_assigned = true;
-                    sym.assigned_val = "".to_string();
-                    head += 1;
-                    let mut br = 0;
-                    let mut lhead = head;
-                    if sym.is_ptr == true {
-                        if lexeme[head].get_token_type() == Null {
-                            while lexeme[lhead].get_token_type() != Semicolon
-                                && lexeme[lhead].get_token_type() != Comma
-                            {
-                                lhead += 1;
+        let mut members: Vec<Member> = Vec::new();
+        loop {
+            if let Some(comment) = self.raw_comment() {
+                self.head += 1;
+                members.push(Member {
+                    access,
+                    kind: MemberKind::Comment(comment),
+                });
+                continue;
+            }
+            match self.peek_type() {
+                None => return self.error("expected `}`"),
+                Some(RightCurlyBrace) => break,
+                //rust has no implicit conversions to prevent
+                Some(KeywordExplicit) => {
+                    self.bump()?;
+                }
+                //virtual ~A(), every Drop impl is called through the value
+                Some(KeywordVirtual) if self.check_nth(1, BitwiseNegate) => {
+                    self.bump()?;
+                }
+                Some(BitwiseNegate) if self.check_nth(1, Identifier) => {
+                    let head = self.head;
+                    let member_start = self.current_span();
+                    self.bump()?;
+                    let destructor = self.bump()?.get_token_value();
+                    let result = self
+                        .parse_function_rest(
+                            format!("~{}", destructor),
+                            CType::Primitive(Void, Modifier::Default),
+                            member_start,
+                        )
+                        .map(MemberKind::Destructor);
+                    let kind = self.or_recover(head, result, MemberKind::Unsupported);
+                    members.push(Member { access, kind });
+                }
+                //friend V operator*(double s, V v), friend classes are not supported
+                Some(KeywordFriend)
+                    if !self.check_nth(1, KeywordClass) && !self.check_nth(1, KeywordStruct) =>
+                {
+                    let head = self.head;
+                    self.bump()?;
+                    let result = self.parse_member(true);
+                    let kind = self.or_recover(head, result, MemberKind::Unsupported);
+                    members.push(Member { access, kind });
+                }
+                //member function template, template<typename U> void f(U u)
+                Some(KeywordTemplate) if self.check_nth(1, LessThan) => {
+                    let head = self.head;
+                    self.bump()?;
+                    let result = self.parse_template_params().and_then(|params| {
+                        match self.with_type_params(&params, |parser| parser.parse_member(false))? {
+                            MemberKind::Method(mut method) => {
+                                method.template = params;
+                                Ok(MemberKind::Method(method))
                             }
-                            sym.is_assigned = false;
-                        }
-                    }
-                    let mut temp_lex: Vec<Token> = Vec::new();
-                    while lexeme[head].get_token_type() != Semicolon
-                        && !(br == 0 && lexeme[head].get_token_type() == Comma)
-                    {
-                        if lexeme[head].get_token_type() == LeftBracket {
-                            br += 1;
-                        }
-                        if lexeme[head].get_token_type() == RightBracket {
-                            br -= 1;
+                            _ => self.error("member variable templates are not supported"),
                         }
-                        temp_lex.push(lexeme[head].clone());
-                        //parse assigned value for expression
-
-                        head += 1;
-                    }
-                    temp_lex.push(lexeme[head].clone());
-                    let a_val = self.parse_expr(&temp_lex);
-                    let mut a_value = String::new();
-                    for val in a_val {
-                        a_value = a_value + &val;
-                    }
-                    sym.assigned_val.push_str(a_value.as_str());
-
-                    continue;
+                    });
+                    let kind = self.or_recover(head, result, MemberKind::Unsupported);
+                    members.push(Member { access, kind });
                 }
-
-                Semicolon | Comma => {
-                    // used enum value in the symbol table
-                    sym.symbol_type = type_token.get_token_type();
-                    self.sym_tab.push(sym.clone());
+                _ if self.unsupported_ahead().is_some() => {
+                    members.push(Member {
+                        access,
+                        kind: MemberKind::Unsupported(self.parse_unsupported()),
+                    });
                 }
-                //int * a ;
-                Multiplication => {
-                    sym.is_ptr = true;
+                Some(KeywordPublic) | Some(keywordPrivate) | Some(KeywordProtected)
+                    if self.check_nth(1, Colon) =>
+                {
+                    access = match self.bump()?.get_token_type() {
+                        KeywordPublic => Access::Public,
+                        KeywordProtected => Access::Protected,
+                        _ => Access::Private,
+                    };
+                    self.bump()?;
+                }
+                Some(Identifier)
+                    if self
+                        .peek()
+                        .is_some_and(|token| token.get_token_value() == name)
+                        && self.check_nth(1, LeftBracket) =>
+                {
+                    let head = self.head;
+                    let member_start = self.current_span();
+                    self.bump()?;
+                    let result = self
+                        .parse_function_rest(name.clone(), CType::Named(name.clone()), member_start)
+                        .map(MemberKind::Constructor);
+                    let kind = self.or_recover(head, result, MemberKind::Unsupported);
+                    members.push(Member { access, kind });
                 }
                 _ => {
-                    sym.assigned_val.push_str(&lexeme[head].get_token_value());
+                    let head = self.head;
+                    let result = self.parse_member(false);
+                    let kind = self.or_recover(head, result, MemberKind::Unsupported);
+                    members.push(Member { access, kind });
                 }
-            };
-            head += 1;
+            }
+        }
+        self.expect(RightCurlyBrace, "`}`")?;
+
+        Ok(Record {
+            kind,
+            name,
+            bases,
+            template: Vec::new(),
+            members,
+            span: self.span_from(start),
+        })
+    }
+
+    // data member, method declaration or friend function
+    fn parse_member(&mut self, is_friend: bool) -> ParseResult<MemberKind> {
+        let member_start = self.current_span();
+        let spec = self.parse_specifiers()?;
+        let (member, ty) = self.parse_declarator(&spec.ty)?;
+        if self.check(LeftBracket) {
+            let mut method = self.parse_function_rest(member, ty, member_start)?;
+            method.is_inline = spec.is_inline;
+            method.is_virtual = spec.is_virtual || method.is_virtual;
+            method.is_static = spec.is_static;
+            self.check_operator(&method, !is_friend && !method.is_static)?;
+            if is_friend {
+                return Ok(MemberKind::Friend(method));
+            }
+            Ok(MemberKind::Method(method))
+        } else if is_friend {
+            self.error("expected friend function")
+        } else {
+            Ok(MemberKind::Field(self.parse_declaration_rest(
+                &spec,
+                member,
+                ty,
+                member_start,
+            )?))
         }
+    }
 
-        if !self.struct_in_body_declaration {
-            if self.strict == false {
-                stream.push(NO_STRICT.get_doc().to_string());
+    /**
+     * parse_enum:
+     * enum name { A, B = 2, C };
+     */
+    fn parse_enum(&mut self) -> ParseResult<EnumDef> {
+        let start = self.current_span();
+        self.expect(KeywordEnum, "`enum`")?;
+        let scoped = self.eat(KeywordClass) || self.eat(KeywordStruct);
+        let name = if self.check(Identifier) {
+            let name = self.bump()?.get_token_value();
+            self.type_names.push(name.clone());
+            Some(name)
+        } else {
+            None
+        };
+        let underlying = if self.eat(Colon) {
+            Some(self.parse_specifiers()?.ty.unqualified().clone())
+        } else {
+            None
+        };
+        self.expect(LeftCurlyBrace, "`{`")?;
+        let mut variants: Vec<Enumerator> = Vec::new();
+        while !self.check(RightCurlyBrace) {
+            let name = self.expect_identifier()?;
+            let value = if self.eat(Assignment) {
+                Some(self.parse_assignment_expr()?)
             } else {
-                stream.push(STRICT.get_doc().to_string());
-            }
-        }
-        //from `from` start declaration statement generation
-        let (_, sym_table_right) = self.sym_tab.split_at(self.from);
-        for i in sym_table_right {
-            // get identifier
-            //for declaration out of any blocks(global)
-            self.from += 1;
-            match i.symbol_modifier {
-                Modifier::Const => {
-                    stream.push("const".to_string());
-                }
-                _ => {
-                    if !self.struct_in_body_declaration {
-                        if self.strict == false {
-                            if self.in_block_stmnt == true {
-                                stream.push("let mut".to_string());
-                            } else {
-                                stream.push("static mut".to_string());
-                            }
-                        } else {
-                            if self.in_block_stmnt == true {
-                                stream.push("let".to_string());
-                            } else {
-                                stream.push("static".to_string());
-                            }
-                        }
-                    }
+                None
+            };
+            variants.push(Enumerator { name, value });
+            if !self.eat(Comma) {
+                break;
+            }
+        }
+        self.expect(RightCurlyBrace, "`}`")?;
+        Ok(EnumDef {
+            name,
+            scoped,
+            underlying,
+            variants,
+            span: self.span_from(start),
+        })
+    }
+
+    /**
+     * parse_function_rest:
+     * parses the parameter list and the body of a function whose
+     * name and return type were already read
+     */
+    fn parse_function_rest(
+        &mut self,
+        name: String,
+        return_type: CType,
+        start: Span,
+    ) -> ParseResult<Function> {
+        let (params, variadic) = self.parse_params()?;
+        //const qualified member function
+        let is_const = self.eat(KeywordConst);
+        //override and final are only keywords in this position
+        let mut is_virtual = false;
+        while self
+            .peek()
+            .is_some_and(|token| ["override", "final"].contains(&&*token.get_token_value()))
+        {
+            self.bump()?;
+            is_virtual = true;
+        }
+        //pure virtual member function, virtual int area() = 0;
+        let is_pure = self.check(Assignment) && self.check_nth(1, NumberInteger);
+        if is_pure {
+            self.bump()?;
+            self.bump()?;
+        }
+        //constructor member initializers, : a(1), b(2)
+        let mut initializers: Vec<Initializer> = Vec::new();
+        if self.eat(Colon) {
+            loop {
+                let member = self.expect_identifier()?;
+                let args = self.parse_args()?;
+                initializers.push(Initializer { member, args });
+                if !self.eat(Comma) {
+                    break;
                 }
             }
-            stream.push(i.id_name.clone());
-            stream.push(":".to_string());
+        }
 
-            if i.is_ptr == true {
-                stream.push("&".to_string());
+        let body = if self.eat(Semicolon) {
+            None
+        } else {
+            Some(self.parse_block()?)
+        };
+        Ok(Function {
+            name,
+            class: None,
+            return_type,
+            params,
+            variadic,
+            is_inline: false,
+            is_extern: false,
+            is_virtual: is_virtual || is_pure,
+            is_pure,
+            initializers,
+            template: Vec::new(),
+            is_static: false,
+            is_const,
+            body,
+            span: self.span_from(start),
+        })
+    }
 
-                if self.strict == false {
-                    stream.push("mut".to_string());
-                }
+    // rust has no traits for unary +, * and & to translate the operator to
+    fn check_operator(&self, function: &Function, is_member: bool) -> ParseResult<()> {
+        let operands = function.params.len() + usize::from(is_member);
+        match function.operator() {
+            Some(op) if operands == 1 && ["+", "*", "&"].contains(&op) => {
+                self.error(&format!("unary `operator{}` is not supported", op))
             }
-            // get the rust type
-            if let Some(rust_type) = parse_type(i.symbol_type, i.symbol_modifier) {
-                if rust_type == "_".to_string() {
-                    //not able to find the type, let the rust compiler do the type inference.
-                    stream.pop();
-                } else {
-                    stream.push(rust_type);
-                }
-            } else {
-                // if type parser dint return Some type, then it must be user defined type.
-                //TODO : should check the typedef table
-                stream.push(typdef_type.clone());
+            _ => Ok(()),
+        }
+    }
+
+    /**
+     * parse_operator_name:
+     * operator keyword and the symbol of the overloaded operator,
+     * operator+ and operator[], the operators without a rust trait
+     * are errors
+     */
+    fn parse_operator_name(&mut self) -> ParseResult<String> {
+        self.expect(KeywordOperator, "`operator`")?;
+        let symbol = match self.peek_type() {
+            Some(LeftSquareBracket) if self.check_nth(1, RightSquareBracket) => {
+                self.bump()?;
+                self.bump()?;
+                "[]".to_string()
+            }
+            Some(Plus)
+            | Some(Minus)
+            | Some(Multiplication)
+            | Some(Divide)
+            | Some(Module)
+            | Some(BitwiseAnd)
+            | Some(AddressOf)
+            | Some(BitwiseOr)
+            | Some(BitwiseXor)
+            | Some(BitwiseNegate)
+            | Some(LogicalNot)
+            | Some(BitwiseLeftShift)
+            | Some(BitwiseRightShift)
+            | Some(Equal)
+            | Some(NotEqual)
+            | Some(LessThan)
+            | Some(GreaterThan)
+            | Some(LessThanOrEqual)
+            | Some(GreaterThanOrEqual)
+            | Some(PlusEqual)
+            | Some(MinusEqual)
+            | Some(MultiplyEqual)
+            | Some(DivideEqual)
+            | Some(ModuleEqual)
+            | Some(BitwiseAndEqual)
+            | Some(BitwiseOrEqual)
+            | Some(BitwiseXorEqual)
+            | Some(LeftShiftEqual)
+            | Some(RightShiftEqual) => self.bump()?.get_token_value(),
+            Some(LeftBracket) => return self.error("`operator()` is not supported"),
+            Some(TokenType::Identifier) | Some(TokenType::Void) => {
+                return self.error("conversion operators are not supported")
+            }
+            _ if self
+                .peek()
+                .is_some_and(|token| token.get_type().0 == TokenKind::DataTypes) =>
+            {
+                return self.error("conversion operators are not supported")
+            }
+            _ => {
+                let symbol = self.peek().map(|token| token.get_token_value());
+                return self.error(&format!(
+                    "`operator{}` is not supported",
+                    symbol.unwrap_or_default()
+                ));
             }
+        };
+        Ok(format!("operator{}", symbol))
+    }
+
+    // (int a, char *b, ...) parameter list and whether it ends with `...`
+    fn parse_params(&mut self) -> ParseResult<(Vec<Param>, bool)> {
+        self.expect(LeftBracket, "`(`")?;
+        let mut params: Vec<Param> = Vec::new();
+        if self.check(Void) && self.check_nth(1, RightBracket) {
+            self.bump()?;
+        }
+        let mut variadic = false;
+        while !self.check(RightBracket) {
+            if self.eat(Ellipsis) {
+                variadic = true;
+                break;
+            }
+            let spec = self.parse_specifiers()?;
+            let (name, ty) = self.parse_abstract_declarator(&spec.ty)?;
+            if self.eat(Assignment) {
+                //default arguments have no rust equivalent
+                self.parse_assignment_expr()?;
+            }
+            params.push(Param { name, ty });
+            if !self.eat(Comma) {
+                break;
+            }
+        }
+        self.expect(RightBracket, "`)`")?;
+        Ok((params, variadic))
+    }
+
+    /*-------------------------- declarations --------------------------*/
 
-            // take care of assignment
-            if i.is_assigned {
-                stream.push("=".to_string());
-                if i.is_ptr == true {
-                    stream.push("&".to_string());
+    // whether the tokens at the head begin a declaration
+    fn is_declaration_start(&self) -> bool {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return false,
+        };
+        match token.get_type() {
+            (TokenKind::DataTypes, _) => true,
+            (_, Signed) | (_, Unsigned) | (_, KeywordStatic) | (_, KeywordConst) => true,
+            (_, KeywordExtern) | (_, KeywordInline) | (_, KeywordRegister) => true,
+            (_, KeywordVolatile) | (_, KeywordConstexpr) | (_, KeywordTypename) => true,
+            (_, KeywordStruct) | (_, KeywordUnion) | (_, KeywordClass) | (_, KeywordEnum) => true,
+            //Stack<int> s;
+            (_, Identifier) if self.is_template_at(0) => {
+                self.type_names.contains(&token.get_token_value())
+            }
+            (_, Identifier) => {
+                //qualified type name, std::ostream &out
+                let mut n = 1;
+                while self.check_nth(n, ScopeResolution) && self.is_path_segment_at(n + 1) {
+                    n += 2;
                 }
-                if self.strict == false && i.is_ptr == true {
-                    stream.push("mut".to_string());
+                //std::vector<int> v;
+                if n > 1 {
+                    n = self.skip_template_args(n);
                 }
-
-                stream.push((&i.assigned_val).to_string());
+                let known = n > 1 || self.type_names.contains(&token.get_token_value());
+                self.check_nth(n, Identifier)
+                    || self.check_nth(n, Main)
+                    || self.check_nth(n, KeywordOperator)
+                    || (known
+                        && (self.check_nth(n, Multiplication)
+                            || self.check_nth(n, BitwiseAnd)
+                            || self.check_nth(n, AddressOf)))
             }
-            if !argument_declaration {
-                stream.push(";".to_string());
+            _ => false,
+        }
+    }
+
+    // name after a `::` in a qualified type name, std::string names the string type
+    fn is_path_segment_at(&self, n: usize) -> bool {
+        self.check_nth(n, Identifier)
+            || self.peek_nth(n).is_some_and(|token| {
+                matches!(token.get_type(), (TokenKind::DataTypes, StringValue))
+            })
+    }
+
+    // whether the n-th token can start a type name, used for casts and sizeof
+    fn is_type_name_at(&self, n: usize) -> bool {
+        match self.peek_nth(n) {
+            Some(token) => match token.get_type() {
+                (TokenKind::DataTypes, _) => true,
+                (_, Signed) | (_, Unsigned) | (_, KeywordConst) => true,
+                (_, KeywordVolatile) | (_, KeywordTypename) => true,
+                (_, KeywordStruct) | (_, KeywordUnion) | (_, KeywordEnum) => true,
+                (_, Identifier) if self.check_nth(n + 1, ScopeResolution) => {
+                    self.is_qualified_type_at(n)
+                }
+                (_, Identifier) => self.type_names.contains(&token.get_token_value()),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    // ns::Name starting at the n-th token names a type of the unit or std::string
+    fn is_qualified_type_at(&self, n: usize) -> bool {
+        let first = self
+            .peek_nth(n)
+            .map(|token| token.get_token_value())
+            .unwrap_or_default();
+        let mut n = n;
+        while self.check_nth(n + 1, ScopeResolution) && self.is_path_segment_at(n + 2) {
+            n += 2;
+        }
+        match self.peek_nth(n) {
+            Some(token) => {
+                let last = token.get_token_value();
+                matches!(token.get_type(), (TokenKind::DataTypes, _))
+                    || self.type_names.contains(&last)
+                    || (first == "std" && std_type(&last).is_some())
             }
+            None => false,
         }
-        stream
     }
 
-    /* parse simple typedef definition of form
-     * typedef typename newtype;
+    /**
+     * parse_specifiers:
+     * reads storage class, qualifiers and the base type
+     * unsigned long int => Primitive(Long, Unsigned)
      */
-    fn parse_typdef(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-
-        let mut struct_token_index = 1;
-        let mut alias_type_index = 2;
-        //extract struct definition if present
-        //typdef struct strcutname{} alias_type;
-        let mut struct_lexeme: Vec<Token> = Vec::new();
-
-        if lexeme.get(1).unwrap().get_token_type() == TokenType::KeywordStruct {
-            let mut head: usize = 1;
-            while lexeme.get(head).unwrap().get_token_type() != TokenType::RightCurlyBrace {
-                struct_lexeme.push(lexeme.get(head).unwrap().clone());
-                head += 1;
-            }
-            struct_lexeme.push(lexeme.get(head).unwrap().clone());
-            //3 token will be struct name
-            struct_token_index = 2;
-            //move head to point to alias type token
-            head += 1;
-            alias_type_index = head;
-        }
-
-        let alias_type = lexeme[alias_type_index].get_token_value();
-        let mut struct_token = lexeme[struct_token_index].clone();
-
-        if alias_type == struct_token.get_token_value() {
-            //rust doesnt allow to have struct with same name as type alias, so we need to scramble the name here
-            let new_name = struct_token.get_token_value() + "_alas_t";
-            struct_lexeme[1].set_token_value(&new_name);
-            struct_token = struct_lexeme[1].clone();
-        }
-
-        stream.push("\n".to_string());
-        let mut parsed_struct = self.parse_struct(&struct_lexeme);
-        stream.append(&mut parsed_struct);
-
-        stream.push("type".to_string());
-        stream.push(alias_type.clone() + "=");
-        self.typde_def_table.push(alias_type.clone());
-        if let Some(typ) = parse_type(struct_token.get_token_type(), Modifier::Default) {
-            stream.push(typ);
+    fn parse_specifiers(&mut self) -> ParseResult<Specifiers> {
+        let mut modifier = Modifier::Default;
+        let mut primitive: Option<TokenType> = None;
+        let mut named: Option<String> = None;
+        let mut is_static = false;
+        let mut is_const = false;
+        let mut is_extern = false;
+        let mut is_volatile = false;
+        let mut is_inline = false;
+        let mut is_virtual = false;
+        let mut generic: Option<Vec<TemplateArg>> = None;
+
+        while let Some(token) = self.peek() {
+            match token.get_type() {
+                (_, KeywordStatic) => is_static = true,
+                (_, KeywordConst) | (_, KeywordConstexpr) => is_const = true,
+                (_, KeywordExtern) => is_extern = true,
+                (_, KeywordVolatile) => is_volatile = true,
+                (_, KeywordInline) => is_inline = true,
+                (_, KeywordVirtual) => is_virtual = true,
+                //no meaning left for rust
+                (_, KeywordRegister) | (_, KeywordExplicit) | (_, KeywordTypename) => {}
+                (_, Signed) => modifier = Modifier::Signed,
+                (_, Unsigned) => modifier = Modifier::Unsigned,
+                (TokenKind::DataTypes, token_type) => {
+                    primitive = Some(match (primitive, token_type) {
+                        (Some(Long), Integer) | (Some(Long), Long) => Long,
+                        (Some(Short), Integer) => Short,
+                        (Some(Long), Double) => Double,
+                        (_, token_type) => token_type,
+                    });
+                }
+                (_, KeywordStruct) | (_, KeywordUnion) | (_, KeywordClass) | (_, KeywordEnum) => {
+                    self.bump()?;
+                    if token.get_token_type() == KeywordEnum && !self.eat(KeywordClass) {
+                        self.eat(KeywordStruct);
+                    }
+                    named = Some(self.expect_identifier()?);
+                    continue;
+                }
+                (_, Identifier)
+                    if primitive.is_none()
+                        && named.is_none()
+                        && modifier == Modifier::Default
+                        && self.is_template_at(0) =>
+                {
+                    let name = self.bump()?.get_token_value();
+                    generic = Some(self.parse_template_args()?);
+                    named = Some(name);
+                    continue;
+                }
+                (_, Identifier)
+                    if primitive.is_none() && named.is_none() && modifier == Modifier::Default =>
+                {
+                    let mut name = token.get_token_value();
+                    //qualified type name, std::ostream
+                    while self.check_nth(1, ScopeResolution) && self.is_path_segment_at(2) {
+                        self.bump()?;
+                        self.bump()?;
+                        name.push_str("::");
+                        name.push_str(
+                            &self
+                                .peek()
+                                .map(|token| token.get_token_value())
+                                .unwrap_or_default(),
+                        );
+                    }
+                    //std::vector<int>, geo::Stack<int>
+                    let last = name.rsplit("::").next().unwrap_or_default().to_string();
+                    let is_template = self.templates.contains(&last)
+                        || (name.starts_with("std::") && is_std_template(&last));
+                    if name.contains("::") && is_template && self.check_nth(1, LessThan) {
+                        self.bump()?;
+                        generic = Some(self.parse_template_args()?);
+                        named = Some(name);
+                        continue;
+                    }
+                    named = Some(name);
+                }
+                _ => break,
+            }
+            self.bump()?;
+        }
+
+        let ty = match (primitive, named) {
+            (Some(token_type), _) => CType::Primitive(token_type, modifier),
+            (None, Some(name)) => match generic {
+                Some(args) => CType::Generic(name, args),
+                None => CType::Named(name),
+            },
+            //unsigned a; => unsigned int a;
+            (None, None) if modifier != Modifier::Default => CType::Primitive(Integer, modifier),
+            (None, None) => return self.error("expected type"),
+        };
+        //const T * keeps its constness, it decides between *const and *mut
+        let ty = if is_const {
+            CType::Const(Box::new(ty))
         } else {
-            stream.push(struct_token.get_token_value());
-        }
-        stream.push(";".to_string());
-        return stream;
+            ty
+        };
+        Ok(Specifiers {
+            ty,
+            is_static,
+            is_const,
+            is_extern,
+            is_volatile,
+            is_inline,
+            is_virtual,
+        })
     }
 
     /**
-     * parse_if:
-     * parse c/c++ if statements into rust
-     * equivalent statements
+     * parse_declarator:
+     * reads pointers, name and array dimensions of one declarator
+     * int *a[10] => (a, Array(Pointer(int), 10))
      */
-    fn parse_if(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-
-        stream.push("if".to_string());
-        stream.push("(".to_string());
-        head += 1;
-
-        //skip '('
-        head += 1;
-
-        // condition
-        while lexeme[head].get_token_type() != RightBracket {
-            stream.push(lexeme[head].get_token_value());
-            head += 1;
+    fn parse_declarator(&mut self, base: &CType) -> ParseResult<(String, CType)> {
+        match self.parse_abstract_declarator(base)? {
+            (Some(name), ty) => Ok((name, ty)),
+            (None, _) => self.error("expected identifier"),
         }
-        head += 1;
-        stream.push(")".to_string());
-        stream.push("== true".to_string());
-        stream.push("{".to_string());
+    }
 
-        if lexeme[head].get_token_type() == LeftCurlyBrace {
-            head += 1;
+    // declarator with an optional name, as in prototypes and casts
+    fn parse_abstract_declarator(&mut self, base: &CType) -> ParseResult<(Option<String>, CType)> {
+        //only the type a pointer points to stays const
+        let mut ty = if self.check(Multiplication) {
+            base.clone()
+        } else {
+            base.unqualified().clone()
+        };
+        while self.eat(Multiplication) {
+            ty = CType::Pointer(Box::new(ty));
+            while self.eat(KeywordConst) || self.eat(KeywordVolatile) {}
+        }
+        //int &r, rvalue references are taken like the value they refer to
+        if self.eat(BitwiseAnd) || self.eat(AddressOf) || self.eat(LogicalAnd) {
+            ty = CType::Reference(Box::new(ty));
+        }
+        //int (*cmp)(int, int) and void (*handlers[4])(int)
+        if self.check(LeftBracket) && self.check_nth(1, Multiplication) {
+            return self.parse_function_pointer(ty);
+        }
+        let mut name = match self.peek_type() {
+            Some(Identifier) | Some(Main) => Some(self.bump()?.get_token_value()),
+            Some(KeywordOperator) => Some(self.parse_operator_name()?),
+            _ => None,
+        };
+        //member of a class template defined outside of it, Stack<T>::push
+        if name
+            .as_ref()
+            .is_some_and(|name| self.templates.contains(name))
+            && self.check(LessThan)
+        {
+            self.parse_template_args()?;
         }
-
-        // collect if body
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while head < lexeme.len() {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
+        //qualified name of a member defined outside of its class, A::f
+        while let Some(ref mut path) = name {
+            if !self.eat(ScopeResolution) {
+                break;
+            }
+            path.push_str("::");
+            if self.check(KeywordOperator) {
+                path.push_str(&self.parse_operator_name()?);
+            } else {
+                path.push_str(&self.expect_identifier()?);
+            }
         }
-        // parse if body
-        stream.append(&mut self.parse_program(&temp_lexeme));
-
-        stream.push("}".to_string());
-        stream
+        let ty = self.parse_dimensions(ty)?;
+        Ok((name, ty))
     }
 
     /**
-     * parse_while:
-     * parse c/c++ while statements into rust
-     * equivalent statements
+     * parse_function_pointer:
+     * (*name)(params) after the return type, the dimensions inside the
+     * parentheses make an array of function pointers
      */
-    fn parse_while(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        let mut no_cond = false;
-        head += 1;
-
-        //skip '('
-        head += 1;
-        // condition
-        let mut cond_stream: Vec<String> = Vec::new();
-        while lexeme[head].get_token_type() != RightBracket {
-            cond_stream.push(lexeme[head].get_token_value());
-            head += 1;
-        }
-        if cond_stream.len() == 1
-            && (cond_stream[0] == "1".to_string() || cond_stream[0] == "true".to_string())
-        {
-            no_cond = true;
+    fn parse_function_pointer(&mut self, ret: CType) -> ParseResult<(Option<String>, CType)> {
+        self.expect(LeftBracket, "`(`")?;
+        let mut pointers = 0;
+        while self.eat(Multiplication) {
+            pointers += 1;
+            while self.eat(KeywordConst) || self.eat(KeywordVolatile) {}
+        }
+        let name = match self.peek_type() {
+            Some(Identifier) => Some(self.bump()?.get_token_value()),
+            _ => None,
+        };
+        let mut dimensions: Vec<Option<Box<Expr>>> = Vec::new();
+        while self.check(LeftSquareBracket) {
+            dimensions.push(self.parse_dimension()?);
+        }
+        self.expect(RightBracket, "`)` after function pointer name")?;
+        let (params, _) = self.parse_params()?;
+        let mut ty = CType::Function(
+            Box::new(ret),
+            params.into_iter().map(|param| param.ty).collect(),
+        );
+        for _ in 0..pointers {
+            ty = CType::Pointer(Box::new(ty));
         }
-        head += 1;
-
-        if lexeme[head].get_token_type() == LeftCurlyBrace {
-            head += 1;
+        for length in dimensions.into_iter().rev() {
+            ty = CType::Array(Box::new(ty), length);
         }
+        Ok((name, ty))
+    }
 
-        // collect while body
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while head < lexeme.len() {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
+    // [n][m] after a declarator name
+    fn parse_dimensions(&mut self, element: CType) -> ParseResult<CType> {
+        let mut ty = element;
+        let mut dimensions: Vec<Option<Box<Expr>>> = Vec::new();
+        while self.check(LeftSquareBracket) {
+            dimensions.push(self.parse_dimension()?);
         }
-        // parse while body
-        let mut body_stream = &mut self.parse_program(&temp_lexeme);
-
-        if no_cond == true {
-            stream.push("loop".to_string());
-        } else {
-            stream.push("while".to_string());
-            stream.push("(".to_string());
-            stream.append(&mut cond_stream);
-            stream.push(")".to_string());
-            stream.push("== true".to_string());
+        //int a[2][3] is an array of 2 arrays of 3 ints
+        for length in dimensions.into_iter().rev() {
+            ty = CType::Array(Box::new(ty), length);
         }
-        stream.push("{".to_string());
-        stream.append(&mut body_stream);
+        Ok(ty)
+    }
 
-        stream.push("}".to_string());
-        stream
+    fn parse_dimension(&mut self) -> ParseResult<Option<Box<Expr>>> {
+        self.expect(LeftSquareBracket, "`[`")?;
+        if self.eat(RightSquareBracket) {
+            return Ok(None);
+        }
+        let length = self.parse_expr()?;
+        self.expect(RightSquareBracket, "`]`")?;
+        Ok(Some(Box::new(length)))
     }
 
-    /**
-     * parse_dowhile:
-     * parse c/c++ do while statements into rust
-     * equivalent statements
-     */
-    fn parse_dowhile(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut temp_stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        let mut lookahead: usize;
-
-        head += 2;
-        lookahead = head;
-
-        lookahead = skip_block(&lexeme, lookahead) - 1;
-        // collect while body
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while head < lookahead {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
-        }
-        // parse while body
-
-        temp_stream.append(&mut self.parse_program(&temp_lexeme));
-        temp_lexeme.clear();
-
-        head += 3;
-        if (lexeme[head].get_token_value() == String::from("1")
-            || lexeme[head].get_token_value() == String::from("true"))
-            && lexeme[head + 1].get_token_type() == RightBracket
-        {
-            stream.push("loop".to_string());
-            stream.push("{".to_string());
-            stream.append(&mut temp_stream);
+    fn parse_type_name(&mut self) -> ParseResult<CType> {
+        let spec = self.parse_specifiers()?;
+        let (_, ty) = self.parse_abstract_declarator(&spec.ty)?;
+        Ok(ty)
+    }
 
-            stream.push("}".to_string());
-        } else {
-            stream.push("while".to_string());
-            stream.push("{".to_string());
-            stream.append(&mut temp_stream);
-            stream.push("(".to_string());
-            while lexeme[head].get_token_type() != RightBracket {
-                stream.push(lexeme[head].get_token_value());
-                head += 1;
-            }
-            stream.push(")".to_string());
-            stream.push("== true".to_string());
-            stream.push("}".to_string());
-            stream.push("{".to_string());
-            stream.push("}".to_string());
-        }
-        stream.push(";".to_string());
-        stream
-    }
-
-    fn parse_switch(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut head: usize = 2;
-        let mut lookahead: usize = 2;
-        let mut stream: Vec<String> = Vec::new();
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-
-        stream.push("match".to_string());
-
-        // find starting of switch block
-        while lexeme[lookahead].get_token_type() != LeftCurlyBrace {
-            lookahead += 1;
-        }
-        // {
-        // move back to find the variable/result to be matched
-        lookahead -= 1;
-        // single variable
-        if lookahead - head == 1 {
-            stream.push(lexeme[lookahead - 1].get_token_value());
-        }
-        // expression
-        else {
-            while head < lookahead {
-                let l: Token = lexeme[head].clone();
-                temp_lexeme.push(l);
-                head += 1;
-            }
-            head -= 1;
-            stream.append(&mut self.parse_program(&temp_lexeme));
-
-            temp_lexeme.clear();
-        }
-        // move forward to the starting of the block
-        head += 3;
-        stream.push("{".to_string());
-
-        //head is at case
-        lookahead = skip_block(&lexeme, head) - 1;
-        while head < lookahead {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
-        }
-        stream.append(&mut self.parse_case(&temp_lexeme));
-        stream.push("}".to_string());
-        stream
-    }
-
-    fn parse_case(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        //head is at case
-        let mut head: usize = 0;
-        let mut lookahead: usize;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        let mut def: bool = false;
-        //look whether default case is handled for exaustive search
-        while head < lexeme.len() {
-            if lexeme[head].get_token_type() == KeywordDefault {
-                stream.push("_".to_string());
-                def = true;
-            } else {
-                head += 1; //head is at matching value
-                stream.push(lexeme[head].get_token_value());
-            }
+    // parses a complete declaration statement at the head
+    fn parse_declaration(&mut self) -> ParseResult<Declaration> {
+        let spec = self.parse_specifiers()?;
+        self.parse_declaration_after(spec)
+    }
 
-            head += 1; // head is at :
-            stream.push("=>".to_string());
+    fn parse_declaration_after(&mut self, spec: Specifiers) -> ParseResult<Declaration> {
+        let start = self.current_span();
+        let (name, ty) = self.parse_declarator(&spec.ty)?;
+        self.parse_declaration_rest(&spec, name, ty, start)
+    }
 
-            // either brace or no brace
-            head += 1;
-            if lexeme[head].get_token_type() == LeftCurlyBrace {
-                head += 1;
-                lookahead = skip_block(&lexeme, head) - 1;
-            } else {
-                lookahead = head;
-                while lookahead < lexeme.len()
-                    && lexeme[lookahead].get_token_type() != KeywordCase
-                    && lexeme[lookahead].get_token_type() != KeywordDefault
+    /**
+     * parse_declaration_rest:
+     * parses the initializer of the first declarator and the remaining
+     * comma separated declarators up to the semicolon
+     */
+    fn parse_declaration_rest(
+        &mut self,
+        spec: &Specifiers,
+        name: String,
+        ty: CType,
+        start: Span,
+    ) -> ParseResult<Declaration> {
+        let mut declarators: Vec<Declarator> = Vec::new();
+        let mut current = (name, ty, start);
+        loop {
+            let (name, ty, declarator_start) = current;
+            let init = match ty {
+                _ if self.eat(Assignment) => Some(self.parse_initializer()?),
+                //A a(1, 2); calls the constructor like A a = A(1, 2);
+                CType::Named(ref class) | CType::Generic(ref class, _)
+                    if self.check(LeftBracket) =>
                 {
-                    lookahead += 1;
+                    let callee_start = self.current_span();
+                    let args = self.parse_args()?;
+                    let span = self.span_from(callee_start);
+                    let callee = Expr::new(ExprKind::Ident(class.clone()), span);
+                    Some(Expr::new(ExprKind::Call(Box::new(callee), args), span))
                 }
+                _ => None,
+            };
+            declarators.push(Declarator {
+                name,
+                ty,
+                init,
+                span: self.span_from(declarator_start),
+            });
+            if !self.eat(Comma) {
+                break;
             }
-            while head < lookahead {
-                let l: Token = lexeme[head].clone();
-                temp_lexeme.push(l);
-                head += 1;
-            }
-            stream.push("{".to_string());
-            stream.append(&mut self.parse_program(&temp_lexeme));
-            stream.push("}".to_string());
+            let next_start = self.current_span();
+            let (name, ty) = self.parse_declarator(&spec.ty)?;
+            current = (name, ty, next_start);
+        }
+        self.expect(Semicolon, "`;` after declaration")?;
+        Ok(Declaration {
+            is_static: spec.is_static,
+            is_const: spec.is_const,
+            is_extern: spec.is_extern,
+            is_volatile: spec.is_volatile,
+            declarators,
+            span: self.span_from(start),
+        })
+    }
 
-            if head < lexeme.len() && lexeme[head].get_token_type() == RightCurlyBrace {
-                head += 1;
+    fn parse_initializer(&mut self) -> ParseResult<Expr> {
+        if !self.check(LeftCurlyBrace) {
+            return self.parse_assignment_expr();
+        }
+        let start = self.current_span();
+        self.bump()?;
+        let mut values: Vec<Expr> = Vec::new();
+        while !self.check(RightCurlyBrace) {
+            values.push(self.parse_initializer()?);
+            if !self.eat(Comma) {
+                break;
             }
-            temp_lexeme.clear();
         }
-        if def == false {
-            stream.push("_".to_string());
-            stream.push("=>".to_string());
-            stream.push("{".to_string());
-            stream.push("}".to_string());
-        }
-        stream
+        self.expect(RightCurlyBrace, "`}`")?;
+        Ok(Expr::new(ExprKind::InitList(values), self.span_from(start)))
     }
 
-    /**
-     * parse_for:
-     * parse c/c++ do while statements into rust
-     * equivalent statements
-     *
-     * Identify infinite loops and replace for with loop{}
-     */
-    fn parse_for(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        let mut lookahead: usize;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-
-        while lexeme[head].get_token_type() != LeftBracket {
-            head += 1;
-        }
-        head += 1;
-        lookahead = head;
+    /*-------------------------- statements --------------------------*/
 
-        //for (int i =0; )
-        let decl: bool = if lexeme[head].get_token_kind() == TokenKind::DataTypes {
-            true
-        } else {
-            false
-        };
-        // let mut no_init:bool; //no initialization
-        let mut no_cond: bool = false; //if no condition to terminate
-        let mut no_updation: bool = false; //no inc/dec of loop counter
-
-        let mut body: Vec<String> = Vec::new();
-        let mut updation: Vec<String> = Vec::new();
-        let mut term_cond: Vec<String> = Vec::new();
-        // initial assignment
-        lookahead = skip_stmt(&lexeme, lookahead);
-
-        //incase of initialization expression for (;i<10;i++) ; common case
-        if head + 1 < lookahead {
-            while head < lookahead {
-                let l: Token = lexeme[head].clone();
-                temp_lexeme.push(l);
-                head += 1;
-            }
-
-            if decl == true {
-                stream.append(&mut self.parse_declaration(&temp_lexeme, false));
-            } else {
-                stream.append(&mut self.parse_assignment(&temp_lexeme));
+    // { stmt* }
+    fn parse_block(&mut self) -> ParseResult<Vec<Stmt>> {
+        self.expect(LeftCurlyBrace, "`{`")?;
+        let mut stmts: Vec<Stmt> = Vec::new();
+        while !self.check(RightCurlyBrace) {
+            if self.raw_comment().is_none() && self.at_end() {
+                return self.error("expected `}`");
             }
-        } else {
-            head += 1;
-            // no_init = true;
-        }
-        temp_lexeme.clear();
+            stmts.push(self.parse_stmt_or_recover());
+        }
+        //comments before the closing brace
+        while let Some(comment) = self.raw_comment() {
+            let span = token_span(&self.lexeme[self.head]);
+            self.head += 1;
+            stmts.push(Stmt {
+                kind: StmtKind::Comment(comment),
+                span,
+            });
+        }
+        self.expect(RightCurlyBrace, "`}`")?;
+        Ok(stmts)
+    }
 
-        // terminating condition
-        lookahead = skip_stmt(&lexeme, lookahead);
+    // parses one statement, a failed statement is kept as an unsupported region
+    fn parse_stmt_or_recover(&mut self) -> Stmt {
+        let head = self.head;
+        let result = self.parse_stmt();
+        self.or_recover(head, result, |unsupported| Stmt {
+            span: unsupported.span,
+            kind: StmtKind::Unsupported(unsupported),
+        })
+    }
 
-        if head + 1 < lookahead {
-            while head < lookahead - 1 {
-                term_cond.push(lexeme[head].get_token_value());
-                head += 1;
+    fn parse_stmt(&mut self) -> ParseResult<Stmt> {
+        if let Some(comment) = self.raw_comment() {
+            let span = token_span(&self.lexeme[self.head]);
+            self.head += 1;
+            return Ok(Stmt {
+                kind: StmtKind::Comment(comment),
+                span,
+            });
+        }
+        let start = self.current_span();
+        let kind = match self.peek_type() {
+            Some(LeftCurlyBrace) => StmtKind::Block(self.parse_block()?),
+            Some(KeywordIf) => self.parse_if()?,
+            Some(KeywordWhile) => self.parse_while()?,
+            Some(KeywordDo) => self.parse_dowhile()?,
+            Some(KeywordFor) => self.parse_for()?,
+            Some(KeywordSwitch) => self.parse_switch()?,
+            Some(KeywordBreak) => {
+                self.bump()?;
+                self.expect(Semicolon, "`;` after break")?;
+                StmtKind::Break
             }
-        } else {
-            no_cond = true;
-        }
-        head += 1;
-        temp_lexeme.clear();
-
-        lookahead = head;
-        // update expression
-        while lexeme[lookahead].get_token_type() != RightBracket {
-            let l: Token = lexeme[lookahead].clone();
-            temp_lexeme.push(l);
-            lookahead += 1;
-        }
-        //no_updation
-        if head == lookahead {
-            no_updation = true;
-        } else {
-            temp_lexeme.push(Token::new(
-                String::from(";"),
-                TokenKind::SpecialChars,
-                Semicolon,
-                0,
-                0,
-            ));
-            updation.append(&mut self.parse_program(&temp_lexeme));
-            temp_lexeme.clear();
-        }
-        head = lookahead;
-        head += 1;
-        if lexeme[head].get_token_type() == LeftCurlyBrace {
-            head += 1;
-            lookahead = skip_block(&lexeme, head);
-        } else {
-            lookahead = skip_stmt(&lexeme, head);
-        }
+            Some(KeywordContinue) => {
+                self.bump()?;
+                self.expect(Semicolon, "`;` after continue")?;
+                StmtKind::Continue
+            }
+            //goto *p; jumps to a computed address
+            Some(KeywordGoto) if !self.check_nth(1, Identifier) => StmtKind::Unsupported(
+                self.skip_construct("computed `goto` is not supported".to_string()),
+            ),
+            Some(KeywordGoto) => {
+                self.bump()?;
+                let label = self.expect_identifier()?;
+                self.expect(Semicolon, "`;` after goto")?;
+                StmtKind::Goto(label)
+            }
+            Some(Identifier) if self.check_nth(1, Colon) => {
+                let label = self.expect_identifier()?;
+                self.bump()?;
+                StmtKind::Label(label)
+            }
+            Some(KeywordReturn) => {
+                self.bump()?;
+                let value = if self.check(Semicolon) {
+                    None
+                } else {
+                    Some(self.parse_expr()?)
+                };
+                self.expect(Semicolon, "`;` after return")?;
+                StmtKind::Return(value)
+            }
+            Some(Semicolon) => {
+                self.bump()?;
+                StmtKind::Empty
+            }
+            _ if self.unsupported_ahead().is_some() => {
+                StmtKind::Unsupported(self.parse_unsupported())
+            }
+            _ if self.is_declaration_start() => StmtKind::Declaration(self.parse_declaration()?),
+            _ => {
+                let expr = self.parse_expr()?;
+                self.expect(Semicolon, "`;` after expression")?;
+                StmtKind::Expr(expr)
+            }
+        };
+        Ok(Stmt {
+            kind,
+            span: self.span_from(start),
+        })
+    }
 
-        // lookahead = skip_block(&lexeme, lookahead);
-        while head < lookahead {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
-        }
-        body.append(&mut self.parse_program(&temp_lexeme));
+    // (condition)
+    fn parse_condition(&mut self) -> ParseResult<Expr> {
+        self.expect(LeftBracket, "`(`")?;
+        let cond = self.parse_expr()?;
+        self.expect(RightBracket, "`)`")?;
+        Ok(cond)
+    }
 
-        if no_cond == true {
-            stream.push("loop".to_string());
+    /**
+     * parse_if:
+     * if (cond) stmt [else stmt]
+     */
+    fn parse_if(&mut self) -> ParseResult<StmtKind> {
+        self.expect(KeywordIf, "`if`")?;
+        let cond = self.parse_condition()?;
+        let then = Box::new(self.parse_stmt()?);
+        let otherwise = if self.eat(KeywordElse) {
+            Some(Box::new(self.parse_stmt()?))
         } else {
-            stream.push("while".to_string());
-            stream.append(&mut term_cond); //append termianating condition
-        }
-        stream.push("{".to_string());
-        stream.append(&mut body);
-        if no_updation != true {
-            stream.append(&mut updation);
-        }
+            None
+        };
+        Ok(StmtKind::If {
+            cond,
+            then,
+            otherwise,
+        })
+    }
 
-        stream.push("}".to_string());
+    fn parse_while(&mut self) -> ParseResult<StmtKind> {
+        self.expect(KeywordWhile, "`while`")?;
+        let cond = self.parse_condition()?;
+        let body = Box::new(self.parse_stmt()?);
+        Ok(StmtKind::While { cond, body })
+    }
 
-        stream
+    fn parse_dowhile(&mut self) -> ParseResult<StmtKind> {
+        self.expect(KeywordDo, "`do`")?;
+        let body = Box::new(self.parse_stmt()?);
+        self.expect(KeywordWhile, "`while` after do block")?;
+        let cond = self.parse_condition()?;
+        self.expect(Semicolon, "`;` after do while")?;
+        Ok(StmtKind::DoWhile { body, cond })
     }
 
-    /* parse_assignment:
-     * parse c/c++ assignment statements into rust equivalent code
-     * compound assignments must be converted to declarations
-     * as rust doesnt support compound assignment
+    /**
+     * parse_for:
+     * for (init; cond; step) stmt, every clause is optional
      */
-    fn parse_assignment(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        // let mut lookahead = lexeme.len();
-        let mut thead: usize = 2;
-        let mut lexeme1: Vec<Token> = Vec::new();
-
-        let mut n = 2;
-        let m = 3;
-        let mut tstream: Vec<String> = Vec::new();
-        if lexeme[n].get_token_kind() == TokenKind::UnaryOperators {
-            while lexeme[thead].get_token_type() != Semicolon {
-                lexeme1.push(lexeme[thead].clone());
-                thead += 1;
-            }
-            lexeme1.push(lexeme[thead].clone());
-            stream.push(lexeme[0].get_token_value());
-            stream.push(lexeme[1].get_token_value());
-            stream.append(&mut self.parse_expr(&lexeme1));
-        } else if lexeme[m].get_token_kind() == TokenKind::UnaryOperators {
-            while lexeme[thead].get_token_type() != Semicolon {
-                lexeme1.push(lexeme[thead].clone());
-                thead += 1;
-            }
-            lexeme1.push(lexeme[thead].clone());
-            stream.push(lexeme[0].get_token_value());
-            stream.push(lexeme[1].get_token_value());
-            stream.append(&mut self.parse_expr(&lexeme1));
-        } else if lexeme[m].get_token_kind() == TokenKind::BinaryOperators {
-            while lexeme[thead].get_token_type() != Semicolon {
-                lexeme1.push(lexeme[thead].clone());
-                thead += 1;
-            }
-            lexeme1.push(lexeme[thead].clone());
-            stream.push(lexeme[0].get_token_value());
-            stream.push(lexeme[1].get_token_value());
-            stream.append(&mut self.parse_expr(&lexeme1));
-        } else if lexeme[n].get_token_type() == BitwiseAnd {
-            stream.push(lexeme[0].get_token_value());
-            stream.push(lexeme[1].get_token_value());
-
-            while lexeme[thead].get_token_type() != Semicolon {
-                stream.push(lexeme[thead].get_token_value());
-                thead += 1;
-            }
+    fn parse_for(&mut self) -> ParseResult<StmtKind> {
+        self.expect(KeywordFor, "`for`")?;
+        self.expect(LeftBracket, "`(`")?;
+
+        let init = if self.check(Semicolon) {
+            self.bump()?;
+            None
         } else {
-            if lexeme[m].get_token_type() == Assignment {
-                while lexeme[thead].get_token_type() != Semicolon
-                    && lexeme[thead].get_token_type() != Comma
-                {
-                    lexeme1.push(lexeme[thead].clone());
-                    thead += 1;
-                }
-                lexeme1.push(lexeme[thead].clone());
-                stream.append(&mut self.parse_program(&lexeme1));
-            }
-            stream.push(lexeme[0].get_token_value());
-            stream.push(lexeme[1].get_token_value());
-            if lexeme[n].get_token_kind() == TokenKind::UnaryOperators {
-                stream.push(lexeme[m].get_token_value());
+            let start = self.current_span();
+            let kind = if self.is_declaration_start() {
+                StmtKind::Declaration(self.parse_declaration()?)
             } else {
-                stream.push(lexeme[n].get_token_value());
-                n += 1;
-                if lexeme[n].get_token_type() == LeftBracket
-                    || lexeme[n].get_token_type() == LeftSquareBracket
-                {
-                    while lexeme[n].get_token_type() != Semicolon {
-                        stream.push(lexeme[n].get_token_value());
-                        n += 1;
-                    }
-                }
-                stream.push(";".to_string());
-            }
-        }
-        if tstream.len() > 0 {
-            stream.append(&mut tstream);
-        }
-        stream
+                let expr = self.parse_expr()?;
+                self.expect(Semicolon, "`;`")?;
+                StmtKind::Expr(expr)
+            };
+            Some(Box::new(Stmt {
+                kind,
+                span: self.span_from(start),
+            }))
+        };
+        let cond = if self.check(Semicolon) {
+            None
+        } else {
+            Some(self.parse_expr()?)
+        };
+        self.expect(Semicolon, "`;`")?;
+        let step = if self.check(RightBracket) {
+            None
+        } else {
+            Some(self.parse_expr()?)
+        };
+        self.expect(RightBracket, "`)`")?;
+        let body = Box::new(self.parse_stmt()?);
+        Ok(StmtKind::For {
+            init,
+            cond,
+            step,
+            body,
+        })
     }
 
-    /* parse_expr:
-     * parse c/c++ expression statements into rust equivalent code
+    /**
+     * parse_switch:
+     * switch (cond) { case v: stmt* ... default: stmt* }
+     * statements are attached to the case label preceding them
      */
-    fn parse_expr(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        // let mut lookahead = lexeme.len();
-        let mut tstream: Vec<String> = Vec::new();
-        let mut thead: usize = 0;
-
-        let mut prev_id = " ".to_string();
-        let mut typ = Others;
-        //a=b+c++;
-        while thead < lexeme.len() && lexeme[thead].get_token_type() != Semicolon {
-            if lexeme[thead].get_token_kind() == TokenKind::UnaryOperators {
-                if lexeme[thead].get_token_type() == SizeOf {
-                    stream.push("std::mem::size_of(".to_string());
-                    thead += 2;
-                    if lexeme[thead].get_token_kind() == TokenKind::DataTypes {
-                        if let Some(t) =
-                            parse_type(lexeme[thead].get_token_type(), Modifier::Default)
-                        {
-                            stream.push(t)
-                        }
-                    } else {
-                        stream.push(lexeme[thead].get_token_value());
-                    }
-                    stream.push(")".to_string());
-                    thead += 1;
+    fn parse_switch(&mut self) -> ParseResult<StmtKind> {
+        self.expect(KeywordSwitch, "`switch`")?;
+        let cond = self.parse_condition()?;
+        self.expect(LeftCurlyBrace, "`{`")?;
+
+        let mut cases: Vec<SwitchCase> = Vec::new();
+        while !self.check(RightCurlyBrace) {
+            let start = self.current_span();
+            let is_label = self.raw_comment().is_none();
+            let label = if is_label && self.eat(KeywordCase) {
+                let value = self.parse_assignment_expr()?;
+                if self.eat(Ellipsis) {
+                    Some(CaseLabel::Range(value, self.parse_assignment_expr()?))
                 } else {
-                    //incase of post
-                    if typ == Identifier {
-                        tstream.push(prev_id.clone());
-                        tstream.push(match lexeme[thead].get_token_type() {
-                            Increment => "+=1".to_string(),
-                            Decrement => "-=1".to_string(),
-                            _ => " ;".to_string(),
-                        });
-                        tstream.push(";".to_string());
-
-                        thead += 1;
-                    //continue;
-                    }
-                    // incase of pre
-                    else {
-                        stream.push("(".to_string());
-                        stream.push(lexeme[thead + 1].get_token_value());
-                        stream.push(match lexeme[thead].get_token_type() {
-                            Increment => "+=1".to_string(),
-                            Decrement => "-=1".to_string(),
-                            _ => " ;".to_string(),
-                        });
-                        stream.push(")".to_string());
-                        thead += 1;
-                    }
+                    Some(CaseLabel::Value(value))
                 }
-            } else if lexeme[thead].get_token_kind() == TokenKind::SpecialChars {
-                if lexeme[thead].get_token_type() == LeftBracket
-                    && lexeme[thead + 1].get_token_kind() == TokenKind::DataTypes
-                {
-                    //type cast expression.
-                    let mut temp_lexeme: Vec<Token> = Vec::new();
-                    while thead < lexeme.len()
-                        && lexeme[thead].get_token_type() != Semicolon
-                        && lexeme[thead].get_token_type() != Comma
-                    {
-                        temp_lexeme.push(lexeme[thead].clone());
-                        thead += 1;
+            } else if is_label && self.eat(KeywordDefault) {
+                Some(CaseLabel::Default)
+            } else {
+                None
+            };
+
+            match label {
+                Some(label) => {
+                    self.expect(Colon, "`:` after case label")?;
+                    cases.push(SwitchCase {
+                        label,
+                        body: Vec::new(),
+                        span: self.span_from(start),
+                    });
+                }
+                None => {
+                    if self.raw_comment().is_none() && self.at_end() {
+                        return self.error("expected `}`");
+                    }
+                    let stmt = self.parse_stmt_or_recover();
+                    match cases.last_mut() {
+                        Some(case) => case.body.push(stmt),
+                        None => {
+                            if let StmtKind::Comment(_) = stmt.kind {
+                                continue;
+                            }
+                            return Err(ParseError {
+                                message: "statement before the first case label".to_string(),
+                                span: stmt.span,
+                            });
+                        }
                     }
-                    let mut parsed_stmnt = self.parse_typecast(&temp_lexeme);
-                    stream.append(&mut parsed_stmnt);
-                    //move back from end of statement, so next move thead inc will not panic
-                    thead -= 1;
                 }
-            } else {
-                stream.push(lexeme[thead].get_token_value());
             }
+        }
+        self.expect(RightCurlyBrace, "`}`")?;
+        Ok(StmtKind::Switch { cond, cases })
+    }
 
-            typ = lexeme[thead].get_token_type();
-            prev_id = lexeme[thead].get_token_value();
+    /*-------------------------- expressions --------------------------*/
 
-            thead += 1;
+    /**
+     * parse_expr:
+     * expression including the comma operator
+     */
+    pub fn parse_expr(&mut self) -> ParseResult<Expr> {
+        let first = self.parse_assignment_expr()?;
+        if !self.check(Comma) {
+            return Ok(first);
         }
-        stream.push(";".to_string());
-        if tstream.len() > 0 {
-            stream.append(&mut tstream);
+        let start = first.span;
+        let mut exprs = vec![first];
+        while self.eat(Comma) {
+            exprs.push(self.parse_assignment_expr()?);
         }
-        stream
+        Ok(Expr::new(ExprKind::Comma(exprs), self.span_from(start)))
     }
 
-    /**
-     * Parse simple type case statement of form (int)a or (int *)a
-     * */
-    fn parse_typecast(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-
-        //get the type
-
-        let mut lookahead: usize = 0;
+    // assignment is right associative : a = b = c => a = (b = c)
+    fn parse_assignment_expr(&mut self) -> ParseResult<Expr> {
+        let lhs = self.parse_conditional()?;
+        let op = match self.peek_type() {
+            Some(Assignment) => None,
+            Some(token_type) => match BinaryOp::from_assign_token(token_type) {
+                Some(op) => Some(op),
+                None => return Ok(lhs),
+            },
+            None => return Ok(lhs),
+        };
+        self.bump()?;
+        let rhs = self.parse_assignment_expr()?;
+        let span = lhs.span.to(rhs.span);
+        Ok(Expr::new(
+            ExprKind::Assign(op, Box::new(lhs), Box::new(rhs)),
+            span,
+        ))
+    }
 
-        while lexeme[lookahead].get_token_type() != RightBracket {
-            lookahead += 1;
-        }
+    // ternary is right associative : a ? b : c ? d : e => a ? b : (c ? d : e)
+    fn parse_conditional(&mut self) -> ParseResult<Expr> {
+        let cond = self.parse_binary(1)?;
+        if !self.eat(TernaryOpetator) {
+            return Ok(cond);
+        }
+        let then = self.parse_expr()?;
+        self.expect(Colon, "`:` in conditional expression")?;
+        let otherwise = self.parse_assignment_expr()?;
+        let span = cond.span.to(otherwise.span);
+        Ok(Expr::new(
+            ExprKind::Conditional(Box::new(cond), Box::new(then), Box::new(otherwise)),
+            span,
+        ))
+    }
 
-        //lookahead - 1 is *, then its a pointer
-        let is_ptr = if lexeme[lookahead - 1].get_token_type() == Multiplication {
-            true
-        } else {
-            false
-        };
-        let type_index = lookahead - 2;
-        //move lookahead to poit to expression
-        lookahead += 1;
-        while lookahead < lexeme.len() {
-            //TODO: skip address of op operator ?
+    // binary operator at the cursor
+    fn peek_binary_op(&self) -> Option<BinaryOp> {
+        self.peek_type().and_then(BinaryOp::from_token)
+    }
 
-            stream.push(lexeme[lookahead].get_token_value());
-            if lexeme[lookahead].get_token_type() == BitwiseAnd && !self.strict {
-                stream.push(" mut ".to_string());
+    /**
+     * parse_binary:
+     * precedence climbing over binary operators binding at least
+     * as tight as min_precedence, all of them left associative
+     */
+    fn parse_binary(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        let mut lhs = self.parse_member_pointer()?;
+        while let Some(op) = self.peek_binary_op() {
+            if op.precedence() < min_precedence {
+                break;
             }
-            lookahead += 1;
+            self.bump()?;
+            let rhs = self.parse_binary(op.precedence() + 1)?;
+            let span = lhs.span.to(rhs.span);
+            lhs = Expr::new(ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)), span);
         }
+        Ok(lhs)
+    }
 
-        //get the rust equivalent type
-        let mut typ: String = "".to_string();
-        if let Some(t) = parse_type(lexeme[type_index].get_token_type(), Modifier::Default) {
-            typ = t;
+    // pointer to member access obj.*ptr and obj->*ptr, binds tighter than `*`
+    fn parse_member_pointer(&mut self) -> ParseResult<Expr> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let arrow = match self.peek_type() {
+                Some(DotStar) => false,
+                Some(ArrowStar) => true,
+                _ => return Ok(lhs),
+            };
+            self.bump()?;
+            let rhs = self.parse_unary()?;
+            let span = lhs.span.to(rhs.span);
+            lhs = Expr::new(
+                ExprKind::MemberPointer(Box::new(lhs), Box::new(rhs), arrow),
+                span,
+            );
         }
+    }
 
-        if is_ptr {
-            //TODO : Equivalent of this??
-            if self.strict {
-                stream.push(" as & ".to_string());
-            } else {
-                stream.push(" as &mut ".to_string());
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let start = self.current_span();
+        let op = match self.peek_type() {
+            Some(Plus) => UnaryOp::Plus,
+            Some(Minus) => UnaryOp::Minus,
+            Some(LogicalNot) => UnaryOp::Not,
+            Some(BitwiseNegate) => UnaryOp::BitNot,
+            Some(Multiplication) => UnaryOp::Deref,
+            //`&` after a cast is lexed as bitwise and
+            Some(AddressOf) | Some(BitwiseAnd) => UnaryOp::AddressOf,
+            Some(Increment) => UnaryOp::PreIncrement,
+            Some(Decrement) => UnaryOp::PreDecrement,
+            Some(SizeOf) => {
+                self.bump()?;
+                if self.check(Ellipsis) {
+                    //sizeof...(Args) counts a template parameter pack
+                    return self.error("`sizeof...` is not supported");
+                }
+                let kind = if self.check(LeftBracket) && self.is_type_name_at(1) {
+                    self.bump()?;
+                    let ty = self.parse_type_name()?;
+                    self.expect(RightBracket, "`)`")?;
+                    ExprKind::SizeOfType(ty)
+                } else {
+                    //sizeof x and sizeof(x)
+                    ExprKind::SizeOfExpr(Box::new(self.parse_unary()?))
+                };
+                return Ok(Expr::new(kind, self.span_from(start)));
             }
-            stream.push(typ);
-        } else {
-            stream.push(" as ".to_string());
-            stream.push(typ);
-        }
-
-        //check if its a pointer
-        return stream;
+            Some(KeywordNew) => {
+                self.bump()?;
+                let ty = self.parse_type_name()?;
+                let args = if self.check(LeftBracket) {
+                    self.parse_args()?
+                } else {
+                    Vec::new()
+                };
+                return Ok(Expr::new(ExprKind::New(ty, args), self.span_from(start)));
+            }
+            Some(KeywordDelete) => {
+                self.bump()?;
+                //delete[] p frees an array
+                if self.eat(LeftSquareBracket) {
+                    self.expect(RightSquareBracket, "`]` after `delete[`")?;
+                }
+                let operand = self.parse_unary()?;
+                return Ok(Expr::new(
+                    ExprKind::Delete(Box::new(operand)),
+                    self.span_from(start),
+                ));
+            }
+            //static_cast<int>(a) is the cast (int)a
+            Some(Identifier) if self.is_named_cast() => {
+                let name = self.bump()?.get_token_value();
+                if name == "dynamic_cast" {
+                    return self.error("`dynamic_cast` is not supported");
+                }
+                self.expect(LessThan, "`<` after the cast")?;
+                let ty = self.parse_type_name()?;
+                self.expect(GreaterThan, "`>` after cast type")?;
+                self.expect(LeftBracket, "`(` after cast type")?;
+                let operand = self.parse_expr()?;
+                self.expect(RightBracket, "`)` after cast operand")?;
+                return Ok(Expr::new(
+                    ExprKind::Cast(ty, Box::new(operand)),
+                    self.span_from(start),
+                ));
+            }
+            Some(LeftBracket) if self.is_type_name_at(1) => {
+                //type cast (int)a
+                self.bump()?;
+                let ty = self.parse_type_name()?;
+                self.expect(RightBracket, "`)` after cast type")?;
+                let operand = self.parse_unary()?;
+                return Ok(Expr::new(
+                    ExprKind::Cast(ty, Box::new(operand)),
+                    self.span_from(start),
+                ));
+            }
+            _ => return self.parse_postfix(),
+        };
+        self.bump()?;
+        let operand = self.parse_unary()?;
+        Ok(Expr::new(
+            ExprKind::Unary(op, Box::new(operand)),
+            self.span_from(start),
+        ))
     }
 
-    fn parse_array_declaration(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut typ: String = " ".to_string();
+    // static_cast<T>, reinterpret_cast<T>, const_cast<T> and dynamic_cast<T>
+    fn is_named_cast(&self) -> bool {
+        let is_cast = self.peek().is_some_and(|token| {
+            matches!(
+                token.get_token_value().as_str(),
+                "static_cast" | "reinterpret_cast" | "const_cast" | "dynamic_cast"
+            )
+        });
+        is_cast && self.check_nth(1, LessThan)
+    }
 
-        //int a[10];
-        if let Some(t) = parse_type(lexeme[0].get_token_type(), Modifier::Default) {
-            typ = t;
+    // calls, indexing, member access and postfix increment/decrement
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let start = expr.span;
+            let kind = match self.peek_type() {
+                Some(LeftBracket) => ExprKind::Call(Box::new(expr), self.parse_args()?),
+                Some(LeftSquareBracket) => {
+                    self.bump()?;
+                    let index = self.parse_expr()?;
+                    self.expect(RightSquareBracket, "`]`")?;
+                    ExprKind::Index(Box::new(expr), Box::new(index))
+                }
+                Some(Increment) => {
+                    self.bump()?;
+                    ExprKind::Postfix(PostfixOp::Increment, Box::new(expr))
+                }
+                Some(Decrement) => {
+                    self.bump()?;
+                    ExprKind::Postfix(PostfixOp::Decrement, Box::new(expr))
+                }
+                Some(Arrow) => {
+                    self.bump()?;
+                    let member = self.expect_identifier()?;
+                    ExprKind::Member(Box::new(expr), member, true)
+                }
+                Some(Dot) => {
+                    self.bump()?;
+                    let member = self.expect_identifier()?;
+                    ExprKind::Member(Box::new(expr), member, false)
+                }
+                _ => return Ok(expr),
+            };
+            expr = Expr::new(kind, self.span_from(start));
         }
-        if !self.struct_in_body_declaration {
-            if self.strict == true {
-                stream.push(STRICT.get_doc().to_string());
-                stream.push("let".to_string());
-            } else {
-                stream.push(NO_STRICT.get_doc().to_string());
-                stream.push("let mut".to_string());
-            }
-        }
-
-        let mut head = 0;
-        stream.push(lexeme[head + 1].get_token_value());
-        stream.push(":".to_string());
-        stream
-            .push("[".to_string() + &typ[..] + ";" + &lexeme[head + 3].get_token_value()[..] + "]");
-        head = 5;
-        let mut lookahead = head;
-        while lexeme[lookahead].get_token_type() != Semicolon {
-            lookahead += 1;
-        }
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        if lexeme[head].get_token_type() == Comma {
-            temp_lexeme.push(lexeme[0].clone());
-            //move to next
-            head += 1;
-            while lexeme[head].get_token_type() != Semicolon {
-                temp_lexeme.push(lexeme[head].clone());
-                head += 1;
-            }
-            stream.push(";".to_string());
-            temp_lexeme.push(lexeme[head].clone());
-            stream.append(&mut self.parse_program(&temp_lexeme));
-        } else if lexeme[head].get_token_type() == Assignment {
-            while lexeme[head].get_token_type() != Semicolon
-                && lexeme[head].get_token_type() != RightCurlyBrace
-            {
-                stream.push(match lexeme[head].get_token_type() {
-                    LeftCurlyBrace => "[".to_string(),
+    }
 
-                    _ => lexeme[head].get_token_value(),
-                });
-                head += 1;
+    // (a, b) arguments of a call or constructor
+    fn parse_args(&mut self) -> ParseResult<Vec<Expr>> {
+        self.expect(LeftBracket, "`(`")?;
+        let mut args: Vec<Expr> = Vec::new();
+        while !self.check(RightBracket) {
+            args.push(self.parse_assignment_expr()?);
+            if !self.eat(Comma) {
+                break;
             }
-            stream.push("]".to_string());
-            stream.push(";".to_string());
-        } else {
-            stream.push(";".to_string());
-        }
-
-        stream
-    }
-
-    // not tested
-    fn parse_struct(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        stream.push(lexeme[head].get_token_value()); //push the keyword struct
-        head += 1;
-        //push the struct id_name
-        stream.push(lexeme[head].get_token_value()); //push the struct name
-        let name = lexeme[head].get_token_value();
-        stream.push("{".to_string());
-        head += 2;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while lexeme[head].get_token_type() != RightCurlyBrace {
-            while lexeme[head].get_token_type() != Semicolon {
-                temp_lexeme.push(lexeme[head].clone());
-                head += 1
-            }
-            temp_lexeme.push(lexeme[head].clone());
-            head += 1;
-            //check if line comment is present
-            if lexeme[head].get_token_type() == SingleLineComment {
-                temp_lexeme.push(lexeme[head].clone());
-                head += 1;
-            }
-            stream.append(&mut self.parse_struct_inbody_decl(&temp_lexeme, &name));
-            temp_lexeme.clear();
-        }
-        stream.push(lexeme[head].get_token_value() + "\n");
-
-        stream
-    }
-
-    // not tested
-    fn parse_struct_inbody_decl(&mut self, lexeme: &Vec<Token>, _name: &String) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        //push the identifier
-        self.struct_in_body_declaration = true;
-        let mut head: usize = 0;
-        let mut array_decl = false;
-        while head + 2 < lexeme.len() {
-            if lexeme[head].get_token_type() == LeftSquareBracket
-                && lexeme[head + 2].get_token_type() == RightSquareBracket
-                && lexeme[head + 1].get_token_type() == NumberInteger
-            //this pretty much defines the array decl, i guess
-            {
-                array_decl = true;
-            };
-            head += 1;
         }
+        self.expect(RightBracket, "`)` after arguments")?;
+        Ok(args)
+    }
 
-        let mut parsed_decl: Vec<String>;
-        if array_decl {
-            parsed_decl = self.parse_array_declaration(&lexeme);
-        } else {
-            parsed_decl = self.parse_declaration(&lexeme, true);
-        }
-        //remove tailing semicolon added by parser
-        if parsed_decl.last().unwrap() == ";" {
-            parsed_decl.pop();
-        }
-        self.struct_in_body_declaration = false;
-        stream.append(&mut parsed_decl);
-        stream.push(",".to_string());
-        stream
-    }
-
-    // not tested
-    fn parse_struct_decl(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-
-        stream.push(STRUCT_INIT.get_doc().to_string());
-        stream.push("let".to_string());
-        let mut head = 1;
-        //struct FilePointer fp;
-        let struct_name = lexeme[head].get_token_value();
-        head += 1;
-        stream.push(lexeme[head].get_token_value()); //push the identifer => let a
-        stream.push("=".to_string());
-        stream.push(struct_name.clone());
-        stream.push("{".to_string());
-
-        for row in &self.struct_mem {
-            if row.name == struct_name {
-                stream.push(row.identifier.clone());
-                stream.push(":".to_string());
-                stream.push(get_default_value_for(row.member_type));
-                stream.push(",".to_string());
-            }
-        }
-        stream.push("};".to_string());
-
-        stream
-    }
-
-    //parse tagged union
-    fn parse_union(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        stream.push("enum".to_string()); //push the keyword union
-        head += 1;
-        //push the struct id_name
-        stream.push(lexeme[head].get_token_value()); //push the struct name
-        let name = lexeme[head].get_token_value();
-        stream.push("{".to_string());
-        head += 2;
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        while lexeme[head].get_token_type() != RightCurlyBrace {
-            while lexeme[head].get_token_type() != Semicolon {
-                temp_lexeme.push(lexeme[head].clone());
-                head += 1
-            }
-            temp_lexeme.push(lexeme[head].clone());
-            head += 1;
-            stream.append(&mut self.parse_union_inbody_decl(&temp_lexeme, &name));
-            temp_lexeme.clear();
-        }
-        stream.push(lexeme[head].get_token_value() + "\n");
-
-        stream
-    }
-
-    /* parse union type declarations
-     * input : union tag_name var [;= ...]
-     * output : let [mut] variant_name = Sometype_variant
-     */
-    fn parse_union_decl(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let start = self.current_span();
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return self.error("expected expression"),
+        };
+        let kind = match token.get_type() {
+            (_, NumberInteger) => ExprKind::Literal(Literal::Integer(number(token))),
+            (_, NumberFloat) => ExprKind::Literal(Literal::Float(number(token))),
+            (_, CharValue) => ExprKind::Literal(Literal::Char(token.get_token_value())),
+            (TokenKind::Values, StringValue) => {
+                ExprKind::Literal(Literal::Str(token.get_token_value()))
+            }
+            (_, True) => ExprKind::Literal(Literal::Bool(true)),
+            (_, False) => ExprKind::Literal(Literal::Bool(false)),
+            (_, Null) => ExprKind::Literal(Literal::Null),
+            //qualified name, A::f is a rust path as well
+            (_, Identifier) if self.check_nth(1, ScopeResolution) => {
+                let mut path = self.bump()?.get_token_value();
+                while self.eat(ScopeResolution) {
+                    path.push_str("::");
+                    path.push_str(&self.expect_identifier()?);
+                }
+                return Ok(Expr::new(ExprKind::Ident(path), self.span_from(start)));
+            }
+            //max<int>(a, b)
+            (_, Identifier) if self.is_template_at(0) => {
+                let name = self.bump()?.get_token_value();
+                let args = self.parse_template_args()?;
+                return Ok(Expr::new(
+                    ExprKind::Generic(name, args),
+                    self.span_from(start),
+                ));
+            }
+            (_, Identifier) | (_, Main) => ExprKind::Ident(token.get_token_value()),
+            (_, KeywordThis) => ExprKind::This,
+            (_, LeftBracket) => {
+                self.bump()?;
+                let expr = self.parse_expr()?;
+                self.expect(RightBracket, "`)`")?;
+                return Ok(expr);
+            }
+            (_, LeftCurlyBrace) => return self.parse_initializer(),
+            _ => return self.error("expected expression"),
+        };
+        self.bump()?;
+        Ok(Expr::new(kind, start))
+    }
+}
 
-        stream.push(UNION_DECL.get_doc().to_string());
+/**
+ * number:
+ * splits the text of a numeric literal token into its digits,
+ * the radix and suffix were recorded by the tokenizer
+ */
+fn number(token: &Token) -> Number {
+    let text = token.get_token_value();
+    let radix = token.get_radix();
+    let digits = match radix {
+        Radix::Hexadecimal | Radix::Binary => &text[2..],
+        Radix::Octal => &text[1..],
+        Radix::Decimal => &text[..],
+    };
+    //f is a digit of hexadecimal literals
+    let digits = if radix == Radix::Hexadecimal {
+        digits.trim_end_matches(['u', 'U', 'l', 'L'])
+    } else {
+        digits.trim_end_matches(['u', 'U', 'l', 'L', 'f', 'F'])
+    };
+    Number {
+        digits: digits.replace('\'', "_"),
+        radix,
+        suffix: token.get_suffix(),
+    }
+}
 
-        //push the keyword let
-        stream.push("let".to_string());
-        if !self.strict {
-            stream.push("mut".to_string());
-        }
+/*-------------------------- macros --------------------------*/
+
+/**
+ * parse_macro:
+ * reads a #define line into a macro definition. `macros` are the
+ * definitions translated before it, an object-like macro may only
+ * refer to translated constants to be a constant itself.
+ * The preprocessor uses the same rules to decide which macros it
+ * leaves unexpanded. `globals` are the names declared at file scope
+ * before it, the body may use them
+ */
+pub fn parse_macro(line: &str, macros: &[MacroDef], globals: &[String]) -> MacroDef {
+    let text = line.trim().to_string();
+    let rest = text.trim_start_matches('#').trim_start();
+    let rest = rest.strip_prefix("define").unwrap_or(rest).trim_start();
+    let name_len = rest
+        .find(|ch: char| ch != '_' && !ch.is_alphanumeric())
+        .unwrap_or(rest.len());
+    let name = rest[..name_len].to_string();
+    let mut rest = &rest[name_len..];
+
+    //a `(` right after the name makes it a function-like macro
+    let mut params: Option<Vec<String>> = None;
+    if rest.starts_with('(') {
+        let close = rest.find(')').unwrap_or(rest.len() - 1);
+        let names: Vec<String> = rest[1..close]
+            .split(',')
+            .map(|param| param.trim().to_string())
+            .filter(|param| !param.is_empty())
+            .collect();
+        params = Some(names);
+        rest = &rest[close + 1..];
+    }
+    let body = classify_macro(&name, &params, rest.trim(), macros, globals);
+    let grouped = params
+        .as_ref()
+        .is_some_and(|params| is_grouped(params, rest.trim()));
+    MacroDef {
+        name,
+        params,
+        body,
+        grouped,
+        text,
+    }
+}
 
-        stream.push(lexeme[head + 2].get_token_value());
-        head += 3;
-        while lexeme[head].get_token_type() != Semicolon {
-            stream.push(lexeme[head].get_token_value());
-            head += 1;
-        }
+// whether every parameter in the body is spelled between parentheses, (x)
+fn is_grouped(params: &[String], body: &str) -> bool {
+    let tokens = match Tokenizer::new(&format!("{}\n", body)).tokenize() {
+        Ok(tokens) => tokens,
+        Err(_) => return false,
+    };
+    tokens.iter().enumerate().all(|(index, token)| {
+        token.get_token_type() != Identifier
+            || !params.iter().any(|param| *param == token.get_token_value())
+            || (index > 0
+                && tokens[index - 1].get_token_type() == LeftBracket
+                && tokens
+                    .get(index + 1)
+                    .is_some_and(|next| next.get_token_type() == RightBracket))
+    })
+}
 
-        stream.push(";".to_string());
+// rust item kind the macro body can become, or why it can not
+fn classify_macro(
+    name: &str,
+    params: &Option<Vec<String>>,
+    body: &str,
+    macros: &[MacroDef],
+    globals: &[String],
+) -> MacroBody {
+    if name.is_empty() {
+        return MacroBody::Unsupported("macro name missing".to_string());
+    }
+    let tokens = match Tokenizer::new(&format!("{}\n", body)).tokenize() {
+        Ok(tokens) => tokens,
+        Err(errors) => return MacroBody::Unsupported(errors[0].kind.to_string()),
+    };
+    if tokens
+        .iter()
+        .all(|token| token.get_token_kind() == TokenKind::Comments)
+    {
+        return MacroBody::Empty;
+    }
+    if macros.iter().any(|def| def.name == name) {
+        return MacroBody::Unsupported(format!(
+            "`{}` is defined more than once, rust items can not be redefined",
+            name
+        ));
+    }
+    if params
+        .as_ref()
+        .is_some_and(|params| params.iter().any(|param| param.ends_with("...")))
+    {
+        return MacroBody::Unsupported("variadic macros have no rust equivalent".to_string());
+    }
+    if has_hash(body) {
+        return MacroBody::Unsupported(
+            "the `#` and `##` operators have no rust equivalent".to_string(),
+        );
+    }
 
-        stream
+    let mut parser = Parser::new(&tokens);
+    if let Ok(expr) = parser.parse_expr() {
+        if parser.at_end() {
+            return match *params {
+                Some(ref params) => match unused_param(params, &tokens, &expr.idents()) {
+                    Some(param) => MacroBody::Unsupported(format!(
+                        "parameter `{}` is not used as a value",
+                        param
+                    )),
+                    None => match caller_name(params, &[&expr], &[], macros, globals) {
+                        Some(name) => MacroBody::Unsupported(caller_reason(&name)),
+                        None => MacroBody::Expression(expr),
+                    },
+                },
+                None if is_constant(&expr, macros) => MacroBody::Constant(expr),
+                None => MacroBody::Unsupported("the body is not a constant expression".to_string()),
+            };
+        }
     }
-    /* parse union body into Some type body
-     * return rust stream
-     */
-    fn parse_union_inbody_decl(&mut self, lexeme: &Vec<Token>, name: &String) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head = 0;
-        //push the identifier
-        stream.push(lexeme[head + 1].get_token_value());
-        stream.push("(".to_string());
-        let mut struct_memt = CStructMem {
-            identifier: "NONE".to_string(),
-            name: name.clone(),
-            member_type: TokenType::Others,
-        };
-        let mut rust_type = "RUST_TYPE".to_string();
-        //push the type
-        if let Some(rust_typ) = parse_type(lexeme[head].get_token_type(), Modifier::Default) {
-            rust_type = rust_typ.clone();
-            stream.push(rust_typ);
-            struct_memt.member_type = lexeme[head].get_token_type();
-            struct_memt.identifier = lexeme[head + 1].get_token_value();
-        }
-        head += 2;
-        stream.push("),".to_string());
-        //update struct member table (may require for analysis
-
-        self.struct_mem.push(struct_memt.clone());
-        while lexeme[head].get_token_type() != Semicolon {
-            if lexeme[head].get_token_type() == Comma {
-                head += 1;
-            }
-            struct_memt.identifier = lexeme[head].get_token_value();
-            stream.push(lexeme[head].get_token_value());
-            stream.push("(".to_string());
-            stream.push(rust_type.clone());
-            head += 1;
-            stream.push("),".to_string());
-            self.struct_mem.push(struct_memt.clone());
-        }
-        stream
-    }
-
-    // not tested
-    fn parse_class(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head: usize = 0;
-        let mut method_stream: Vec<String> = Vec::new();
-        stream.push("struct".to_string()); //push the keyword struct
-        head += 1;
-        //push the struct id_name
-        let class_name = lexeme[head].get_token_value();
-        stream.push(class_name.clone()); //push the class name
-        let name = lexeme[head].get_token_value();
-        stream.push("{".to_string());
-        head += 2;
-        let mut modifier: String = " ".to_string();
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        let mut tstream: Vec<String> = Vec::new();
-
-        while lexeme[head].get_token_type() != RightCurlyBrace
-            && lexeme[head + 1].get_token_type() != Semicolon
-        {
-            match lexeme[head].get_type() {
-                (TokenKind::Modifiers, _) => {
-                    match lexeme[head].get_token_type() {
-                        KeywordPublic => {
-                            head += 2;
-                            modifier = "pub".to_string();
-                        }
-                        KeywordProtected | keywordPrivate => {
-                            head += 2;
-                            modifier = "".to_string();
-                        }
-                        _ => {}
-                    };
-                }
-                (_, Identifier) => {
-                    if lexeme[head].get_token_value() == class_name {
-                        tstream.push(CONSTRUCTOR.get_doc().to_string());
-                        let mut lookahead = head;
-                        while lexeme[lookahead].get_token_type() != LeftCurlyBrace {
-                            lookahead += 1;
-                        }
-                        lookahead += 1;
-                        lookahead = skip_block(lexeme, lookahead);
-                        while head < lookahead {
-                            tstream.push(lexeme[head].get_token_value());
-                            head += 1;
-                        }
-                        tstream.push("\n **/\n".to_string());
-                        continue;
-                    }
-                }
 
-                _ => {}
+    //statements may leave out the final `;`, do { ... } while (0)
+    let tokens = match Tokenizer::new(&format!("{} ;\n", body)).tokenize() {
+        Ok(tokens) => tokens,
+        Err(errors) => return MacroBody::Unsupported(errors[0].kind.to_string()),
+    };
+    let mut parser = Parser::new(&tokens);
+    match (params, parser.parse_stmt()) {
+        //statements of a block are recovered from, any error rejects the body
+        (Some(params), Ok(stmt))
+            if parser.errors.is_empty() && (parser.eat(Semicolon) || parser.at_end()) =>
+        {
+            if !parser.at_end() {
+                return MacroBody::Unsupported("the body is not a single statement".to_string());
             }
-
-            if lexeme[head + 2].get_token_type() == LeftBracket {
-                while lexeme[head].get_token_type() != RightCurlyBrace {
-                    temp_lexeme.push(lexeme[head].clone());
-                    head += 1;
-                }
-                temp_lexeme.push(lexeme[head].clone());
-                head += 1;
-                method_stream.append(&mut self.parse_method_decl(&temp_lexeme, &modifier));
-                temp_lexeme.clear();
-            } else {
-                while lexeme[head].get_token_type() != RightCurlyBrace
-                    && lexeme[head].get_token_kind() != TokenKind::Modifiers
-                {
-                    while lexeme[head].get_token_type() != Semicolon {
-                        temp_lexeme.push(lexeme[head].clone());
-                        head += 1
-                    }
-                    temp_lexeme.push(lexeme[head].clone());
-                    head += 1;
-                    stream.append(&mut self.parse_class_inbody_decl(
-                        &temp_lexeme,
-                        &name,
-                        &modifier,
-                    ));
-                    temp_lexeme.clear();
+            let mut idents: Vec<String> = Vec::new();
+            stmt_idents(&stmt, &mut idents);
+            let mut exprs: Vec<&Expr> = Vec::new();
+            let mut declared: Vec<String> = Vec::new();
+            stmt_values(&stmt, &mut exprs, &mut declared);
+            match unused_param(params, &tokens, &idents) {
+                Some(param) => {
+                    MacroBody::Unsupported(format!("parameter `{}` is not used as a value", param))
                 }
+                None => match caller_name(params, &exprs, &declared, macros, globals) {
+                    Some(name) => MacroBody::Unsupported(caller_reason(&name)),
+                    None => MacroBody::Statement(stmt),
+                },
             }
         }
-        stream.push(lexeme[head].get_token_value());
-        stream.push(
-            "\n\n/**Method declarations are wrapped inside the impl block \
-    \n * Which implements the corresponding structure\
-    \n **/\n"
-                .to_string(),
-        );
-        stream.push("impl".to_string());
-        stream.push(name.clone());
-        stream.push("{\n".to_string());
-        if tstream.len() > 0 {
-            stream.append(&mut tstream);
-        }
-        stream.append(&mut method_stream);
-
-        stream.push("}\n".to_string());
-        stream
+        (None, Ok(_)) => MacroBody::Unsupported(
+            "object-like macros expanding to statements have no rust equivalent".to_string(),
+        ),
+        _ => MacroBody::Unsupported("the body is not an expression or a statement".to_string()),
     }
+}
 
-    // not tested
-    fn parse_method_decl(&mut self, lexeme: &Vec<Token>, modifier: &String) -> Vec<String> {
-        let mut temp_lexeme: Vec<Token> = Vec::new();
-        let mut head: usize = 3;
-        let mut lookahead: usize = head;
-        let mut stream: Vec<String> = Vec::new();
-        if modifier.len() > 1 {
-            stream.push(modifier.clone());
-        }
-        stream.push("fn".to_string());
-        stream.push(lexeme[1].get_token_value());
-        stream.push("(".to_string());
-        stream.push("&self".to_string()); //first argument of method must be self, for sefety we consider reference/borrow
-                                          // parse arguments differenly for functions that are not main
-                                          // collect arguments
-        while lexeme[lookahead].get_token_type() != RightBracket {
-            lookahead += 1;
-        }
-        if head < lookahead {
-            stream.push(",".to_string());
-        }
-        while head < lookahead {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
+// `#` outside of string and character literals
+fn has_hash(body: &str) -> bool {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for ch in body.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '#' => return true,
+            None => {}
         }
+    }
+    false
+}
 
-        // parse arguments
-        stream.append(&mut self.parse_arguments(&temp_lexeme));
-        temp_lexeme.clear();
+// a parameter whose name appears in the body other than as a value, s.field
+fn unused_param<'p>(params: &'p [String], tokens: &[Token], idents: &[String]) -> Option<&'p str> {
+    params
+        .iter()
+        .find(|param| {
+            let spelled = tokens
+                .iter()
+                .filter(|token| {
+                    token.get_token_type() == Identifier && token.get_token_value() == **param
+                })
+                .count();
+            spelled != idents.iter().filter(|ident| ident == param).count()
+        })
+        .map(|param| param.as_str())
+}
 
-        stream.push(")".to_string());
+/**
+ * caller_name:
+ * a name the body reads from the scope the macro is used in, one that is
+ * not a parameter, declared by the body or at file scope, a translated
+ * macro or a called function. macro_rules! hygiene keeps it from
+ * resolving to the caller's local
+ */
+fn caller_name(
+    params: &[String],
+    exprs: &[&Expr],
+    declared: &[String],
+    macros: &[MacroDef],
+    globals: &[String],
+) -> Option<String> {
+    let mut names: Vec<String> = Vec::new();
+    for expr in exprs {
+        value_names(expr, &mut names);
+    }
+    names.into_iter().find(|name| {
+        !params.contains(name)
+            && !declared.contains(name)
+            && !globals.contains(name)
+            && !macros.iter().any(|def| def.name == *name)
+    })
+}
 
-        // parse return type
-        if let Some(rust_type) = parse_type(lexeme[0].get_token_type(), Modifier::Default) {
-            if rust_type != "void".to_string() {
-                stream.push("->".to_string());
-                stream.push(rust_type);
+/**
+ * file_scope_names:
+ * variables, functions and unscoped enumerators declared by the items,
+ * a macro body may use them as hygiene only hides the caller's locals
+ */
+pub fn file_scope_names(items: &[Item]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for item in items {
+        match item.kind {
+            ItemKind::Declaration(ref decl) => {
+                names.extend(decl.declarators.iter().map(|d| d.name.clone()))
             }
+            ItemKind::Function(ref function) if function.class.is_none() => {
+                names.push(function.name.clone())
+            }
+            ItemKind::Enum(ref def) if !def.scoped => {
+                names.extend(def.variants.iter().map(|variant| variant.name.clone()))
+            }
+            _ => {}
         }
-
-        stream.push("{".to_string());
-        while lexeme[head].get_token_type() != LeftCurlyBrace {
-            head += 1
-        }
-        head += 1;
-
-        // collect function body
-        // len - 1  so that '}' is excluded
-        while head < lexeme.len() - 1 {
-            let l: Token = lexeme[head].clone();
-            temp_lexeme.push(l);
-            head += 1;
-        }
-        // parse function body
-        stream.append(&mut self.parse_program(&temp_lexeme));
-        stream.push("}".to_string());
-        stream
     }
+    names
+}
 
-    // not tested
-    fn parse_class_inbody_decl(
-        &mut self,
-        lexeme: &Vec<Token>,
-        name: &String,
-        modifier: &String,
-    ) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
-        let mut head = 0;
-        //push the identifier
-        if modifier.len() > 1 {
-            stream.push(modifier.clone());
-        }
-        stream.push(lexeme[head + 1].get_token_value());
-        stream.push(":".to_string());
-        let mut struct_memt = CStructMem {
-            identifier: "NONE".to_string(),
-            name: name.clone(),
-            member_type: TokenType::Others,
-        };
+fn caller_reason(name: &str) -> String {
+    format!(
+        "the body uses `{}` from the caller's scope, macro_rules! hygiene would not resolve it",
+        name
+    )
+}
 
-        let mut rust_type: String = " ".to_string();
-        if let Some(rust_typ) = parse_type(lexeme[0].get_token_type(), Modifier::Default) {
-            rust_type = rust_typ.clone();
-            stream.push(rust_typ);
-            struct_memt.member_type = lexeme[0].get_token_type();
-            struct_memt.identifier = lexeme[1].get_token_value();
+// names used as values, without the names of called functions
+fn value_names(expr: &Expr, names: &mut Vec<String>) {
+    match expr.kind {
+        ExprKind::Ident(ref name) => names.push(name.clone()),
+        ExprKind::Call(ref callee, ref args) if matches!(callee.kind, ExprKind::Ident(_)) => {
+            for arg in args {
+                value_names(arg, names);
+            }
         }
-
-        stream.push(",".to_string());
-        self.struct_mem.push(struct_memt.clone());
-        head += 2;
-        while lexeme[head].get_token_type() != Semicolon {
-            if lexeme[head].get_token_type() == Comma {
-                head += 1;
+        _ => {
+            for child in expr.children() {
+                value_names(child, names);
             }
-            stream.push(lexeme[head].get_token_value());
-            stream.push(":".to_string());
-            stream.push(rust_type.clone());
-
-            struct_memt.identifier = lexeme[head].get_token_value();
-            self.struct_mem.push(struct_memt.clone());
-            head += 1;
         }
-        stream
     }
+}
 
-    // not tested
-    fn parse_class_decl(&mut self, lexeme: &Vec<Token>) -> Vec<String> {
-        let mut stream: Vec<String> = Vec::new();
+// expressions of the statement and the names its declarations introduce
+fn stmt_values<'s>(stmt: &'s Stmt, exprs: &mut Vec<&'s Expr>, declared: &mut Vec<String>) {
+    if let StmtKind::Declaration(ref decl) = stmt.kind {
+        declared.extend(
+            decl.declarators
+                .iter()
+                .map(|declarator| declarator.name.clone()),
+        );
+    }
+    exprs.extend(stmt.exprs());
+    for child in stmt.children() {
+        stmt_values(child, exprs, declared);
+    }
+}
 
-        stream.push(STRUCT_INIT.get_doc().to_string());
-        stream.push("let".to_string());
-        let mut head = 0;
-        //struct FilePointer fp;
-        let struct_name = lexeme[head].get_token_value();
-        head += 1;
-        stream.push(lexeme[head].get_token_value()); //push the identifer => let a
-        stream.push("=".to_string());
-        stream.push(struct_name.clone());
-        stream.push("{".to_string());
+// whether the expression only combines literals and translated constants
+fn is_constant(expr: &Expr, macros: &[MacroDef]) -> bool {
+    match expr.kind {
+        ExprKind::Literal(Literal::Null) => false,
+        ExprKind::Literal(_) => true,
+        ExprKind::Ident(ref name) => macros
+            .iter()
+            .any(|def| def.name == *name && matches!(def.body, MacroBody::Constant(_))),
+        ExprKind::Unary(UnaryOp::Plus, ref operand)
+        | ExprKind::Unary(UnaryOp::Minus, ref operand)
+        | ExprKind::Unary(UnaryOp::Not, ref operand)
+        | ExprKind::Unary(UnaryOp::BitNot, ref operand)
+        | ExprKind::Cast(CType::Primitive(..), ref operand) => is_constant(operand, macros),
+        ExprKind::Binary(_, ref lhs, ref rhs) => {
+            is_constant(lhs, macros) && is_constant(rhs, macros)
+        }
+        ExprKind::Conditional(ref cond, ref then, ref otherwise) => {
+            is_constant(cond, macros) && is_constant(then, macros) && is_constant(otherwise, macros)
+        }
+        ExprKind::SizeOfType(CType::Primitive(..)) => true,
+        _ => false,
+    }
+}
 
-        for row in &self.struct_mem {
-            if row.name == struct_name {
-                stream.push(row.identifier.clone());
-                stream.push(":".to_string());
-                stream.push(get_default_value_for(row.member_type));
-                stream.push(",".to_string());
+fn stmt_idents(stmt: &Stmt, idents: &mut Vec<String>) {
+    let mut exprs: Vec<&Expr> = Vec::new();
+    let mut stmts: Vec<&Stmt> = Vec::new();
+    match stmt.kind {
+        StmtKind::Declaration(ref decl) => {
+            for declarator in &decl.declarators {
+                //the declared name is spelled but is not a value
+                idents.push(declarator.name.clone());
+                exprs.extend(declarator.init.iter());
             }
         }
-        stream.push("};".to_string());
+        StmtKind::Expr(ref expr) => exprs.push(expr),
+        StmtKind::Block(ref body) => stmts.extend(body.iter()),
+        StmtKind::If {
+            ref cond,
+            ref then,
+            ref otherwise,
+        } => {
+            exprs.push(cond);
+            stmts.push(then);
+            stmts.extend(otherwise.iter().map(|stmt| &**stmt));
+        }
+        StmtKind::While { ref cond, ref body } | StmtKind::DoWhile { ref body, ref cond } => {
+            exprs.push(cond);
+            stmts.push(body);
+        }
+        StmtKind::For {
+            ref init,
+            ref cond,
+            ref step,
+            ref body,
+        } => {
+            stmts.extend(init.iter().map(|stmt| &**stmt));
+            exprs.extend(cond.iter());
+            exprs.extend(step.iter());
+            stmts.push(body);
+        }
+        StmtKind::Switch {
+            ref cond,
+            ref cases,
+        } => {
+            exprs.push(cond);
+            for case in cases {
+                match case.label {
+                    CaseLabel::Value(ref value) => exprs.push(value),
+                    CaseLabel::Range(ref low, ref high) => {
+                        exprs.push(low);
+                        exprs.push(high);
+                    }
+                    CaseLabel::Default => {}
+                }
+                stmts.extend(case.body.iter());
+            }
+        }
+        StmtKind::Return(ref value) => exprs.extend(value.iter()),
+        StmtKind::Break
+        | StmtKind::Continue
+        | StmtKind::Goto(_)
+        | StmtKind::Label(_)
+        | StmtKind::Comment(_)
+        | StmtKind::Unsupported(_)
+        | StmtKind::Empty => {}
+    }
+    for expr in exprs {
+        expr.collect_idents(idents);
+    }
+    for stmt in stmts {
+        stmt_idents(stmt, idents);
+    }
+}
 
-        stream
+pub fn token_span(token: &Token) -> Span {
+    Span {
+        lo: token.get_token_id(),
+        hi: token.get_token_id(),
+        line: token.get_token_line_num(),
+        column: token.get_column(),
+        start: token.get_start(),
+        end: token.get_end(),
     }
 }
@@ -1,5 +1,1136 @@
-#[cfg(test)]
+use library::ast::*;
+use library::lexeme::definition::TokenType;
+use library::lexer::tokenizer::Tokenizer;
+use library::parser::parser::init_parser;
+
+fn parse(text: &str) -> TranslationUnit {
+    let tokens = Tokenizer::new(text).tokenize().expect("lexing failed");
+    let (unit, errors) = init_parser(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+    unit
+}
+
+// body of the first function in the unit
+fn function_body(unit: &TranslationUnit) -> Vec<Stmt> {
+    for item in &unit.items {
+        if let ItemKind::Function(ref function) = item.kind {
+            return function.body.clone().expect("function without body");
+        }
+    }
+    panic!("no function found");
+}
+
+// expression of the first expression statement in main
+fn first_expr(text: &str) -> Expr {
+    let unit = parse(text);
+    for stmt in function_body(&unit) {
+        if let StmtKind::Expr(expr) = stmt.kind {
+            return expr;
+        }
+    }
+    panic!("no expression statement found");
+}
+
+fn int() -> CType {
+    CType::Primitive(TokenType::Integer, Modifier::Default)
+}
+
+#[test]
+fn test_function_signature() {
+    let unit = parse("unsigned long add(int a, char *b) { return a; }");
+    match unit.items[0].kind {
+        ItemKind::Function(ref function) => {
+            assert_eq!(function.name, "add");
+            assert_eq!(
+                function.return_type,
+                CType::Primitive(TokenType::Long, Modifier::Unsigned)
+            );
+            assert_eq!(function.params.len(), 2);
+            assert_eq!(function.params[0].name, Some("a".to_string()));
+            assert_eq!(function.params[0].ty, int());
+            assert_eq!(
+                function.params[1].ty,
+                CType::Pointer(Box::new(CType::Primitive(
+                    TokenType::Character,
+                    Modifier::Default
+                )))
+            );
+            let body = function.body.as_ref().unwrap();
+            assert_eq!(body.len(), 1);
+            match body[0].kind {
+                StmtKind::Return(Some(_)) => {}
+                ref kind => panic!("expected return, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_prototype_has_no_body() {
+    let unit = parse("int add(int, int);");
+    match unit.items[0].kind {
+        ItemKind::Function(ref function) => {
+            assert!(function.body.is_none());
+            assert_eq!(function.params[0].name, None);
+        }
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_declaration_with_several_declarators() {
+    let unit = parse("int a = 1, *b, c[10];");
+    match unit.items[0].kind {
+        ItemKind::Declaration(ref decl) => {
+            let names: Vec<&str> = decl.declarators.iter().map(|d| d.name.as_str()).collect();
+            assert_eq!(names, vec!["a", "b", "c"]);
+            assert!(decl.declarators[0].init.is_some());
+            assert!(decl.declarators[1].ty.is_pointer());
+            assert!(decl.declarators[2].ty.is_array());
+            assert_eq!(*decl.declarators[2].ty.base(), int());
+        }
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_binary_precedence() {
+    let expr = first_expr("int main(){ a + b * c; }");
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Add, ref lhs, ref rhs) => {
+            assert_eq!(lhs.kind, ExprKind::Ident("a".to_string()));
+            match rhs.kind {
+                ExprKind::Binary(BinaryOp::Mul, _, _) => {}
+                ref kind => panic!("expected multiplication, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected addition, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_assignment_is_right_associative() {
+    let expr = first_expr("int main(){ a = b += 2; }");
+    match expr.kind {
+        ExprKind::Assign(None, _, ref rhs) => match rhs.kind {
+            ExprKind::Assign(Some(BinaryOp::Add), _, _) => {}
+            ref kind => panic!("expected compound assignment, found {:?}", kind),
+        },
+        ref kind => panic!("expected assignment, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_postfix_chain() {
+    let expr = first_expr("int main(){ a.b[1](x, y)++; }");
+    match expr.kind {
+        ExprKind::Postfix(PostfixOp::Increment, ref call) => match call.kind {
+            ExprKind::Call(ref callee, ref args) => {
+                assert_eq!(args.len(), 2);
+                match callee.kind {
+                    ExprKind::Index(ref member, _) => match member.kind {
+                        ExprKind::Member(_, ref name, false) => assert_eq!(name, "b"),
+                        ref kind => panic!("expected member, found {:?}", kind),
+                    },
+                    ref kind => panic!("expected index, found {:?}", kind),
+                }
+            }
+            ref kind => panic!("expected call, found {:?}", kind),
+        },
+        ref kind => panic!("expected postfix, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_cast_and_sizeof() {
+    let expr = first_expr("int main(){ (float)a + sizeof(int); }");
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Add, ref lhs, ref rhs) => {
+            match lhs.kind {
+                ExprKind::Cast(CType::Primitive(TokenType::Float, _), _) => {}
+                ref kind => panic!("expected cast, found {:?}", kind),
+            }
+            assert_eq!(rhs.kind, ExprKind::SizeOfType(int()));
+        }
+        ref kind => panic!("expected addition, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_expression_span_covers_its_tokens() {
+    // tokens : int main ( ) { a + b ; }
+    let expr = first_expr("int main(){ a + b; }");
+    assert_eq!(expr.span.lo, 5);
+    assert_eq!(expr.span.hi, 7);
+    assert_eq!(expr.span.line, 1);
+    assert_eq!(expr.span.column, 13);
+    //byte range of `a + b`
+    assert_eq!((expr.span.start, expr.span.end), (12, 17));
+}
+
+#[test]
+fn test_control_flow_statements() {
+    let unit = parse(
+        "int main(){
+            if (a) b = 1; else { b = 2; }
+            for (int i = 0; i < 10; i++) {}
+            while (1) break;
+            do { a--; } while (a);
+        }",
+    );
+    let body = function_body(&unit);
+    assert_eq!(body.len(), 4);
+    match body[0].kind {
+        StmtKind::If {
+            otherwise: Some(_), ..
+        } => {}
+        ref kind => panic!("expected if else, found {:?}", kind),
+    }
+    match body[1].kind {
+        StmtKind::For {
+            init: Some(ref init),
+            cond: Some(_),
+            step: Some(_),
+            ..
+        } => match init.kind {
+            StmtKind::Declaration(_) => {}
+            ref kind => panic!("expected declaration, found {:?}", kind),
+        },
+        ref kind => panic!("expected for, found {:?}", kind),
+    }
+    match body[2].kind {
+        StmtKind::While { .. } => {}
+        ref kind => panic!("expected while, found {:?}", kind),
+    }
+    match body[3].kind {
+        StmtKind::DoWhile { .. } => {}
+        ref kind => panic!("expected do while, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_switch_cases() {
+    let unit = parse(
+        "int main(){
+            switch (a) {
+                case 1: b = 1; break;
+                case 2:
+                default: b = 3;
+            }
+        }",
+    );
+    match function_body(&unit)[0].kind {
+        StmtKind::Switch { ref cases, .. } => {
+            assert_eq!(cases.len(), 3);
+            assert_eq!(cases[0].body.len(), 2);
+            assert!(cases[1].body.is_empty());
+            assert_eq!(cases[2].label, CaseLabel::Default);
+        }
+        ref kind => panic!("expected switch, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_case_ranges() {
+    let unit = parse("int main(){ switch (c) { case 'a' ... 'z': n = 1; } }");
+    match function_body(&unit)[0].kind {
+        StmtKind::Switch { ref cases, .. } => {
+            assert!(matches!(cases[0].label, CaseLabel::Range(..)));
+        }
+        ref kind => panic!("expected switch, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_goto_and_labels() {
+    let unit = parse("int main(){ retry: n++; if (n < 3) goto retry; goto *p; }");
+    let body = function_body(&unit);
+    assert_eq!(body[0].kind, StmtKind::Label("retry".to_string()));
+    match body[2].kind {
+        StmtKind::If { ref then, .. } => {
+            assert_eq!(then.kind, StmtKind::Goto("retry".to_string()))
+        }
+        ref kind => panic!("expected if, found {:?}", kind),
+    }
+    match body[3].kind {
+        StmtKind::Unsupported(ref unsupported) => {
+            assert_eq!(unsupported.reason, "computed `goto` is not supported")
+        }
+        ref kind => panic!("expected unsupported statement, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_class_members() {
+    let unit = parse(
+        "class A {
+            int a;
+          public:
+            A() { a = 1; }
+            int get() { return a; }
+        };",
+    );
+    match unit.items[0].kind {
+        ItemKind::Record(ref record) => {
+            assert_eq!(record.kind, RecordKind::Class);
+            assert_eq!(record.name, "A");
+            assert_eq!(record.members.len(), 3);
+            assert_eq!(record.members[0].access, Access::Private);
+            match record.members[1].kind {
+                MemberKind::Constructor(_) => {}
+                ref kind => panic!("expected constructor, found {:?}", kind),
+            }
+            assert_eq!(record.members[2].access, Access::Public);
+            match record.members[2].kind {
+                MemberKind::Method(ref method) => assert_eq!(method.name, "get"),
+                ref kind => panic!("expected method, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected class, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_typedef_names_anonymous_struct() {
+    let unit = parse("typedef struct { int x; } Point; Point p;");
+    match unit.items[0].kind {
+        ItemKind::Record(ref record) => assert_eq!(record.name, "Point"),
+        ref kind => panic!("expected struct, found {:?}", kind),
+    }
+    match unit.items[1].kind {
+        ItemKind::Declaration(ref decl) => {
+            assert_eq!(decl.declarators[0].ty, CType::Named("Point".to_string()))
+        }
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_comments_and_directives_are_kept() {
+    let unit = parse("#include <stdio.h>\n// note\nint a;");
+    assert_eq!(
+        unit.items[0].kind,
+        ItemKind::Directive("#include <stdio.h>".to_string())
+    );
+    assert_eq!(unit.items[1].kind, ItemKind::Comment("// note".to_string()));
+}
+
+#[test]
+fn test_missing_semicolon_is_an_error() {
+    let tokens = Tokenizer::new("int main(){ a = 1 }")
+        .tokenize()
+        .expect("lexing failed");
+    let (unit, errors) = init_parser(&tokens);
+    let err = &errors[0];
+    assert!(err.message.starts_with("expected `;`"), "{}", err.message);
+    assert_eq!(err.span.line, 1);
+    assert_eq!(err.span.column, 19);
+    assert!(err
+        .to_string()
+        .starts_with("line 1, column 19: expected `;`"));
+    //the function is kept, the statement becomes an unsupported region
+    match function_body(&unit)[0].kind {
+        StmtKind::Unsupported(ref unsupported) => {
+            assert_eq!(unsupported.text, "a = 1");
+            assert_eq!(unsupported.reason, err.message);
+        }
+        ref kind => panic!("expected unsupported statement, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_full_precedence_ladder() {
+    // a || b && c | d ^ e & f == g < h << i + j * k
+    let expr = first_expr("int main(){ a || b && c | d ^ e & f == g < h << i + j * k; }");
+    let mut ops = Vec::new();
+    let mut node = &expr;
+    while let ExprKind::Binary(op, _, ref rhs) = node.kind {
+        ops.push(op);
+        node = rhs;
+    }
+    assert_eq!(
+        ops,
+        vec![
+            BinaryOp::Or,
+            BinaryOp::And,
+            BinaryOp::BitOr,
+            BinaryOp::BitXor,
+            BinaryOp::BitAnd,
+            BinaryOp::Eq,
+            BinaryOp::Lt,
+            BinaryOp::Shl,
+            BinaryOp::Add,
+            BinaryOp::Mul,
+        ]
+    );
+}
+
+#[test]
+fn test_binary_operators_are_left_associative() {
+    let expr = first_expr("int main(){ a - b - c; }");
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Sub, ref lhs, ref rhs) => {
+            assert_eq!(rhs.kind, ExprKind::Ident("c".to_string()));
+            match lhs.kind {
+                ExprKind::Binary(BinaryOp::Sub, _, _) => {}
+                ref kind => panic!("expected subtraction, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected subtraction, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_conditional_is_right_associative() {
+    let expr = first_expr("int main(){ x = a ? b : c ? d : e; }");
+    match expr.kind {
+        ExprKind::Assign(None, _, ref rhs) => match rhs.kind {
+            ExprKind::Conditional(_, ref then, ref otherwise) => {
+                assert_eq!(then.kind, ExprKind::Ident("b".to_string()));
+                match otherwise.kind {
+                    ExprKind::Conditional(..) => {}
+                    ref kind => panic!("expected conditional, found {:?}", kind),
+                }
+            }
+            ref kind => panic!("expected conditional, found {:?}", kind),
+        },
+        ref kind => panic!("expected assignment, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_comma_operator_has_lowest_precedence() {
+    let expr = first_expr("int main(){ a = 1, b = 2; }");
+    match expr.kind {
+        ExprKind::Comma(ref exprs) => {
+            assert_eq!(exprs.len(), 2);
+            for expr in exprs {
+                match expr.kind {
+                    ExprKind::Assign(..) => {}
+                    ref kind => panic!("expected assignment, found {:?}", kind),
+                }
+            }
+        }
+        ref kind => panic!("expected comma, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_unary_chain_and_sizeof_without_parentheses() {
+    let expr = first_expr("int main(){ -*p + sizeof x; }");
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Add, ref lhs, ref rhs) => {
+            match lhs.kind {
+                ExprKind::Unary(UnaryOp::Minus, ref operand) => match operand.kind {
+                    ExprKind::Unary(UnaryOp::Deref, _) => {}
+                    ref kind => panic!("expected dereference, found {:?}", kind),
+                },
+                ref kind => panic!("expected negation, found {:?}", kind),
+            }
+            match rhs.kind {
+                ExprKind::SizeOfExpr(ref operand) => {
+                    assert_eq!(operand.kind, ExprKind::Ident("x".to_string()))
+                }
+                ref kind => panic!("expected sizeof, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected addition, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_cast_to_typedef_pointer() {
+    let expr = first_expr("typedef int T; int main(){ (T*)p; }");
+    match expr.kind {
+        ExprKind::Cast(CType::Pointer(ref inner), _) => {
+            assert_eq!(**inner, CType::Named("T".to_string()))
+        }
+        ref kind => panic!("expected cast, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_arrow_member_access() {
+    let expr = first_expr("int main(){ p->next->value; }");
+    match expr.kind {
+        ExprKind::Member(ref base, ref name, true) => {
+            assert_eq!(name, "value");
+            match base.kind {
+                ExprKind::Member(_, ref name, true) => assert_eq!(name, "next"),
+                ref kind => panic!("expected member, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected member, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_new_and_delete_expressions() {
+    let unit = parse("int main(){ p = new Point(1, 2); q = new int[n]; delete[] q; }");
+    let body = function_body(&unit);
+    let values: Vec<&ExprKind> = body
+        .iter()
+        .map(|stmt| match stmt.kind {
+            StmtKind::Expr(Expr {
+                kind: ExprKind::Assign(None, _, ref value),
+                ..
+            }) => &value.kind,
+            StmtKind::Expr(ref expr) => &expr.kind,
+            ref kind => panic!("expected expression, found {:?}", kind),
+        })
+        .collect();
+    match *values[0] {
+        ExprKind::New(CType::Named(ref name), ref args) => {
+            assert_eq!(name, "Point");
+            assert_eq!(args.len(), 2);
+        }
+        ref kind => panic!("expected new, found {:?}", kind),
+    }
+    match *values[1] {
+        ExprKind::New(CType::Array(ref inner, Some(_)), ref args) => {
+            assert_eq!(**inner, int());
+            assert!(args.is_empty());
+        }
+        ref kind => panic!("expected new[], found {:?}", kind),
+    }
+    match *values[2] {
+        ExprKind::Delete(ref pointer) => {
+            assert_eq!(pointer.kind, ExprKind::Ident("q".to_string()))
+        }
+        ref kind => panic!("expected delete, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_member_pointer_binds_tighter_than_multiplication() {
+    let expr = first_expr("int main(){ -a.*b * c->*d; }");
+    match expr.kind {
+        ExprKind::Binary(BinaryOp::Mul, ref lhs, ref rhs) => {
+            match lhs.kind {
+                ExprKind::MemberPointer(ref object, _, false) => match object.kind {
+                    ExprKind::Unary(UnaryOp::Minus, _) => {}
+                    ref kind => panic!("expected negation, found {:?}", kind),
+                },
+                ref kind => panic!("expected member pointer, found {:?}", kind),
+            }
+            match rhs.kind {
+                ExprKind::MemberPointer(_, _, true) => {}
+                ref kind => panic!("expected member pointer, found {:?}", kind),
+            }
+        }
+        ref kind => panic!("expected multiplication, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_compound_bitwise_assignment_and_address_of() {
+    let expr = first_expr("int main(){ x <<= (int)&y ^ z; }");
+    match expr.kind {
+        ExprKind::Assign(Some(BinaryOp::Shl), _, ref rhs) => match rhs.kind {
+            ExprKind::Binary(BinaryOp::BitXor, ref lhs, _) => match lhs.kind {
+                ExprKind::Cast(_, ref operand) => match operand.kind {
+                    ExprKind::Unary(UnaryOp::AddressOf, _) => {}
+                    ref kind => panic!("expected address of, found {:?}", kind),
+                },
+                ref kind => panic!("expected cast, found {:?}", kind),
+            },
+            ref kind => panic!("expected xor, found {:?}", kind),
+        },
+        ref kind => panic!("expected assignment, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_variadic_parameters() {
+    let unit = parse("int printf(const char *fmt, ...);");
+    match unit.items[0].kind {
+        ItemKind::Function(ref function) => {
+            assert_eq!(function.params.len(), 1);
+            assert!(function.variadic);
+        }
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_const_stays_on_the_pointee() {
+    let unit = parse("struct S { const char *name; const struct S *next; };\nconst int n = 1;\n");
+    let char_type = CType::Primitive(TokenType::Character, Modifier::Default);
+    match unit.items[0].kind {
+        ItemKind::Record(ref record) => {
+            let fields = record.fields();
+            assert_eq!(
+                fields[0].ty,
+                CType::Pointer(Box::new(CType::Const(Box::new(char_type))))
+            );
+            assert!(fields[1].ty.is_const_pointer());
+            assert_eq!(*fields[1].ty.pointee(), CType::Named("S".to_string()));
+        }
+        ref kind => panic!("expected record, found {:?}", kind),
+    }
+    //const values are not pointees, the declaration is const
+    match unit.items[1].kind {
+        ItemKind::Declaration(ref decl) => assert_eq!(decl.declarators[0].ty, int()),
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_macro_definitions() {
+    let unit = parse(
+        "#define MAX 256\n#define MASK (MAX - 1)\n#define NOW time(0)\n#define ADD(a, b) ((a) + (b))\n#define SWAP(a, b) do { int t = a; a = b; b = t; } while (0)\n#define GET(s, f) ((s).f)\n#define MAX 128\n#define PUT(a) do { t = a; } while (0)\n#define TWICE(x) (x * 2)\n",
+    );
+    let bodies: Vec<MacroBody> = unit
+        .items
+        .iter()
+        .map(|item| match item.kind {
+            ItemKind::Macro(ref def) => def.body.clone(),
+            _ => panic!("expected macro, found {:?}", item.kind),
+        })
+        .collect();
+    assert!(matches!(bodies[0], MacroBody::Constant(_)));
+    assert!(matches!(bodies[1], MacroBody::Constant(_)));
+    assert_eq!(
+        bodies[2],
+        MacroBody::Unsupported("the body is not a constant expression".to_string())
+    );
+    assert!(matches!(bodies[3], MacroBody::Expression(_)));
+    assert!(matches!(bodies[4], MacroBody::Statement(_)));
+    assert_eq!(
+        bodies[5],
+        MacroBody::Unsupported("parameter `f` is not used as a value".to_string())
+    );
+    assert!(matches!(bodies[6], MacroBody::Unsupported(_)));
+    //`t` would have to come from where the macro is used
+    assert_eq!(
+        bodies[7],
+        MacroBody::Unsupported(
+            "the body uses `t` from the caller's scope, macro_rules! hygiene would not resolve it"
+                .to_string()
+        )
+    );
+    assert!(matches!(bodies[8], MacroBody::Expression(_)));
+    match unit.items[3].kind {
+        ItemKind::Macro(ref def) => {
+            assert_eq!(def.name, "ADD");
+            assert_eq!(def.params, Some(vec!["a".to_string(), "b".to_string()]));
+            assert!(def.grouped);
+        }
+        _ => panic!("expected macro"),
+    }
+    match unit.items[8].kind {
+        ItemKind::Macro(ref def) => assert!(!def.grouped),
+        _ => panic!("expected macro"),
+    }
+}
+
+#[test]
+fn test_unsupported_constructs_are_skipped() {
+    let unit = parse(
+        "template <> int max<int>(int a, int b) { return a > b ? a : b; }\nnamespace fs = std::filesystem;\nclass A { friend class B; int x; };\nint main(){ try { f(); } catch (...) { } goto end; int y; }\n",
+    );
+    let reasons: Vec<&str> = unit
+        .items
+        .iter()
+        .filter_map(|item| match item.kind {
+            ItemKind::Unsupported(ref unsupported) => Some(unsupported.reason.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            "explicit specializations are not supported",
+            "namespace aliases are not supported"
+        ]
+    );
+    match unit.items[0].kind {
+        ItemKind::Unsupported(ref unsupported) => {
+            assert_eq!(
+                unsupported.text,
+                "template <> int max<int>(int a, int b) { return a > b ? a : b; }"
+            );
+            assert_eq!(unsupported.span.line, 1);
+        }
+        ref kind => panic!("expected unsupported item, found {:?}", kind),
+    }
+    match unit.items[2].kind {
+        ItemKind::Record(ref record) => {
+            assert!(matches!(record.members[0].kind, MemberKind::Unsupported(_)));
+            assert_eq!(record.fields().len(), 1);
+        }
+        ref kind => panic!("expected class, found {:?}", kind),
+    }
+    let body = function_body(&unit);
+    assert_eq!(body.len(), 3);
+    match body[0].kind {
+        StmtKind::Unsupported(ref unsupported) => {
+            assert_eq!(unsupported.reason, "`try` is not supported");
+            assert_eq!(unsupported.text, "try { f(); } catch (...) { }");
+        }
+        ref kind => panic!("expected unsupported statement, found {:?}", kind),
+    }
+    assert!(matches!(body[2].kind, StmtKind::Declaration(_)));
+}
+
+#[test]
+fn test_extern_prototypes() {
+    let unit = parse("extern int a(int);\nextern \"C\" { int b(void); }\nint c(int);\n");
+    let externs: Vec<bool> = unit
+        .items
+        .iter()
+        .map(|item| match item.kind {
+            ItemKind::Function(ref function) => function.is_extern,
+            ref kind => panic!("expected function, found {:?}", kind),
+        })
+        .collect();
+    assert_eq!(externs, vec![true, true, false]);
+}
+
+#[test]
+fn test_specifier_keywords() {
+    let unit = parse(
+        "extern \"C\" { extern int count; }\nstatic inline int twice(int x) { return x * 2; }\nconstexpr int N = 4;\nvolatile unsigned *const volatile reg;\nenum class Color : unsigned char { Red, Green };\n",
+    );
+    match unit.items[0].kind {
+        ItemKind::Declaration(ref decl) => assert!(decl.is_extern),
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+    match unit.items[1].kind {
+        ItemKind::Function(ref function) => assert!(function.is_inline),
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+    match unit.items[2].kind {
+        ItemKind::Declaration(ref decl) => assert!(decl.is_const),
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+    match unit.items[3].kind {
+        ItemKind::Declaration(ref decl) => assert!(decl.is_volatile),
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+    match unit.items[4].kind {
+        ItemKind::Enum(ref def) => {
+            assert_eq!(def.name, Some("Color".to_string()));
+            assert_eq!(def.variants.len(), 2);
+            assert!(def.scoped);
+            assert_eq!(
+                def.underlying,
+                Some(CType::Primitive(TokenType::Character, Modifier::Unsigned))
+            );
+        }
+        ref kind => panic!("expected enum, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_sizeof_pack_is_an_error() {
+    let tokens = Tokenizer::new("int main(){ n = sizeof...(Args); }")
+        .tokenize()
+        .expect("lexing failed");
+    let (_, errors) = init_parser(&tokens);
+    assert_eq!(
+        errors[0].message,
+        "`sizeof...` is not supported, found `...`"
+    );
+}
+
+#[test]
+fn test_named_casts() {
+    let body = function_body(&parse("int f(A *a){ return static_cast<int>(a->x); }"));
+    match body[0].kind {
+        StmtKind::Return(Some(Expr {
+            kind: ExprKind::Cast(ref ty, _),
+            ..
+        })) => assert_eq!(*ty, CType::Primitive(TokenType::Integer, Modifier::Default)),
+        ref kind => panic!("expected a cast, found {:?}", kind),
+    }
+    let tokens = Tokenizer::new("int f(A *a){ return dynamic_cast<B *>(a)->y; }")
+        .tokenize()
+        .expect("lexing failed");
+    let (_, errors) = init_parser(&tokens);
+    assert_eq!(
+        errors[0].message,
+        "`dynamic_cast` is not supported, found `<`"
+    );
+}
+
+#[test]
+fn test_recovery_keeps_the_rest_of_the_file() {
+    let text = "int broken(int a {\n    return a;\n}\nint ok(){ return 1; }\n}\nstruct S { int x; int y z; int w; };\nint main(){\n    if (x) { y = ; }\n    z = 2;\n}\n";
+    let tokens = Tokenizer::new(text).tokenize().expect("lexing failed");
+    let (unit, errors) = init_parser(&tokens);
+    let lines: Vec<u32> = errors.iter().map(|err| err.span.line).collect();
+    assert_eq!(lines, vec![1, 5, 6, 8]);
+
+    match unit.items[0].kind {
+        ItemKind::Unsupported(ref unsupported) => {
+            assert_eq!(unsupported.text, "int broken(int a {\n    return a;\n}");
+            assert_eq!(unsupported.reason, errors[0].message);
+        }
+        ref kind => panic!("expected unsupported item, found {:?}", kind),
+    }
+    match unit.items[1].kind {
+        ItemKind::Function(ref function) => assert_eq!(function.name, "ok"),
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+    //stray closing brace
+    assert!(matches!(unit.items[2].kind, ItemKind::Unsupported(_)));
+    match unit.items[3].kind {
+        ItemKind::Record(ref record) => {
+            assert_eq!(record.fields().len(), 2);
+            assert!(matches!(record.members[1].kind, MemberKind::Unsupported(_)));
+        }
+        ref kind => panic!("expected struct, found {:?}", kind),
+    }
+    let body = match unit.items[4].kind {
+        ItemKind::Function(ref function) => function.body.clone().expect("main without body"),
+        ref kind => panic!("expected function, found {:?}", kind),
+    };
+    match body[0].kind {
+        StmtKind::If { ref then, .. } => match then.kind {
+            StmtKind::Block(ref stmts) => {
+                assert!(matches!(stmts[0].kind, StmtKind::Unsupported(_)))
+            }
+            ref kind => panic!("expected block, found {:?}", kind),
+        },
+        ref kind => panic!("expected if, found {:?}", kind),
+    }
+    assert!(matches!(body[1].kind, StmtKind::Expr(_)));
+}
+
+#[test]
+fn test_function_pointer_declarators() {
+    let unit = parse(
+        "typedef int (*binop)(int, int);\nvoid sort(int *v, int (*cmp)(const void *, const void *));\nvoid (*handlers[3])(int);\n",
+    );
+    let binop = CType::Pointer(Box::new(CType::Function(
+        Box::new(int()),
+        vec![int(), int()],
+    )));
+    match unit.items[0].kind {
+        ItemKind::Typedef(ref typedef) => assert_eq!(typedef.ty, binop),
+        ref kind => panic!("expected typedef, found {:?}", kind),
+    }
+    match unit.items[1].kind {
+        ItemKind::Function(ref function) => {
+            assert_eq!(function.params[1].name, Some("cmp".to_string()));
+            assert!(function.params[1].ty.is_function_pointer());
+        }
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+    match unit.items[2].kind {
+        ItemKind::Declaration(ref decl) => {
+            let handler = &decl.declarators[0];
+            assert_eq!(handler.name, "handlers");
+            match handler.ty {
+                CType::Array(ref inner, Some(_)) => assert!(inner.is_function_pointer()),
+                ref ty => panic!("expected array, found {:?}", ty),
+            }
+        }
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_base_classes_and_virtual_methods() {
+    let unit = parse(
+        "class Square : public Shape, private virtual Named {\npublic:\n  virtual int area() = 0;\n  int sides() const override { return 4; }\n  int plain() { return 1; }\n};\n",
+    );
+    let record = match unit.items[0].kind {
+        ItemKind::Record(ref record) => record,
+        ref kind => panic!("expected record, found {:?}", kind),
+    };
+    assert_eq!(record.bases, vec!["Shape".to_string(), "Named".to_string()]);
+    let methods: Vec<&Function> = record
+        .members
+        .iter()
+        .filter_map(|member| match member.kind {
+            MemberKind::Method(ref method) => Some(method),
+            _ => None,
+        })
+        .collect();
+    assert!(methods[0].is_virtual && methods[0].is_pure);
+    assert!(methods[0].body.is_none());
+    assert!(methods[1].is_virtual && !methods[1].is_pure);
+    assert!(!methods[2].is_virtual);
+}
+
+#[test]
+fn test_constructor_initializers_and_destructor() {
+    let unit = parse(
+        "class A {\npublic:\n  A(int x) : a(x), b(x, 2) { }\n  virtual ~A() { a = 0; }\n};\nint main(){ A a(1); }\n",
+    );
+    let record = match unit.items[0].kind {
+        ItemKind::Record(ref record) => record,
+        ref kind => panic!("expected record, found {:?}", kind),
+    };
+    match record.members[0].kind {
+        MemberKind::Constructor(ref constructor) => {
+            let members: Vec<&str> = constructor
+                .initializers
+                .iter()
+                .map(|initializer| initializer.member.as_str())
+                .collect();
+            assert_eq!(members, vec!["a", "b"]);
+            assert_eq!(constructor.initializers[1].args.len(), 2);
+        }
+        ref kind => panic!("expected constructor, found {:?}", kind),
+    }
+    match record.members[1].kind {
+        MemberKind::Destructor(ref destructor) => assert_eq!(destructor.name, "~A"),
+        ref kind => panic!("expected destructor, found {:?}", kind),
+    }
+    match function_body(&unit)[0].kind {
+        StmtKind::Declaration(ref decl) => match decl.declarators[0].init {
+            Some(Expr {
+                kind: ExprKind::Call(ref callee, ref args),
+                ..
+            }) => {
+                assert_eq!(callee.kind, ExprKind::Ident("A".to_string()));
+                assert_eq!(args.len(), 1);
+            }
+            ref init => panic!("expected constructor call, found {:?}", init),
+        },
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_static_and_const_methods() {
+    let unit = parse(
+        "class A {\npublic:\n  static int zero() { return 0; }\n  int get() const { return 1; }\n};\nint main(){ return A::zero(); }\n",
+    );
+    let record = match unit.items[0].kind {
+        ItemKind::Record(ref record) => record,
+        ref kind => panic!("expected record, found {:?}", kind),
+    };
+    match (&record.members[0].kind, &record.members[1].kind) {
+        (MemberKind::Method(zero), MemberKind::Method(get)) => {
+            assert!(zero.is_static && !zero.is_const);
+            assert!(get.is_const && !get.is_static);
+        }
+        kinds => panic!("expected methods, found {:?}", kinds),
+    }
+    match function_body(&unit)[0].kind {
+        StmtKind::Return(Some(Expr {
+            kind: ExprKind::Call(ref callee, _),
+            ..
+        })) => assert_eq!(callee.kind, ExprKind::Ident("A::zero".to_string())),
+        ref kind => panic!("expected qualified call, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_member_definitions_outside_of_class() {
+    let unit =
+        parse("int A::get(int a) const { return a; }\nA::A(int x) : a(x) { }\nA::~A() { }\n");
+    let functions: Vec<&Function> = unit
+        .items
+        .iter()
+        .filter_map(|item| match item.kind {
+            ItemKind::Function(ref function) => Some(function),
+            _ => None,
+        })
+        .collect();
+    let names: Vec<(&str, Option<&str>)> = functions
+        .iter()
+        .map(|function| (function.name.as_str(), function.class.as_deref()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![("get", Some("A")), ("A", Some("A")), ("~A", Some("A"))]
+    );
+    assert!(functions[0].is_const);
+    assert_eq!(functions[1].return_type, CType::Named("A".to_string()));
+    assert_eq!(functions[1].initializers.len(), 1);
+}
+
+#[test]
+fn test_operator_overloads_and_references() {
+    let unit = parse(
+        "class V {\npublic:\n  int x;\n  V operator+(const V &o) const { return o; }\n  int &operator[](int i) { return x; }\n  friend bool operator==(const V &a, const V &b) { return a.x == b.x; }\n};\nV operator-(V a, V b) { return a; }\n",
+    );
+    let record = match unit.items[0].kind {
+        ItemKind::Record(ref record) => record,
+        ref kind => panic!("expected record, found {:?}", kind),
+    };
+    match (
+        &record.members[1].kind,
+        &record.members[2].kind,
+        &record.members[3].kind,
+    ) {
+        (MemberKind::Method(add), MemberKind::Method(index), MemberKind::Friend(eq)) => {
+            assert_eq!(add.operator(), Some("+"));
+            assert_eq!(
+                add.params[0].ty,
+                CType::Reference(Box::new(CType::Named("V".to_string())))
+            );
+            assert_eq!(index.operator(), Some("[]"));
+            assert!(index.return_type.is_reference());
+            assert_eq!(eq.operator(), Some("=="));
+            assert_eq!(eq.params.len(), 2);
+        }
+        kinds => panic!("expected operators, found {:?}", kinds),
+    }
+    match unit.items[1].kind {
+        ItemKind::Function(ref function) => assert_eq!(function.operator(), Some("-")),
+        ref kind => panic!("expected function, found {:?}", kind),
+    }
+}
+
+#[test]
+fn test_unsupported_operators_are_errors() {
+    for text in &[
+        "class F { public: int operator()(int a) { return a; } };\n",
+        "class F { public: operator int() { return 0; } };\n",
+    ] {
+        let tokens = Tokenizer::new(text).tokenize().expect("lexing failed");
+        let (_, errors) = init_parser(&tokens);
+        assert!(!errors.is_empty(), "{}", text);
+    }
+}
+
+#[test]
+fn test_templates() {
+    let unit = parse(
+        "template <typename T, int N>\nclass Buffer { public: T items[N]; };\n\
+         template <class T> T first(Buffer<T, 4> b) { return b.items[0]; }\n\
+         template class Buffer<int, 4>;\n\
+         int main() { Buffer<Buffer<int, 2>> nested; return first<int>(nested.items[0]); }\n",
+    );
+    match unit.items[0].kind {
+        ItemKind::Record(ref record) => assert_eq!(
+            record.template,
+            vec![
+                TemplateParam {
+                    name: "T".to_string(),
+                    ty: None
+                },
+                TemplateParam {
+                    name: "N".to_string(),
+                    ty: Some(int())
+                }
+            ]
+        ),
+        ref kind => panic!("expected class template, found {:?}", kind),
+    }
+    match unit.items[1].kind {
+        ItemKind::Function(ref function) => {
+            assert_eq!(function.template.len(), 1);
+            match function.params[0].ty {
+                CType::Generic(ref name, ref args) => {
+                    assert_eq!(name, "Buffer");
+                    assert_eq!(args[0], TemplateArg::Type(CType::Named("T".to_string())));
+                }
+                ref ty => panic!("expected template type, found {:?}", ty),
+            }
+        }
+        ref kind => panic!("expected function template, found {:?}", kind),
+    }
+    assert_eq!(
+        unit.items[2].kind,
+        ItemKind::Instantiation("template class Buffer<int, 4>;".to_string())
+    );
+    let body = match unit.items[3].kind {
+        ItemKind::Function(ref function) => function.body.clone().expect("main without body"),
+        ref kind => panic!("expected main, found {:?}", kind),
+    };
+    match body[0].kind {
+        StmtKind::Declaration(ref decl) => match decl.declarators[0].ty {
+            CType::Generic(_, ref args) => {
+                assert!(matches!(args[0], TemplateArg::Type(CType::Generic(..))))
+            }
+            ref ty => panic!("expected nested template type, found {:?}", ty),
+        },
+        ref kind => panic!("expected declaration, found {:?}", kind),
+    }
+    match body[1].kind {
+        StmtKind::Return(Some(Expr {
+            kind: ExprKind::Call(ref callee, _),
+            ..
+        })) => assert_eq!(
+            callee.kind,
+            ExprKind::Generic("first".to_string(), vec![TemplateArg::Type(int())])
+        ),
+        ref kind => panic!("expected call with template arguments, found {:?}", kind),
+    }
+}
+
 #[test]
-fn test_that_works() {
-    assert_eq!(1, 1);
+fn test_namespaces_and_using() {
+    let unit = parse(
+        "namespace geo { struct Point { int x; }; namespace detail { int f() { return 1; } } }\n\
+         namespace util::math { int cube(int v) { return v * v * v; } }\n\
+         namespace { int hidden; }\n\
+         using namespace std;\nusing geo::detail::f;\n\
+         int main() { geo::Point p; std::vector<int> v; vector<long> w; std::string s; return f(); }\n",
+    );
+    match unit.items[0].kind {
+        ItemKind::Namespace(ref namespace) => {
+            assert_eq!(namespace.name, Some("geo".to_string()));
+            assert!(matches!(namespace.items[0].kind, ItemKind::Record(_)));
+            assert!(matches!(
+                namespace.items[1].kind,
+                ItemKind::Namespace(Namespace {
+                    name: Some(ref name),
+                    ..
+                }) if name == "detail"
+            ));
+        }
+        ref kind => panic!("expected namespace, found {:?}", kind),
+    }
+    //namespace a::b { } nests b inside a
+    match unit.items[1].kind {
+        ItemKind::Namespace(ref namespace) => match namespace.items[0].kind {
+            ItemKind::Namespace(ref inner) => {
+                assert_eq!(namespace.name, Some("util".to_string()));
+                assert_eq!(inner.name, Some("math".to_string()));
+                assert!(matches!(inner.items[0].kind, ItemKind::Function(_)));
+            }
+            ref kind => panic!("expected nested namespace, found {:?}", kind),
+        },
+        ref kind => panic!("expected namespace, found {:?}", kind),
+    }
+    assert!(matches!(
+        unit.items[2].kind,
+        ItemKind::Namespace(Namespace { name: None, .. })
+    ));
+    assert_eq!(
+        unit.items[3].kind,
+        ItemKind::Using(Using {
+            path: "std".to_string(),
+            namespace: true
+        })
+    );
+    assert_eq!(
+        unit.items[4].kind,
+        ItemKind::Using(Using {
+            path: "geo::detail::f".to_string(),
+            namespace: false
+        })
+    );
+    let body = function_body(&unit);
+    let types: Vec<CType> = body
+        .iter()
+        .filter_map(|stmt| match stmt.kind {
+            StmtKind::Declaration(ref decl) => Some(decl.declarators[0].ty.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            CType::Named("geo::Point".to_string()),
+            CType::Generic("std::vector".to_string(), vec![TemplateArg::Type(int())]),
+            CType::Generic(
+                "vector".to_string(),
+                vec![TemplateArg::Type(CType::Primitive(
+                    TokenType::Long,
+                    Modifier::Default
+                ))]
+            ),
+            CType::Named("std::string".to_string()),
+        ]
+    );
 }
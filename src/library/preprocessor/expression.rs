@@ -0,0 +1,227 @@
+#![allow(dead_code)]
+
+use library::preprocessor::pp_token::PpToken;
+
+/**
+ * evaluate:
+ * value of the constant expression of an #if or #elif directive,
+ * `defined` and identifiers must already be replaced by numbers
+ */
+pub fn evaluate(tokens: &[PpToken]) -> Result<i64, String> {
+    if tokens.is_empty() {
+        return Err("#if with no expression".to_string());
+    }
+    let mut evaluator = Evaluator {
+        tokens,
+        pos: 0,
+        unevaluated: 0,
+    };
+    let value = evaluator.conditional()?;
+    match evaluator.peek() {
+        Some(token) => Err(format!("unexpected `{}` in #if expression", token.text())),
+        None => Ok(value),
+    }
+}
+
+//Evaluator precedence climbing over the tokens of a constant expression
+struct Evaluator<'a> {
+    tokens: &'a [PpToken],
+    pos: usize,
+    //depth of operands skipped by && || and ?:, errors are not raised there
+    unevaluated: u32,
+}
+
+impl<'a> Evaluator<'a> {
+    fn peek(&self) -> Option<&'a PpToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        match self.peek() {
+            Some(token) if token.is_punct(punct) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn conditional(&mut self) -> Result<i64, String> {
+        let cond = self.binary(1)?;
+        if !self.eat("?") {
+            return Ok(cond);
+        }
+        let then = self.operand(cond == 0, |evaluator| evaluator.conditional())?;
+        if !self.eat(":") {
+            return Err("expected `:` in #if expression".to_string());
+        }
+        let otherwise = self.operand(cond != 0, |evaluator| evaluator.conditional())?;
+        Ok(if cond != 0 { then } else { otherwise })
+    }
+
+    // operand whose value is discarded when skipped is true
+    fn operand<F>(&mut self, skipped: bool, parse: F) -> Result<i64, String>
+    where
+        F: Fn(&mut Evaluator<'a>) -> Result<i64, String>,
+    {
+        if skipped {
+            self.unevaluated += 1;
+        }
+        let value = parse(self);
+        if skipped {
+            self.unevaluated -= 1;
+        }
+        value
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<i64, String> {
+        let mut lhs = self.unary()?;
+        while let Some(PpToken::Punct(op)) = self.peek() {
+            let op = op.as_str();
+            let precedence = match binary_precedence(op) {
+                Some(precedence) if precedence >= min_precedence => precedence,
+                _ => break,
+            };
+            self.pos += 1;
+            let skipped = (op == "&&" && lhs == 0) || (op == "||" && lhs != 0);
+            let rhs = self.operand(skipped, |evaluator| evaluator.binary(precedence + 1))?;
+            lhs = self.apply(op, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    fn apply(&self, op: &str, lhs: i64, rhs: i64) -> Result<i64, String> {
+        let value = match op {
+            "*" => lhs.wrapping_mul(rhs),
+            "/" | "%" if rhs == 0 => {
+                if self.unevaluated > 0 {
+                    0
+                } else {
+                    return Err("division by zero in #if".to_string());
+                }
+            }
+            "/" => lhs.wrapping_div(rhs),
+            "%" => lhs.wrapping_rem(rhs),
+            "+" => lhs.wrapping_add(rhs),
+            "-" => lhs.wrapping_sub(rhs),
+            "<<" => lhs.wrapping_shl(rhs as u32),
+            ">>" => lhs.wrapping_shr(rhs as u32),
+            "<" => (lhs < rhs) as i64,
+            ">" => (lhs > rhs) as i64,
+            "<=" => (lhs <= rhs) as i64,
+            ">=" => (lhs >= rhs) as i64,
+            "==" => (lhs == rhs) as i64,
+            "!=" => (lhs != rhs) as i64,
+            "&" => lhs & rhs,
+            "^" => lhs ^ rhs,
+            "|" => lhs | rhs,
+            "&&" => (lhs != 0 && rhs != 0) as i64,
+            _ => (lhs != 0 || rhs != 0) as i64,
+        };
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err("unexpected end of #if expression".to_string()),
+        };
+        self.pos += 1;
+        match *token {
+            PpToken::Punct(ref op) if op == "+" => self.unary(),
+            PpToken::Punct(ref op) if op == "-" => Ok(self.unary()?.wrapping_neg()),
+            PpToken::Punct(ref op) if op == "!" => Ok((self.unary()? == 0) as i64),
+            PpToken::Punct(ref op) if op == "~" => Ok(!self.unary()?),
+            PpToken::Punct(ref op) if op == "(" => {
+                let value = self.conditional()?;
+                if !self.eat(")") {
+                    return Err("expected `)` in #if expression".to_string());
+                }
+                Ok(value)
+            }
+            PpToken::Number(ref text) => parse_integer(text),
+            PpToken::Literal(ref text) if !text.ends_with('"') => char_value(text),
+            _ => Err(format!(
+                "expected value in #if expression, found `{}`",
+                token.text()
+            )),
+        }
+    }
+}
+
+// c/c++ binding power of binary operators allowed in #if
+fn binary_precedence(op: &str) -> Option<u8> {
+    match op {
+        "*" | "/" | "%" => Some(10),
+        "+" | "-" => Some(9),
+        "<<" | ">>" => Some(8),
+        "<" | ">" | "<=" | ">=" => Some(7),
+        "==" | "!=" => Some(6),
+        "&" => Some(5),
+        "^" => Some(4),
+        "|" => Some(3),
+        "&&" => Some(2),
+        "||" => Some(1),
+        _ => None,
+    }
+}
+
+/**
+ * parse_integer:
+ * value of a decimal, hex, binary or octal integer literal with
+ * optional u/l suffixes and ' digit separators
+ */
+pub fn parse_integer(text: &str) -> Result<i64, String> {
+    let digits: String = text
+        .chars()
+        .filter(|ch| *ch != '\'')
+        .collect::<String>()
+        .to_lowercase();
+    let digits = digits.trim_end_matches(['u', 'l']);
+    let (radix, digits) = if let Some(hex) = digits.strip_prefix("0x") {
+        (16, hex)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        (2, binary)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (8, &digits[1..])
+    } else {
+        (10, digits)
+    };
+    u64::from_str_radix(digits, radix)
+        .map(|value| value as i64)
+        .map_err(|_| format!("invalid integer constant `{}` in #if", text))
+}
+
+// value of a character literal, 'a' or '\n'
+fn char_value(text: &str) -> Result<i64, String> {
+    let quoted = text.trim_start_matches(|ch| ch != '\'');
+    let inner: Vec<char> = quoted
+        .strip_prefix('\'')
+        .and_then(|quoted| quoted.strip_suffix('\''))
+        .unwrap_or("")
+        .chars()
+        .collect();
+    let value = match inner.as_slice() {
+        ['\\', 'x', hex @ ..] => {
+            let hex: String = hex.iter().collect();
+            i64::from_str_radix(&hex, 16).ok()
+        }
+        ['\\', octal @ ..] if !octal.is_empty() && octal.iter().all(|ch| ch.is_digit(8)) => {
+            let octal: String = octal.iter().collect();
+            i64::from_str_radix(&octal, 8).ok()
+        }
+        ['\\', escape] => match *escape {
+            'n' => Some(10),
+            't' => Some(9),
+            'r' => Some(13),
+            'a' => Some(7),
+            'b' => Some(8),
+            'f' => Some(12),
+            'v' => Some(11),
+            other => Some(other as i64),
+        },
+        [ch] => Some(*ch as i64),
+        _ => None,
+    };
+    value.ok_or_else(|| format!("invalid character constant {} in #if", text))
+}
@@ -0,0 +1,7 @@
+pub mod expression;
+pub mod pp_token;
+#[allow(clippy::module_inception)]
+pub mod preprocessor;
+
+#[cfg(test)]
+mod preprocessor_test;
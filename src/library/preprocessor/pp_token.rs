@@ -0,0 +1,162 @@
+#![allow(dead_code)]
+
+//PpToken preprocessing token, spaces and comments are kept as tokens so
+//that text between macro uses passes through the preprocessor unchanged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PpToken {
+    Ident(String),
+    //pp-number, any run of digits, letters, `.`, `'` and exponent signs
+    Number(String),
+    //string or character literal, quotes included
+    Literal(String),
+    Punct(String),
+    //spaces, new lines and comments
+    Space(String),
+}
+
+impl PpToken {
+    pub fn text(&self) -> &str {
+        match *self {
+            PpToken::Ident(ref text)
+            | PpToken::Number(ref text)
+            | PpToken::Literal(ref text)
+            | PpToken::Punct(ref text)
+            | PpToken::Space(ref text) => text,
+        }
+    }
+
+    pub fn is_space(&self) -> bool {
+        matches!(*self, PpToken::Space(_))
+    }
+
+    pub fn is_punct(&self, value: &str) -> bool {
+        match *self {
+            PpToken::Punct(ref text) => text == value,
+            _ => false,
+        }
+    }
+
+    pub fn is_ident(&self, value: &str) -> bool {
+        match *self {
+            PpToken::Ident(ref text) => text == value,
+            _ => false,
+        }
+    }
+
+    // number of new lines covered by the token
+    pub fn newlines(&self) -> u32 {
+        match *self {
+            PpToken::Space(ref text) => text.matches('\n').count() as u32,
+            _ => 0,
+        }
+    }
+}
+
+//multi character punctuators, longest first
+const PUNCTUATORS: [&str; 26] = [
+    "...", "<<=", ">>=", "->*", "##", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&",
+    "||", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "::", ".*",
+];
+
+/**
+ * lex:
+ * splits text into preprocessing tokens, joining the text of all
+ * the tokens gives back the input
+ */
+pub fn lex(text: &str) -> Vec<PpToken> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<PpToken> = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let start = pos;
+        let current = chars[pos];
+        let next = chars.get(pos + 1).cloned().unwrap_or('\0');
+
+        let token = if current.is_whitespace() {
+            while pos < chars.len() && chars[pos].is_whitespace() {
+                pos += 1;
+            }
+            PpToken::Space(collect(&chars, start, pos))
+        } else if current == '/' && next == '/' {
+            while pos < chars.len() && chars[pos] != '\n' {
+                pos += 1;
+            }
+            PpToken::Space(collect(&chars, start, pos))
+        } else if current == '/' && next == '*' {
+            pos += 2;
+            while pos < chars.len() && !(chars[pos] == '*' && chars.get(pos + 1) == Some(&'/')) {
+                pos += 1;
+            }
+            pos = (pos + 2).min(chars.len());
+            PpToken::Space(collect(&chars, start, pos))
+        } else if current == '_' || current.is_alphabetic() {
+            while pos < chars.len() && (chars[pos] == '_' || chars[pos].is_alphanumeric()) {
+                pos += 1;
+            }
+            PpToken::Ident(collect(&chars, start, pos))
+        } else if current.is_ascii_digit() || (current == '.' && next.is_ascii_digit()) {
+            pos += 1;
+            while pos < chars.len() {
+                let ch = chars[pos];
+                let following = chars.get(pos + 1).cloned().unwrap_or('\0');
+                if (ch == 'e' || ch == 'E' || ch == 'p' || ch == 'P')
+                    && (following == '+' || following == '-')
+                {
+                    pos += 2;
+                } else if ch == '\'' && following.is_alphanumeric() {
+                    //digit separator 1'000
+                    pos += 2;
+                } else if ch == '_' || ch == '.' || ch.is_alphanumeric() {
+                    pos += 1;
+                } else {
+                    break;
+                }
+            }
+            PpToken::Number(collect(&chars, start, pos))
+        } else if current == '"' || current == '\'' {
+            pos += 1;
+            while pos < chars.len() && chars[pos] != current && chars[pos] != '\n' {
+                if chars[pos] == '\\' {
+                    pos += 1;
+                }
+                pos += 1;
+            }
+            pos = (pos + 1).min(chars.len());
+            PpToken::Literal(collect(&chars, start, pos))
+        } else {
+            let rest: String = chars[pos..chars.len().min(pos + 3)].iter().collect();
+            let punct = PUNCTUATORS
+                .iter()
+                .find(|punct| rest.starts_with(*punct))
+                .map(|punct| punct.to_string())
+                .unwrap_or_else(|| current.to_string());
+            pos += punct.chars().count();
+            PpToken::Punct(punct)
+        };
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn collect(chars: &[char], start: usize, end: usize) -> String {
+    chars[start..end].iter().collect()
+}
+
+// text of the tokens, joined back together
+pub fn join(tokens: &[PpToken]) -> String {
+    tokens.iter().map(|token| token.text()).collect()
+}
+
+// tokens without leading and trailing spaces
+pub fn trim(tokens: &[PpToken]) -> &[PpToken] {
+    let start = tokens
+        .iter()
+        .position(|token| !token.is_space())
+        .unwrap_or(tokens.len());
+    let end = tokens
+        .iter()
+        .rposition(|token| !token.is_space())
+        .map_or(start, |index| index + 1);
+    &tokens[start..end]
+}
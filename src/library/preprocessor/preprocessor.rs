@@ -0,0 +1,920 @@
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use library::ast::{MacroBody, MacroDef};
+use library::lexer::tokenizer::Tokenizer;
+use library::parser::parser::{file_scope_names, init_parser, parse_macro};
+use library::preprocessor::expression;
+use library::preprocessor::pp_token::{join, lex, trim, PpToken};
+
+//nested #include limit, guards against files including themselves
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+//PreprocessError error raised by a directive or a macro invocation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessError {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+}
+
+impl fmt::Display for PreprocessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+pub type PreprocessResult<T> = Result<T, PreprocessError>;
+
+//LineMap source file and line of every line of the preprocessed text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineMap {
+    lines: Vec<(PathBuf, u32)>,
+}
+
+impl LineMap {
+    // file and line the output line comes from, lines are counted from 1
+    pub fn locate(&self, line: u32) -> Option<(&Path, u32)> {
+        let index = (line as usize).checked_sub(1)?;
+        self.lines
+            .get(index)
+            .map(|entry| (entry.0.as_path(), entry.1))
+    }
+}
+
+//Macro object-like or function-like macro definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Macro {
+    pub name: String,
+    //parameter names, None for object-like macros
+    pub params: Option<Vec<String>>,
+    //last parameter is `...`, bound to __VA_ARGS__
+    pub variadic: bool,
+    //replacement list, surrounding spaces removed
+    pub body: Vec<PpToken>,
+    //translated to a rust item, uses in the source are not expanded
+    pub translated: bool,
+}
+
+//Conditional state of one #if/#ifdef/#ifndef group
+struct Conditional {
+    //lines of the current branch are kept
+    active: bool,
+    //a branch of the group was already taken
+    taken: bool,
+    //the group itself is inside kept lines
+    parent_active: bool,
+    seen_else: bool,
+    line: u32,
+}
+
+pub struct Preprocessor {
+    macros: HashMap<String, Macro>,
+    include_dirs: Vec<PathBuf>,
+    //files marked with #pragma once
+    once: HashSet<PathBuf>,
+    //files being processed, innermost last
+    files: Vec<PathBuf>,
+    //current line, used for errors and __LINE__
+    line: u32,
+    //pass #define lines on and keep the macros crust translates
+    translate: bool,
+    //definitions of the translated macros, in order
+    translated: Vec<MacroDef>,
+    //expanding source lines rather than a directive
+    in_source: bool,
+    //origin of the output lines, included files are inserted whole
+    line_map: LineMap,
+    //source lines emitted so far, macro bodies may use the names they declare
+    emitted: String,
+}
+
+/**
+ * init_preprocessor:
+ * preprocesses the source of `file`, `defines` are given as
+ * NAME or NAME=VALUE like the -D option of c compilers. The line map
+ * gives the file and line every line of the output comes from
+ */
+pub fn init_preprocessor(
+    text: &str,
+    file: &Path,
+    defines: &[String],
+    include_dirs: &[String],
+) -> PreprocessResult<(String, LineMap)> {
+    let include_dirs: Vec<PathBuf> = include_dirs.iter().map(PathBuf::from).collect();
+    let mut preprocessor = Preprocessor::new(&include_dirs);
+    preprocessor.translate_macros(true);
+    for define in defines {
+        preprocessor.define(define)?;
+    }
+    let text = preprocessor.preprocess(text, file)?;
+    Ok((text, preprocessor.line_map))
+}
+
+impl Preprocessor {
+    pub fn new(include_dirs: &[PathBuf]) -> Preprocessor {
+        Preprocessor {
+            macros: HashMap::new(),
+            include_dirs: include_dirs.to_vec(),
+            once: HashSet::new(),
+            files: Vec::new(),
+            line: 0,
+            translate: false,
+            translated: Vec::new(),
+            in_source: false,
+            line_map: LineMap::default(),
+            emitted: String::new(),
+        }
+    }
+
+    /**
+     * translate_macros:
+     * when set, #define lines of macros that crust can turn into rust
+     * items are kept in the output and their uses are not expanded
+     */
+    pub fn translate_macros(&mut self, translate: bool) {
+        self.translate = translate;
+    }
+
+    /**
+     * define:
+     * defines a macro from the command line, NAME defines it as 1
+     */
+    pub fn define(&mut self, definition: &str) -> PreprocessResult<()> {
+        let text = match definition.find('=') {
+            Some(index) => format!("{} {}", &definition[..index], &definition[index + 1..]),
+            None => format!("{} 1", definition),
+        };
+        self.parse_define(&lex(&text))
+    }
+
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.macros.contains_key(name)
+    }
+
+    // appends text to the output, its lines come from `line` on in the current file
+    fn emit(&mut self, output: &mut String, text: &str, line: u32) {
+        let file = self.files.last().cloned().unwrap_or_default();
+        let count = text.matches('\n').count() as u32;
+        self.line_map
+            .lines
+            .extend((line..line + count).map(|line| (file.clone(), line)));
+        output.push_str(text);
+        self.emitted.push_str(text);
+    }
+
+    fn error<T>(&self, message: &str) -> PreprocessResult<T> {
+        let file = match self.files.last() {
+            Some(file) => file.display().to_string(),
+            None => "<command line>".to_string(),
+        };
+        Err(PreprocessError {
+            message: message.to_string(),
+            file,
+            line: self.line,
+        })
+    }
+
+    /**
+     * preprocess:
+     * runs directives and expands macros in text read from `file`.
+     * Every input line produces one output line so that line numbers
+     * of the tokens stay the same, included files are inserted whole
+     * and the line map records where their lines come from
+     */
+    pub fn preprocess(&mut self, text: &str, file: &Path) -> PreprocessResult<String> {
+        if self.files.is_empty() {
+            self.line_map = LineMap::default();
+            self.emitted.clear();
+        }
+        self.files.push(file.to_path_buf());
+        let saved_line = self.line;
+
+        let mut output = String::new();
+        //kept lines waiting for macro expansion
+        let mut pending = String::new();
+        let mut pending_line = 1;
+        let mut conditionals: Vec<Conditional> = Vec::new();
+        let mut in_comment = false;
+        let mut line_no: u32 = 1;
+
+        for (line, count) in logical_lines(text) {
+            let is_directive = !in_comment && line.trim_start().starts_with('#');
+            in_comment = ends_in_comment(&line, in_comment);
+            let active = conditionals.last().is_none_or(|cond| cond.active);
+
+            if is_directive {
+                self.flush(&mut pending, pending_line, &mut output)?;
+                self.line = line_no;
+                if let Some(text) = self.directive(&line, &mut conditionals, active)? {
+                    //lines of an included file are already in the line map
+                    output.push_str(&text);
+                }
+                self.emit(&mut output, &"\n".repeat(count as usize), line_no);
+                pending_line = line_no + count;
+            } else if active {
+                if pending.is_empty() {
+                    pending_line = line_no;
+                }
+                pending.push_str(&line);
+                pending.push_str(&"\n".repeat(count as usize));
+            } else {
+                self.emit(&mut output, &"\n".repeat(count as usize), line_no);
+            }
+            line_no += count;
+        }
+        self.flush(&mut pending, pending_line, &mut output)?;
+
+        if let Some(cond) = conditionals.last() {
+            self.line = cond.line;
+            return self.error("unterminated conditional directive");
+        }
+        self.files.pop();
+        self.line = saved_line;
+        Ok(output)
+    }
+
+    // expands macros in the pending lines and moves them to the output
+    fn flush(
+        &mut self,
+        pending: &mut String,
+        line: u32,
+        output: &mut String,
+    ) -> PreprocessResult<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        self.line = line;
+        let tokens = lex(pending);
+        self.in_source = true;
+        let expanded = self.expand(&tokens, &[], true);
+        self.in_source = false;
+        let expanded = expanded?;
+        self.emit(output, &join(&expanded), line);
+        pending.clear();
+        Ok(())
+    }
+
+    /**
+     * directive:
+     * runs one directive line, returns the text to emit in its place.
+     * Directives other than conditionals are ignored in skipped lines
+     */
+    fn directive(
+        &mut self,
+        line: &str,
+        conditionals: &mut Vec<Conditional>,
+        active: bool,
+    ) -> PreprocessResult<Option<String>> {
+        let tokens = lex(line);
+        let tokens = trim(&tokens);
+        //tokens[0] is `#`
+        let rest = trim(&tokens[1..]);
+        let (name, rest) = match rest.first() {
+            Some(PpToken::Ident(name)) => (name.as_str(), trim(&rest[1..])),
+            Some(_) if active => return self.error("invalid preprocessing directive"),
+            //null directive `#`
+            _ => ("", rest),
+        };
+
+        match name {
+            "if" | "ifdef" | "ifndef" => {
+                let value = if !active {
+                    false
+                } else if name == "if" {
+                    self.eval_condition(rest)?
+                } else {
+                    let defined = self.is_defined(self.macro_name(rest)?);
+                    defined == (name == "ifdef")
+                };
+                conditionals.push(Conditional {
+                    active: value,
+                    taken: value || !active,
+                    parent_active: active,
+                    seen_else: false,
+                    line: self.line,
+                });
+            }
+            "elif" => {
+                let (parent_active, taken) = match conditionals.last() {
+                    Some(cond) if cond.seen_else => return self.error("#elif after #else"),
+                    Some(cond) => (cond.parent_active, cond.taken),
+                    None => return self.error("#elif without #if"),
+                };
+                let value = parent_active && !taken && self.eval_condition(rest)?;
+                if let Some(cond) = conditionals.last_mut() {
+                    cond.active = value;
+                    cond.taken = taken || value;
+                }
+            }
+            "else" => match conditionals.last_mut() {
+                Some(ref cond) if cond.seen_else => return self.error("#else after #else"),
+                Some(cond) => {
+                    cond.active = cond.parent_active && !cond.taken;
+                    cond.taken = true;
+                    cond.seen_else = true;
+                }
+                None => return self.error("#else without #if"),
+            },
+            "endif" => {
+                if conditionals.pop().is_none() {
+                    return self.error("#endif without #if");
+                }
+            }
+            _ if !active => {}
+            "define" => {
+                self.parse_define(rest)?;
+                if self.translate {
+                    return Ok(self.pass_definition(line));
+                }
+            }
+            "undef" => {
+                let name = self.macro_name(rest)?.to_string();
+                self.macros.remove(&name);
+            }
+            "include" => return self.include(rest, line),
+            "pragma" => {
+                if rest.len() == 1 && rest[0].is_ident("once") {
+                    let file = self.files.last().cloned().unwrap_or_default();
+                    self.once.insert(canonical(&file));
+                }
+            }
+            "error" => return self.error(&format!("#error {}", join(rest))),
+            "warning" | "line" | "" => {}
+            _ => return self.error(&format!("invalid preprocessing directive `#{}`", name)),
+        }
+        Ok(None)
+    }
+
+    /**
+     * pass_definition:
+     * the #define line handed on to the parser, macros that become
+     * rust items are marked so that their uses are left alone
+     */
+    fn pass_definition(&mut self, line: &str) -> Option<String> {
+        let mut def = parse_macro(line, &self.translated, &[]);
+        //the source read so far is only parsed for bodies that need its names
+        if def.params.is_some() && !def.is_translated() {
+            let tokens = Tokenizer::new(&self.emitted).tokenize().unwrap_or_default();
+            let (unit, _) = init_parser(&tokens);
+            def = parse_macro(line, &self.translated, &file_scope_names(&unit.items));
+        }
+        if def.body == MacroBody::Empty {
+            return None;
+        }
+        if def.is_translated() {
+            if let Some(mac) = self.macros.get_mut(&def.name) {
+                mac.translated = true;
+            }
+            self.translated.push(def);
+        }
+        Some(line.trim().to_string())
+    }
+
+    // translated macros are left for the rust item in source lines
+    fn is_kept(&self, mac: &Macro) -> bool {
+        self.in_source && mac.translated
+    }
+
+    // the identifier following #ifdef, #ifndef and #undef
+    fn macro_name<'t>(&self, tokens: &'t [PpToken]) -> PreprocessResult<&'t str> {
+        match tokens.first() {
+            Some(PpToken::Ident(name)) => Ok(name),
+            Some(_) => self.error("macro names must be identifiers"),
+            None => self.error("macro name missing"),
+        }
+    }
+
+    /**
+     * parse_define:
+     * NAME body or NAME(params) body, a `(` right after the name
+     * makes it a function-like macro
+     */
+    fn parse_define(&mut self, tokens: &[PpToken]) -> PreprocessResult<()> {
+        let tokens = trim(tokens);
+        let name = self.macro_name(tokens)?.to_string();
+        if name == "defined" {
+            return self.error("`defined` cannot be used as a macro name");
+        }
+        let mut pos = 1;
+        let mut params: Option<Vec<String>> = None;
+        let mut variadic = false;
+
+        if tokens.get(pos).is_some_and(|token| token.is_punct("(")) {
+            let mut names: Vec<String> = Vec::new();
+            pos += 1;
+            loop {
+                while tokens.get(pos).is_some_and(|token| token.is_space()) {
+                    pos += 1;
+                }
+                match tokens.get(pos) {
+                    Some(PpToken::Ident(param)) if !variadic => names.push(param.clone()),
+                    Some(token) if token.is_punct("...") && !variadic => variadic = true,
+                    Some(token) if token.is_punct(")") && names.is_empty() && !variadic => break,
+                    _ => return self.error(&format!("invalid parameter list of macro `{}`", name)),
+                }
+                pos += 1;
+                while tokens.get(pos).is_some_and(|token| token.is_space()) {
+                    pos += 1;
+                }
+                match tokens.get(pos) {
+                    Some(token) if token.is_punct(",") => pos += 1,
+                    Some(token) if token.is_punct(")") => break,
+                    _ => return self.error(&format!("invalid parameter list of macro `{}`", name)),
+                }
+            }
+            pos += 1;
+            params = Some(names);
+        }
+
+        //comments and runs of spaces in the body become a single space
+        let body: Vec<PpToken> = trim(&tokens[pos..])
+            .iter()
+            .map(|token| {
+                if token.is_space() {
+                    PpToken::Space(" ".to_string())
+                } else {
+                    token.clone()
+                }
+            })
+            .collect();
+
+        if body.first().is_some_and(|token| token.is_punct("##"))
+            || body.last().is_some_and(|token| token.is_punct("##"))
+        {
+            return self.error("`##` cannot appear at either end of a macro expansion");
+        }
+        let mac = Macro {
+            name: name.clone(),
+            params,
+            variadic,
+            body,
+            translated: false,
+        };
+        if mac.params.is_some() {
+            for (index, token) in mac.body.iter().enumerate() {
+                if token.is_punct("#") {
+                    let operand = next_operand(&mac.body, index + 1);
+                    if operand.is_none_or(|operand| param_index(&mac, &mac.body[operand]).is_none())
+                    {
+                        return self.error("`#` is not followed by a macro parameter");
+                    }
+                }
+            }
+        }
+        self.macros.insert(name, mac);
+        Ok(())
+    }
+
+    /**
+     * include:
+     * "file" is searched next to the including file and then in the
+     * include directories, <file> only in the include directories.
+     * Headers that are not found are kept as #include lines
+     */
+    fn include(&mut self, tokens: &[PpToken], line: &str) -> PreprocessResult<Option<String>> {
+        let mut tokens = tokens.to_vec();
+        if let Some(&PpToken::Ident(_)) = tokens.first() {
+            //#include MACRO
+            tokens = self.expand(&tokens, &[], false)?;
+        }
+        let tokens = trim(&tokens);
+        let (name, quoted) = match tokens.first() {
+            Some(PpToken::Literal(text)) if text.starts_with('"') && text.len() > 1 => {
+                (text[1..text.len() - 1].to_string(), true)
+            }
+            Some(token)
+                if token.is_punct("<") && tokens.last().is_some_and(|t| t.is_punct(">")) =>
+            {
+                (join(&tokens[1..tokens.len() - 1]), false)
+            }
+            _ => return self.error("#include expects \"FILENAME\" or <FILENAME>"),
+        };
+
+        let path = match self.find_include(&name, quoted) {
+            Some(path) => path,
+            None => return Ok(Some(line.trim().to_string())),
+        };
+        if self.once.contains(&canonical(&path)) {
+            return Ok(None);
+        }
+        if self.files.len() >= MAX_INCLUDE_DEPTH {
+            return self.error("#include nested too deeply");
+        }
+        let mut text = String::new();
+        let read = File::open(&path).and_then(|mut file| file.read_to_string(&mut text));
+        if let Err(err) = read {
+            return self.error(&format!("unable to read `{}`: {}", path.display(), err));
+        }
+        self.preprocess(&text, &path).map(Some)
+    }
+
+    fn find_include(&self, name: &str, quoted: bool) -> Option<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        if quoted {
+            if let Some(dir) = self.files.last().and_then(|file| file.parent()) {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+        dirs.extend(self.include_dirs.iter().cloned());
+        dirs.into_iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /**
+     * eval_condition:
+     * resolves defined X and defined(X), expands the remaining macros
+     * and evaluates the result, unknown identifiers count as 0
+     */
+    fn eval_condition(&mut self, tokens: &[PpToken]) -> PreprocessResult<bool> {
+        let mut resolved: Vec<PpToken> = Vec::new();
+        let mut pos = 0;
+        while pos < tokens.len() {
+            if !tokens[pos].is_ident("defined") {
+                resolved.push(tokens[pos].clone());
+                pos += 1;
+                continue;
+            }
+            pos = skip_spaces(tokens, pos + 1);
+            let parenthesized = tokens.get(pos).is_some_and(|token| token.is_punct("("));
+            if parenthesized {
+                pos = skip_spaces(tokens, pos + 1);
+            }
+            let name = match tokens.get(pos) {
+                Some(PpToken::Ident(name)) => name.clone(),
+                _ => return self.error("`defined` requires a macro name"),
+            };
+            pos += 1;
+            if parenthesized {
+                pos = skip_spaces(tokens, pos);
+                if !tokens.get(pos).is_some_and(|token| token.is_punct(")")) {
+                    return self.error("missing `)` after `defined`");
+                }
+                pos += 1;
+            }
+            let value = if self.is_defined(&name) { "1" } else { "0" };
+            resolved.push(PpToken::Number(value.to_string()));
+        }
+
+        let expanded = self.expand(&resolved, &[], false)?;
+        let operands: Vec<PpToken> = expanded
+            .into_iter()
+            .filter(|token| !token.is_space())
+            .map(|token| match token {
+                PpToken::Ident(ref name) if name == "true" => PpToken::Number("1".to_string()),
+                PpToken::Ident(_) => PpToken::Number("0".to_string()),
+                token => token,
+            })
+            .collect();
+        match expression::evaluate(&operands) {
+            Ok(value) => Ok(value != 0),
+            Err(message) => self.error(&message),
+        }
+    }
+
+    /**
+     * expand:
+     * replaces every macro use in tokens with its expansion, macros in
+     * `disabled` are being expanded and are not replaced again.
+     * `top` is set for source lines so that __LINE__ follows them
+     */
+    fn expand(
+        &mut self,
+        tokens: &[PpToken],
+        disabled: &[String],
+        top: bool,
+    ) -> PreprocessResult<Vec<PpToken>> {
+        let mut out: Vec<PpToken> = Vec::new();
+        let mut pos = 0;
+        while pos < tokens.len() {
+            let name = match tokens[pos] {
+                PpToken::Ident(ref name) => name.clone(),
+                ref token => {
+                    if top {
+                        self.line += token.newlines();
+                    }
+                    out.push(token.clone());
+                    pos += 1;
+                    continue;
+                }
+            };
+            if name == "__LINE__" {
+                out.push(PpToken::Number(self.line.to_string()));
+                pos += 1;
+                continue;
+            }
+            if name == "__FILE__" {
+                let file = self.files.last().cloned().unwrap_or_default();
+                out.push(PpToken::Literal(format!(
+                    "{:?}",
+                    file.display().to_string()
+                )));
+                pos += 1;
+                continue;
+            }
+            let mac = match self.macros.get(&name) {
+                Some(mac) if !disabled.contains(&name) && !self.is_kept(mac) => mac.clone(),
+                _ => {
+                    out.push(tokens[pos].clone());
+                    pos += 1;
+                    continue;
+                }
+            };
+            let mut inner = disabled.to_vec();
+            inner.push(name.clone());
+
+            let (body, end) = if mac.params.is_some() {
+                //function-like macro name without arguments is left alone
+                let open = skip_spaces(tokens, pos + 1);
+                if !tokens.get(open).is_some_and(|token| token.is_punct("(")) {
+                    out.push(tokens[pos].clone());
+                    pos += 1;
+                    continue;
+                }
+                let (args, end) = self.collect_args(tokens, open, &name)?;
+                (self.substitute(&mac, args, disabled)?, end)
+            } else {
+                (mac.body.clone(), pos + 1)
+            };
+            let expansion = self.expand(&body, &inner, false)?;
+
+            //keep the expansion from pasting into its neighbours
+            if out.last().is_some_and(|token| !token.is_space()) {
+                out.push(PpToken::Space(" ".to_string()));
+            }
+            out.extend(expansion);
+            //arguments spread over several lines keep the line count
+            let newlines: u32 = tokens[pos..end].iter().map(|token| token.newlines()).sum();
+            if newlines > 0 {
+                out.push(PpToken::Space("\n".repeat(newlines as usize)));
+                if top {
+                    self.line += newlines;
+                }
+            } else if tokens.get(end).is_some_and(|token| !token.is_space()) {
+                out.push(PpToken::Space(" ".to_string()));
+            }
+            pos = end;
+        }
+        Ok(out)
+    }
+
+    // arguments of a macro invocation starting at `(`, and the position after `)`
+    fn collect_args(
+        &self,
+        tokens: &[PpToken],
+        open: usize,
+        name: &str,
+    ) -> PreprocessResult<(Vec<Vec<PpToken>>, usize)> {
+        let mut args: Vec<Vec<PpToken>> = vec![Vec::new()];
+        let mut depth = 0;
+        for (pos, token) in tokens.iter().enumerate().skip(open + 1) {
+            if token.is_punct(")") && depth == 0 {
+                let args = args.iter().map(|arg| trim(arg).to_vec()).collect();
+                return Ok((args, pos + 1));
+            }
+            if token.is_punct(",") && depth == 0 {
+                args.push(Vec::new());
+                continue;
+            }
+            if token.is_punct("(") {
+                depth += 1;
+            } else if token.is_punct(")") {
+                depth -= 1;
+            }
+            if let Some(arg) = args.last_mut() {
+                arg.push(token.clone());
+            }
+        }
+        self.error(&format!(
+            "unterminated argument list invoking macro `{}`",
+            name
+        ))
+    }
+
+    /**
+     * substitute:
+     * body of a function-like macro with its parameters replaced,
+     * #param is stringified and ## pastes its operands together.
+     * Arguments are macro expanded unless they are operands of # or ##
+     */
+    fn substitute(
+        &mut self,
+        mac: &Macro,
+        mut args: Vec<Vec<PpToken>>,
+        disabled: &[String],
+    ) -> PreprocessResult<Vec<PpToken>> {
+        let named = mac.params.as_ref().map_or(0, |params| params.len());
+        //F() passes one empty argument
+        if named == 0 && args.len() == 1 && args[0].is_empty() {
+            args.clear();
+        }
+        if mac.variadic && args.len() >= named {
+            let rest = args.split_off(named);
+            let mut va_args: Vec<PpToken> = Vec::new();
+            for (index, arg) in rest.into_iter().enumerate() {
+                if index > 0 {
+                    va_args.push(PpToken::Punct(",".to_string()));
+                    va_args.push(PpToken::Space(" ".to_string()));
+                }
+                va_args.extend(arg);
+            }
+            args.push(va_args);
+        } else if args.len() != named {
+            return self.error(&format!(
+                "macro `{}` requires {} arguments, but {} given",
+                mac.name,
+                named,
+                args.len()
+            ));
+        }
+        let mut expanded: Vec<Vec<PpToken>> = Vec::new();
+        for arg in &args {
+            expanded.push(self.expand(arg, disabled, false)?);
+        }
+
+        let body = &mac.body;
+        let mut out: Vec<PpToken> = Vec::new();
+        let mut pos = 0;
+        while pos < body.len() {
+            let token = &body[pos];
+            if token.is_punct("#") {
+                if let Some(operand) = next_operand(body, pos + 1) {
+                    if let Some(param) = param_index(mac, &body[operand]) {
+                        out.push(PpToken::Literal(stringify(&args[param])));
+                        pos = operand + 1;
+                        continue;
+                    }
+                }
+            }
+            if token.is_punct("##") {
+                while out.last().is_some_and(|token| token.is_space()) {
+                    out.pop();
+                }
+                let operand = match next_operand(body, pos + 1) {
+                    Some(operand) => operand,
+                    None => break,
+                };
+                let mut rhs = match param_index(mac, &body[operand]) {
+                    Some(param) => args[param].clone(),
+                    None => vec![body[operand].clone()],
+                };
+                if rhs.is_empty() {
+                    //, ## __VA_ARGS__ drops the comma when there are no variable arguments
+                    if body[operand].is_ident("__VA_ARGS__")
+                        && out.last().is_some_and(|token| token.is_punct(","))
+                    {
+                        out.pop();
+                    }
+                } else if let Some(lhs) = out.pop() {
+                    let pasted = format!("{}{}", lhs.text(), rhs[0].text());
+                    out.extend(lex(&pasted));
+                    out.extend(rhs.drain(1..));
+                } else {
+                    out.extend(rhs);
+                }
+                pos = operand + 1;
+                continue;
+            }
+            if let Some(param) = param_index(mac, token) {
+                let pasted =
+                    next_operand(body, pos + 1).is_some_and(|next| body[next].is_punct("##"));
+                if pasted {
+                    out.extend(args[param].iter().cloned());
+                } else {
+                    out.extend(expanded[param].iter().cloned());
+                }
+                pos += 1;
+                continue;
+            }
+            out.push(token.clone());
+            pos += 1;
+        }
+        Ok(out)
+    }
+}
+
+// position of the parameter named by token, __VA_ARGS__ follows the named ones
+fn param_index(mac: &Macro, token: &PpToken) -> Option<usize> {
+    let params = mac.params.as_ref()?;
+    let name = match *token {
+        PpToken::Ident(ref name) => name,
+        _ => return None,
+    };
+    if mac.variadic && name == "__VA_ARGS__" {
+        return Some(params.len());
+    }
+    params.iter().position(|param| param == name)
+}
+
+// position of the first non space token at or after pos
+fn next_operand(tokens: &[PpToken], pos: usize) -> Option<usize> {
+    let pos = skip_spaces(tokens, pos);
+    if pos < tokens.len() {
+        Some(pos)
+    } else {
+        None
+    }
+}
+
+fn skip_spaces(tokens: &[PpToken], mut pos: usize) -> usize {
+    while tokens.get(pos).is_some_and(|token| token.is_space()) {
+        pos += 1;
+    }
+    pos
+}
+
+// #arg : spelling of the argument as a string literal
+fn stringify(arg: &[PpToken]) -> String {
+    let mut text = String::from("\"");
+    for token in arg {
+        match *token {
+            PpToken::Space(_) => {
+                if !text.ends_with(' ') {
+                    text.push(' ');
+                }
+            }
+            PpToken::Literal(ref literal) => {
+                text.push_str(&literal.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            ref token => text.push_str(token.text()),
+        }
+    }
+    text.push('"');
+    text
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/**
+ * logical_lines:
+ * source lines with backslash-newline continuations joined,
+ * along with the number of physical lines each one spans
+ */
+fn logical_lines(text: &str) -> Vec<(String, u32)> {
+    let mut lines: Vec<(String, u32)> = Vec::new();
+    let mut current = String::new();
+    let mut count = 0;
+    for line in text.split('\n') {
+        count += 1;
+        let line = line.trim_end_matches('\r');
+        if let Some(joined) = line.strip_suffix('\\') {
+            current.push_str(joined);
+            continue;
+        }
+        current.push_str(line);
+        lines.push((current.clone(), count));
+        current.clear();
+        count = 0;
+    }
+    if count > 0 {
+        lines.push((current, count));
+    }
+    //text ending with a new line has an empty last piece
+    if text.ends_with('\n') {
+        if let Some(&(ref last, 1)) = lines.last() {
+            if last.is_empty() {
+                lines.pop();
+            }
+        }
+    }
+    lines
+}
+
+// whether a /* comment is still open at the end of the line
+fn ends_in_comment(line: &str, mut in_comment: bool) -> bool {
+    let chars: Vec<char> = line.chars().collect();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let next = chars.get(pos + 1).cloned().unwrap_or('\0');
+        if in_comment {
+            if chars[pos] == '*' && next == '/' {
+                in_comment = false;
+                pos += 1;
+            }
+        } else if chars[pos] == '/' && next == '/' {
+            return false;
+        } else if chars[pos] == '/' && next == '*' {
+            in_comment = true;
+            pos += 1;
+        } else if chars[pos] == '"' || chars[pos] == '\'' {
+            let quote = chars[pos];
+            pos += 1;
+            while pos < chars.len() && chars[pos] != quote {
+                if chars[pos] == '\\' {
+                    pos += 1;
+                }
+                pos += 1;
+            }
+        }
+        pos += 1;
+    }
+    in_comment
+}
@@ -0,0 +1,290 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use library::preprocessor::expression::evaluate;
+use library::preprocessor::pp_token::{join, lex};
+use library::preprocessor::preprocessor::{init_preprocessor, Preprocessor};
+
+fn preprocess(text: &str) -> String {
+    preprocess_with(text, &[])
+}
+
+// preprocessing with every macro expanded, as c compilers do
+fn preprocess_with(text: &str, defines: &[&str]) -> String {
+    let mut preprocessor = Preprocessor::new(&[]);
+    for define in defines {
+        preprocessor.define(define).expect("invalid define");
+    }
+    preprocessor
+        .preprocess(text, Path::new("test.cpp"))
+        .expect("preprocess failed")
+}
+
+fn translate(text: &str) -> String {
+    init_preprocessor(text, Path::new("test.cpp"), &[], &[])
+        .expect("preprocess failed")
+        .0
+}
+
+// output with whitespace collapsed
+fn squeeze(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn error_of(text: &str) -> String {
+    Preprocessor::new(&[])
+        .preprocess(text, Path::new("test.cpp"))
+        .unwrap_err()
+        .message
+}
+
+fn eval(text: &str) -> i64 {
+    let tokens: Vec<_> = lex(text).into_iter().filter(|t| !t.is_space()).collect();
+    evaluate(&tokens).expect("evaluation failed")
+}
+
+#[test]
+fn test_lex_round_trips_text() {
+    let text = "int a = 0x1F'00 + 'c'; /* note */ s = \"a\\\"b\"; // end\n";
+    assert_eq!(join(&lex(text)), text);
+}
+
+#[test]
+fn test_object_like_macro() {
+    let out = preprocess("#define MAX 256\nint a[MAX];\n");
+    assert_eq!(squeeze(&out), "int a[ 256 ];");
+}
+
+#[test]
+fn test_function_like_macro() {
+    let out = preprocess("#define SQUARE(x) ((x) * (x))\ny = SQUARE(a + 1);\n");
+    assert_eq!(squeeze(&out), "y = ((a + 1) * (a + 1)) ;");
+}
+
+#[test]
+fn test_function_like_macro_without_arguments_is_kept() {
+    let out = preprocess("#define f(x) x\nint f;\n");
+    assert_eq!(squeeze(&out), "int f;");
+}
+
+#[test]
+fn test_nested_and_recursive_macros() {
+    let out =
+        preprocess("#define A B + 1\n#define B A * 2\n#define TWICE(x) x x\nA; TWICE(TWICE(z));\n");
+    assert_eq!(squeeze(&out), "A * 2 + 1 ; z z z z ;");
+}
+
+#[test]
+fn test_stringify_and_paste() {
+    let out = preprocess(
+        "#define STR(x) #x\n#define CAT(a, b) a ## b\nconst char *s = STR(a  \"q\");\nint CAT(var, 1);\n",
+    );
+    assert_eq!(
+        squeeze(&out),
+        "const char *s = \"a \\\"q\\\"\" ; int var1 ;"
+    );
+}
+
+#[test]
+fn test_variadic_macro() {
+    let out = preprocess(
+        "#define LOG(fmt, ...) printf(fmt, ## __VA_ARGS__)\nLOG(\"a\", 1, 2);\nLOG(\"b\");\n",
+    );
+    assert_eq!(squeeze(&out), "printf(\"a\",1, 2) ; printf(\"b\") ;");
+}
+
+#[test]
+fn test_macro_arguments_across_lines_keep_line_count() {
+    let text = "#define ADD(a, b) a + b\nx = ADD(1,\n 2);\ny;\n";
+    let out = preprocess(text);
+    assert_eq!(out.lines().count(), text.lines().count());
+    assert_eq!(out.lines().nth(3), Some("y;"));
+}
+
+#[test]
+fn test_macros_not_expanded_in_strings_and_comments() {
+    let out = preprocess("#define N 1\ns = \"N\"; // N\n/* N */\n");
+    assert_eq!(squeeze(&out), "s = \"N\"; // N /* N */");
+}
+
+#[test]
+fn test_ifdef_ifndef_else() {
+    let text =
+        "#ifdef FEATURE\nint on;\n#else\nint off;\n#endif\n#ifndef FEATURE\nint missing;\n#endif\n";
+    assert_eq!(squeeze(&preprocess(text)), "int off; int missing;");
+    assert_eq!(squeeze(&preprocess_with(text, &["FEATURE"])), "int on;");
+}
+
+#[test]
+fn test_if_elif_chain() {
+    let text = "#if LEVEL > 2\nhigh;\n#elif LEVEL == 2\nmid;\n#elif defined(LEVEL)\nlow;\n#else\nnone;\n#endif\n";
+    assert_eq!(squeeze(&preprocess_with(text, &["LEVEL=3"])), "high;");
+    assert_eq!(squeeze(&preprocess_with(text, &["LEVEL=2"])), "mid;");
+    assert_eq!(squeeze(&preprocess_with(text, &["LEVEL=1"])), "low;");
+    assert_eq!(squeeze(&preprocess(text)), "none;");
+}
+
+#[test]
+fn test_nested_conditionals_in_skipped_group() {
+    let text =
+        "#if 0\n#if 1\nskipped;\n#else\nskipped;\n#endif\n#error never\n#else\nkept;\n#endif\n";
+    assert_eq!(squeeze(&preprocess(text)), "kept;");
+}
+
+#[test]
+fn test_undef() {
+    let out = preprocess("#define X 1\n#undef X\n#ifdef X\nyes;\n#endif\nX;\n");
+    assert_eq!(squeeze(&out), "X;");
+}
+
+#[test]
+fn test_line_numbers_are_preserved() {
+    let text = "#define A 1\n#if 0\na;\n#endif\nint b = A;\n";
+    let out = preprocess(text);
+    assert_eq!(out.lines().count(), 5);
+    assert_eq!(squeeze(out.lines().nth(4).unwrap()), "int b = 1 ;");
+}
+
+#[test]
+fn test_line_continuation() {
+    let out = preprocess("#define SUM(a, b) \\\n    ((a) + (b))\nx = SUM(1, 2);\n");
+    assert_eq!(squeeze(&out), "x = ((1) + (2)) ;");
+    assert_eq!(out.lines().count(), 3);
+}
+
+#[test]
+fn test_line_and_file_macros() {
+    let out = preprocess("a;\nint l = __LINE__;\n");
+    assert_eq!(squeeze(&out), "a; int l = 2;");
+    assert!(preprocess("__FILE__\n").contains("\"test.cpp\""));
+}
+
+#[test]
+fn test_include_guard_and_pragma_once() {
+    let path = "src/test/resources/include/main.cpp";
+    let mut text = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut text))
+        .expect("unable to read test file");
+    let (out, _) = init_preprocessor(&text, Path::new(path), &[], &[]).expect("preprocess failed");
+    assert_eq!(out.matches("int guarded;").count(), 1);
+    assert_eq!(out.matches("int once;").count(), 1);
+    //system headers are not available, the directive is kept
+    assert!(out.contains("#include <stdio.h>"));
+    //ONCE_VALUE becomes a rust constant, its definition is passed on
+    assert!(out.contains("#define ONCE_VALUE 7"));
+    assert!(out.contains("int value = ONCE_VALUE;"));
+}
+
+#[test]
+fn test_include_directories() {
+    let dirs = vec!["src/test/resources/include".to_string()];
+    let (out, _) = init_preprocessor("#include <guarded.h>\n", Path::new("test.cpp"), &[], &dirs)
+        .expect("preprocess failed");
+    assert_eq!(squeeze(&out), "int guarded;");
+}
+
+#[test]
+fn test_line_map_points_into_included_files() {
+    let path = "src/test/resources/include/main.cpp";
+    let mut text = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut text))
+        .expect("unable to read test file");
+    let (out, lines) =
+        init_preprocessor(&text, Path::new(path), &[], &[]).expect("preprocess failed");
+    let line_of = |needle: &str| {
+        out.lines()
+            .position(|line| line.contains(needle))
+            .expect("line not found") as u32
+            + 1
+    };
+    let (file, line) = lines.locate(line_of("int value")).expect("line not mapped");
+    assert!(file.ends_with("main.cpp"));
+    assert_eq!(line, 6);
+    let (file, line) = lines
+        .locate(line_of("int guarded;"))
+        .expect("line not mapped");
+    assert!(file.ends_with("guarded.h"));
+    assert_eq!(line, 3);
+    let (file, line) = lines.locate(line_of("int once;")).expect("line not mapped");
+    assert!(file.ends_with("once.h"));
+    assert_eq!(line, 3);
+    assert_eq!(lines.locate(out.lines().count() as u32 + 1), None);
+}
+
+#[test]
+fn test_command_line_function_macro() {
+    let out = preprocess_with("y = TWICE(3);\n", &["TWICE(x)=(2 * (x))"]);
+    assert_eq!(squeeze(&out), "y = (2 * (3)) ;");
+}
+
+#[test]
+fn test_translated_macros_are_not_expanded() {
+    let out = translate(
+        "#define MAX_LEN 256\n#define KB(x) ((x) * 1024)\n#if MAX_LEN > 100\nint a[MAX_LEN];\n#endif\nint b = KB(4);\n",
+    );
+    assert_eq!(out.lines().next(), Some("#define MAX_LEN 256"));
+    assert_eq!(out.lines().nth(1), Some("#define KB(x) ((x) * 1024)"));
+    assert!(out.contains("int a[MAX_LEN];"));
+    assert!(out.contains("int b = KB(4);"));
+}
+
+#[test]
+fn test_macros_using_globals_are_not_expanded() {
+    //`total` is declared at file scope, `t` would be the caller's local
+    let out = translate(
+        "int total;\n#define ADD(x) (total += (x))\n#define SWAP(a, b) do { t = a; a = b; b = t; } while (0)\nint f() { int t, x, y; ADD(x); SWAP(x, y); }\n",
+    );
+    assert!(out.contains("ADD(x);"));
+    assert!(!out.contains("SWAP(x, y);"));
+}
+
+#[test]
+fn test_untranslated_macros_are_expanded() {
+    let out = translate(
+        "#define GUARD_H\n#define BEGIN {\n#define STR(x) #x\n#define N 2\n#define N 3\nBEGIN s = STR(a); N;\n",
+    );
+    //empty macros are flags, their definition is dropped
+    assert!(!out.contains("GUARD_H"));
+    assert!(out.contains("#define BEGIN {"));
+    assert!(out.contains("{ s = \"a\" ; 3 ;"));
+}
+
+#[test]
+fn test_constant_expressions() {
+    assert_eq!(eval("1 + 2 * 3"), 7);
+    assert_eq!(eval("(1 + 2) * 3"), 9);
+    assert_eq!(eval("0x10 | 010 | 0b1"), 25);
+    assert_eq!(eval("1 << 4 >> 2"), 4);
+    assert_eq!(eval("-1 < 0 && !0"), 1);
+    assert_eq!(eval("~0 == -1"), 1);
+    assert_eq!(eval("2 > 1 ? 10 : 20"), 10);
+    assert_eq!(eval("'A' == 65 && '\\'' == 39 && '\\n' == 10"), 1);
+    assert_eq!(eval("100UL / 7 % 4"), 2);
+    //right operand is not evaluated
+    assert_eq!(eval("0 && 1 / 0"), 0);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        error_of("#if 1\nint a;\n"),
+        "unterminated conditional directive"
+    );
+    assert_eq!(error_of("#endif\n"), "#endif without #if");
+    assert_eq!(
+        error_of("#if 1\n#else\n#else\n#endif\n"),
+        "#else after #else"
+    );
+    assert_eq!(error_of("#error stop here\n"), "#error stop here");
+    assert_eq!(error_of("#if 1 / 0\n#endif\n"), "division by zero in #if");
+    assert_eq!(
+        error_of("#define F(a, b) a\nF(1);\n"),
+        "macro `F` requires 2 arguments, but 1 given"
+    );
+    assert_eq!(error_of("#foo\n"), "invalid preprocessing directive `#foo`");
+    let err = init_preprocessor("\n\n#if\n#endif\n", Path::new("x.c"), &[], &[]).unwrap_err();
+    assert_eq!(err.to_string(), "x.c:3: #if with no expression");
+}
@@ -1,20 +1,23 @@
 #![allow(dead_code)]
-#[allow(unused_variables)]
 extern crate getopts;
 
 use std::env;
+use std::fmt;
 use std::fs::File;
 use std::io;
 use std::io::BufReader;
 use std::io::Read;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use getopts::Options;
 
+use library::codegen::generator;
 use library::lexer::tokenizer::Tokenizer;
 use library::parser::parser;
+use library::preprocessor::preprocessor;
+use library::preprocessor::preprocessor::LineMap;
 
 mod library;
 
@@ -22,6 +25,9 @@ struct Settings {
     strict: bool,
     project_name: Option<String>,
     files: Vec<String>,
+    //macros given as NAME or NAME=VALUE
+    defines: Vec<String>,
+    include_dirs: Vec<String>,
 }
 
 fn print_usage(program: &str, opts: Options) {
@@ -36,6 +42,13 @@ fn main() {
     let mut opts = Options::new();
     opts.optflag("s", "strict", "Strict mode (immutable)");
     opts.optopt("p", "project-name", "Cargo project name", "NAME");
+    opts.optmulti("D", "define", "Define a macro", "NAME[=VALUE]");
+    opts.optmulti(
+        "I",
+        "include",
+        "Add a directory to the include search path",
+        "DIR",
+    );
     opts.optflag("h", "help", "show this help message");
 
     let matches = match opts.parse(&args[1..]) {
@@ -52,6 +65,8 @@ fn main() {
         Settings {
             strict: matches.opt_present("s"),
             project_name: matches.opt_str("p"),
+            defines: matches.opt_strs("D"),
+            include_dirs: matches.opt_strs("I"),
             files: matches.free,
         }
     };
@@ -63,49 +78,31 @@ fn get_settings_interactively() -> Settings {
     let mut input = String::new();
 
     print!("Enter the C/C++ file to be converted to Rust : ");
-    io::stdout()
-        .flush()
-        .ok()
-        .expect("FATAL : Buffer flush failed");
+    io::stdout().flush().expect("FATAL : Buffer flush failed");
     io::stdin().read_line(&mut input).expect("Unable to read");
 
     let mut strict = String::new();
 
     print!("Enter the translation mode [(S/s)trict/(L/l)oose] : ");
-    io::stdout()
-        .flush()
-        .ok()
-        .expect("FATAL : Buffer flush failed");
+    io::stdout().flush().expect("FATAL : Buffer flush failed");
     io::stdin().read_line(&mut strict).expect("Unable to read");
     let strict = strict.trim();
-    let strict: bool = match &strict[..] {
-        "S" | "Strict" | "s" => true,
-        _ => false,
-    };
+    let strict: bool = matches!(strict, "S" | "Strict" | "s");
 
     let mut cargo = String::new();
     print!("Do you want to create a cargo project :[Y/N]");
-    io::stdout()
-        .flush()
-        .ok()
-        .expect("FATAL : Buffer flush failed.");
+    io::stdout().flush().expect("FATAL : Buffer flush failed.");
     io::stdin()
         .read_line(&mut cargo)
         .expect("Unable to read input");
     let cargo = cargo.trim();
-    let cargo: bool = match &cargo[..] {
-        "Y" | "y" => true,
-        _ => false,
-    };
+    let cargo: bool = matches!(cargo, "Y" | "y");
 
     let mut project_name = None;
-    if cargo == true {
+    if cargo {
         let mut project = String::new();
         print!("Enter cargo project name : ");
-        io::stdout()
-            .flush()
-            .ok()
-            .expect("FATAL : Buffer flush failed");
+        io::stdout().flush().expect("FATAL : Buffer flush failed");
         io::stdin()
             .read_line(&mut project)
             .expect("Unable to read input");
@@ -113,9 +110,25 @@ fn get_settings_interactively() -> Settings {
     }
 
     Settings {
-        strict: strict,
-        project_name: project_name,
+        strict,
+        project_name,
         files: vec![input.trim().to_owned()],
+        defines: Vec::new(),
+        include_dirs: Vec::new(),
+    }
+}
+
+// error at a line of the preprocessed text, shown at the file and line it comes from
+fn report(line_map: &LineMap, line: u32, column: u32, message: &dyn fmt::Display) {
+    match line_map.locate(line) {
+        Some((file, line)) => println!(
+            "  {}:{}, column {}: {}",
+            file.display(),
+            line,
+            column,
+            message
+        ),
+        None => println!("  line {}, column {}: {}", line, column, message),
     }
 }
 
@@ -137,19 +150,59 @@ fn invoke(settings: &Settings) {
 
         println!("Input file size : {}bytes ", size);
 
+        print!("Preprocessing");
+        let (text, line_map) = match preprocessor::init_preprocessor(
+            &text,
+            Path::new(input),
+            &settings.defines,
+            &settings.include_dirs,
+        ) {
+            Ok(preprocessed) => preprocessed,
+            Err(err) => {
+                println!("\nUnable to preprocess `{}`, {}.", input, err);
+                std::process::exit(1);
+            }
+        };
+
         let mut tok = Tokenizer::new(&text);
         print!("Tokenizing");
 
-        let tokens = tok.tokenize();
+        let mut tokens = match tok.tokenize() {
+            Ok(tokens) => tokens,
+            Err(errors) => {
+                println!("\nUnable to tokenize `{}`:", input);
+                for err in errors {
+                    report(&line_map, err.line, err.column, &err.kind);
+                }
+                std::process::exit(1);
+            }
+        };
+        //lines of the preprocessed text back to the lines of the source files
+        for token in tokens.iter_mut() {
+            if let Some((_, line)) = line_map.locate(token.get_token_line_num()) {
+                token.set_token_ln(line);
+            }
+        }
         print!("Invoking Parser ....");
 
         let mode = if settings.strict { "Strict" } else { "Loose" };
-        let rust_lexeme = parser::init_parser(&tokens, settings.strict);
+        let (unit, errors) = parser::init_parser(&tokens);
+        if !errors.is_empty() {
+            //the regions are kept as comments in the translated code
+            println!("\nUnable to parse parts of `{}`:", input);
+            for err in &errors {
+                //tokens carry the lines of the source files, the span points into the text
+                let start = err.span.start.min(text.len());
+                let line = text[..start].matches('\n').count() as u32 + 1;
+                report(&line_map, line, err.span.column, &err.message);
+            }
+        }
+        let rust_lexeme = generator::init_generator(&unit, settings.strict);
         //regenerate the code from lexemes
         let mut o: String = String::new();
         for i in rust_lexeme {
-            o = o + " ";
-            o = o + &i[..];
+            o += " ";
+            o += &i[..];
         }
 
         let mut fname = PathBuf::from(input);
@@ -158,7 +211,7 @@ fn invoke(settings: &Settings) {
 
         if let Some(ref project_name) = settings.project_name {
             let child = Command::new("cargo")
-                .args(&["new", "--bin"])
+                .args(["new", "--bin"])
                 .arg(&project_name[..])
                 .status()
                 .expect("Failed to create project");
@@ -0,0 +1,6 @@
+#include "guarded.h"
+#include "guarded.h"
+#include "once.h"
+#include "once.h"
+#include <stdio.h>
+int value = ONCE_VALUE;
@@ -18,3 +18,23 @@ static
 const
 default
 return
+nullptr
+goto
+extern
+inline
+volatile
+register
+constexpr
+explicit
+template
+typename
+namespace
+using
+virtual
+friend
+operator
+this
+delete
+try
+catch
+throw
@@ -27,3 +27,13 @@
 ->
 ::
 ?
+^
+^=
+<<=
+>>=
+&=
+|=
+.
+.*
+->*
+...